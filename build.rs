@@ -0,0 +1,73 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+/// Reads `instructions.in` (crate root; one `architecture mnemonic
+/// operand_shape flags` instruction fact per line) and generates the `match`
+/// table `models::controlflow::instrs::classify_raw` consults, writing it to
+/// `$OUT_DIR/instrs.rs`.
+///
+/// Keeping the table itself in a plain data file instead of hand-written
+/// match arms means teaching binlex a new architecture's prologue shape, or
+/// retuning which mnemonics get wildcard-masked during signature
+/// normalization, is a one-line edit to `instructions.in` rather than a code
+/// change to the disassembler.
+fn main() {
+    println!("cargo:rerun-if-changed=instructions.in");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR not set");
+    let source_path = Path::new(&manifest_dir).join("instructions.in");
+    let source = fs::read_to_string(&source_path)
+        .unwrap_or_else(|error| panic!("failed to read {}: {}", source_path.display(), error));
+
+    let mut arms = String::new();
+
+    for (line_number, raw_line) in source.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() != 4 {
+            panic!(
+                "instructions.in:{}: expected 4 whitespace-separated fields, got {}: {:?}",
+                line_number + 1,
+                fields.len(),
+                raw_line,
+            );
+        }
+
+        let (architecture, mnemonic, operand_shape, flags) = (fields[0], fields[1], fields[2], fields[3]);
+        let has_flag = |name: &str| flags.split(',').any(|flag| flag == name);
+
+        arms.push_str(&format!(
+            "        (\"{architecture}\", \"{mnemonic}\") => InstructionClass {{ operand_shape: \"{operand_shape}\", is_prologue: {is_prologue}, is_call: {is_call}, is_branch: {is_branch}, is_nop: {is_nop}, normalize: {normalize} }},\n",
+            architecture = architecture,
+            mnemonic = mnemonic,
+            operand_shape = operand_shape,
+            is_prologue = has_flag("prologue"),
+            is_call = has_flag("call"),
+            is_branch = has_flag("branch"),
+            is_nop = has_flag("nop"),
+            normalize = has_flag("normalize"),
+        ));
+    }
+
+    let generated = format!(
+        "/// Generated from `instructions.in` by `build.rs`; see\n\
+         /// `models::controlflow::instrs::classify` for the public entry point.\n\
+         pub(super) fn classify_raw(architecture: &str, mnemonic: &str) -> InstructionClass {{\n\
+         \x20\x20\x20\x20match (architecture, mnemonic) {{\n\
+{arms}\
+         \x20\x20\x20\x20\x20\x20\x20\x20_ => InstructionClass::default(),\n\
+         \x20\x20\x20\x20}}\n\
+         }}\n",
+        arms = arms,
+    );
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let out_path = Path::new(&out_dir).join("instrs.rs");
+    fs::write(&out_path, generated)
+        .unwrap_or_else(|error| panic!("failed to write {}: {}", out_path.display(), error));
+}
@@ -1,5 +1,9 @@
 use std::process;
-use clap::Parser;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use clap::{Parser, ValueEnum};
 use binlex::AUTHOR;
 use binlex::VERSION;
 use binlex::io::JSON;
@@ -11,6 +15,275 @@ use serde_json::Value;
 use rayon::prelude::*;
 use rayon::ThreadPoolBuilder;
 
+/// Selects which digest `blcompare` indexes and scores pairs against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+enum Similarity {
+    /// A BK-tree over `signature.tlsh`, bounded by `--threshold`.
+    Tlsh,
+    /// MinHash LSH banding over `signature.minhash`, tuned by `-b`/`-r`.
+    MinHash,
+}
+
+/// Parses a `MinHash32::hexdigest`-style string (`k` concatenated 8-hex-char
+/// `u32` hashes) back into its `k` values.
+fn parse_minhash(digest: &str) -> Option<Vec<u32>> {
+    if digest.len() % 8 != 0 { return None; }
+    digest.as_bytes()
+        .chunks(8)
+        .map(|chunk| u32::from_str_radix(std::str::from_utf8(chunk).ok()?, 16).ok())
+        .collect()
+}
+
+/// Hashes one band (a contiguous slice of `r` MinHash values) into a bucket key.
+fn band_hash(band: &[u32]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    band.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Splits `signature` into `bands` bands of `rows` rows each (`bands * rows`
+/// must equal `signature.len()`) and returns one bucket key per band.
+fn band_keys(signature: &[u32], bands: usize, rows: usize) -> Option<Vec<(usize, u64)>> {
+    if bands == 0 || rows == 0 || signature.len() != bands * rows { return None; }
+    Some((0..bands)
+        .map(|band_index| {
+            let start = band_index * rows;
+            (band_index, band_hash(&signature[start..start + rows]))
+        })
+        .collect())
+}
+
+/// Estimated Jaccard similarity between two equal-length MinHash signatures:
+/// the fraction of positions at which the two signatures' hash values agree.
+fn minhash_similarity(a: &[u32], b: &[u32]) -> f64 {
+    if a.is_empty() || a.len() != b.len() { return 0.0; }
+    a.iter().zip(b).filter(|(x, y)| x == y).count() as f64 / a.len() as f64
+}
+
+/// One node of a `BkTree`: the rhs entry it represents (by index into the
+/// caller's entry list) and its children keyed by their integer
+/// `TLSH::compare` distance from this node.
+struct BkNode {
+    index: usize,
+    digest: String,
+    children: HashMap<u32, usize>,
+}
+
+/// A BK-tree (Burkhard-Keller tree) over TLSH digests. Since `TLSH::compare`
+/// is an integer metric, a node's children are labeled by their exact
+/// distance from it; querying for all entries within `threshold` of a digest
+/// only has to descend into children whose edge label `e` satisfies
+/// `|e - d| <= threshold` (the triangle inequality), so most of the tree is
+/// pruned instead of scanned.
+struct BkTree {
+    nodes: Vec<BkNode>,
+}
+
+impl BkTree {
+    fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Inserts the entry at `index` with TLSH digest `digest` into the tree.
+    /// Digests identical to one already indexed are not inserted again.
+    fn insert(&mut self, index: usize, digest: String) {
+        if self.nodes.is_empty() {
+            self.nodes.push(BkNode { index, digest, children: HashMap::new() });
+            return;
+        }
+
+        let mut current = 0usize;
+        loop {
+            let distance = match TLSH::compare(digest.clone(), self.nodes[current].digest.clone()).ok() {
+                Some(distance) => distance,
+                None => return,
+            };
+
+            if distance == 0 { return; }
+
+            match self.nodes[current].children.get(&distance) {
+                Some(&child) => current = child,
+                None => {
+                    let new_index = self.nodes.len();
+                    self.nodes.push(BkNode { index, digest, children: HashMap::new() });
+                    self.nodes[current].children.insert(distance, new_index);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Returns the indices of every entry within `threshold` of `query_digest`.
+    fn query(&self, query_digest: &str, threshold: u32) -> Vec<usize> {
+        let mut matches = Vec::new();
+
+        if self.nodes.is_empty() { return matches; }
+
+        let mut stack = vec![0usize];
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+
+            let distance = match TLSH::compare(query_digest.to_string(), node.digest.clone()).ok() {
+                Some(distance) => distance,
+                None => continue,
+            };
+
+            if distance <= threshold {
+                matches.push(node.index);
+            }
+
+            for (&edge, &child) in &node.children {
+                if edge.abs_diff(distance) <= threshold {
+                    stack.push(child);
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+/// A union-find (disjoint-set) structure over trait vertex indices, used to
+/// turn accumulated similarity edges into connected components in one pass
+/// instead of re-scanning the edge list per cluster.
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<usize>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        Self { parent: (0..size).collect(), rank: vec![0; size] }
+    }
+
+    fn find(&mut self, x: usize) -> usize {
+        if self.parent[x] != x {
+            self.parent[x] = self.find(self.parent[x]);
+        }
+        self.parent[x]
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (root_a, root_b) = (self.find(a), self.find(b));
+        if root_a == root_b { return; }
+        match self.rank[root_a].cmp(&self.rank[root_b]) {
+            std::cmp::Ordering::Less => self.parent[root_a] = root_b,
+            std::cmp::Ordering::Greater => self.parent[root_b] = root_a,
+            std::cmp::Ordering::Equal => {
+                self.parent[root_b] = root_a;
+                self.rank[root_a] += 1;
+            }
+        }
+    }
+}
+
+/// One trait in a `--cluster` output record: the fields an analyst needs to
+/// go find the member again (`address`) and the identifier it was clustered
+/// on (`sha256`).
+#[derive(Serialize, Deserialize)]
+pub struct ClusterMemberJson {
+    pub address: Option<u64>,
+    pub sha256: String,
+}
+
+/// A connected component of the similarity graph accumulated by `--cluster`,
+/// reported as a candidate malware family.
+#[derive(Serialize, Deserialize)]
+pub struct ClusterJson {
+    /// The type of this entity, always `"cluster"`.
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// A stable id for this cluster, unique within one run's output.
+    pub id: usize,
+    /// Every trait unioned into this cluster.
+    pub members: Vec<ClusterMemberJson>,
+    /// The smallest intra-cluster edge distance (TLSH distance, or `1.0 -
+    /// minhash_similarity` in `--similarity minhash` mode).
+    pub minimum_distance: f64,
+    /// The mean intra-cluster edge distance.
+    pub mean_distance: f64,
+}
+
+/// Groups accumulated `(lhs, rhs, distance)` similarity edges into connected
+/// components via union-find, keyed on each side's `sha256`. Edges missing a
+/// `sha256` on either side are dropped, since the trait identifier is what
+/// makes a vertex addressable across edges. Singleton components (no edge
+/// survived) are not reported as clusters. Clusters are returned largest
+/// first.
+fn cluster(edges: &[(Value, Value, f64)]) -> Vec<ClusterJson> {
+    let mut vertex_ids: HashMap<String, usize> = HashMap::new();
+    let mut vertex_values: Vec<Value> = Vec::new();
+    let mut weighted_edges: Vec<(usize, usize, f64)> = Vec::new();
+
+    for (lhs, rhs, distance) in edges {
+        let sha256_lhs = match lhs.get("sha256").and_then(|v| v.as_str()) {
+            Some(sha256) => sha256,
+            None => continue,
+        };
+        let sha256_rhs = match rhs.get("sha256").and_then(|v| v.as_str()) {
+            Some(sha256) => sha256,
+            None => continue,
+        };
+
+        let id_lhs = *vertex_ids.entry(sha256_lhs.to_string()).or_insert_with(|| {
+            vertex_values.push(lhs.clone());
+            vertex_values.len() - 1
+        });
+        let id_rhs = *vertex_ids.entry(sha256_rhs.to_string()).or_insert_with(|| {
+            vertex_values.push(rhs.clone());
+            vertex_values.len() - 1
+        });
+
+        weighted_edges.push((id_lhs, id_rhs, *distance));
+    }
+
+    let mut union_find = UnionFind::new(vertex_values.len());
+    for (id_lhs, id_rhs, _) in &weighted_edges {
+        union_find.union(*id_lhs, *id_rhs);
+    }
+
+    let mut members_by_root: HashMap<usize, Vec<usize>> = HashMap::new();
+    for id in 0..vertex_values.len() {
+        let root = union_find.find(id);
+        members_by_root.entry(root).or_insert_with(Vec::new).push(id);
+    }
+
+    let mut distances_by_root: HashMap<usize, Vec<f64>> = HashMap::new();
+    for (id_lhs, _, distance) in &weighted_edges {
+        let root = union_find.find(*id_lhs);
+        distances_by_root.entry(root).or_insert_with(Vec::new).push(*distance);
+    }
+
+    let mut clusters: Vec<ClusterJson> = members_by_root
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .enumerate()
+        .map(|(cluster_id, (root, members))| {
+            let distances = distances_by_root.get(&root).cloned().unwrap_or_default();
+            let minimum_distance = distances.iter().cloned().fold(f64::INFINITY, f64::min);
+            let mean_distance = if distances.is_empty() {
+                0.0
+            } else {
+                distances.iter().sum::<f64>() / distances.len() as f64
+            };
+
+            ClusterJson {
+                type_: "cluster".to_string(),
+                id: cluster_id,
+                members: members.into_iter().map(|id| ClusterMemberJson {
+                    address: vertex_values[id].get("address").and_then(|v| v.as_u64()),
+                    sha256: vertex_values[id].get("sha256").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+                }).collect(),
+                minimum_distance,
+                mean_distance,
+            }
+        })
+        .collect();
+
+    clusters.sort_by(|a, b| b.members.len().cmp(&a.members.len()));
+    clusters
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct ComparisonJson {
     /// The type of this entity, always `"comparison"`.
@@ -21,7 +294,11 @@ pub struct ComparisonJson {
     /// The address of the next sequential block, if any.
     pub rhs: Value,
     /// TLSH Similarity Score
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tlsh: Option<u32>,
+    /// Estimated Jaccard similarity from MinHash LSH banding (`--similarity minhash`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub minhash: Option<f64>,
 }
 
 #[derive(Parser, Debug)]
@@ -38,6 +315,27 @@ struct Args {
     input_rhs: String,
     #[arg(short, long, default_value_t = 1)]
     pub threads: usize,
+    /// Only emit pairs whose TLSH distance is at most this value.
+    #[arg(long, default_value_t = 100)]
+    pub threshold: u32,
+    /// Which digest to index and score pairs against.
+    #[arg(long, value_enum, default_value_t = Similarity::Tlsh)]
+    pub similarity: Similarity,
+    /// Number of MinHash LSH bands (`--similarity minhash` only).
+    #[arg(short = 'b', long, default_value_t = 16)]
+    pub bands: usize,
+    /// Number of MinHash rows per band (`--similarity minhash` only); `bands * rows`
+    /// must equal the number of hashes in `signature.minhash`.
+    #[arg(short = 'r', long, default_value_t = 4)]
+    pub rows: usize,
+    /// Only treat a MinHash pair as an edge once its estimated similarity
+    /// reaches this value (`--similarity minhash` only).
+    #[arg(long, default_value_t = 0.0)]
+    pub min_similarity: f64,
+    /// Instead of streaming pairwise comparisons, group them into connected
+    /// components over the similarity graph and emit `ClusterJson` records.
+    #[arg(long, default_value_t = false)]
+    pub cluster: bool,
 }
 
 fn main () {
@@ -51,14 +349,19 @@ fn main () {
             process::exit(1);
         });
 
+    let similarity_field = match args.similarity {
+        Similarity::Tlsh => "tlsh",
+        Similarity::MinHash => "minhash",
+    };
+
     let json_lhs = JSON::from_file_or_stdin_with_filter(args.input_lhs, |value| {
         let architecture = value.get("architecture").and_then(|v| v.as_str()).map(String::from);
-        let tlsh_normalized = value
+        let digest = value
             .get("signature")
-            .and_then(|v| v.get("tlsh"))
+            .and_then(|v| v.get(similarity_field))
             .and_then(|v| v.as_str())
             .map(String::from);
-        if tlsh_normalized.is_none() { return false; }
+        if digest.is_none() { return false; }
         if architecture.is_none() { return false; }
         true
     }).unwrap_or_else(|error| {
@@ -68,12 +371,12 @@ fn main () {
 
     let json_rhs = JSON::from_file_with_filter(&args.input_rhs, |value| {
         let architecture = value.get("architecture").and_then(|v| v.as_str()).map(String::from);
-        let tlsh_normalized = value
+        let digest = value
             .get("signature")
-            .and_then(|v| v.get("tlsh"))
+            .and_then(|v| v.get(similarity_field))
             .and_then(|v| v.as_str())
             .map(String::from);
-        if tlsh_normalized.is_none() { return false; }
+        if digest.is_none() { return false; }
         if architecture.is_none() { return false; }
         true
     }).unwrap_or_else(|error| {
@@ -83,47 +386,156 @@ fn main () {
 
     let rhs_entries: Vec<Value> = json_rhs.values().into_iter().cloned().collect();
 
-    json_lhs.values().par_iter().for_each(|value_lhs| {
-        let type_lhs = value_lhs.get("type").and_then(|v| v.as_str()).map(String::from).unwrap();
-        let architecture_lhs = value_lhs.get("architecture").and_then(|v| v.as_str()).map(String::from).unwrap();
-        let tlsh_lhs = value_lhs
-            .get("signature")
-            .and_then(|v| v.get("tlsh"))
-            .and_then(|v| v.as_str())
-            .map(String::from).unwrap();
+    // Every edge is normalized to `(lhs, rhs, distance)`, where `distance` is
+    // the TLSH distance in `Similarity::Tlsh` mode and `1.0 - minhash_similarity`
+    // in `Similarity::MinHash` mode (so "smaller is closer" holds in both
+    // modes), letting `--cluster` union edges from either mode the same way.
+    let edges: Vec<(Value, Value, f64)> = match args.similarity {
+        Similarity::Tlsh => {
+            // Partition rhs entries into one BK-tree per `(architecture, type)`, so a
+            // query only ever descends a tree whose entries already satisfy those
+            // equality filters instead of checking them on every comparison.
+            let mut rhs_trees: HashMap<(String, String), BkTree> = HashMap::new();
+            for (index, value_rhs) in rhs_entries.iter().enumerate() {
+                let type_rhs = value_rhs.get("type").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let architecture_rhs = value_rhs.get("architecture").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let tlsh_rhs = value_rhs
+                    .get("signature")
+                    .and_then(|v| v.get("tlsh"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from).unwrap();
+
+                rhs_trees
+                    .entry((architecture_rhs, type_rhs))
+                    .or_insert_with(BkTree::new)
+                    .insert(index, tlsh_rhs);
+            }
+
+            json_lhs.values().par_iter().flat_map(|value_lhs| {
+                let type_lhs = value_lhs.get("type").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let architecture_lhs = value_lhs.get("architecture").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let tlsh_lhs = value_lhs
+                    .get("signature")
+                    .and_then(|v| v.get("tlsh"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from).unwrap();
+
+                let tree = match rhs_trees.get(&(architecture_lhs, type_lhs)) {
+                    Some(tree) => tree,
+                    None => return Vec::new(),
+                };
 
-        for value_rhs in &rhs_entries {
-            let type_rhs = value_rhs.get("type").and_then(|v| v.as_str()).map(String::from).unwrap();
-            let architecture_rhs = value_rhs.get("architecture").and_then(|v| v.as_str()).map(String::from).unwrap();
-            let tlsh_rhs = value_rhs
-                .get("signature")
-                .and_then(|v| v.get("tlsh"))
-                .and_then(|v| v.as_str())
-                .map(String::from).unwrap();
+                tree.query(&tlsh_lhs, args.threshold).into_iter().filter_map(|index| {
+                    let value_rhs = &rhs_entries[index];
+                    let tlsh_rhs = value_rhs
+                        .get("signature")
+                        .and_then(|v| v.get("tlsh"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from).unwrap();
 
-            if architecture_lhs != architecture_rhs { continue; }
-            if type_lhs != type_rhs { continue; }
+                    TLSH::compare(tlsh_lhs.clone(), tlsh_rhs).ok()
+                        .map(|distance| (value_lhs.clone(), value_rhs.clone(), distance as f64))
+                }).collect::<Vec<_>>()
+            }).collect()
+        }
+        Similarity::MinHash => {
+            // Partition rhs entries into one LSH band index per
+            // `(architecture, type)`, keyed on `(band_index, band_hash)`, so a
+            // query only has to gather the candidates sharing at least one
+            // band bucket instead of scanning every rhs entry.
+            let mut rhs_buckets: HashMap<(String, String), HashMap<(usize, u64), Vec<usize>>> = HashMap::new();
+            for (index, value_rhs) in rhs_entries.iter().enumerate() {
+                let type_rhs = value_rhs.get("type").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let architecture_rhs = value_rhs.get("architecture").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let minhash_rhs = value_rhs
+                    .get("signature")
+                    .and_then(|v| v.get("minhash"))
+                    .and_then(|v| v.as_str())
+                    .and_then(parse_minhash);
+
+                let Some(minhash_rhs) = minhash_rhs else { continue; };
+                let Some(keys) = band_keys(&minhash_rhs, args.bands, args.rows) else { continue; };
+
+                let buckets = rhs_buckets.entry((architecture_rhs, type_rhs)).or_insert_with(HashMap::new);
+                for key in keys {
+                    buckets.entry(key).or_insert_with(Vec::new).push(index);
+                }
+            }
+
+            json_lhs.values().par_iter().flat_map(|value_lhs| {
+                let type_lhs = value_lhs.get("type").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let architecture_lhs = value_lhs.get("architecture").and_then(|v| v.as_str()).map(String::from).unwrap();
+                let minhash_lhs = value_lhs
+                    .get("signature")
+                    .and_then(|v| v.get("minhash"))
+                    .and_then(|v| v.as_str())
+                    .and_then(parse_minhash);
+
+                let Some(minhash_lhs) = minhash_lhs else { return Vec::new(); };
+                let Some(keys) = band_keys(&minhash_lhs, args.bands, args.rows) else { return Vec::new(); };
 
-            let tlsh_similarity = TLSH::compare(tlsh_lhs.clone(), tlsh_rhs.clone()).ok();
+                let buckets = match rhs_buckets.get(&(architecture_lhs, type_lhs)) {
+                    Some(buckets) => buckets,
+                    None => return Vec::new(),
+                };
+
+                let mut candidates: HashSet<usize> = HashSet::new();
+                for key in keys {
+                    if let Some(indices) = buckets.get(&key) {
+                        candidates.extend(indices.iter().copied());
+                    }
+                }
+
+                candidates.into_iter().filter_map(|index| {
+                    let value_rhs = &rhs_entries[index];
+                    let minhash_rhs = value_rhs
+                        .get("signature")
+                        .and_then(|v| v.get("minhash"))
+                        .and_then(|v| v.as_str())
+                        .and_then(parse_minhash)?;
+
+                    let similarity = minhash_similarity(&minhash_lhs, &minhash_rhs);
+                    if similarity < args.min_similarity { return None; }
+
+                    Some((value_lhs.clone(), value_rhs.clone(), 1.0 - similarity))
+                }).collect::<Vec<_>>()
+            }).collect()
+        }
+    };
 
+    if args.cluster {
+        cluster(&edges).par_iter().for_each(|cluster| {
+            let serialized = match serde_json::to_string(&cluster) {
+                Ok(s) => s,
+                Err(e) => {
+                    eprintln!("Serialization error: {}", e);
+                    return;
+                }
+            };
+
+            Stdout::print(serialized);
+        });
+    } else {
+        edges.par_iter().for_each(|(value_lhs, value_rhs, distance)| {
             let comparison = ComparisonJson {
                 type_: "comparison".to_string(),
                 lhs: value_lhs.clone(),
                 rhs: value_rhs.clone(),
-                tlsh: tlsh_similarity,
+                tlsh: if args.similarity == Similarity::Tlsh { Some(*distance as u32) } else { None },
+                minhash: if args.similarity == Similarity::MinHash { Some(1.0 - distance) } else { None },
             };
 
             let serialized = match serde_json::to_string(&comparison) {
                 Ok(s) => s,
                 Err(e) => {
                     eprintln!("Serialization error: {}", e);
-                    continue;
+                    return;
                 }
             };
 
             Stdout::print(serialized);
-        }
-    });
+        });
+    }
 
     process::exit(0);
 }
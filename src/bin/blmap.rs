@@ -0,0 +1,74 @@
+use std::process;
+use std::fs;
+use clap::Parser;
+use binlex::formats::symbol::SymbolIoJson;
+use binlex::models::symbols::Symbols;
+use binlex::models::terminal::args::VERSION;
+use binlex::models::terminal::args::AUTHOR;
+use binlex::models::terminal::io::Stdout;
+use binlex::types::lz4string::LZ4String;
+
+#[derive(Parser, Debug)]
+#[command(
+    name = "blmap",
+    version = VERSION,
+    about = format!("A Binlex Linker Map Symbol Importer\n\nVersion: {}", VERSION),
+    after_help = format!("Author: {}", AUTHOR),
+)]
+struct Cli {
+    #[arg(short, long, required = true)]
+    input: String,
+    #[arg(short, long)]
+    output: Option<String>,
+    /// Added to every address parsed from the map file, for maps emitted
+    /// relative to a section or module rather than the full image.
+    #[arg(long, default_value_t = 0)]
+    image_base: u64,
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    let contents = match fs::read_to_string(&cli.input) {
+        Ok(contents) => contents,
+        Err(error) => {
+            eprintln!("{}", error);
+            process::exit(1);
+        }
+    };
+
+    let symbols = Symbols::parse_linker_map(&contents, cli.image_base);
+
+    let results: Vec<LZ4String> = symbols
+        .values()
+        .map(|symbol| symbol.process())
+        .filter_map(|symbol_json| {
+            let result = SymbolIoJson {
+                type_: "function".to_string(),
+                names: symbol_json.names,
+                file_offset: None,
+                relative_virtual_address: None,
+                virtual_address: Some(symbol_json.address),
+            };
+            serde_json::to_string(&result).ok()
+        })
+        .map(|json| LZ4String::new(&json))
+        .collect();
+
+    match &cli.output {
+        Some(output_file) => {
+            let joined = results.iter().map(|record| record.to_string()).collect::<Vec<String>>().join("\n");
+            if let Err(error) = fs::write(output_file, joined) {
+                eprintln!("{}", error);
+                process::exit(1);
+            }
+        }
+        None => {
+            for result in &results {
+                Stdout.print(result);
+            }
+        }
+    }
+
+    process::exit(0);
+}
@@ -11,6 +11,7 @@ use binlex::models::terminal::args::AUTHOR;
 use binlex::models::terminal::io::Stdout;
 use binlex::models::terminal::io::JSON;
 use binlex::models::controlflow::symbol::SymbolIoJson;
+use binlex::models::symbols::Symbols;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -31,6 +32,10 @@ fn process_value(parsed: &Value) -> Result<LZ4String, Error> {
     let function_name = parsed.get("name").unwrap().as_str().unwrap().to_string();
     let mut function_names = BTreeSet::<String>::new();
     if !function_name.starts_with("fcn.") {
+        let demangled = Symbols::demangle(&function_name);
+        if demangled.display != function_name {
+            function_names.insert(demangled.display);
+        }
         function_names.insert(function_name);
     }
     let symbol = SymbolIoJson {
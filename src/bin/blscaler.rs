@@ -1,4 +1,4 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use rayon::prelude::*;
 use serde_json::Value;
 use std::io::IsTerminal;
@@ -12,8 +12,22 @@ use binlex::types::lz4string::LZ4String;
 use binlex::models::terminal::args::VERSION;
 use binlex::models::terminal::args::AUTHOR;
 use binlex::models::terminal::io::Stdout;
+use binlex::models::terminal::error::{CliError, report_and_exit};
 use serde_json::de::Deserializer;
 
+/// Feature-scaling strategies available to `blscaler`.
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ScalingMethod {
+    /// `(x - min) / (max - min)`; an all-zero vector when `max == min`.
+    MinMax,
+    /// `(x - mean) / std`; an all-zero vector when `std == 0`.
+    ZScore,
+    /// `x / ||x||_2`; an all-zero vector when the norm is `0`.
+    UnitNorm,
+    /// `(x - median) / IQR`; an all-zero vector when `IQR == 0`.
+    Robust,
+}
+
 #[derive(Parser, Debug)]
 #[command(
     name = "blscaler",
@@ -27,25 +41,82 @@ struct Args {
     #[arg(short, long)]
     output: Option<String>,
     #[arg(short, long, default_value_t = 1)]
-    threads: usize
+    threads: usize,
+    #[arg(long, value_enum, default_value_t = ScalingMethod::MinMax)]
+    method: ScalingMethod,
 }
 
-fn normalize(data: &[f64]) -> Vec<f64> {
+fn min_max(data: &[f64]) -> Vec<f64> {
     let min = data.iter().cloned().fold(f64::INFINITY, f64::min);
     let max = data.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+    if max == min {
+        return vec![0.0; data.len()];
+    }
     data.iter().map(|&x| (x - min) / (max - min)).collect()
 }
 
-fn process_value(mut parsed: Value) -> String {
+fn z_score(data: &[f64]) -> Vec<f64> {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let std = (data.iter().map(|&x| (x - mean).powi(2)).sum::<f64>() / n).sqrt();
+    if std == 0.0 {
+        return vec![0.0; data.len()];
+    }
+    data.iter().map(|&x| (x - mean) / std).collect()
+}
+
+fn unit_norm(data: &[f64]) -> Vec<f64> {
+    let norm = data.iter().map(|&x| x * x).sum::<f64>().sqrt();
+    if norm == 0.0 {
+        return vec![0.0; data.len()];
+    }
+    data.iter().map(|&x| x / norm).collect()
+}
+
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    if sorted.is_empty() { return 0.0; }
+    let index = fraction * (sorted.len() - 1) as f64;
+    let lower = index.floor() as usize;
+    let upper = index.ceil() as usize;
+    if lower == upper {
+        return sorted[lower];
+    }
+    let weight = index - lower as f64;
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+fn robust(data: &[f64]) -> Vec<f64> {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let median = percentile(&sorted, 0.5);
+    let q25 = percentile(&sorted, 0.25);
+    let q75 = percentile(&sorted, 0.75);
+    let iqr = q75 - q25;
+    if iqr == 0.0 {
+        return vec![0.0; data.len()];
+    }
+    data.iter().map(|&x| (x - median) / iqr).collect()
+}
+
+fn scale(method: ScalingMethod, data: &[f64]) -> Vec<f64> {
+    match method {
+        ScalingMethod::MinMax => min_max(data),
+        ScalingMethod::ZScore => z_score(data),
+        ScalingMethod::UnitNorm => unit_norm(data),
+        ScalingMethod::Robust => robust(data),
+    }
+}
+
+fn process_value(method: ScalingMethod, mut parsed: Value) -> String {
     if let Some(feature) = parsed
         .get_mut("signature")
         .and_then(|signature| signature.get_mut("feature"))
     {
         if let Some(array) = feature.as_array() {
             let values: Vec<f64> = array.iter().filter_map(|v| v.as_f64()).collect();
-            let normalized_values = normalize(&values);
+            let scaled_values = scale(method, &values);
             *feature = Value::Array(
-                normalized_values
+                scaled_values
                     .into_iter()
                     .filter_map(|num| Number::from_f64(num).map(Value::Number)) // Filter out non-finite numbers
                     .collect(),
@@ -55,8 +126,7 @@ fn process_value(mut parsed: Value) -> String {
     let result = match serde_json::to_string(&parsed) {
         Ok(result) => result,
         Err(error) => {
-            eprintln!("{}", error);
-            process::exit(1);
+            report_and_exit(CliError::Usage(error.to_string()));
         }
     };
     return result;
@@ -74,16 +144,12 @@ fn main() {
     let input_reader: Box<dyn BufRead> = if let Some(input) = args.input.clone() {
         let file = match File::open(input) {
             Ok(file) => file,
-            Err(error) => {
-                eprintln!("{}", error);
-                process::exit(1);
-            },
+            Err(error) => report_and_exit(CliError::Io(error)),
         };
         Box::new(io::BufReader::new(file))
     } else {
         if io::stdin().is_terminal() {
-            eprintln!("failed to read standard input");
-            process::exit(1);
+            report_and_exit(CliError::Usage("failed to read standard input".to_string()));
         }
         Box::new(io::BufReader::new(io::stdin()))
     };
@@ -92,16 +158,14 @@ fn main() {
         .into_iter::<Value>()
         .map(|value| match value {
             Ok(value) => value,
-            Err(error) => {
-                eprintln!("Error parsing JSON: {}", error);
-                process::exit(1);
-            }
+            Err(error) => report_and_exit(CliError::Usage(format!("error parsing json: {}", error))),
         })
         .collect();
 
+    let method = args.method;
     let results: Vec<LZ4String> = values.into_par_iter()
         .map(|value| {
-            LZ4String::from(process_value(value))
+            LZ4String::from(process_value(method, value))
         })
         .collect();
 
@@ -1,6 +1,9 @@
+use std::str::FromStr;
 use pyo3::prelude::*;
-use std::sync::{Arc, Mutex};
+use pyo3::exceptions::PyValueError;
 use binlex::Config as InnerConfig;
+use binlex::config::ConfigMinhashBackend;
+use std::sync::{Arc, Mutex};
 
 #[pyclass]
 pub struct ConfigSignatures {
@@ -990,6 +993,32 @@ impl ConfigFormatsFileHashingTLSH {
         let mut inner = self.inner.lock().unwrap();
         inner.formats.file.hashing.tlsh.minimum_byte_size = value;
     }
+
+    /// Maximum TLSH distance for two digests to be considered near-duplicates by
+    /// `cluster`. Lower is stricter.
+    #[getter]
+    pub fn get_diff_threshold(&self) -> u32 {
+        let inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.tlsh.diff_threshold
+    }
+
+    #[setter]
+    pub fn set_diff_threshold(&mut self, value: u32) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.tlsh.diff_threshold = value;
+    }
+
+    /// Groups `digests` into near-duplicate clusters by agglomerative TLSH distance,
+    /// using this object's configured `diff_threshold`. Returns each cluster as the
+    /// list of `digests` indices belonging to it.
+    pub fn cluster(&self, digests: Vec<String>) -> Vec<Vec<usize>> {
+        let threshold = {
+            let inner = self.inner.lock().unwrap();
+            inner.formats.file.hashing.tlsh.diff_threshold
+        };
+        let refs: Vec<&str> = digests.iter().map(|digest| digest.as_str()).collect();
+        binlex::config::ConfigTLSH::cluster(&refs, threshold)
+    }
 }
 
 
@@ -1058,6 +1087,51 @@ impl ConfigFormatsFileHashingMinhash {
         let mut inner = self.inner.lock().unwrap();
         inner.formats.file.hashing.minhash.seed = value;
     }
+
+    /// FracMinHash scale factor. Nonzero switches the sketch from bottom-k to
+    /// FracMinHash (keep every shingle hash `<= u64::MAX / scaled`), taking
+    /// precedence over `number_of_hashes`. `0` keeps the bottom-k behavior.
+    #[getter]
+    pub fn get_scaled(&self) -> u64 {
+        let inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.minhash.scaled
+    }
+
+    #[setter]
+    pub fn set_scaled(&mut self, value: u64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.minhash.scaled = value;
+    }
+
+    /// Minimum containment score (`|A ∩ B| / |A|`) for a pair to be emitted by
+    /// downstream matching; `0.0` emits every pair.
+    #[getter]
+    pub fn get_containment_threshold(&self) -> f64 {
+        let inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.minhash.containment_threshold
+    }
+
+    #[setter]
+    pub fn set_containment_threshold(&mut self, value: f64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.minhash.containment_threshold = value;
+    }
+
+    /// Hash function used to digest each shingle: `"default"` or `"xxh3"`.
+    #[getter]
+    pub fn get_hash_backend(&self) -> String {
+        let inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.minhash.hash_backend.to_string()
+    }
+
+    #[setter]
+    pub fn set_hash_backend(&mut self, value: String) -> PyResult<()> {
+        let backend = ConfigMinhashBackend::from_str(&value)
+            .map_err(PyValueError::new_err)?;
+        let mut inner = self.inner.lock().unwrap();
+        inner.formats.file.hashing.minhash.hash_backend = backend;
+        Ok(())
+    }
 }
 
 #[pyclass]
@@ -1157,6 +1231,23 @@ impl Config {
     pub fn disable_block_heuristics(&mut self) {
         self.inner.lock().unwrap().disable_block_heuristics();
     }
+
+    /// Writes this configuration to a compact `bincode` archive at `path`,
+    /// resolved against `mmap.directory` if relative.
+    pub fn save_archive(&self, path: String) -> PyResult<()> {
+        self.inner.lock().unwrap().save_archive(&path)
+            .map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
+    /// Reads a configuration previously written by `save_archive`.
+    #[staticmethod]
+    pub fn load_archive(path: String) -> PyResult<Self> {
+        let config = InnerConfig::load_archive(&path)
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+        Ok(Self {
+            inner: Arc::new(Mutex::new(config)),
+        })
+    }
 }
 
 #[pyclass]
@@ -16,6 +16,10 @@ impl BinaryArchitecture {
         let inner = match value {
             0x00 => InnerBinaryArchitecture::AMD64,
             0x01 => InnerBinaryArchitecture::I386,
+            0x02 => InnerBinaryArchitecture::HOLEYBYTES,
+            0x04 => InnerBinaryArchitecture::ARM64,
+            0x05 => InnerBinaryArchitecture::RISCV,
+            0x06 => InnerBinaryArchitecture::M68K,
             _ => InnerBinaryArchitecture::UNKNOWN,
         };
         BinaryArchitecture { inner }
@@ -111,6 +111,14 @@ impl Function {
         Ok(function.tlsh())
     }
 
+    #[pyo3(text_signature = "($self, other_digest)")]
+    pub fn tlsh_distance(&self, py: Python, other_digest: String) -> PyResult<Option<u32>> {
+        let binding = self.cfg.borrow(py);
+        let inner = binding.inner.lock().unwrap();
+        let function = InnerFunction::new(self.address, &inner)?;
+        Ok(function.tlsh_distance(&other_digest))
+    }
+
     #[pyo3(text_signature = "($self)")]
     pub fn minhash(&self, py: Python) -> PyResult<Option<String>> {
         let binding = self.cfg.borrow(py);
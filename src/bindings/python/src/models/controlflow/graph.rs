@@ -1,11 +1,50 @@
 use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 use binlex::models::controlflow::graph::GraphOptions as InnerGraphOptions;
 use binlex::models::controlflow::graph::GraphQueue as InnerGraphQueue;
 use binlex::models::controlflow::graph::Graph as InnerGraph;
+use binlex::models::controlflow::graph::TrapReason as InnerTrapReason;
+use binlex::models::sink::SinkKind as InnerSinkKind;
+use crate::models::binary::BinaryArchitecture;
 use crate::models::controlflow::instruction::Instruction;
 
+/// Why `GraphQueue` rejected an address during disassembly.
+#[pyclass(eq, eq_int)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrapReason {
+    IllegalOpcode,
+    OutOfBoundsTarget,
+    MisalignedTarget,
+    OverlappingInstruction,
+    DecodeLimitExceeded,
+}
+
+impl From<InnerTrapReason> for TrapReason {
+    fn from(reason: InnerTrapReason) -> Self {
+        match reason {
+            InnerTrapReason::IllegalOpcode => TrapReason::IllegalOpcode,
+            InnerTrapReason::OutOfBoundsTarget => TrapReason::OutOfBoundsTarget,
+            InnerTrapReason::MisalignedTarget => TrapReason::MisalignedTarget,
+            InnerTrapReason::OverlappingInstruction => TrapReason::OverlappingInstruction,
+            InnerTrapReason::DecodeLimitExceeded => TrapReason::DecodeLimitExceeded,
+        }
+    }
+}
+
+impl From<TrapReason> for InnerTrapReason {
+    fn from(reason: TrapReason) -> Self {
+        match reason {
+            TrapReason::IllegalOpcode => InnerTrapReason::IllegalOpcode,
+            TrapReason::OutOfBoundsTarget => InnerTrapReason::OutOfBoundsTarget,
+            TrapReason::MisalignedTarget => InnerTrapReason::MisalignedTarget,
+            TrapReason::OverlappingInstruction => InnerTrapReason::OverlappingInstruction,
+            TrapReason::DecodeLimitExceeded => InnerTrapReason::DecodeLimitExceeded,
+        }
+    }
+}
+
 #[pyclass]
 pub struct GraphOptions {
     #[pyo3(get, set)]
@@ -136,6 +175,25 @@ impl GraphQueue {
         self.inner.dequeue_all()
     }
 
+    #[pyo3(text_signature = "($self, address, reason)")]
+    pub fn insert_trap(&mut self, address: u64, reason: TrapReason) {
+        self.inner.insert_trap(address, reason.into());
+    }
+
+    #[pyo3(text_signature = "($self, address)")]
+    pub fn trap_reason(&self, address: u64) -> Option<TrapReason> {
+        self.inner.trap_reason(address).map(TrapReason::from)
+    }
+
+    #[pyo3(text_signature = "($self)")]
+    pub fn traps(&self) -> BTreeMap<u64, TrapReason> {
+        self.inner
+            .traps()
+            .iter()
+            .map(|entry| (*entry.key(), TrapReason::from(*entry.value())))
+            .collect()
+    }
+
 }
 
 #[pyclass]
@@ -147,9 +205,9 @@ pub struct Graph {
 #[pymethods]
 impl Graph {
     #[new]
-    #[pyo3(text_signature = "()")]
-    pub fn new() -> Self {
-        let inner = InnerGraph::new();
+    #[pyo3(text_signature = "(architecture)")]
+    pub fn new(architecture: Py<BinaryArchitecture>, py: Python) -> Self {
+        let inner = InnerGraph::new(architecture.borrow(py).inner);
         let inner_options = inner.options.clone();
         Self {
             inner: inner,
@@ -343,12 +401,88 @@ impl Graph {
         self.inner.options.tags.clone()
     }
 
+    /// Selects where `Block`/`Signature` stream their JSON output to:
+    /// `"none"` (the default), `"file"`, `"stdout"`, or `"tcp"`. `"file"` and
+    /// `"tcp"` read their destination from `sink_target` (a file path or a
+    /// `host:port` address, respectively).
+    #[setter]
+    fn set_option_sink_kind(&mut self, sink_kind: String) -> PyResult<()> {
+        let target = match &self.inner.options.sink {
+            InnerSinkKind::File(target) | InnerSinkKind::Tcp(target) => target.clone(),
+            InnerSinkKind::None | InnerSinkKind::Stdout => String::new(),
+        };
+        self.inner.options.sink = match sink_kind.to_ascii_lowercase().as_str() {
+            "none" => InnerSinkKind::None,
+            "file" => InnerSinkKind::File(target),
+            "stdout" => InnerSinkKind::Stdout,
+            "tcp" => InnerSinkKind::Tcp(target),
+            other => return Err(PyValueError::new_err(format!("unknown sink kind: {}", other))),
+        };
+        Ok(())
+    }
+
+    #[getter]
+    fn get_option_sink_kind(&self) -> String {
+        match self.inner.options.sink {
+            InnerSinkKind::None => "none",
+            InnerSinkKind::File(_) => "file",
+            InnerSinkKind::Stdout => "stdout",
+            InnerSinkKind::Tcp(_) => "tcp",
+        }.to_string()
+    }
+
+    #[setter]
+    fn set_option_sink_target(&mut self, sink_target: String) {
+        self.inner.options.sink = match &self.inner.options.sink {
+            InnerSinkKind::File(_) => InnerSinkKind::File(sink_target),
+            InnerSinkKind::Tcp(_) => InnerSinkKind::Tcp(sink_target),
+            other => other.clone(),
+        };
+    }
+
+    #[getter]
+    fn get_option_sink_target(&self) -> Option<String> {
+        match &self.inner.options.sink {
+            InnerSinkKind::File(target) | InnerSinkKind::Tcp(target) => Some(target.clone()),
+            InnerSinkKind::None | InnerSinkKind::Stdout => None,
+        }
+    }
+
+    /// Opens the configured sink (see `sink_kind`/`sink_target`) so
+    /// subsequent `Block.json()`/`Signature.json()` calls stream their output
+    /// as it is produced, instead of only returning it to the caller.
+    #[pyo3(text_signature = "($self)")]
+    pub fn open_sink(&mut self) -> PyResult<()> {
+        self.inner.open_sink().map_err(|error| PyValueError::new_err(error.to_string()))
+    }
+
     #[pyo3(text_signature = "($self, cfg)")]
     pub fn absorb(&mut self, py: Python, cfg: Py<Self>) {
         let mut a = cfg.borrow_mut(py);
         self.inner.absorb(&mut a.inner);
     }
 
+    /// Serializes this graph's control-flow analysis state into a compact
+    /// binary form, so it can be cached to disk instead of re-disassembled
+    /// on the next run. See `Graph.from_packed`.
+    #[pyo3(text_signature = "($self)")]
+    pub fn to_packed(&self) -> Vec<u8> {
+        self.inner.to_packed()
+    }
+
+    /// Reconstructs a `Graph` from bytes produced by `to_packed`. Run
+    /// configuration (hashing, compression, etc.) isn't carried by the
+    /// packed bytes, so the result starts with default options; set them
+    /// again via the usual `option_*` properties before use.
+    #[staticmethod]
+    #[pyo3(text_signature = "(bytes)")]
+    pub fn from_packed(bytes: Vec<u8>) -> PyResult<Self> {
+        let inner = InnerGraph::from_packed(&bytes, InnerGraphOptions::new())
+            .map_err(|error| PyValueError::new_err(error.to_string()))?;
+        let inner_options = inner.options.clone();
+        Ok(Self { inner, inner_options })
+    }
+
 }
 
 
@@ -360,6 +494,7 @@ pub fn graph_init(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<GraphOptions>()?;
     m.add_class::<GraphQueue>()?;
     m.add_class::<Graph>()?;
+    m.add_class::<TrapReason>()?;
     py.import_bound("sys")?
         .getattr("modules")?
         .set_item("binlex.models.controlflow.graph", m)?;
@@ -1,7 +1,11 @@
 use pyo3::prelude::*;
+use pyo3::basic::CompareOp;
+use pyo3::exceptions::PyValueError;
 
 use std::io::Error;
 use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 use binlex::models::controlflow::instruction::Instruction as InnerInstruction;
 
 use crate::models::binary::BinaryArchitecture;
@@ -56,6 +60,56 @@ impl Instruction {
         self.inner.print()
     }
 
+    #[staticmethod]
+    #[pyo3(text_signature = "(data)")]
+    pub fn from_json(data: String) -> Result<Self, Error> {
+        Ok(Self {
+            inner: InnerInstruction::from_json(&data)?,
+        })
+    }
+
+    /// Equality and ordering are keyed on `address` alone, matching how
+    /// instructions are deduplicated and sorted elsewhere in the crate (e.g.
+    /// `Block`/`Function` addressing).
+    pub fn __richcmp__(&self, other: &Instruction, op: CompareOp) -> bool {
+        match op {
+            CompareOp::Eq => self.inner.address == other.inner.address,
+            CompareOp::Ne => self.inner.address != other.inner.address,
+            CompareOp::Lt => self.inner.address < other.inner.address,
+            CompareOp::Le => self.inner.address <= other.inner.address,
+            CompareOp::Gt => self.inner.address > other.inner.address,
+            CompareOp::Ge => self.inner.address >= other.inner.address,
+        }
+    }
+
+    pub fn __hash__(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.inner.address.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Instruction(address=0x{:x}, size={})",
+            self.inner.address,
+            self.inner.size(),
+        )
+    }
+
+    pub fn __str__(&self) -> String {
+        self.__repr__()
+    }
+
+    /// Pickling support: reconstructs the instruction from its JSON form via
+    /// `from_json`, so `Instruction` can cross `multiprocessing`/`pickle`
+    /// boundaries without the default `__new__`-then-`__setstate__` flow,
+    /// which would need `architecture` to reconstruct an empty instance first.
+    pub fn __reduce__(&self, py: Python) -> PyResult<(Py<PyAny>, (String,))> {
+        let json = self.json().map_err(|error| PyValueError::new_err(error.to_string()))?;
+        let class = py.get_type_bound::<Instruction>();
+        let from_json = class.getattr("from_json")?.unbind();
+        Ok((from_json, (json,)))
+    }
 }
 
 #[pymodule]
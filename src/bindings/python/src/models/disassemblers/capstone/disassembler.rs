@@ -4,7 +4,8 @@ use pyo3::Py;
 use std::io::Error;
 use std::collections::BTreeSet;
 use std::collections::BTreeMap;
-use binlex::models::disassemblers::capstone::disassembler::Disassembler as InnerDisassembler;
+use binlex::models::disassemblers::backend;
+use binlex::models::disassemblers::backend::DisassemblerBackend;
 use crate::models::binary::BinaryArchitecture;
 use crate::models::controlflow::graph::Graph;
 use pyo3::types::PyBytes;
@@ -16,6 +17,19 @@ pub struct Disassembler{
     executable_address_ranges: BTreeMap<u64, u64>,
 }
 
+impl Disassembler {
+    /// Builds the concrete `DisassemblerBackend` for `self.machine`, deferring
+    /// to `binlex::models::disassemblers::backend::for_architecture` so this
+    /// binding doesn't have to keep its own copy of the architecture-to-backend
+    /// dispatch table in sync with the core crate's.
+    fn backend(&self, py: Python) -> Result<Box<dyn DisassemblerBackend>, Error> {
+        let machine_binding = &self.machine.borrow(py);
+        let image = self.image.as_bytes(py).to_vec();
+        let executable_address_ranges = self.executable_address_ranges.clone();
+        backend::for_architecture(machine_binding.inner, image, executable_address_ranges)
+    }
+}
+
 #[pymethods]
 impl Disassembler {
     #[new]
@@ -30,8 +44,7 @@ impl Disassembler {
 
     #[pyo3(text_signature = "($self, address, cfg)")]
     pub fn disassemble_function(&self, py: Python, address: u64, cfg: Py<Graph>) -> Result<u64, Error> {
-        let machine_binding = &self.machine.borrow(py);
-        let disassembler = InnerDisassembler::new(machine_binding.inner, self.image.as_bytes(py), self.executable_address_ranges.clone())?;
+        let disassembler = self.backend(py)?;
         let cfg_ref=  &mut cfg.borrow_mut(py);
         let result = disassembler.disassemble_function(address, &mut cfg_ref.inner)?;
         return Ok(result);
@@ -39,8 +52,7 @@ impl Disassembler {
 
     #[pyo3(text_signature = "($self, address, cfg)")]
     pub fn disassemble_block(&self, py: Python, address: u64, cfg: Py<Graph>) -> Result<u64, Error> {
-        let machine_binding = &self.machine.borrow(py);
-        let disassembler = InnerDisassembler::new(machine_binding.inner, self.image.as_bytes(py), self.executable_address_ranges.clone())?;
+        let disassembler = self.backend(py)?;
         let cfg_ref=  &mut cfg.borrow_mut(py);
         let result = disassembler.disassemble_block(address, &mut cfg_ref.inner)?;
         return Ok(result);
@@ -48,8 +60,7 @@ impl Disassembler {
 
     #[pyo3(text_signature = "($self, addresses, cfg)")]
     pub fn disassemble_controlflow(&self, py: Python, addresses: BTreeSet<u64>, cfg: Py<Graph>) -> Result<(), Error> {
-        let machine_binding = &self.machine.borrow(py);
-        let disassembler = InnerDisassembler::new(machine_binding.inner, self.image.as_bytes(py), self.executable_address_ranges.clone())?;
+        let disassembler = self.backend(py)?;
         let cfg_ref=  &mut cfg.borrow_mut(py);
         disassembler.disassemble_control_flow(addresses, &mut cfg_ref.inner)?;
         Ok(())
@@ -57,8 +68,7 @@ impl Disassembler {
 
     #[pyo3(text_signature = "($self, addresses, cfg)")]
     pub fn disassemble_linear_pass(&self, py: Python, valid_jump_threshold: usize, valid_instruction_threshold: usize) -> Result<BTreeSet<u64>, Error> {
-        let machine_binding = &self.machine.borrow(py);
-        let disassembler = InnerDisassembler::new(machine_binding.inner, self.image.as_bytes(py), self.executable_address_ranges.clone())?;
+        let disassembler = self.backend(py)?;
         let results = disassembler.disassemble_linear_pass(valid_jump_threshold, valid_instruction_threshold);
         let mut asdf = BTreeSet::<u64>::new();
         for result in results {
@@ -51,17 +51,22 @@ pub enum Format {
     CODE = 0x00,
     /// Portable Executable
     PE = 0x01,
+    /// Executable and Linkable Format
+    ELF = 0x02,
+    /// Mach-O
+    MACHO = 0x03,
     /// Unknown formats
-    UNKNOWN = 0x02,
+    UNKNOWN = 0x04,
 }
 
 impl Format {
     pub fn from_file(path: String) -> Result<Format, Error> {
         let mut file = File::open(path)?;
-        let mut buffer = [0u8; 2];
+        let mut buffer = [0u8; 4];
         file.seek(SeekFrom::Start(0x00))?;
         file.read_exact(&mut buffer)?;
-        if buffer == [0x4d, 0x5a] {
+
+        if buffer[0..2] == [0x4d, 0x5a] {
             file.seek(SeekFrom::Start(0x3c))?;
             let mut pe_offset = [0u8; 4];
             file.read_exact(&mut pe_offset)?;
@@ -73,6 +78,20 @@ impl Format {
                 return Ok(Format::PE);
             }
         }
+
+        if buffer == [0x7f, 0x45, 0x4c, 0x46] {
+            return Ok(Format::ELF);
+        }
+
+        if buffer == [0xfe, 0xed, 0xfa, 0xce]
+            || buffer == [0xce, 0xfa, 0xed, 0xfe]
+            || buffer == [0xfe, 0xed, 0xfa, 0xcf]
+            || buffer == [0xcf, 0xfa, 0xed, 0xfe]
+            || buffer == [0xca, 0xfe, 0xba, 0xbe]
+            || buffer == [0xbe, 0xba, 0xfe, 0xca] {
+            return Ok(Format::MACHO);
+        }
+
         return Ok(Format::UNKNOWN);
     }
 }
@@ -82,6 +101,8 @@ impl fmt::Display for Format {
         let format: &str = match self {
             Format::CODE => "code",
             Format::PE => "pe",
+            Format::ELF => "elf",
+            Format::MACHO => "macho",
             Format::UNKNOWN => "unknown",
         };
         write!(f, "{}", format)
@@ -94,6 +115,8 @@ impl FromStr for Format {
         match s {
             "code" => Ok(Format::CODE),
             "pe" => Ok(Format::PE),
+            "elf" => Ok(Format::ELF),
+            "macho" => Ok(Format::MACHO),
             "unknown" => Ok(Format::UNKNOWN),
             _ => Err(format!("invalid format")),
         }
@@ -108,6 +131,8 @@ pub enum Architecture {
     AMD64 = 0x00,
     /// 32-bit Intel architecture.
     I386 = 0x01,
+    /// 64-bit ARM architecture.
+    ARM64 = 0x02,
     /// Unknown architecture.
     UNKNOWN= 0x03,
 }
@@ -118,6 +143,7 @@ impl fmt::Display for Architecture {
         let architecture = match self {
             Architecture::AMD64 => "amd64",
             Architecture::I386 => "i386",
+            Architecture::ARM64 => "arm64",
             Architecture::UNKNOWN => "unknown",
         };
         write!(f, "{}", architecture)
@@ -130,6 +156,7 @@ impl FromStr for Architecture {
         match s {
             "amd64" => Ok(Architecture::AMD64),
             "i386" => Ok(Architecture::I386),
+            "arm64" => Ok(Architecture::ARM64),
             _ => Err(format!("invalid architecutre")),
         }
     }
@@ -151,6 +178,28 @@ pub struct ConfigSignatures {
 pub struct ConfigFunctions {
     pub hashing: ConfigHashing,
     pub heuristics: ConfigHeuristics,
+    #[serde(default)]
+    pub lsh: ConfigLSH,
+}
+
+/// Settings for the MinHash LSH banding index used to cluster near-duplicate
+/// functions. A signature's `number_of_hashes` (see `ConfigMinhash`) must
+/// equal `bands * rows` for a function to be indexed.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConfigLSH {
+    pub enabled: bool,
+    pub bands: usize,
+    pub rows: usize,
+}
+
+impl Default for ConfigLSH {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            bands: 16,
+            rows: 4,
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -164,8 +213,19 @@ pub struct ConfigFormats {
     pub file: ConfigFile,
 }
 
+/// The current on-disk schema version for `Config`. Bumped whenever a field is
+/// added, renamed, or restructured in a way that an older `binlex.toml` can't be
+/// deserialized into directly; `Config::from_file` uses this to decide whether a
+/// loaded file needs to be migrated forward before use.
+pub const CONFIG_SCHEMA_VERSION: u32 = 3;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Config {
+    /// The schema version this configuration was written with. Missing from files
+    /// written before versioning was introduced, in which case it defaults to `0`
+    /// and `Config::from_file` migrates it up to `CONFIG_SCHEMA_VERSION`.
+    #[serde(default)]
+    pub schema_version: u32,
     pub general: ConfigGeneral,
     pub formats: ConfigFormats,
     pub blocks: ConfigBlocks,
@@ -178,6 +238,8 @@ pub struct Config {
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConfigDisassembler {
     pub sweep: ConfigDisassemblerSweep,
+    #[serde(default)]
+    pub prologues: ConfigDisassemblerPrologues,
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -185,6 +247,18 @@ pub struct ConfigDisassemblerSweep {
     pub enabled: bool,
 }
 
+/// Function prologue signatures, one list per architecture, expressed in the
+/// nibble-with-wildcard syntax `Genome` parses (`?` = wildcard nibble, hex
+/// digit = fixed nibble). Loaded here instead of compiled in so new
+/// architectures or custom prologues can be added without touching code.
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct ConfigDisassemblerPrologues {
+    #[serde(default)]
+    pub amd64: Vec<String>,
+    #[serde(default)]
+    pub i386: Vec<String>,
+}
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConfigHeuristics {
     pub features: ConfigHeuristicFeatures,
@@ -245,12 +319,156 @@ pub struct ConfigMinhash {
     pub shingle_size: usize,
     pub maximum_byte_size: usize,
     pub seed: u64,
+    /// FracMinHash scale factor. When nonzero, shingle hashes are kept whenever
+    /// `hash <= u64::MAX / scaled` instead of keeping the `number_of_hashes`
+    /// smallest permutation hashes, producing a sketch whose size scales with
+    /// the input instead of staying fixed. Takes precedence over
+    /// `number_of_hashes` when set. `0` keeps the existing bottom-k behavior.
+    #[serde(default)]
+    pub scaled: u64,
+    /// Minimum containment score (`|A ∩ B| / |A|`, see `FracMinHashSketch::containment`)
+    /// for a pair to be emitted by downstream matching. `0.0` emits every pair.
+    #[serde(default)]
+    pub containment_threshold: f64,
+    /// Hash function used to digest each shingle before it's fed into the sketch.
+    #[serde(default)]
+    pub hash_backend: ConfigMinhashBackend,
+}
+
+/// Selects the hash function a `ConfigMinhash`-driven sketch digests shingles with.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum ConfigMinhashBackend {
+    /// The existing per-byte `std::hash::Hash`-based combinator.
+    Default,
+    /// A single-pass XXH3 digest, seeded with `ConfigMinhash::seed`.
+    Xxh3,
+}
+
+impl Default for ConfigMinhashBackend {
+    fn default() -> Self {
+        ConfigMinhashBackend::Default
+    }
+}
+
+impl fmt::Display for ConfigMinhashBackend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let backend = match self {
+            ConfigMinhashBackend::Default => "default",
+            ConfigMinhashBackend::Xxh3 => "xxh3",
+        };
+        write!(f, "{}", backend)
+    }
+}
+
+impl FromStr for ConfigMinhashBackend {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(ConfigMinhashBackend::Default),
+            "xxh3" => Ok(ConfigMinhashBackend::Xxh3),
+            _ => Err(format!("invalid minhash hash backend")),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct ConfigTLSH {
     pub enabled: bool,
     pub minimum_byte_size: usize,
+    /// Maximum TLSH distance (see `ConfigTLSH::distance`) for two digests to be
+    /// considered near-duplicates by `ConfigTLSH::cluster`. Lower is stricter;
+    /// `100` is a commonly used default for "likely related" files.
+    #[serde(default = "ConfigTLSH::default_diff_threshold")]
+    pub diff_threshold: u32,
+}
+
+impl ConfigTLSH {
+    fn default_diff_threshold() -> u32 {
+        100
+    }
+
+    /// Decodes a 70-character TLSH hex digest into its length bucket, the two
+    /// Q-ratio quartiles, and its 128 2-bit body buckets.
+    ///
+    /// Mirrors `models::hashing::tlsh::TLSH::decode`; duplicated here since this
+    /// config type isn't reachable from that tree.
+    fn decode(digest: &str) -> Option<(u8, u8, u8, [u8; 128])> {
+        if digest.len() != 70 { return None; }
+        let mut bytes = [0u8; 35];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&digest[index * 2..index * 2 + 2], 16).ok()?;
+        }
+        let length_bucket = bytes[1];
+        let q1_ratio = bytes[2] >> 4;
+        let q2_ratio = bytes[2] & 0x0f;
+        let mut buckets = [0u8; 128];
+        for (index, body_byte) in bytes[3..35].iter().enumerate() {
+            for nibble in 0..4 {
+                buckets[index * 4 + nibble] = (body_byte >> (6 - nibble * 2)) & 0x3;
+            }
+        }
+        Some((length_bucket, q1_ratio, q2_ratio, buckets))
+    }
+
+    fn qratio_distance(a: u8, b: u8) -> u32 {
+        let diff = (a as i32 - b as i32).unsigned_abs();
+        diff.min(16 - diff) * 12
+    }
+
+    /// Computes the TLSH distance between two hex digests. Lower scores mean more
+    /// similar; `0` means identical. Returns `None` if either digest is malformed.
+    pub fn distance(digest_a: &str, digest_b: &str) -> Option<u32> {
+        let (length_a, q1_a, q2_a, body_a) = Self::decode(digest_a)?;
+        let (length_b, q1_b, q2_b, body_b) = Self::decode(digest_b)?;
+
+        let mut distance = (length_a as i32 - length_b as i32).unsigned_abs() * 12;
+        distance += Self::qratio_distance(q1_a, q1_b);
+        distance += Self::qratio_distance(q2_a, q2_b);
+
+        for (value_a, value_b) in body_a.iter().zip(body_b.iter()) {
+            let diff = (*value_a as i32 - *value_b as i32).unsigned_abs();
+            distance += if diff > 1 { 6 } else { diff };
+        }
+
+        Some(distance)
+    }
+
+    /// Groups `digests` into near-duplicate clusters by agglomerative (single-linkage)
+    /// grouping: any pair whose `distance` is at or below `diff_threshold` is merged
+    /// into the same cluster via union-find. Malformed digests never merge with
+    /// anything and end up in a singleton cluster of their own. Returns each cluster
+    /// as the list of indices into `digests` belonging to it.
+    pub fn cluster(digests: &[&str], diff_threshold: u32) -> Vec<Vec<usize>> {
+        let mut parent: Vec<usize> = (0..digests.len()).collect();
+
+        fn find(parent: &mut Vec<usize>, i: usize) -> usize {
+            if parent[i] != i {
+                parent[i] = find(parent, parent[i]);
+            }
+            parent[i]
+        }
+
+        for i in 0..digests.len() {
+            for j in (i + 1)..digests.len() {
+                if let Some(distance) = Self::distance(digests[i], digests[j]) {
+                    if distance <= diff_threshold {
+                        let root_i = find(&mut parent, i);
+                        let root_j = find(&mut parent, j);
+                        if root_i != root_j {
+                            parent[root_i] = root_j;
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut clusters: std::collections::BTreeMap<usize, Vec<usize>> = std::collections::BTreeMap::new();
+        for i in 0..digests.len() {
+            let root = find(&mut parent, i);
+            clusters.entry(root).or_default().push(i);
+        }
+        clusters.into_values().collect()
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -262,6 +480,7 @@ impl Config {
     #[allow(dead_code)]
     pub fn new() -> Self {
         Config {
+            schema_version: CONFIG_SCHEMA_VERSION,
             general: ConfigGeneral {
                 threads: 1,
                 minimal: false,
@@ -276,6 +495,7 @@ impl Config {
                         tlsh: ConfigTLSH {
                             enabled: true,
                             minimum_byte_size: 50,
+                            diff_threshold: 100,
                         },
                         minhash: ConfigMinhash {
                             enabled: true,
@@ -283,6 +503,9 @@ impl Config {
                             shingle_size: 4,
                             maximum_byte_size: 50,
                             seed: 0,
+                            scaled: 0,
+                            containment_threshold: 0.0,
+                            hash_backend: ConfigMinhashBackend::Default,
                         }
                     },
                     heuristics: ConfigHeuristics {
@@ -306,6 +529,7 @@ impl Config {
                     tlsh: ConfigTLSH {
                         enabled: true,
                         minimum_byte_size: 50,
+                        diff_threshold: 100,
                     },
                     minhash: ConfigMinhash {
                         enabled: true,
@@ -313,6 +537,9 @@ impl Config {
                         shingle_size: 4,
                         maximum_byte_size: 50,
                         seed: 0,
+                        scaled: 0,
+                        containment_threshold: 0.0,
+                        hash_backend: ConfigMinhashBackend::Default,
                     }
                 },
                 heuristics: ConfigHeuristics {
@@ -335,6 +562,7 @@ impl Config {
                     tlsh: ConfigTLSH {
                         enabled: true,
                         minimum_byte_size: 50,
+                        diff_threshold: 100,
                     },
                     minhash: ConfigMinhash {
                         enabled: true,
@@ -342,6 +570,9 @@ impl Config {
                         shingle_size: 4,
                         maximum_byte_size: 50,
                         seed: 0,
+                        scaled: 0,
+                        containment_threshold: 0.0,
+                        hash_backend: ConfigMinhashBackend::Default,
                     }
                 },
                 heuristics: ConfigHeuristics {
@@ -354,7 +585,8 @@ impl Config {
                     entropy: ConfigHeuristicEntropy {
                         enabled: true,
                     }
-                }
+                },
+                lsh: ConfigLSH::default(),
             },
             signatures: ConfigSignatures {
                 hashing: ConfigHashing {
@@ -364,6 +596,7 @@ impl Config {
                     tlsh: ConfigTLSH {
                         enabled: true,
                         minimum_byte_size: 50,
+                        diff_threshold: 100,
                     },
                     minhash: ConfigMinhash {
                         enabled: true,
@@ -371,6 +604,9 @@ impl Config {
                         shingle_size: 4,
                         maximum_byte_size: 50,
                         seed: 0,
+                        scaled: 0,
+                        containment_threshold: 0.0,
+                        hash_backend: ConfigMinhashBackend::Default,
                     }
                 },
                 heuristics: ConfigHeuristics {
@@ -394,6 +630,27 @@ impl Config {
             disassembler: ConfigDisassembler {
                 sweep: ConfigDisassemblerSweep {
                     enabled: true,
+                },
+                prologues: ConfigDisassemblerPrologues {
+                    amd64: vec![
+                        // mov reg, rsp
+                        // sub rsp, imm
+                        "4?8B??4?83EC??".to_string(),
+                        // mov reg, rsp
+                        // mov qword [reg + local], param
+                        "4?8B??4?89????".to_string(),
+                        // sub rsp, imm
+                        "4?83EC??".to_string(),
+                        // mov rbp, rsp
+                        // sub rsp, imm
+                        "4?8BEC4?81EC????????".to_string(),
+                    ],
+                    i386: vec![
+                        // mov [esp + local], param
+                        // push reg (x2)
+                        // sub esp, imm
+                        "894424??5?5?83EC??".to_string(),
+                    ],
                 }
             }
         }
@@ -489,14 +746,41 @@ impl Config {
         toml::to_string_pretty(self).map_err(|e| Error::new(ErrorKind::Other, e))
     }
 
-    /// Reads the Configuration TOML from a File Path
+    /// Reads the Configuration TOML from a File Path, migrating it forward to
+    /// `CONFIG_SCHEMA_VERSION` if it was written by an older version of binlex.
     pub fn from_file(file_path: &str) -> Result<Config, Error> {
         let toml_string = fs::read_to_string(file_path)?;
-        let config: Config = toml::from_str(&toml_string)
+        let mut config: Config = toml::from_str(&toml_string)
             .map_err(|error| Error::new(ErrorKind::InvalidData, format!("failed to read configuration file {}\n\n{}", file_path, error)))?;
+        Config::migrate(&mut config);
         Ok(config)
     }
 
+    /// Migrates a deserialized `Config` forward to `CONFIG_SCHEMA_VERSION` in place.
+    ///
+    /// Each step below only needs to backfill whatever a given on-disk version was
+    /// missing; `#[serde(default)]` on newer fields already gives sane zero values,
+    /// so migrations here only need to override the ones that should differ from
+    /// `Default`.
+    fn migrate(config: &mut Config) {
+        if config.schema_version < 1 {
+            // Schema version 0 predates `mmap.cache`; binaries from that era always
+            // cached mapped files, so preserve that behavior rather than silently
+            // switching existing installs to the new default of disabled caching.
+            config.mmap.cache.enabled = true;
+        }
+        if config.schema_version < 2 {
+            // Schema version 1 predates `disassembler.prologues`; those installs
+            // relied on the hardcoded AMD64/I386 prologue signatures, so backfill
+            // the same patterns explicitly rather than leaving the lists empty.
+            config.disassembler.prologues = Config::new().disassembler.prologues;
+        }
+        // Schema version 2 predates `functions.lsh`; `#[serde(default)]` already
+        // backfills `ConfigLSH::default()` for files missing the section, so
+        // schema version 3 needs no further migration step here.
+        config.schema_version = CONFIG_SCHEMA_VERSION;
+    }
+
     /// Write the configuration TOML to a file
     #[allow(dead_code)]
     pub fn write_to_file(&self, file_path: &str) -> Result<(), Error> {
@@ -506,6 +790,44 @@ impl Config {
         Ok(())
     }
 
+    /// Resolves `file_path` against `mmap.directory` so archives placed alongside
+    /// the mmap subsystem's own cache files can be named relative to it, while an
+    /// absolute path is used as given.
+    fn resolve_archive_path(directory: &str, file_path: &str) -> PathBuf {
+        let path = PathBuf::from(file_path);
+        if path.is_absolute() { path } else { PathBuf::from(directory).join(path) }
+    }
+
+    /// Serializes this configuration with the same compact `bincode` backend used
+    /// elsewhere for on-disk artifacts (see `Instruction::bincode`/`Block::bincode`)
+    /// and writes it to `file_path`, resolved against `self.mmap.directory` if
+    /// relative. Unlike `write_to_file`'s TOML output, this is meant to be read back
+    /// with `load_archive` rather than edited by hand.
+    #[allow(dead_code)]
+    pub fn save_archive(&self, file_path: &str) -> Result<(), Error> {
+        let path = Self::resolve_archive_path(&self.mmap.directory, file_path);
+        if let Some(parent_directory) = path.parent() {
+            if !parent_directory.exists() {
+                fs::create_dir_all(parent_directory)?;
+            }
+        }
+        let bytes = bincode::serialize(self)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        fs::write(path, bytes)
+    }
+
+    /// Reads and deserializes a configuration archive previously written by
+    /// `save_archive`. Relative `file_path`s are resolved against the default mmap
+    /// directory (`Config::default_file_mapping_directory`), since a freshly loaded
+    /// `Config` doesn't yet have its own `mmap.directory` to resolve against.
+    #[allow(dead_code)]
+    pub fn load_archive(file_path: &str) -> Result<Config, Error> {
+        let path = Self::resolve_archive_path(&Config::default_file_mapping_directory(), file_path);
+        let bytes = fs::read(path)?;
+        bincode::deserialize(&bytes)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+
     /// Writes Default TOML Configuration File To Configuration Directory
     #[allow(dead_code)]
     pub fn write_default(&self) -> Result<(), Error> {
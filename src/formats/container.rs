@@ -0,0 +1,339 @@
+use crate::models::compression::{self, CompressionAlgorithm};
+use crate::types::CachedFile;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+
+/// Magic bytes identifying a `Container` file on disk.
+const CONTAINER_MAGIC: &[u8; 4] = b"BLXC";
+
+/// Version of the on-disk `Container` trailer/footer layout.
+///
+/// Bumped from `1` to add the trailing `compressed` flag byte; `ContainerReader::open`
+/// rejects a file written with any other version, including `1`.
+const CONTAINER_VERSION: u32 = 2;
+
+/// Size in bytes of the fixed footer appended after the trailer: magic, version,
+/// trailer offset, count, and a one-byte flag recording whether records are compressed.
+const FOOTER_SIZE: usize = 25;
+
+/// Size in bytes of a single trailer entry (`address`, `offset`, `length`).
+const TRAILER_ENTRY_SIZE: usize = 24;
+
+/// A single record's address and location within the backing file.
+#[derive(Debug, Clone, Copy)]
+struct ContainerIndexEntry {
+    /// The address the record was written under.
+    address: u64,
+    /// Byte offset of the record's payload within the file.
+    offset: u64,
+    /// Length of the record's payload in bytes.
+    length: u64,
+}
+
+/// Builds a `Container` file: many serializable records (e.g. `FunctionJson`), each
+/// keyed by an address, written to one `CachedFile`-backed file with O(log n)
+/// random access by address.
+///
+/// Unlike `OutputStore`, whose trailer is looked up by sequential record index,
+/// `Container`'s trailer is laid out in Eytzinger order (a binary search tree
+/// flattened into an array, root at index 0, children of node `i` at `2i+1`/`2i+2`)
+/// so `ContainerReader::get` can binary-search by address directly against the
+/// mapped trailer bytes without touching any record payload but the one it returns.
+pub struct ContainerWriter {
+    file: CachedFile,
+    cursor: u64,
+    index: Vec<ContainerIndexEntry>,
+    compression_algorithm: Option<CompressionAlgorithm>,
+}
+
+impl ContainerWriter {
+    /// Creates a new `Container` writer backed by a file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The `PathBuf` specifying the backing file's location.
+    /// * `cache` - If `true`, the backing file is retained on disk after the writer is dropped.
+    pub fn new(path: PathBuf, cache: bool) -> Result<Self, Error> {
+        let file = CachedFile::new(path, false, cache)?;
+        Ok(Self {
+            file,
+            cursor: 0,
+            index: Vec::new(),
+            compression_algorithm: None,
+        })
+    }
+
+    /// Creates a new `Container` writer whose record payloads are compressed with
+    /// `algorithm` before being written, e.g. to shrink a `Container` of `FunctionJson`
+    /// records when `GraphOptions::enable_compression` is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The `PathBuf` specifying the backing file's location.
+    /// * `cache` - If `true`, the backing file is retained on disk after the writer is dropped.
+    /// * `algorithm` - The `CompressionAlgorithm` applied to each record's payload.
+    pub fn new_with_compression(path: PathBuf, cache: bool, algorithm: CompressionAlgorithm) -> Result<Self, Error> {
+        let mut writer = Self::new(path, cache)?;
+        writer.compression_algorithm = Some(algorithm);
+        Ok(writer)
+    }
+
+    /// Serializes `record` as JSON and appends it to the container, keyed by `address`.
+    ///
+    /// Addresses need not be appended in sorted order; `finalize` sorts the index
+    /// before laying out the trailer.
+    pub fn append<T: Serialize>(&mut self, address: u64, record: &T) -> Result<(), Error> {
+        let json = serde_json::to_vec(record)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        let payload = match self.compression_algorithm {
+            Some(algorithm) => compression::compress(&json, algorithm),
+            None => json,
+        };
+        let length = payload.len() as u64;
+
+        self.file.write(&payload[..])?;
+
+        self.index.push(ContainerIndexEntry {
+            address,
+            offset: self.cursor,
+            length,
+        });
+        self.cursor += length;
+
+        Ok(())
+    }
+
+    /// The number of records appended so far.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Flattens `sorted` (ascending by address) into an Eytzinger-ordered array of
+    /// the same length via an in-order traversal, so index `0` holds the tree's
+    /// root, and node `i`'s children live at `2i+1`/`2i+2`.
+    fn eytzinger(sorted: &[ContainerIndexEntry]) -> Vec<ContainerIndexEntry> {
+        let mut tree = sorted.to_vec();
+        let mut cursor = 0usize;
+        Self::fill_eytzinger(sorted, 0, &mut cursor, &mut tree);
+        tree
+    }
+
+    fn fill_eytzinger(sorted: &[ContainerIndexEntry], i: usize, cursor: &mut usize, tree: &mut [ContainerIndexEntry]) {
+        if i < sorted.len() {
+            Self::fill_eytzinger(sorted, 2 * i + 1, cursor, tree);
+            tree[i] = sorted[*cursor];
+            *cursor += 1;
+            Self::fill_eytzinger(sorted, 2 * i + 2, cursor, tree);
+        }
+    }
+
+    /// Finalizes the container by writing the Eytzinger-ordered trailer and a
+    /// fixed footer recording its offset and length.
+    ///
+    /// After `finalize`, the file can be reopened with `ContainerReader::open`,
+    /// which locates any record by address in O(log n) without decoding the rest.
+    pub fn finalize(mut self) -> Result<(), Error> {
+        self.index.sort_by_key(|entry| entry.address);
+        let tree = Self::eytzinger(&self.index);
+
+        let trailer_offset = self.cursor;
+        let mut trailer = Vec::with_capacity(tree.len() * TRAILER_ENTRY_SIZE);
+        for entry in &tree {
+            trailer.extend_from_slice(&entry.address.to_le_bytes());
+            trailer.extend_from_slice(&entry.offset.to_le_bytes());
+            trailer.extend_from_slice(&entry.length.to_le_bytes());
+        }
+        self.file.write(&trailer[..])?;
+
+        let mut footer = Vec::with_capacity(FOOTER_SIZE);
+        footer.extend_from_slice(CONTAINER_MAGIC);
+        footer.extend_from_slice(&CONTAINER_VERSION.to_le_bytes());
+        footer.extend_from_slice(&trailer_offset.to_le_bytes());
+        footer.extend_from_slice(&(tree.len() as u64).to_le_bytes());
+        footer.push(self.compression_algorithm.is_some() as u8);
+        self.file.write(&footer[..])?;
+
+        Ok(())
+    }
+}
+
+/// A read-only handle over a finalized `Container` file.
+///
+/// Opening a `Container` only maps the file and parses its fixed-size trailer;
+/// record payloads are decoded lazily, one at a time, by `get`.
+pub struct ContainerReader {
+    file: CachedFile,
+    tree: Vec<ContainerIndexEntry>,
+    compressed: bool,
+}
+
+impl ContainerReader {
+    /// Opens a previously finalized `Container` for random-access reads.
+    pub fn open(path: PathBuf) -> Result<Self, Error> {
+        let file = CachedFile::new(path, false, true)?;
+        let mapped = file.mmap()?;
+        let size = mapped.len();
+
+        if size < FOOTER_SIZE {
+            return Err(Error::new(ErrorKind::InvalidData, "container file is too small"));
+        }
+
+        let footer = &mapped[size - FOOTER_SIZE..];
+        if &footer[0..4] != CONTAINER_MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid container magic"));
+        }
+        let version = u32::from_le_bytes(footer[4..8].try_into().unwrap());
+        if version != CONTAINER_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("unsupported container version {}", version)));
+        }
+        let trailer_offset = u64::from_le_bytes(footer[8..16].try_into().unwrap()) as usize;
+        let count = u64::from_le_bytes(footer[16..24].try_into().unwrap()) as usize;
+        let compressed = footer[24] != 0;
+
+        let trailer_size = count
+            .checked_mul(TRAILER_ENTRY_SIZE)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "container trailer count overflows"))?;
+        let trailer_end = trailer_offset
+            .checked_add(trailer_size)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "container trailer offset overflows"))?;
+        let trailer = mapped
+            .get(trailer_offset..trailer_end)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "container trailer runs past end of file"))?;
+
+        let mut tree = Vec::with_capacity(count);
+        let mut offset = 0;
+        for _ in 0..count {
+            let address = u64::from_le_bytes(trailer[offset..offset + 8].try_into().unwrap());
+            let entry_offset = u64::from_le_bytes(trailer[offset + 8..offset + 16].try_into().unwrap());
+            let length = u64::from_le_bytes(trailer[offset + 16..offset + 24].try_into().unwrap());
+            tree.push(ContainerIndexEntry { address, offset: entry_offset, length });
+            offset += TRAILER_ENTRY_SIZE;
+        }
+
+        drop(mapped);
+        Ok(Self { file, tree, compressed })
+    }
+
+    /// The number of records in the container.
+    pub fn len(&self) -> usize {
+        self.tree.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.tree.is_empty()
+    }
+
+    /// Looks up the record at `address` in O(log n) by walking the Eytzinger-ordered
+    /// trailer, then decodes and returns only that record without touching any
+    /// other payload. Returns `Ok(None)` if no record was written under `address`.
+    pub fn get<T: DeserializeOwned>(&self, address: u64) -> Result<Option<T>, Error> {
+        let mut i = 0usize;
+        while i < self.tree.len() {
+            let entry = &self.tree[i];
+            if address == entry.address {
+                let mapped = self.file.mmap()?;
+                let start = entry.offset as usize;
+                let end = start
+                    .checked_add(entry.length as usize)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "container record length overflows"))?;
+                let payload = mapped
+                    .get(start..end)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "container record runs past end of file"))?;
+                let record = if self.compressed {
+                    let json = compression::decompress(payload)?;
+                    serde_json::from_slice(&json)
+                        .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?
+                } else {
+                    serde_json::from_slice(payload)
+                        .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?
+                };
+                return Ok(Some(record));
+            }
+            i = if address < entry.address { 2 * i + 1 } else { 2 * i + 2 };
+        }
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use std::fs;
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct TestRecord {
+        value: u64,
+    }
+
+    fn temp_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("binlex_container_test_{}_{}.bin", std::process::id(), name))
+    }
+
+    #[test]
+    fn container_round_trips_records_by_address() {
+        let path = temp_path("roundtrip");
+        let mut writer = ContainerWriter::new(path.clone(), true).unwrap();
+        writer.append(10, &TestRecord { value: 1 }).unwrap();
+        writer.append(20, &TestRecord { value: 2 }).unwrap();
+        writer.finalize().unwrap();
+
+        let reader = ContainerReader::open(path.clone()).unwrap();
+        assert_eq!(reader.len(), 2);
+        assert_eq!(reader.get::<TestRecord>(10).unwrap(), Some(TestRecord { value: 1 }));
+        assert_eq!(reader.get::<TestRecord>(20).unwrap(), Some(TestRecord { value: 2 }));
+        assert_eq!(reader.get::<TestRecord>(30).unwrap(), None);
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn container_open_rejects_a_trailer_that_runs_past_the_mapped_file() {
+        let path = temp_path("truncated-trailer");
+        let mut writer = ContainerWriter::new(path.clone(), true).unwrap();
+        writer.append(10, &TestRecord { value: 1 }).unwrap();
+        writer.finalize().unwrap();
+
+        let mut bytes = fs::read(&path).unwrap();
+        let len = bytes.len();
+        let count_start = len - FOOTER_SIZE + 16;
+        let inflated_count = u64::from_le_bytes(bytes[count_start..count_start + 8].try_into().unwrap()) + 1_000_000;
+        bytes[count_start..count_start + 8].copy_from_slice(&inflated_count.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+
+        assert!(ContainerReader::open(path.clone()).is_err());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn container_get_rejects_a_record_that_runs_past_the_mapped_file() {
+        let path = temp_path("truncated-record");
+        let mut writer = ContainerWriter::new(path.clone(), true).unwrap();
+        writer.append(10, &TestRecord { value: 1 }).unwrap();
+        writer.finalize().unwrap();
+
+        let mut bytes = fs::read(&path).unwrap();
+        let len = bytes.len();
+        let trailer_offset_start = len - FOOTER_SIZE + 8;
+        let trailer_offset = u64::from_le_bytes(bytes[trailer_offset_start..trailer_offset_start + 8].try_into().unwrap()) as usize;
+        let length_start = trailer_offset + 16;
+        let inflated_length = u64::from_le_bytes(bytes[length_start..length_start + 8].try_into().unwrap()) + 1_000_000;
+        bytes[length_start..length_start + 8].copy_from_slice(&inflated_length.to_le_bytes());
+        fs::write(&path, &bytes).unwrap();
+
+        let reader = ContainerReader::open(path.clone()).unwrap();
+        assert!(reader.get::<TestRecord>(10).is_err());
+
+        fs::remove_file(&path).ok();
+    }
+}
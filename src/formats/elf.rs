@@ -0,0 +1,148 @@
+use lief::Binary;
+use std::io::{Cursor, Error, ErrorKind};
+use std::collections::BTreeSet;
+use std::collections::BTreeMap;
+use crate::Architecture;
+use crate::formats::File;
+use crate::formats::Executable;
+use crate::formats::error::{LoaderError, LoaderStage};
+use crate::Config;
+use lief::elf::header::MACHINE_TYPES as ElfMachineType;
+
+/// Represents an ELF (Executable and Linkable Format) file, encapsulating the
+/// `lief::elf::Binary` and associated metadata. Mirrors the shape of `formats::pe::PE`
+/// so the two loaders can be driven identically through the `Executable` trait.
+pub struct ELF {
+    pub elf: lief::elf::Binary,
+    pub file: File,
+    pub config: Config,
+}
+
+impl ELF {
+    /// Creates a new `ELF` instance by reading an ELF file from the provided path.
+    ///
+    /// # Parameters
+    /// - `path`: The file path to the ELF file to be loaded.
+    ///
+    /// # Returns
+    /// A `Result` containing the `ELF` object on success or an `Error` on failure.
+    pub fn new(path: String, config: Config) -> Result<Self, Error> {
+        let mut file = File::new(path.clone(), config.clone())?;
+        match file.read() {
+            Ok(_) => (),
+            Err(error) => {
+                return Err(LoaderError::new(LoaderStage::FileRead, Some(path.clone()), "failed to read elf file")
+                    .with_source(error)
+                    .into());
+            }
+        };
+        if let Some(Binary::ELF(elf)) = Binary::parse(&path) {
+            return Ok(Self {
+                elf,
+                file,
+                config,
+            });
+        }
+        return Err(LoaderError::new(LoaderStage::FormatParse, Some(path), "invalid elf file").into());
+    }
+
+    /// Creates a new `ELF` instance from a byte vector containing ELF file data.
+    ///
+    /// # Parameters
+    /// - `bytes`: A vector of bytes representing the ELF file data.
+    ///
+    /// # Returns
+    /// A `Result` containing the `ELF` object on success or an `Error` on failure.
+    #[allow(dead_code)]
+    pub fn from_bytes(bytes: Vec<u8>, config: Config) -> Result<Self, Error> {
+        let file = File::from_bytes(bytes, config.clone());
+        let mut cursor = Cursor::new(&file.data());
+        if let Some(Binary::ELF(elf)) = Binary::from(&mut cursor) {
+            return Ok(Self { elf, file, config });
+        }
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid elf file"));
+    }
+
+    /// Returns the entry point address of the ELF file.
+    pub fn entrypoint(&self) -> u64 {
+        self.elf.header().entrypoint()
+    }
+
+    /// Returns the architecture of the ELF file as mapped to binlex's `Architecture` enum.
+    pub fn architecture(&self) -> Architecture {
+        match self.elf.header().machine_type() {
+            ElfMachineType::X86_64 => Architecture::AMD64,
+            ElfMachineType::I386 => Architecture::I386,
+            _ => Architecture::UNKNOWN,
+        }
+    }
+
+    /// Returns the addresses covered by every executable (`PF_X`) segment.
+    pub fn executable_virtual_address_ranges(&self) -> BTreeMap<u64, u64> {
+        let mut ranges = BTreeMap::<u64, u64>::new();
+        for segment in self.elf.segments() {
+            if !segment.flags().contains(lief::elf::segment::Flags::EXECUTE) { continue; }
+            let start = segment.virtual_address();
+            let end = start + segment.virtual_size();
+            ranges.insert(start, end);
+        }
+        ranges
+    }
+
+    /// Returns the set of entry points discovered from the symbol table and the ELF
+    /// header's entry point, analogous to `PE::entrypoints`.
+    pub fn entrypoints(&self) -> BTreeSet<u64> {
+        let mut entrypoints = BTreeSet::<u64>::new();
+        entrypoints.insert(self.entrypoint());
+        for symbol in self.elf.dynamic_symbols() {
+            if symbol.value() == 0 { continue; }
+            entrypoints.insert(symbol.value());
+        }
+        for symbol in self.elf.static_symbols() {
+            if symbol.value() == 0 { continue; }
+            entrypoints.insert(symbol.value());
+        }
+        entrypoints
+    }
+
+    /// Returns the size of the ELF file.
+    pub fn size(&self) -> u64 {
+        self.file.size()
+    }
+
+    /// Returns the SHA-256 hash value of the ELF file.
+    pub fn sha256(&self) -> Option<String> {
+        self.file.sha256()
+    }
+
+    /// Returns the TLSH of the ELF file.
+    pub fn tlsh(&self) -> Option<String> {
+        self.file.tlsh()
+    }
+}
+
+impl Executable for ELF {
+    fn architecture(&self) -> Architecture {
+        ELF::architecture(self)
+    }
+
+    fn entrypoints(&self) -> BTreeSet<u64> {
+        ELF::entrypoints(self)
+    }
+
+    fn executable_virtual_address_ranges(&self) -> BTreeMap<u64, u64> {
+        ELF::executable_virtual_address_ranges(self)
+    }
+
+    fn sha256(&self) -> Option<String> {
+        ELF::sha256(self)
+    }
+
+    fn tlsh(&self) -> Option<String> {
+        ELF::tlsh(self)
+    }
+
+    fn size(&self) -> u64 {
+        ELF::size(self)
+    }
+}
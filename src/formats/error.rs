@@ -0,0 +1,101 @@
+use std::fmt;
+use std::io;
+
+/// The pipeline stage a `LoaderError` originated in, so a single error type can carry
+/// enough context to tell a malformed input apart from an internal disassembly fault.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LoaderStage {
+    /// Reading the raw bytes of the input file.
+    FileRead,
+    /// Parsing the container format (PE/ELF/Mach-O) itself.
+    FormatParse,
+    /// Locating or validating entry points / symbols to seed analysis with.
+    EntrypointDiscovery,
+    /// Disassembling a specific function or block.
+    Disassembly,
+}
+
+impl fmt::Display for LoaderStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stage = match self {
+            LoaderStage::FileRead => "file read",
+            LoaderStage::FormatParse => "format parse",
+            LoaderStage::EntrypointDiscovery => "entrypoint discovery",
+            LoaderStage::Disassembly => "disassembly",
+        };
+        write!(f, "{}", stage)
+    }
+}
+
+/// A structured, context-carrying error for the format loaders and disassembly
+/// pipeline, so callers can tell a malformed-input error apart from an internal fault
+/// and report exactly which file and address triggered it.
+#[derive(Debug)]
+pub struct LoaderError {
+    /// The pipeline stage the error occurred in.
+    pub stage: LoaderStage,
+    /// The path of the file being processed, if known.
+    pub path: Option<String>,
+    /// The virtual address being processed, if the error is address-specific.
+    pub address: Option<u64>,
+    /// A human-readable description of what went wrong.
+    pub message: String,
+    /// The underlying `io::Error`, if the failure originated from one.
+    pub source: Option<io::Error>,
+}
+
+impl LoaderError {
+    /// Creates a new `LoaderError` with no address or underlying source error.
+    pub fn new(stage: LoaderStage, path: Option<String>, message: impl Into<String>) -> Self {
+        Self {
+            stage,
+            path,
+            address: None,
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Attaches the address being processed when the error occurred.
+    #[allow(dead_code)]
+    pub fn with_address(mut self, address: u64) -> Self {
+        self.address = Some(address);
+        self
+    }
+
+    /// Attaches the underlying `io::Error` that caused this error.
+    #[allow(dead_code)]
+    pub fn with_source(mut self, source: io::Error) -> Self {
+        self.source = Some(source);
+        self
+    }
+}
+
+impl fmt::Display for LoaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.stage)?;
+        if let Some(path) = &self.path {
+            write!(f, " ({})", path)?;
+        }
+        if let Some(address) = self.address {
+            write!(f, " at 0x{:x}", address)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if let Some(source) = &self.source {
+            write!(f, ": {}", source)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for LoaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_ref().map(|e| e as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<LoaderError> for io::Error {
+    fn from(error: LoaderError) -> Self {
+        io::Error::new(io::ErrorKind::Other, error.to_string())
+    }
+}
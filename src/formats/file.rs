@@ -3,13 +3,29 @@ use std::io::{Read, Error};
 use crate::models::hashing::sha256::SHA256;
 use crate::models::hashing::tlsh::TLSH;
 use std::io::ErrorKind;
+use memmap2::Mmap;
+use crate::types::Nodegraph;
+
+/// The backing storage for a `File`'s bytes: either owned in memory, or memory-mapped
+/// from disk so large inputs don't pay for a whole-file copy when mapping is enabled.
+/// The mapped variant keeps the originating file handle alive alongside the `Mmap`,
+/// since the mapping must not outlive it on some platforms.
+enum FileData {
+    Owned(Vec<u8>),
+    Mapped(StdFile, Mmap),
+}
 
 /// Represents a file with its contents and an optional file path.
+///
+/// `File` is read through the standard `Read` trait regardless of how its bytes are
+/// backed; callers that only need a byte slice can still use `data()`, which hands
+/// back the owned bytes or, for a file opened with `open_mapped`, a view into the
+/// mapping, without copying either.
 pub struct File {
-    /// The contents of the file as a byte vector.
-    pub data: Vec<u8>,
+    data: FileData,
     /// The path of the file, if available.
     pub path: Option<String>,
+    cursor: usize,
 }
 
 impl File {
@@ -24,8 +40,9 @@ impl File {
     /// A `File` instance with the given path and empty data.
     pub fn new(path: String) -> Self {
         Self {
-            data: Vec::new(),
+            data: FileData::Owned(Vec::new()),
             path: Some(path),
+            cursor: 0,
         }
     }
 
@@ -41,8 +58,30 @@ impl File {
     #[allow(dead_code)]
     pub fn from_bytes(bytes: Vec<u8>) -> Self {
         Self {
-            data: bytes,
+            data: FileData::Owned(bytes),
             path: None,
+            cursor: 0,
+        }
+    }
+
+    /// Opens `path` as a memory-mapped `File`, so `data()` reads straight out of the
+    /// mapping instead of copying the whole file into a `Vec<u8>` up front.
+    #[allow(dead_code)]
+    pub fn open_mapped(path: String) -> Result<Self, Error> {
+        let handle = StdFile::open(&path)?;
+        let mmap = unsafe { Mmap::map(&handle)? };
+        Ok(Self {
+            data: FileData::Mapped(handle, mmap),
+            path: Some(path),
+            cursor: 0,
+        })
+    }
+
+    /// Returns the file's bytes as a slice, whether owned or memory-mapped.
+    pub fn data(&self) -> &[u8] {
+        match &self.data {
+            FileData::Owned(bytes) => bytes,
+            FileData::Mapped(_, mmap) => mmap,
         }
     }
 
@@ -55,9 +94,16 @@ impl File {
     #[allow(dead_code)]
     pub fn tlsh(&self) -> Option<String> {
         if self.size() <= 0 { return None; }
-        TLSH::new(&self.data, 50).hexdigest()
+        TLSH::new(self.data(), 50).hexdigest()
     }
 
+    /// Builds a `Nodegraph` Bloom filter over every `shingle_size`-byte shingle of
+    /// the file's data, for a cheap yes/no shared-shingle screen against another
+    /// file before paying for per-function MinHash/TLSH comparisons.
+    #[allow(dead_code)]
+    pub fn nodegraph(&self, shingle_size: usize, size_bits: usize, num_hashes: usize) -> Nodegraph {
+        Nodegraph::from_bytes(self.data(), shingle_size, size_bits, num_hashes)
+    }
 
     /// Computes the SHA-256 hash of the file's data.
     ///
@@ -68,7 +114,7 @@ impl File {
     #[allow(dead_code)]
     pub fn sha256(&self) -> Option<String> {
         if self.size() <= 0 { return None; }
-        SHA256::new(&self.data).hexdigest()
+        SHA256::new(self.data()).hexdigest()
     }
 
     /// Returns the size of the file in bytes.
@@ -78,7 +124,7 @@ impl File {
     /// The size of the file in bytes as a `u64`.
     #[allow(dead_code)]
     pub fn size(&self) -> u64 {
-        self.data.len() as u64
+        self.data().len() as u64
     }
 
     /// Reads the content of the file from the given path and stores it in `data`.
@@ -94,8 +140,21 @@ impl File {
     pub fn read(&mut self) -> Result<(), Error> {
         if self.path.is_none() { return Err(Error::new(ErrorKind::InvalidInput, "missing file path to write")); }
         let mut file = StdFile::open(&self.path.clone().unwrap())?;
-        file.read_to_end(&mut self.data)?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+        self.data = FileData::Owned(bytes);
         Ok(())
     }
 
 }
+
+impl Read for File {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        let bytes = self.data();
+        let remaining = &bytes[self.cursor.min(bytes.len())..];
+        let count = remaining.len().min(buf.len());
+        buf[..count].copy_from_slice(&remaining[..count]);
+        self.cursor += count;
+        Ok(count)
+    }
+}
@@ -1,10 +1,32 @@
 use lief::Binary;
 use std::io::{Cursor, Error, ErrorKind};
+use std::collections::{BTreeMap, BTreeSet};
 use crate::Architecture;
 use crate::formats::File;
 use crate::Config;
 use lief::macho::header::CpuType as MachoCpuType;
 
+/// Mach-O `VM_PROT_EXECUTE`, used to pick out executable segments the same
+/// way `ELF::executable_virtual_address_ranges` filters on `PF_X`.
+const VM_PROT_EXECUTE: u32 = 0x4;
+
+/// Classic fat-binary magic (32-bit `fat_arch` entries).
+const FAT_MAGIC: u32 = 0xcafebabe;
+/// 64-bit fat-binary magic (`fat_arch_64` entries), used once a slice's offset
+/// or size would overflow 32 bits.
+const FAT_MAGIC_64: u32 = 0xcafebabf;
+
+/// One architecture slice of a fat Mach-O, bundling everything a disassembler
+/// needs to analyze it independently of its sibling slices: the slice's own
+/// raw bytes, its architecture, and the virtual address ranges/entry points
+/// to seed a linear sweep with.
+pub struct MachoSlice {
+    pub architecture: Architecture,
+    pub bytes: Vec<u8>,
+    pub executable_virtual_address_ranges: BTreeMap<u64, u64>,
+    pub entrypoints: BTreeSet<u64>,
+}
+
 pub struct MACHO {
     pub macho: lief::macho::FatBinary,
     pub file: File,
@@ -48,7 +70,7 @@ impl MACHO {
     #[allow(dead_code)]
     pub fn from_bytes(bytes: Vec<u8>, config: Config) -> Result<Self, Error> {
         let file = File::from_bytes(bytes, config.clone());
-        let mut cursor = Cursor::new(&file.data);
+        let mut cursor = Cursor::new(&file.data());
         if let Some(Binary::MachO(macho)) = Binary::from(&mut cursor) {
             return Ok(Self{
                 macho: macho,
@@ -73,9 +95,126 @@ impl MACHO {
         let architecture = match cpu_type.unwrap() {
             MachoCpuType::X86 => Architecture::I386,
             MachoCpuType::X86_64 => Architecture::AMD64,
+            // `arm64e` is a `cpu_subtype` of `ARM64`, not a distinct `cpu_type`, so it
+            // is already covered here alongside plain `arm64` slices.
+            MachoCpuType::ARM64 => Architecture::ARM64,
             _ => { return None; },
         };
         Some(architecture)
     }
 
+    /// Reads a big-endian `u32` at `offset` in the underlying file, bounds-checked
+    /// against the file's length instead of panicking on a truncated/malformed fat
+    /// header.
+    fn read_u32_be(data: &[u8], offset: usize) -> Option<u32> {
+        let end = offset.checked_add(4)?;
+        if end > data.len() { return None; }
+        Some(u32::from_be_bytes(data[offset..end].try_into().ok()?))
+    }
+
+    /// Reads a big-endian `u64` at `offset`, bounds-checked like `read_u32_be`.
+    fn read_u64_be(data: &[u8], offset: usize) -> Option<u64> {
+        let end = offset.checked_add(8)?;
+        if end > data.len() { return None; }
+        Some(u64::from_be_bytes(data[offset..end].try_into().ok()?))
+    }
+
+    /// Parses the fat header's `fat_arch`/`fat_arch_64` entry for `index`, returning
+    /// its `(offset, size)` within the file. Handles both the classic 32-bit
+    /// `FAT_MAGIC` layout (20-byte entries) and the `FAT_MAGIC_64` layout used once a
+    /// slice no longer fits in 32 bits (32-byte entries).
+    fn fat_arch_header(&self, index: usize) -> Option<(u64, u64)> {
+        let data = self.file.data();
+        let magic = Self::read_u32_be(&data, 0)?;
+        let number_of_archs = Self::read_u32_be(&data, 4)? as usize;
+        if index >= number_of_archs { return None; }
+
+        match magic {
+            FAT_MAGIC => {
+                let entry_offset = 8 + index * 20;
+                let offset = Self::read_u32_be(&data, entry_offset + 8)? as u64;
+                let size = Self::read_u32_be(&data, entry_offset + 12)? as u64;
+                Some((offset, size))
+            }
+            FAT_MAGIC_64 => {
+                let entry_offset = 8 + index * 32;
+                let offset = Self::read_u64_be(&data, entry_offset + 8)?;
+                let size = Self::read_u64_be(&data, entry_offset + 16)?;
+                Some((offset, size))
+            }
+            _ => None,
+        }
+    }
+
+    /// Extracts the raw bytes of a single architecture slice out of the fat binary.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if `index` is out of range or the slice's recorded offset/size
+    /// falls outside the file.
+    pub fn slice_bytes(&self, index: usize) -> Option<Vec<u8>> {
+        let (offset, size) = self.fat_arch_header(index)?;
+        let data = self.file.data();
+        let start = usize::try_from(offset).ok()?;
+        let end = start.checked_add(usize::try_from(size).ok()?)?;
+        if end > data.len() { return None; }
+        Some(data[start..end].to_vec())
+    }
+
+    /// Returns the addresses covered by every executable (`VM_PROT_EXECUTE`) segment
+    /// of the slice at `index`, analogous to `ELF::executable_virtual_address_ranges`.
+    pub fn executable_virtual_address_ranges(&self, index: usize) -> BTreeMap<u64, u64> {
+        let mut ranges = BTreeMap::<u64, u64>::new();
+        let Some(binary) = self.macho.iter().nth(index) else { return ranges; };
+        for segment in binary.segments() {
+            if segment.init_protection() & VM_PROT_EXECUTE == 0 { continue; }
+            let start = segment.virtual_address();
+            let end = start + segment.virtual_size();
+            ranges.insert(start, end);
+        }
+        ranges
+    }
+
+    /// Returns the entry points to seed analysis with for the slice at `index`: the
+    /// slice's own entry point plus every non-zero symbol table value, analogous to
+    /// `ELF::entrypoints`.
+    pub fn entrypoints(&self, index: usize) -> BTreeSet<u64> {
+        let mut entrypoints = BTreeSet::<u64>::new();
+        if let Some(entrypoint) = self.entrypoint(index) {
+            entrypoints.insert(entrypoint);
+        }
+        if let Some(binary) = self.macho.iter().nth(index) {
+            for symbol in binary.symbols() {
+                let value = symbol.value();
+                if value == 0 { continue; }
+                entrypoints.insert(value);
+            }
+        }
+        entrypoints
+    }
+
+    /// Builds a `MachoSlice` (bytes, architecture, executable ranges, and
+    /// entrypoints) for every architecture in the fat binary, so a single fat file
+    /// can be disassembled and analyzed once per architecture instead of only
+    /// enumerated.
+    ///
+    /// # Returns
+    ///
+    /// Slices whose architecture is unrecognized or whose bytes can't be extracted
+    /// are skipped rather than failing the whole file.
+    pub fn slices(&self) -> Vec<MachoSlice> {
+        let mut slices = Vec::new();
+        for index in 0..self.number_of_binaries() {
+            let Some(architecture) = self.architecture(index) else { continue; };
+            let Some(bytes) = self.slice_bytes(index) else { continue; };
+            slices.push(MachoSlice {
+                architecture,
+                bytes,
+                executable_virtual_address_ranges: self.executable_virtual_address_ranges(index),
+                entrypoints: self.entrypoints(index),
+            });
+        }
+        slices
+    }
+
 }
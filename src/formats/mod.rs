@@ -2,7 +2,35 @@ pub mod file;
 pub mod pe;
 pub mod elf;
 pub mod macho;
+pub mod error;
+pub mod container;
 
 pub use pe::PE;
 pub use file::File;
 pub use elf::ELF;
+pub use error::{LoaderError, LoaderStage};
+pub use container::{ContainerReader, ContainerWriter};
+
+use std::collections::{BTreeMap, BTreeSet};
+use crate::Architecture;
+
+/// A format-agnostic view over a loaded executable image.
+///
+/// `PE`, `ELF`, and (eventually) `MACHO` each implement this so the disassembly/CFG
+/// pipeline in `main()` can drive any of the three major object formats without being
+/// hardwired to `formats::pe::PE`.
+pub trait Executable {
+    /// The CPU architecture the image targets.
+    fn architecture(&self) -> Architecture;
+    /// Addresses to seed analysis with: exported/symbol-table functions, the image's
+    /// own entry point, and any other format-specific entry points.
+    fn entrypoints(&self) -> BTreeSet<u64>;
+    /// Virtual address ranges that are marked executable.
+    fn executable_virtual_address_ranges(&self) -> BTreeMap<u64, u64>;
+    /// SHA-256 of the underlying file, if available.
+    fn sha256(&self) -> Option<String>;
+    /// TLSH of the underlying file, if available.
+    fn tlsh(&self) -> Option<String>;
+    /// Size of the underlying file in bytes.
+    fn size(&self) -> u64;
+}
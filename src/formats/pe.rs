@@ -14,6 +14,7 @@ use crate::types::MemoryMappedFile;
 use crate::Config;
 use lief::pe::data_directory::Type as DATA_DIRECTORY;
 use std::mem;
+use ring::digest;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MetadataToken {
@@ -72,19 +73,93 @@ pub enum MetadataToken {
     CustomDebugInformation = 55,
 }
 
+/// The inverse of `MetadataToken as usize`, for table indices discovered by
+/// walking `mask_valid` rather than known ahead of time.
+///
+/// Returns `None` for the reserved/unassigned indices (45-47) between
+/// `GenericParamConstraint` and the Portable PDB tables.
+fn metadata_token_from_index(index: usize) -> Option<MetadataToken> {
+    use MetadataToken::*;
+    Some(match index {
+        0x00 => Module,
+        0x01 => TypeRef,
+        0x02 => TypeDef,
+        0x03 => FieldPtr,
+        0x04 => Field,
+        0x05 => MethodPtr,
+        0x06 => MethodDef,
+        0x07 => ParamPtr,
+        0x08 => Param,
+        0x09 => InterfaceImpl,
+        0x0A => MemberRef,
+        0x0B => Constant,
+        0x0C => CustomAttribute,
+        0x0D => FieldMarshal,
+        0x0E => DeclSecurity,
+        0x0F => ClassLayout,
+        0x10 => FieldLayout,
+        0x11 => StandAloneSig,
+        0x12 => EventMap,
+        0x13 => EventPtr,
+        0x14 => Event,
+        0x15 => PropertyMap,
+        0x16 => PropertyPtr,
+        0x17 => Property,
+        0x18 => MethodSemantics,
+        0x19 => MethodImpl,
+        0x1A => ModuleRef,
+        0x1B => TypeSpec,
+        0x1C => ImplMap,
+        0x1D => FieldRva,
+        0x1E => EncLog,
+        0x1F => EncMap,
+        0x20 => Assembly,
+        0x21 => AssemblyProcessor,
+        0x22 => AssemblyOs,
+        0x23 => AssemblyRef,
+        0x24 => AssemblyRefProcessor,
+        0x25 => AssemblyRefOs,
+        0x26 => File,
+        0x27 => ExportedType,
+        0x28 => ManifestResource,
+        0x29 => NestedClass,
+        0x2A => GenericParam,
+        0x2B => MethodSpec,
+        0x2C => GenericParamConstraint,
+        0x30 => Document,
+        0x31 => MethodDebugInformation,
+        0x32 => LocalScope,
+        0x33 => LocalVariable,
+        0x34 => LocalConstant,
+        0x35 => ImportScope,
+        0x36 => StateMachineMethod,
+        0x37 => CustomDebugInformation,
+        _ => return None,
+    })
+}
+
 #[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct ImageDataDirectory {
     pub virtual_address: u32,
     pub size: u32,
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub union ImageCor20Header0 {
     pub entry_point_token: u32,
     pub entry_point_rva: u32,
 }
 
+// SAFETY: both variants are `u32`, so every bit pattern is valid for either
+// and the union is exactly 4 bytes with no padding, satisfying `Zeroable`/`Pod`
+// the same way a `#[repr(C)]` struct of same-sized `Pod` fields would.
+unsafe impl bytemuck::Zeroable for ImageCor20Header0 {}
+unsafe impl bytemuck::Pod for ImageCor20Header0 {}
+
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct ImageCor20Header {
     pub cb: u32,
     pub major_runtime_version: u16,
@@ -101,18 +176,19 @@ pub struct ImageCor20Header {
 }
 
 impl ImageCor20Header {
-    pub fn from_bytes(bytes: &[u8]) -> Option<&Self> {
-        if bytes.len() != mem::size_of::<Self>() {
-            return None;
-        }
-        if bytes.as_ptr().align_offset(mem::align_of::<Self>()) != 0 {
-            return None;
-        }
-        Some(unsafe { &*(bytes.as_ptr() as *const Self) })
+    /// Casts `bytes` onto `&ImageCor20Header` without copying.
+    ///
+    /// `bytemuck::try_from_bytes` checks the length and alignment invariants
+    /// that the old manual `align_offset`/`size_of` guard was reimplementing
+    /// by hand, and does so without the `unsafe` pointer cast.
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytemuck::try_from_bytes(bytes)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, format!("invalid ImageCor20Header: {error}")))
     }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct Cor20StorageSignature {
     pub signature: u32,
     pub major_version: u16,
@@ -123,18 +199,14 @@ pub struct Cor20StorageSignature {
 }
 
 impl Cor20StorageSignature {
-    pub fn from_bytes(bytes: &[u8]) -> Option<&Self> {
-        if bytes.len() != mem::size_of::<Self>() {
-            return None;
-        }
-        if bytes.as_ptr().align_offset(mem::align_of::<Self>()) != 0 {
-            return None;
-        }
-        Some(unsafe { &*(bytes.as_ptr() as *const Self) })
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytemuck::try_from_bytes(bytes)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, format!("invalid Cor20StorageSignature: {error}")))
     }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct Cor20StorageHeader {
     pub flags: u8,
     pub pad: u8,
@@ -142,56 +214,62 @@ pub struct Cor20StorageHeader {
 }
 
 impl Cor20StorageHeader {
-    pub fn from_bytes(bytes: &[u8]) -> Option<&Self> {
-        if bytes.len() != mem::size_of::<Self>() {
-            return None;
-        }
-        if bytes.as_ptr().align_offset(mem::align_of::<Self>()) != 0 {
-            return None;
-        }
-        Some(unsafe { &*(bytes.as_ptr() as *const Self) })
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytemuck::try_from_bytes(bytes)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, format!("invalid Cor20StorageHeader: {error}")))
     }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct Cor20StreamHeader {
     pub offset: u32,
     pub size: u32,
 }
 
 impl Cor20StreamHeader {
-    pub fn from_bytes(bytes: &[u8]) -> Option<&Self> {
-        if bytes.len() < mem::size_of::<Cor20StreamHeader>() {
-            return None;
-        }
-        Some(unsafe { &*(bytes.as_ptr() as *const Cor20StreamHeader) })
-    }
-
-    pub fn name(&self) -> &[u8] {
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytes
+            .get(..mem::size_of::<Self>())
+            .and_then(|head| bytemuck::try_from_bytes(head).ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid Cor20StreamHeader"))
+    }
+
+    /// Reads the stream's nul-terminated, 4-byte-padded name immediately
+    /// following the fixed header. The name's length isn't known until it's
+    /// scanned, so it can't be modeled as a fixed `Pod` field; `file_data`
+    /// (the full buffer `self` was parsed out of) bounds the scan so a
+    /// header near the end of a truncated or crafted file can't run it past
+    /// the end of the mapping.
+    pub fn name<'data>(&self, file_data: &'data [u8]) -> &'data [u8] {
         let header_size = mem::size_of::<Cor20StreamHeader>();
         let base_ptr = self as *const Self as *const u8;
+        let data_ptr = file_data.as_ptr();
 
-        unsafe {
-            let name_ptr = base_ptr.add(header_size);
+        let self_offset = match (base_ptr as usize).checked_sub(data_ptr as usize) {
+            Some(offset) if offset <= file_data.len() => offset,
+            _ => return &[],
+        };
 
-            let mut len = 0;
-            while *name_ptr.add(len) != 0 {
-                len += 1;
-            }
+        let name_bytes = match file_data.get(self_offset + header_size..) {
+            Some(name_bytes) => name_bytes,
+            None => return &[],
+        };
 
-            let padded_len = (len + 4) & !3;
+        let len = name_bytes.iter().position(|&byte| byte == 0).unwrap_or(name_bytes.len());
+        let padded_len = ((len + 4) & !3).min(name_bytes.len());
 
-            std::slice::from_raw_parts(name_ptr, padded_len)
-        }
+        &name_bytes[..padded_len]
     }
 
-    pub fn header_size(&self) -> usize {
+    pub fn header_size(&self, file_data: &[u8]) -> usize {
         let header_size = mem::size_of::<Cor20StreamHeader>();
-        header_size + self.name().len()
+        header_size + self.name(file_data).len()
     }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct Cor20MetadataTable {
         pub reserved: u32,
         pub major_version: u8,
@@ -203,14 +281,225 @@ pub struct Cor20MetadataTable {
 }
 
 impl Cor20MetadataTable {
-    pub fn from_bytes(bytes: &[u8]) -> Option<&Self> {
-        if bytes.len() != mem::size_of::<Self>() {
-            return None;
-        }
-        if bytes.as_ptr().align_offset(mem::align_of::<Self>()) != 0 {
-            return None;
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytemuck::try_from_bytes(bytes)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, format!("invalid Cor20MetadataTable: {error}")))
+    }
+}
+
+/// A single column within a metadata table row, per ECMA-335 II.22.
+///
+/// Row widths differ per table and depend on `#~` stream state (`heap_sizes`
+/// and every table's row count), so rows can't be read until that state is
+/// known; see `TableSizes`.
+#[derive(Clone, Copy)]
+pub enum Column {
+    U16,
+    U32,
+    String,
+    Guid,
+    Blob,
+    /// A simple table index: 2 bytes if the referenced table has fewer than
+    /// 2^16 rows, else 4.
+    Table(MetadataToken),
+    /// A coded index: `tag_bits` low bits select which of `candidates` is
+    /// referenced (`None` entries are unused tags), sized 4 bytes if any
+    /// candidate table has more rows than `2^(16 - tag_bits)` can address,
+    /// else 2.
+    Coded(u8, &'static [Option<MetadataToken>]),
+}
+
+const RESOLUTION_SCOPE: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::Module),
+    Some(MetadataToken::ModuleRef),
+    Some(MetadataToken::AssemblyRef),
+    Some(MetadataToken::TypeRef),
+];
+const TYPE_DEF_OR_REF: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::TypeDef),
+    Some(MetadataToken::TypeRef),
+    Some(MetadataToken::TypeSpec),
+];
+const HAS_CONSTANT: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::Field),
+    Some(MetadataToken::Param),
+    Some(MetadataToken::Property),
+];
+const HAS_CUSTOM_ATTRIBUTE: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::MethodDef),
+    Some(MetadataToken::Field),
+    Some(MetadataToken::TypeRef),
+    Some(MetadataToken::TypeDef),
+    Some(MetadataToken::Param),
+    Some(MetadataToken::InterfaceImpl),
+    Some(MetadataToken::MemberRef),
+    Some(MetadataToken::Module),
+    None,
+    Some(MetadataToken::Property),
+    Some(MetadataToken::Event),
+    Some(MetadataToken::StandAloneSig),
+    Some(MetadataToken::ModuleRef),
+    Some(MetadataToken::TypeSpec),
+    Some(MetadataToken::Assembly),
+    Some(MetadataToken::AssemblyRef),
+    Some(MetadataToken::File),
+    Some(MetadataToken::ExportedType),
+    Some(MetadataToken::ManifestResource),
+    Some(MetadataToken::GenericParam),
+    Some(MetadataToken::GenericParamConstraint),
+    Some(MetadataToken::MethodSpec),
+];
+const HAS_FIELD_MARSHAL: &[Option<MetadataToken>] = &[Some(MetadataToken::Field), Some(MetadataToken::Param)];
+const HAS_DECL_SECURITY: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::TypeDef),
+    Some(MetadataToken::MethodDef),
+    Some(MetadataToken::Assembly),
+];
+const MEMBER_REF_PARENT: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::TypeDef),
+    Some(MetadataToken::TypeRef),
+    Some(MetadataToken::ModuleRef),
+    Some(MetadataToken::MethodDef),
+    Some(MetadataToken::TypeSpec),
+];
+const HAS_SEMANTICS: &[Option<MetadataToken>] = &[Some(MetadataToken::Event), Some(MetadataToken::Property)];
+const METHOD_DEF_OR_REF: &[Option<MetadataToken>] = &[Some(MetadataToken::MethodDef), Some(MetadataToken::MemberRef)];
+const MEMBER_FORWARDED: &[Option<MetadataToken>] = &[Some(MetadataToken::Field), Some(MetadataToken::MethodDef)];
+const IMPLEMENTATION: &[Option<MetadataToken>] = &[
+    Some(MetadataToken::File),
+    Some(MetadataToken::AssemblyRef),
+    Some(MetadataToken::ExportedType),
+];
+const CUSTOM_ATTRIBUTE_TYPE: &[Option<MetadataToken>] = &[
+    None,
+    None,
+    Some(MetadataToken::MethodDef),
+    Some(MetadataToken::MemberRef),
+    None,
+    None,
+    None,
+    None,
+];
+const TYPE_OR_METHOD_DEF: &[Option<MetadataToken>] = &[Some(MetadataToken::TypeDef), Some(MetadataToken::MethodDef)];
+// Portable PDB's `HasCustomDebugInformation` coded index reuses the same
+// candidate table list and tag width as `HasCustomAttribute`.
+const HAS_CUSTOM_DEBUG_INFORMATION: &[Option<MetadataToken>] = HAS_CUSTOM_ATTRIBUTE;
+
+/// Returns the ECMA-335 (and Portable PDB) column schema for the table at
+/// `index`, or `None` if `index` is a reserved/unassigned table slot.
+///
+/// This schema is what lets `cor20_metadata_table_entries` compute every
+/// table's row stride and walk past tables it has no typed `Entry` for yet.
+fn metadata_table_columns(index: usize) -> Option<&'static [Column]> {
+    use Column::*;
+    use MetadataToken::*;
+    Some(match index {
+        0x00 => &[U16, String, Guid, Guid, Guid], // Module
+        0x01 => &[Coded(2, RESOLUTION_SCOPE), String, String], // TypeRef
+        0x02 => &[U32, String, String, Coded(2, TYPE_DEF_OR_REF), Table(Field), Table(MethodDef)], // TypeDef
+        0x03 => &[Table(Field)], // FieldPtr
+        0x04 => &[U16, String, Blob], // Field
+        0x05 => &[Table(MethodDef)], // MethodPtr
+        0x06 => &[U32, U16, U16, String, Blob, Table(Param)], // MethodDef
+        0x07 => &[Table(Param)], // ParamPtr
+        0x08 => &[U16, U16, String], // Param
+        0x09 => &[Table(TypeDef), Coded(2, TYPE_DEF_OR_REF)], // InterfaceImpl
+        0x0A => &[Coded(3, MEMBER_REF_PARENT), String, Blob], // MemberRef
+        0x0B => &[U16, Coded(2, HAS_CONSTANT), Blob], // Constant
+        0x0C => &[Coded(5, HAS_CUSTOM_ATTRIBUTE), Coded(3, CUSTOM_ATTRIBUTE_TYPE), Blob], // CustomAttribute
+        0x0D => &[Coded(1, HAS_FIELD_MARSHAL), Blob], // FieldMarshal
+        0x0E => &[U16, Coded(2, HAS_DECL_SECURITY), Blob], // DeclSecurity
+        0x0F => &[U16, U32, Table(TypeDef)], // ClassLayout
+        0x10 => &[U32, Table(Field)], // FieldLayout
+        0x11 => &[Blob], // StandAloneSig
+        0x12 => &[Table(TypeDef), Table(Event)], // EventMap
+        0x13 => &[Table(Event)], // EventPtr
+        0x14 => &[U16, String, Coded(2, TYPE_DEF_OR_REF)], // Event
+        0x15 => &[Table(TypeDef), Table(Property)], // PropertyMap
+        0x16 => &[Table(Property)], // PropertyPtr
+        0x17 => &[U16, String, Blob], // Property
+        0x18 => &[U16, Table(MethodDef), Coded(1, HAS_SEMANTICS)], // MethodSemantics
+        0x19 => &[Table(TypeDef), Coded(1, METHOD_DEF_OR_REF), Coded(1, METHOD_DEF_OR_REF)], // MethodImpl
+        0x1A => &[String], // ModuleRef
+        0x1B => &[Blob], // TypeSpec
+        0x1C => &[U16, Coded(1, MEMBER_FORWARDED), String, Table(ModuleRef)], // ImplMap
+        0x1D => &[U32, Table(Field)], // FieldRva
+        0x1E => &[U32, U32], // EncLog
+        0x1F => &[U32], // EncMap
+        0x20 => &[U32, U16, U16, U16, U16, U32, Blob, String, String], // Assembly
+        0x21 => &[U32], // AssemblyProcessor
+        0x22 => &[U32, U32, U32], // AssemblyOs
+        0x23 => &[U16, U16, U16, U16, U32, Blob, String, String, Blob], // AssemblyRef
+        0x24 => &[U32, Table(AssemblyRef)], // AssemblyRefProcessor
+        0x25 => &[U32, U32, U32, Table(AssemblyRef)], // AssemblyRefOs
+        0x26 => &[U32, String, Blob], // File
+        0x27 => &[U32, U32, String, String, Coded(2, IMPLEMENTATION)], // ExportedType
+        0x28 => &[U32, U32, String, Coded(2, IMPLEMENTATION)], // ManifestResource
+        0x29 => &[Table(TypeDef), Table(TypeDef)], // NestedClass
+        0x2A => &[U16, U16, Coded(1, TYPE_OR_METHOD_DEF), String], // GenericParam
+        0x2B => &[Coded(1, METHOD_DEF_OR_REF), Blob], // MethodSpec
+        0x2C => &[Table(GenericParam), Coded(2, TYPE_DEF_OR_REF)], // GenericParamConstraint
+        // Portable PDB tables (not ECMA-335 proper); schemas per the Portable PDB spec.
+        0x30 => &[Blob, Blob, Guid, Guid], // Document
+        0x31 => &[Table(Document), Blob], // MethodDebugInformation
+        0x32 => &[Table(MethodDef), Table(ImportScope), Table(LocalVariable), Table(LocalConstant), U32, U32], // LocalScope
+        0x33 => &[U16, U16, String], // LocalVariable
+        0x34 => &[String, Blob], // LocalConstant
+        0x35 => &[Table(ImportScope), Blob], // ImportScope
+        0x36 => &[Table(MethodDef), Table(MethodDef)], // StateMachineMethod
+        0x37 => &[Coded(5, HAS_CUSTOM_DEBUG_INFORMATION), Guid, Blob], // CustomDebugInformation
+        _ => return None,
+    })
+}
+
+/// Per-`#~`-stream sizing state needed to compute column widths: `heap_sizes`
+/// (from `Cor20MetadataTable`) for heap indices, and every table's row count
+/// for simple/coded table indices, whose width depends on how many rows the
+/// tables they can reference actually have.
+pub struct TableSizes {
+    pub heap_sizes: u8,
+    pub row_counts: [u32; 64],
+}
+
+impl TableSizes {
+    fn heap_index_size(&self, flag: u8) -> usize {
+        if self.heap_sizes & flag != 0 { 4 } else { 2 }
+    }
+
+    fn table_index_size(&self, token: MetadataToken) -> usize {
+        if self.row_counts[token as usize] >= 1 << 16 { 4 } else { 2 }
+    }
+
+    fn coded_index_size(&self, tag_bits: u8, candidates: &[Option<MetadataToken>]) -> usize {
+        let max_rows = candidates
+            .iter()
+            .filter_map(|candidate| candidate.map(|token| self.row_counts[token as usize]))
+            .max()
+            .unwrap_or(0);
+        if max_rows as u64 > 1u64 << (16 - tag_bits as u32) { 4 } else { 2 }
+    }
+
+    fn column_size(&self, column: Column) -> usize {
+        match column {
+            Column::U16 => 2,
+            Column::U32 => 4,
+            Column::String => self.heap_index_size(0x01),
+            Column::Guid => self.heap_index_size(0x02),
+            Column::Blob => self.heap_index_size(0x04),
+            Column::Table(token) => self.table_index_size(token),
+            Column::Coded(tag_bits, candidates) => self.coded_index_size(tag_bits, candidates),
         }
-        Some(unsafe { &*(bytes.as_ptr() as *const Self) })
+    }
+
+    /// The byte width of one row of `index`'s table, or `None` if `index`
+    /// names a reserved/unassigned table slot.
+    fn row_size(&self, index: usize) -> Option<usize> {
+        Some(
+            metadata_table_columns(index)?
+                .iter()
+                .map(|column| self.column_size(*column))
+                .sum(),
+        )
     }
 }
 
@@ -224,17 +513,17 @@ pub struct ModuleEntry {
 }
 
 impl ModuleEntry {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
         if bytes.len() < 2 { return None; }
         let generation = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
         let mut offset: usize = mem::size_of::<u16>();
-        let name = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += name.size();
-        let mv_id = GuidHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let mv_id = GuidHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += mv_id.size();
-        let enc_id = GuidHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let enc_id = GuidHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += enc_id.size();
-        let enc_base_id = GuidHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let enc_base_id = GuidHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         Some(Self {
             generation,
             name,
@@ -262,13 +551,13 @@ pub struct TypeRefEntry {
 }
 
 impl TypeRefEntry {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
         let mut offset: usize = 0;
-        let resolution_scope = ResolutionScopeIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let resolution_scope = ResolutionScopeIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += resolution_scope.size();
-        let name = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += name.size();
-        let namespace = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let namespace = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         Some(Self {
             resolution_scope,
             name,
@@ -295,19 +584,19 @@ pub struct TypeDefEntry {
 }
 
 impl TypeDefEntry {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
         if bytes.len() < 4 { return None; }
         let flags = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
         let mut offset: usize = mem::size_of::<u32>();
-        let name = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += name.size();
-        let namespace = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let namespace = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += namespace.size();
-        let extends = TypeDefOrRefIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let extends = TypeDefOrRefIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += extends.size();
-        let field_list = SimpleTableIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let field_list = SimpleTableIndex::from_bytes(&bytes[offset..], sizes, MetadataToken::Field)?;
         offset += field_list.size();
-        let method_list = SimpleTableIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let method_list = SimpleTableIndex::from_bytes(&bytes[offset..], sizes, MetadataToken::MethodDef)?;
         Some(Self {
             flags,
             name,
@@ -337,13 +626,13 @@ pub struct FieldEntry {
 }
 
 impl FieldEntry {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
         if bytes.len() < 2 { return None; }
         let flags = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
         let mut offset: usize = mem::size_of::<u16>();
-        let name: StringHeapIndex = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let name: StringHeapIndex = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += name.size();
-        let signature = BlobHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let signature = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         Some(Self {
             flags,
             name,
@@ -370,16 +659,16 @@ pub struct MethodDefEntry {
 }
 
 impl MethodDefEntry {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
         let rva = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
         let impl_flags = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
         let flags = u16::from_le_bytes(bytes[6..8].try_into().unwrap());
         let mut offset: usize = 8;
-        let name = StringHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += name.size();
-        let signature = BlobHeapIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let signature = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
         offset += signature.size();
-        let param_list = SimpleTableIndex::from_bytes(&bytes[offset..], heap_size)?;
+        let param_list = SimpleTableIndex::from_bytes(&bytes[offset..], sizes, MetadataToken::Param)?;
         Some(Self{
             rva,
             impl_flags,
@@ -406,8 +695,11 @@ pub struct SimpleTableIndex {
 }
 
 impl SimpleTableIndex {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
-        let size = if heap_size & 1 != 0 { 4 } else { 2 };
+    /// `target` is the table this index points into — required because,
+    /// unlike heap indices, a simple table index's width depends on how many
+    /// rows `target` itself has, not on `heap_sizes`.
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes, target: MetadataToken) -> Option<Self> {
+        let size = sizes.table_index_size(target) as u32;
 
         let offset = match size {
             2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
@@ -434,8 +726,8 @@ pub struct StringHeapIndex {
 }
 
 impl StringHeapIndex {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
-        let size = if heap_size & 1 != 0 { 4 } else { 2 };
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.heap_index_size(0x01) as u32;
 
         let offset = match size {
             2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
@@ -461,8 +753,8 @@ pub struct GuidHeapIndex {
 }
 
 impl GuidHeapIndex {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
-        let size = if heap_size & 2 != 0 { 4 } else { 2 };
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.heap_index_size(0x02) as u32;
 
         let offset = match size {
             2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
@@ -488,8 +780,8 @@ pub struct ResolutionScopeIndex {
 }
 
 impl ResolutionScopeIndex {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
-        let size = if heap_size & 2 != 0 { 4 } else { 2 };
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(2, RESOLUTION_SCOPE) as u32;
 
         let offset = match size {
             2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
@@ -516,8 +808,8 @@ pub struct TypeDefOrRefIndex {
 }
 
 impl TypeDefOrRefIndex {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
-        let size = if heap_size & 2 != 0 { 4 } else { 2 };
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(2, TYPE_DEF_OR_REF) as u32;
 
         let offset = match size {
             2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
@@ -537,14 +829,14 @@ impl TypeDefOrRefIndex {
 }
 
 #[repr(C)]
-pub struct BlobHeapIndex {
+pub struct MemberRefParentIndex {
     pub offset: u32,
     pub size: u32,
 }
 
-impl BlobHeapIndex {
-    pub fn from_bytes(bytes: &[u8], heap_size: u8) -> Option<Self> {
-        let size = if heap_size & 2 != 0 { 4 } else { 2 };
+impl MemberRefParentIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(3, MEMBER_REF_PARENT) as u32;
 
         let offset = match size {
             2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
@@ -552,10 +844,7 @@ impl BlobHeapIndex {
             _ => return None,
         };
 
-        Some(Self {
-            offset,
-            size,
-        })
+        Some(Self { offset, size })
     }
 
     pub fn size(&self) -> usize {
@@ -563,298 +852,1615 @@ impl BlobHeapIndex {
     }
 }
 
-pub enum Entry {
-    Module(ModuleEntry),
-    TypeRef(TypeRefEntry),
-    TypeDef(TypeDefEntry),
-    Field(FieldEntry),
-    MethodDef(MethodDefEntry),
-}
-
 #[repr(C)]
-pub struct TinyHeader {
-    code_size: u8,
+pub struct HasConstantIndex {
+    pub offset: u32,
+    pub size: u32,
 }
 
-impl TinyHeader {
-    pub fn from_bytes(bytes: &[u8]) -> Option<&Self> {
-        if bytes.len() != mem::size_of::<Self>() {
-            return None;
-        }
-        if bytes.as_ptr().align_offset(mem::align_of::<Self>()) != 0 {
-            return None;
-        }
-        Some(unsafe { &*(bytes.as_ptr() as *const Self) })
+impl HasConstantIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(2, HAS_CONSTANT) as u32;
+
+        let offset = match size {
+            2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
+            4 if bytes.len() >= 4 => u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            _ => return None,
+        };
+
+        Some(Self { offset, size })
     }
 
     pub fn size(&self) -> usize {
-        1
+        self.size as usize
     }
 }
 
-pub enum MethodHeader {
-    Tiny(TinyHeader),
-    Fat(FatHeader),
+#[repr(C)]
+pub struct HasCustomAttributeIndex {
+    pub offset: u32,
+    pub size: u32,
 }
 
-impl MethodHeader {
-    pub fn size(&self) -> Option<usize> {
-        match self {
-            Self::Tiny(header) => Some(header.size()),
-            Self::Fat(header) => Some(header.size()),
-        }
+impl HasCustomAttributeIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(5, HAS_CUSTOM_ATTRIBUTE) as u32;
+
+        let offset = match size {
+            2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
+            4 if bytes.len() >= 4 => u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            _ => return None,
+        };
+
+        Some(Self { offset, size })
+    }
+
+    pub fn size(&self) -> usize {
+        self.size as usize
     }
 }
 
 #[repr(C)]
-pub struct FatHeader {
-    pub flags: u16,
-    pub size: u16,
-    pub max_stack: u16,
-    pub code_size: u32,
-    pub local_var_sig_token: u32,
+pub struct CustomAttributeTypeIndex {
+    pub offset: u32,
+    pub size: u32,
 }
 
-impl FatHeader {
-    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
-        if bytes.len() < 12 {
-            return Err(Error::new(ErrorKind::InvalidData, "not enough bytes for FatHeader"));
-        }
-        Ok(Self {
-            flags: u16::from_le_bytes(bytes[0..2].try_into().unwrap()),
-            size: u16::from_le_bytes(bytes[2..4].try_into().unwrap()),
-            max_stack: u16::from_le_bytes(bytes[4..6].try_into().unwrap()),
-            code_size: u32::from_le_bytes(bytes[6..10].try_into().unwrap()),
-            local_var_sig_token: u32::from_le_bytes(bytes[10..12].try_into().unwrap()),
-        })
+impl CustomAttributeTypeIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(3, CUSTOM_ATTRIBUTE_TYPE) as u32;
+
+        let offset = match size {
+            2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
+            4 if bytes.len() >= 4 => u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            _ => return None,
+        };
+
+        Some(Self { offset, size })
     }
 
     pub fn size(&self) -> usize {
-        14
+        self.size as usize
     }
 }
 
-/// Represents a PE (Portable Executable) file, encapsulating the `lief::pe::Binary` and associated metadata.
-pub struct PE {
-    pub pe: lief::pe::Binary,
-    pub file: File,
-    pub config: Config,
+#[repr(C)]
+pub struct MemberForwardedIndex {
+    pub offset: u32,
+    pub size: u32,
 }
 
-impl PE {
-    /// Creates a new `PE` instance by reading a PE file from the provided path.
-    ///
-    /// # Parameters
-    /// - `path`: The file path to the PE file to be loaded.
-    ///
-    /// # Returns
-    /// A `Result` containing the `PE` object on success or an `Error` on failure.
-    pub fn new(path: String, config: Config) -> Result<Self, Error> {
-        let mut file = File::new(path.clone(), config.clone())?;
-        match file.read() {
-            Ok(_) => (),
-            Err(_) => {
-                return Err(Error::new(ErrorKind::InvalidInput, "failed to read file"));
-            }
+impl MemberForwardedIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(1, MEMBER_FORWARDED) as u32;
+
+        let offset = match size {
+            2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
+            4 if bytes.len() >= 4 => u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            _ => return None,
         };
-        if let Some(Binary::PE(pe)) = Binary::parse(&path) {
-            return Ok(Self {
-                pe: pe,
-                file: file,
-                config: config,
-            });
-        }
-        return Err(Error::new(ErrorKind::InvalidInput, "invalid pe file"));
-    }
 
-    /// Converts a relative virtual address to a file offset
-    ///
-    /// # Returns
-    /// The file offset as a `Option<u64>`.
-    pub fn relative_virtual_address_to_file_offset(&self, rva: u64) -> Option<u64> {
-        for section in self.pe.sections() {
-            let section_start_rva = section.virtual_address() as u64;
-            let section_end_rva = section_start_rva + section.virtual_size() as u64;
-            if rva >= section_start_rva && rva < section_end_rva {
-                let section_offset = rva - section_start_rva;
-                let file_offset = section.pointerto_raw_data() as u64 + section_offset;
-                return Some(file_offset);
-            }
-        }
-        None
+        Some(Self { offset, size })
     }
 
-    fn parse_image_cor20_header(&self) -> Option<(u64, &ImageCor20Header)> {
-        if !self.is_dotnet() { return None; }
-        if let Some(clr_runtime_header) = self.pe.data_directory_by_type(DATA_DIRECTORY::CLR_RUNTIME_HEADER) {
-            if let Some(start) = self.relative_virtual_address_to_file_offset(clr_runtime_header.rva() as u64) {
-                let end = start + clr_runtime_header.size() as u64;
-                let data = &self.file.data[start as usize..end as usize];
-                let header = ImageCor20Header::from_bytes(&data)?;
-                return Some((start, header));
-            }
-        }
-        None
+    pub fn size(&self) -> usize {
+        self.size as usize
     }
+}
 
-    pub fn image_cor20_header(&self) -> Option<&ImageCor20Header> {
-        Some(self.parse_image_cor20_header()?.1)
-    }
+#[repr(C)]
+pub struct MethodDefOrRefIndex {
+    pub offset: u32,
+    pub size: u32,
+}
 
-    fn parse_cor20_storage_signature_header(&self) -> Option<(u64, &Cor20StorageSignature)> {
-        if !self.is_dotnet() { return None; }
-        let (_, image_cor20_header) = self.parse_image_cor20_header()?;
-        let rva = image_cor20_header.meta_data.virtual_address as u64;
-        let start = self.relative_virtual_address_to_file_offset(rva)? as usize;
-        let end = start + mem::size_of::<Cor20StorageSignature>() as usize;
-        let data = &self.file.data[start..end];
-        let header = Cor20StorageSignature::from_bytes(&data)?;
-        Some((start as u64, header))
-    }
+impl MethodDefOrRefIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.coded_index_size(1, METHOD_DEF_OR_REF) as u32;
 
-    pub fn cor20_storage_signature_header(&self) -> Option<&Cor20StorageSignature> {
-        Some(self.parse_cor20_storage_signature_header()?.1)
-    }
+        let offset = match size {
+            2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
+            4 if bytes.len() >= 4 => u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            _ => return None,
+        };
 
-    fn parse_cor20_storage_header(&self) -> Option<(u64, &Cor20StorageHeader)> {
-        if !self.is_dotnet() { return None; };
-        let (mut start, cor20_storage_signaure_header) = self.parse_cor20_storage_signature_header()?;
-        start += mem::size_of::<Cor20StorageSignature>() as u64;
-        start += cor20_storage_signaure_header.version_string_size as u64;
-        start -= mem::size_of::<u32>() as u64;
-        let end = start as usize + mem::size_of::<Cor20StorageHeader>() as usize;
-        let data = &self.file.data[start as usize..end];
-        let header = Cor20StorageHeader::from_bytes(data)?;
-        Some((start, header))
+        Some(Self { offset, size })
     }
 
-    pub fn cor20_storage_header(&self) -> Option<&Cor20StorageHeader> {
-        Some(self.parse_cor20_storage_header()?.1)
+    pub fn size(&self) -> usize {
+        self.size as usize
     }
+}
 
-    fn parse_cor20_stream_headers(&self) -> Option<BTreeMap<u64, &Cor20StreamHeader>> {
-        if !self.is_dotnet() { return None; }
-        let (cor20_storage_header_offset, cor20_storage_header) = self.parse_cor20_storage_header()?;
-        let mut offset = cor20_storage_header_offset as usize + mem::size_of::<Cor20StorageHeader>();
-        let mut result = BTreeMap::<u64, &Cor20StreamHeader>::new();
-        for _ in 0.. cor20_storage_header.number_of_streams {
-            let data = &self.file.data[offset..offset + mem::size_of::<Cor20StreamHeader>()];
-            let header = Cor20StreamHeader::from_bytes(data)?;
-            result.insert(offset as u64, header);
-            offset += header.header_size();
-        }
-        if result.len() <= 0 {
-            return None;
-        }
-        Some(result)
-    }
+#[repr(C)]
+pub struct BlobHeapIndex {
+    pub offset: u32,
+    pub size: u32,
+}
 
-    pub fn cor20_stream_headers(&self) -> Vec<&Cor20StreamHeader> {
-        let mut result = Vec::<&Cor20StreamHeader>::new();
-        let headers = self.parse_cor20_stream_headers();
-        if headers.is_none() { return result; }
-        for (_, header) in headers.unwrap() {
-            result.push(header);
-        }
-        result
-    }
+impl BlobHeapIndex {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let size = sizes.heap_index_size(0x04) as u32;
 
-    fn parse_cor20_metadata_table(&self) -> Option<(u64, &Cor20MetadataTable)> {
-        if !self.is_dotnet() { return None; }
-        let (mut start, _) = self.parse_cor20_storage_signature_header()?;
-        for (_, header) in self.parse_cor20_stream_headers()? {
-            if header.name() == vec![0x23, 0x7e, 0x00, 0x00] {
-                start += header.offset as u64;
-            }
-        }
-        let data = &self.file.data[start as usize..start as usize + mem::size_of::<Cor20MetadataTable>()];
-        Some((start, Cor20MetadataTable::from_bytes(data)?))
+        let offset = match size {
+            2 if bytes.len() >= 2 => u16::from_le_bytes(bytes[0..2].try_into().unwrap()) as u32,
+            4 if bytes.len() >= 4 => u32::from_le_bytes(bytes[0..4].try_into().unwrap()),
+            _ => return None,
+        };
+
+        Some(Self {
+            offset,
+            size,
+        })
     }
 
-    pub fn cor20_metadata_table(&self) -> Option<&Cor20MetadataTable> {
-        Some(self.parse_cor20_metadata_table()?.1)
+    pub fn size(&self) -> usize {
+        self.size as usize
     }
+}
 
-    pub fn cor20_metadata_table_entries(&self) -> Option<Vec<Entry>> {
-        if !self.is_dotnet() { return None; }
+#[repr(C)]
+pub struct ParamEntry {
+    pub flags: u16,
+    pub sequence: u16,
+    pub name: StringHeapIndex,
+}
 
-        let (cor20_metadata_table_offset, cor20_metadata_table) = self.parse_cor20_metadata_table()?;
+impl ParamEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        if bytes.len() < 4 { return None; }
+        let flags = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
+        let sequence = u16::from_le_bytes(bytes[2..4].try_into().unwrap());
+        let offset: usize = mem::size_of::<u16>() * 2;
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self { flags, sequence, name })
+    }
 
-        let mut offset: usize = cor20_metadata_table_offset as usize
-            + mem::size_of::<Cor20MetadataTable>()
-            + cor20_metadata_table.mask_valid.count_ones() as usize * 4;
+    pub fn size(&self) -> usize {
+        mem::size_of::<u16>() * 2 + self.name.size()
+    }
+}
+
+#[repr(C)]
+pub struct MemberRefEntry {
+    pub class: MemberRefParentIndex,
+    pub name: StringHeapIndex,
+    pub signature: BlobHeapIndex,
+}
+
+impl MemberRefEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let mut offset: usize = 0;
+        let class = MemberRefParentIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += class.size();
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += name.size();
+        let signature = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self { class, name, signature })
+    }
+
+    pub fn size(&self) -> usize {
+        self.class.size() + self.name.size() + self.signature.size()
+    }
+}
+
+#[repr(C)]
+pub struct ConstantEntry {
+    pub constant_type: u16,
+    pub parent: HasConstantIndex,
+    pub value: BlobHeapIndex,
+}
+
+impl ConstantEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        if bytes.len() < 2 { return None; }
+        let constant_type = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
+        let mut offset: usize = mem::size_of::<u16>();
+        let parent = HasConstantIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += parent.size();
+        let value = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self { constant_type, parent, value })
+    }
+
+    pub fn size(&self) -> usize {
+        mem::size_of::<u16>() + self.parent.size() + self.value.size()
+    }
+}
+
+#[repr(C)]
+pub struct CustomAttributeEntry {
+    pub parent: HasCustomAttributeIndex,
+    pub attribute_type: CustomAttributeTypeIndex,
+    pub value: BlobHeapIndex,
+}
+
+impl CustomAttributeEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let mut offset: usize = 0;
+        let parent = HasCustomAttributeIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += parent.size();
+        let attribute_type = CustomAttributeTypeIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += attribute_type.size();
+        let value = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self { parent, attribute_type, value })
+    }
+
+    pub fn size(&self) -> usize {
+        self.parent.size() + self.attribute_type.size() + self.value.size()
+    }
+}
+
+#[repr(C)]
+pub struct StandAloneSigEntry {
+    pub signature: BlobHeapIndex,
+}
+
+impl StandAloneSigEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let signature = BlobHeapIndex::from_bytes(bytes, sizes)?;
+        Some(Self { signature })
+    }
+
+    pub fn size(&self) -> usize {
+        self.signature.size()
+    }
+}
+
+#[repr(C)]
+pub struct TypeSpecEntry {
+    pub signature: BlobHeapIndex,
+}
+
+impl TypeSpecEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let signature = BlobHeapIndex::from_bytes(bytes, sizes)?;
+        Some(Self { signature })
+    }
+
+    pub fn size(&self) -> usize {
+        self.signature.size()
+    }
+}
+
+#[repr(C)]
+pub struct AssemblyEntry {
+    pub hash_alg_id: u32,
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub build_number: u16,
+    pub revision_number: u16,
+    pub flags: u32,
+    pub public_key: BlobHeapIndex,
+    pub name: StringHeapIndex,
+    pub culture: StringHeapIndex,
+}
+
+impl AssemblyEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        if bytes.len() < 16 { return None; }
+        let hash_alg_id = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+        let major_version = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
+        let minor_version = u16::from_le_bytes(bytes[6..8].try_into().unwrap());
+        let build_number = u16::from_le_bytes(bytes[8..10].try_into().unwrap());
+        let revision_number = u16::from_le_bytes(bytes[10..12].try_into().unwrap());
+        let flags = u32::from_le_bytes(bytes[12..16].try_into().unwrap());
+        let mut offset: usize = 16;
+        let public_key = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += public_key.size();
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += name.size();
+        let culture = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self {
+            hash_alg_id,
+            major_version,
+            minor_version,
+            build_number,
+            revision_number,
+            flags,
+            public_key,
+            name,
+            culture,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        16 + self.public_key.size() + self.name.size() + self.culture.size()
+    }
+}
+
+#[repr(C)]
+pub struct AssemblyRefEntry {
+    pub major_version: u16,
+    pub minor_version: u16,
+    pub build_number: u16,
+    pub revision_number: u16,
+    pub flags: u32,
+    pub public_key_or_token: BlobHeapIndex,
+    pub name: StringHeapIndex,
+    pub culture: StringHeapIndex,
+    pub hash_value: BlobHeapIndex,
+}
+
+impl AssemblyRefEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        if bytes.len() < 12 { return None; }
+        let major_version = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
+        let minor_version = u16::from_le_bytes(bytes[2..4].try_into().unwrap());
+        let build_number = u16::from_le_bytes(bytes[4..6].try_into().unwrap());
+        let revision_number = u16::from_le_bytes(bytes[6..8].try_into().unwrap());
+        let flags = u32::from_le_bytes(bytes[8..12].try_into().unwrap());
+        let mut offset: usize = 12;
+        let public_key_or_token = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += public_key_or_token.size();
+        let name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += name.size();
+        let culture = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += culture.size();
+        let hash_value = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self {
+            major_version,
+            minor_version,
+            build_number,
+            revision_number,
+            flags,
+            public_key_or_token,
+            name,
+            culture,
+            hash_value,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        12 + self.public_key_or_token.size() + self.name.size() + self.culture.size() + self.hash_value.size()
+    }
+}
+
+#[repr(C)]
+pub struct ModuleRefEntry {
+    pub name: StringHeapIndex,
+}
+
+impl ModuleRefEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let name = StringHeapIndex::from_bytes(bytes, sizes)?;
+        Some(Self { name })
+    }
+
+    pub fn size(&self) -> usize {
+        self.name.size()
+    }
+}
+
+#[repr(C)]
+pub struct ImplMapEntry {
+    pub mapping_flags: u16,
+    pub member_forwarded: MemberForwardedIndex,
+    pub import_name: StringHeapIndex,
+    pub import_scope: SimpleTableIndex,
+}
+
+impl ImplMapEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        if bytes.len() < 2 { return None; }
+        let mapping_flags = u16::from_le_bytes(bytes[0..2].try_into().unwrap());
+        let mut offset: usize = mem::size_of::<u16>();
+        let member_forwarded = MemberForwardedIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += member_forwarded.size();
+        let import_name = StringHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += import_name.size();
+        let import_scope = SimpleTableIndex::from_bytes(&bytes[offset..], sizes, MetadataToken::ModuleRef)?;
+        Some(Self { mapping_flags, member_forwarded, import_name, import_scope })
+    }
+
+    pub fn size(&self) -> usize {
+        mem::size_of::<u16>() + self.member_forwarded.size() + self.import_name.size() + self.import_scope.size()
+    }
+}
+
+#[repr(C)]
+pub struct MethodSpecEntry {
+    pub method: MethodDefOrRefIndex,
+    pub instantiation: BlobHeapIndex,
+}
+
+impl MethodSpecEntry {
+    pub fn from_bytes(bytes: &[u8], sizes: &TableSizes) -> Option<Self> {
+        let mut offset: usize = 0;
+        let method = MethodDefOrRefIndex::from_bytes(&bytes[offset..], sizes)?;
+        offset += method.size();
+        let instantiation = BlobHeapIndex::from_bytes(&bytes[offset..], sizes)?;
+        Some(Self { method, instantiation })
+    }
+
+    pub fn size(&self) -> usize {
+        self.method.size() + self.instantiation.size()
+    }
+}
+
+pub enum Entry {
+    Module(ModuleEntry),
+    TypeRef(TypeRefEntry),
+    TypeDef(TypeDefEntry),
+    Field(FieldEntry),
+    MethodDef(MethodDefEntry),
+    Param(ParamEntry),
+    MemberRef(MemberRefEntry),
+    Constant(ConstantEntry),
+    CustomAttribute(CustomAttributeEntry),
+    StandAloneSig(StandAloneSigEntry),
+    TypeSpec(TypeSpecEntry),
+    Assembly(AssemblyEntry),
+    AssemblyRef(AssemblyRefEntry),
+    ModuleRef(ModuleRefEntry),
+    ImplMap(ImplMapEntry),
+    MethodSpec(MethodSpecEntry),
+    /// A row from any table without a typed variant above yet. Carries the
+    /// table it came from and its raw row bytes, so the walker can stay
+    /// correctly aligned across every table in the file even before that
+    /// table gets its own typed decoding.
+    Raw(MetadataToken, Vec<u8>),
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct TinyHeader {
+    code_size: u8,
+}
+
+impl TinyHeader {
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytemuck::try_from_bytes(bytes)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, format!("invalid TinyHeader: {error}")))
+    }
+
+    pub fn size(&self) -> usize {
+        1
+    }
+
+    pub fn code_size(&self) -> usize {
+        self.code_size as usize
+    }
+}
+
+pub enum MethodHeader {
+    Tiny(TinyHeader),
+    Fat(FatHeader),
+}
+
+impl MethodHeader {
+    pub fn size(&self) -> Option<usize> {
+        match self {
+            Self::Tiny(header) => Some(header.size()),
+            Self::Fat(header) => Some(header.size()),
+        }
+    }
+
+    /// The size in bytes of the IL stream that follows this header.
+    pub fn code_size(&self) -> usize {
+        match self {
+            Self::Tiny(header) => header.code_size(),
+            Self::Fat(header) => header.code_size as usize,
+        }
+    }
+}
+
+#[repr(C)]
+pub struct FatHeader {
+    pub flags: u16,
+    pub size: u16,
+    pub max_stack: u16,
+    pub code_size: u32,
+    pub local_var_sig_token: u32,
+}
+
+impl FatHeader {
+    fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < 12 {
+            return Err(Error::new(ErrorKind::InvalidData, "not enough bytes for FatHeader"));
+        }
+        Ok(Self {
+            flags: u16::from_le_bytes(bytes[0..2].try_into().unwrap()),
+            size: u16::from_le_bytes(bytes[2..4].try_into().unwrap()),
+            max_stack: u16::from_le_bytes(bytes[4..6].try_into().unwrap()),
+            code_size: u32::from_le_bytes(bytes[6..10].try_into().unwrap()),
+            local_var_sig_token: u32::from_le_bytes(bytes[10..12].try_into().unwrap()),
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        14
+    }
+}
+
+/// The operand shape of a CIL opcode (ECMA-335 III.1.9), determining how many
+/// bytes of operand data immediately follow the opcode byte(s).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CilOperandKind {
+    InlineNone,
+    ShortInlineVar,
+    ShortInlineI,
+    ShortInlineBrTarget,
+    InlineVar,
+    InlineI,
+    InlineBrTarget,
+    InlineMethod,
+    InlineField,
+    InlineType,
+    InlineString,
+    InlineTok,
+    InlineSig,
+    ShortInlineR,
+    InlineI8,
+    InlineR,
+    /// A jump table: a `u32` count `N` followed by `N` `u32` branch offsets.
+    InlineSwitch,
+}
+
+/// A single decoded CIL instruction from a method body's IL stream.
+#[derive(Debug, Clone)]
+pub struct CilInstruction {
+    /// Offset of this instruction from the start of the IL stream, so
+    /// `InlineBrTarget`/`InlineSwitch` operands can later be resolved
+    /// against other instructions' offsets.
+    pub il_offset: u32,
+    pub mnemonic: &'static str,
+    pub operand_kind: CilOperandKind,
+    /// Raw little-endian operand bytes; empty for `InlineNone`.
+    pub operand: Vec<u8>,
+}
+
+/// Looks up a single-byte CIL opcode. Returns `None` for `0xFE` (the
+/// two-byte opcode prefix, handled by the caller) and for unassigned bytes.
+fn cil_single_byte_opcode(byte: u8) -> Option<(&'static str, CilOperandKind)> {
+    use CilOperandKind::*;
+    Some(match byte {
+        0x00 => ("nop", InlineNone),
+        0x01 => ("break", InlineNone),
+        0x02 => ("ldarg.0", InlineNone),
+        0x03 => ("ldarg.1", InlineNone),
+        0x04 => ("ldarg.2", InlineNone),
+        0x05 => ("ldarg.3", InlineNone),
+        0x06 => ("ldloc.0", InlineNone),
+        0x07 => ("ldloc.1", InlineNone),
+        0x08 => ("ldloc.2", InlineNone),
+        0x09 => ("ldloc.3", InlineNone),
+        0x0A => ("stloc.0", InlineNone),
+        0x0B => ("stloc.1", InlineNone),
+        0x0C => ("stloc.2", InlineNone),
+        0x0D => ("stloc.3", InlineNone),
+        0x0E => ("ldarg.s", ShortInlineVar),
+        0x0F => ("ldarga.s", ShortInlineVar),
+        0x10 => ("starg.s", ShortInlineVar),
+        0x11 => ("ldloc.s", ShortInlineVar),
+        0x12 => ("ldloca.s", ShortInlineVar),
+        0x13 => ("stloc.s", ShortInlineVar),
+        0x14 => ("ldnull", InlineNone),
+        0x15 => ("ldc.i4.m1", InlineNone),
+        0x16 => ("ldc.i4.0", InlineNone),
+        0x17 => ("ldc.i4.1", InlineNone),
+        0x18 => ("ldc.i4.2", InlineNone),
+        0x19 => ("ldc.i4.3", InlineNone),
+        0x1A => ("ldc.i4.4", InlineNone),
+        0x1B => ("ldc.i4.5", InlineNone),
+        0x1C => ("ldc.i4.6", InlineNone),
+        0x1D => ("ldc.i4.7", InlineNone),
+        0x1E => ("ldc.i4.8", InlineNone),
+        0x1F => ("ldc.i4.s", ShortInlineI),
+        0x20 => ("ldc.i4", InlineI),
+        0x21 => ("ldc.i8", InlineI8),
+        0x22 => ("ldc.r4", ShortInlineR),
+        0x23 => ("ldc.r8", InlineR),
+        0x25 => ("dup", InlineNone),
+        0x26 => ("pop", InlineNone),
+        0x27 => ("jmp", InlineMethod),
+        0x28 => ("call", InlineMethod),
+        0x29 => ("calli", InlineSig),
+        0x2A => ("ret", InlineNone),
+        0x2B => ("br.s", ShortInlineBrTarget),
+        0x2C => ("brfalse.s", ShortInlineBrTarget),
+        0x2D => ("brtrue.s", ShortInlineBrTarget),
+        0x2E => ("beq.s", ShortInlineBrTarget),
+        0x2F => ("bge.s", ShortInlineBrTarget),
+        0x30 => ("bgt.s", ShortInlineBrTarget),
+        0x31 => ("ble.s", ShortInlineBrTarget),
+        0x32 => ("blt.s", ShortInlineBrTarget),
+        0x33 => ("bne.un.s", ShortInlineBrTarget),
+        0x34 => ("bge.un.s", ShortInlineBrTarget),
+        0x35 => ("bgt.un.s", ShortInlineBrTarget),
+        0x36 => ("ble.un.s", ShortInlineBrTarget),
+        0x37 => ("blt.un.s", ShortInlineBrTarget),
+        0x38 => ("br", InlineBrTarget),
+        0x39 => ("brfalse", InlineBrTarget),
+        0x3A => ("brtrue", InlineBrTarget),
+        0x3B => ("beq", InlineBrTarget),
+        0x3C => ("bge", InlineBrTarget),
+        0x3D => ("bgt", InlineBrTarget),
+        0x3E => ("ble", InlineBrTarget),
+        0x3F => ("blt", InlineBrTarget),
+        0x40 => ("bne.un", InlineBrTarget),
+        0x41 => ("bge.un", InlineBrTarget),
+        0x42 => ("bgt.un", InlineBrTarget),
+        0x43 => ("ble.un", InlineBrTarget),
+        0x44 => ("blt.un", InlineBrTarget),
+        0x45 => ("switch", InlineSwitch),
+        0x46 => ("ldind.i1", InlineNone),
+        0x47 => ("ldind.u1", InlineNone),
+        0x48 => ("ldind.i2", InlineNone),
+        0x49 => ("ldind.u2", InlineNone),
+        0x4A => ("ldind.i4", InlineNone),
+        0x4B => ("ldind.u4", InlineNone),
+        0x4C => ("ldind.i8", InlineNone),
+        0x4D => ("ldind.i", InlineNone),
+        0x4E => ("ldind.r4", InlineNone),
+        0x4F => ("ldind.r8", InlineNone),
+        0x50 => ("ldind.ref", InlineNone),
+        0x51 => ("stind.ref", InlineNone),
+        0x52 => ("stind.i1", InlineNone),
+        0x53 => ("stind.i2", InlineNone),
+        0x54 => ("stind.i4", InlineNone),
+        0x55 => ("stind.i8", InlineNone),
+        0x56 => ("stind.r4", InlineNone),
+        0x57 => ("stind.r8", InlineNone),
+        0x58 => ("add", InlineNone),
+        0x59 => ("sub", InlineNone),
+        0x5A => ("mul", InlineNone),
+        0x5B => ("div", InlineNone),
+        0x5C => ("div.un", InlineNone),
+        0x5D => ("rem", InlineNone),
+        0x5E => ("rem.un", InlineNone),
+        0x5F => ("and", InlineNone),
+        0x60 => ("or", InlineNone),
+        0x61 => ("xor", InlineNone),
+        0x62 => ("shl", InlineNone),
+        0x63 => ("shr", InlineNone),
+        0x64 => ("shr.un", InlineNone),
+        0x65 => ("neg", InlineNone),
+        0x66 => ("not", InlineNone),
+        0x67 => ("conv.i1", InlineNone),
+        0x68 => ("conv.i2", InlineNone),
+        0x69 => ("conv.i4", InlineNone),
+        0x6A => ("conv.i8", InlineNone),
+        0x6B => ("conv.r4", InlineNone),
+        0x6C => ("conv.r8", InlineNone),
+        0x6D => ("conv.u4", InlineNone),
+        0x6E => ("conv.u8", InlineNone),
+        0x6F => ("callvirt", InlineMethod),
+        0x70 => ("cpobj", InlineType),
+        0x71 => ("ldobj", InlineType),
+        0x72 => ("ldstr", InlineString),
+        0x73 => ("newobj", InlineMethod),
+        0x74 => ("castclass", InlineType),
+        0x75 => ("isinst", InlineType),
+        0x76 => ("conv.r.un", InlineNone),
+        0x79 => ("unbox", InlineType),
+        0x7A => ("throw", InlineNone),
+        0x7B => ("ldfld", InlineField),
+        0x7C => ("ldflda", InlineField),
+        0x7D => ("stfld", InlineField),
+        0x7E => ("ldsfld", InlineField),
+        0x7F => ("ldsflda", InlineField),
+        0x80 => ("stsfld", InlineField),
+        0x81 => ("stobj", InlineType),
+        0x82 => ("conv.ovf.i1.un", InlineNone),
+        0x83 => ("conv.ovf.i2.un", InlineNone),
+        0x84 => ("conv.ovf.i4.un", InlineNone),
+        0x85 => ("conv.ovf.i8.un", InlineNone),
+        0x86 => ("conv.ovf.u1.un", InlineNone),
+        0x87 => ("conv.ovf.u2.un", InlineNone),
+        0x88 => ("conv.ovf.u4.un", InlineNone),
+        0x89 => ("conv.ovf.u8.un", InlineNone),
+        0x8A => ("conv.ovf.i.un", InlineNone),
+        0x8B => ("conv.ovf.u.un", InlineNone),
+        0x8C => ("box", InlineType),
+        0x8D => ("newarr", InlineType),
+        0x8E => ("ldlen", InlineNone),
+        0x8F => ("ldelema", InlineType),
+        0x90 => ("ldelem.i1", InlineNone),
+        0x91 => ("ldelem.u1", InlineNone),
+        0x92 => ("ldelem.i2", InlineNone),
+        0x93 => ("ldelem.u2", InlineNone),
+        0x94 => ("ldelem.i4", InlineNone),
+        0x95 => ("ldelem.u4", InlineNone),
+        0x96 => ("ldelem.i8", InlineNone),
+        0x97 => ("ldelem.i", InlineNone),
+        0x98 => ("ldelem.r4", InlineNone),
+        0x99 => ("ldelem.r8", InlineNone),
+        0x9A => ("ldelem.ref", InlineNone),
+        0x9B => ("stelem.i", InlineNone),
+        0x9C => ("stelem.i1", InlineNone),
+        0x9D => ("stelem.i2", InlineNone),
+        0x9E => ("stelem.i4", InlineNone),
+        0x9F => ("stelem.i8", InlineNone),
+        0xA0 => ("stelem.r4", InlineNone),
+        0xA1 => ("stelem.r8", InlineNone),
+        0xA2 => ("stelem.ref", InlineNone),
+        0xA3 => ("ldelem", InlineType),
+        0xA4 => ("stelem", InlineType),
+        0xA5 => ("unbox.any", InlineType),
+        0xB3 => ("conv.ovf.i1", InlineNone),
+        0xB4 => ("conv.ovf.u1", InlineNone),
+        0xB5 => ("conv.ovf.i2", InlineNone),
+        0xB6 => ("conv.ovf.u2", InlineNone),
+        0xB7 => ("conv.ovf.i4", InlineNone),
+        0xB8 => ("conv.ovf.u4", InlineNone),
+        0xB9 => ("conv.ovf.i8", InlineNone),
+        0xBA => ("conv.ovf.u8", InlineNone),
+        0xC2 => ("refanyval", InlineType),
+        0xC3 => ("ckfinite", InlineNone),
+        0xC6 => ("mkrefany", InlineType),
+        0xD0 => ("ldtoken", InlineTok),
+        0xD1 => ("conv.u2", InlineNone),
+        0xD2 => ("conv.u1", InlineNone),
+        0xD3 => ("conv.i", InlineNone),
+        0xD4 => ("conv.ovf.i", InlineNone),
+        0xD5 => ("conv.ovf.u", InlineNone),
+        0xD6 => ("add.ovf", InlineNone),
+        0xD7 => ("add.ovf.un", InlineNone),
+        0xD8 => ("mul.ovf", InlineNone),
+        0xD9 => ("mul.ovf.un", InlineNone),
+        0xDA => ("sub.ovf", InlineNone),
+        0xDB => ("sub.ovf.un", InlineNone),
+        0xDC => ("endfinally", InlineNone),
+        0xDD => ("leave", InlineBrTarget),
+        0xDE => ("leave.s", ShortInlineBrTarget),
+        0xDF => ("stind.i", InlineNone),
+        0xE0 => ("conv.u", InlineNone),
+        _ => return None,
+    })
+}
+
+/// Looks up a two-byte (`0xFE`-prefixed) CIL opcode by its second byte.
+fn cil_two_byte_opcode(byte: u8) -> Option<(&'static str, CilOperandKind)> {
+    use CilOperandKind::*;
+    Some(match byte {
+        0x00 => ("arglist", InlineNone),
+        0x01 => ("ceq", InlineNone),
+        0x02 => ("cgt", InlineNone),
+        0x03 => ("cgt.un", InlineNone),
+        0x04 => ("clt", InlineNone),
+        0x05 => ("clt.un", InlineNone),
+        0x06 => ("ldftn", InlineMethod),
+        0x07 => ("ldvirtftn", InlineMethod),
+        0x09 => ("ldarg", InlineVar),
+        0x0A => ("ldarga", InlineVar),
+        0x0B => ("starg", InlineVar),
+        0x0C => ("ldloc", InlineVar),
+        0x0D => ("ldloca", InlineVar),
+        0x0E => ("stloc", InlineVar),
+        0x0F => ("localloc", InlineNone),
+        0x11 => ("endfilter", InlineNone),
+        0x12 => ("unaligned.", ShortInlineI),
+        0x13 => ("volatile.", InlineNone),
+        0x14 => ("tail.", InlineNone),
+        0x15 => ("initobj", InlineType),
+        0x16 => ("constrained.", InlineType),
+        0x17 => ("cpblk", InlineNone),
+        0x18 => ("initblk", InlineNone),
+        0x19 => ("no.", ShortInlineI),
+        0x1A => ("rethrow", InlineNone),
+        0x1C => ("sizeof", InlineType),
+        0x1D => ("refanytype", InlineNone),
+        0x1E => ("readonly.", InlineNone),
+        _ => return None,
+    })
+}
+
+/// Decodes a contiguous IL byte stream (as found in a method body, right
+/// after its `TinyHeader`/`FatHeader`) into a flat list of `CilInstruction`s.
+pub fn cil_decode_instructions(bytes: &[u8]) -> Result<Vec<CilInstruction>, Error> {
+    let mut instructions = Vec::new();
+    let mut offset: usize = 0;
+
+    while offset < bytes.len() {
+        let il_offset = offset as u32;
+        let first = bytes[offset];
+
+        let (mnemonic, operand_kind, opcode_size) = if first == 0xFE {
+            let next = *bytes.get(offset + 1)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "truncated two-byte CIL opcode"))?;
+            let (mnemonic, operand_kind) = cil_two_byte_opcode(next)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "unknown two-byte CIL opcode"))?;
+            (mnemonic, operand_kind, 2usize)
+        } else {
+            let (mnemonic, operand_kind) = cil_single_byte_opcode(first)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, "unknown CIL opcode"))?;
+            (mnemonic, operand_kind, 1usize)
+        };
+        offset += opcode_size;
+
+        let operand_size = match operand_kind {
+            CilOperandKind::InlineNone => 0,
+            CilOperandKind::ShortInlineVar | CilOperandKind::ShortInlineI | CilOperandKind::ShortInlineBrTarget => 1,
+            CilOperandKind::InlineVar => 2,
+            CilOperandKind::InlineI
+            | CilOperandKind::InlineBrTarget
+            | CilOperandKind::InlineMethod
+            | CilOperandKind::InlineField
+            | CilOperandKind::InlineType
+            | CilOperandKind::InlineString
+            | CilOperandKind::InlineTok
+            | CilOperandKind::InlineSig
+            | CilOperandKind::ShortInlineR => 4,
+            CilOperandKind::InlineI8 | CilOperandKind::InlineR => 8,
+            CilOperandKind::InlineSwitch => {
+                if offset + 4 > bytes.len() {
+                    return Err(Error::new(ErrorKind::InvalidData, "truncated switch instruction"));
+                }
+                let count = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+                4 + count * 4
+            }
+        };
+
+        if offset + operand_size > bytes.len() {
+            return Err(Error::new(ErrorKind::InvalidData, "truncated CIL operand"));
+        }
+        let operand = bytes[offset..offset + operand_size].to_vec();
+        offset += operand_size;
+
+        instructions.push(CilInstruction {
+            il_offset,
+            mnemonic,
+            operand_kind,
+            operand,
+        });
+    }
+
+    Ok(instructions)
+}
+
+const STRINGS_STREAM_NAME: &[u8] = &[0x23, 0x53, 0x74, 0x72, 0x69, 0x6e, 0x67, 0x73, 0x00, 0x00, 0x00, 0x00]; // "#Strings\0"
+const GUID_STREAM_NAME: &[u8] = &[0x23, 0x47, 0x55, 0x49, 0x44, 0x00, 0x00, 0x00]; // "#GUID\0"
+const BLOB_STREAM_NAME: &[u8] = &[0x23, 0x42, 0x6c, 0x6f, 0x62, 0x00, 0x00, 0x00]; // "#Blob\0"
+const US_STREAM_NAME: &[u8] = &[0x23, 0x55, 0x53, 0x00]; // "#US\0"
+
+/// The `#Strings`/`#GUID`/`#Blob`/`#US` heaps a `#~` metadata stream's
+/// `StringHeapIndex`/`GuidHeapIndex`/`BlobHeapIndex` values are resolved
+/// against. `None` for a heap means the stream wasn't present in the file.
+pub struct Cor20Heaps<'data> {
+    pub strings: Option<&'data [u8]>,
+    pub guids: Option<&'data [u8]>,
+    pub blobs: Option<&'data [u8]>,
+    pub user_strings: Option<&'data [u8]>,
+}
+
+/// Reads one ECMA-335 compressed unsigned integer (II.23.2) at `offset`,
+/// used as the length prefix of `#Blob`/`#US` heap entries: one byte if the
+/// high bit is clear, two bytes if the top bits are `10`, four if `110`.
+///
+/// # Returns
+///
+/// The entry's content (length prefix excluded), or `None` if `offset` is
+/// out of range or the prefix's top bits are `111` (reserved).
+fn read_compressed_blob(heap: &[u8], offset: usize) -> Option<&[u8]> {
+    let first = *heap.get(offset)?;
+    let (length, length_size) = if first & 0x80 == 0 {
+        (first as usize, 1)
+    } else if first & 0xC0 == 0x80 {
+        let second = *heap.get(offset + 1)? as usize;
+        ((((first & 0x3f) as usize) << 8) | second, 2)
+    } else if first & 0xE0 == 0xC0 {
+        let rest = heap.get(offset + 1..offset + 4)?;
+        let length = (((first & 0x1f) as usize) << 24)
+            | ((rest[0] as usize) << 16)
+            | ((rest[1] as usize) << 8)
+            | rest[2] as usize;
+        (length, 4)
+    } else {
+        return None;
+    };
+    heap.get(offset + length_size..offset + length_size + length)
+}
+
+impl StringHeapIndex {
+    /// Resolves this index against `heaps.strings` as a UTF-8, NUL-terminated string.
+    pub fn resolve<'data>(&self, heaps: &Cor20Heaps<'data>) -> Option<&'data str> {
+        let heap = heaps.strings?;
+        let start = self.offset as usize;
+        let terminator = heap.get(start..)?.iter().position(|&byte| byte == 0)?;
+        std::str::from_utf8(&heap[start..start + terminator]).ok()
+    }
+}
+
+impl GuidHeapIndex {
+    /// Resolves this index against `heaps.guids` as a 16-byte GUID.
+    ///
+    /// `#GUID` indices are 1-based; an offset of `0` means "no GUID".
+    pub fn resolve(&self, heaps: &Cor20Heaps<'_>) -> Option<[u8; 16]> {
+        let heap = heaps.guids?;
+        if self.offset == 0 {
+            return None;
+        }
+        let start = (self.offset as usize - 1) * 16;
+        heap.get(start..start + 16)?.try_into().ok()
+    }
+}
+
+impl BlobHeapIndex {
+    /// Resolves this index against `heaps.blobs` as a length-prefixed byte slice.
+    pub fn resolve<'data>(&self, heaps: &Cor20Heaps<'data>) -> Option<&'data [u8]> {
+        read_compressed_blob(heaps.blobs?, self.offset as usize)
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct WinCertificateHeader {
+    pub length: u32,
+    pub revision: u16,
+    pub certificate_type: u16,
+}
+
+impl WinCertificateHeader {
+    pub fn from_bytes(bytes: &[u8]) -> Result<&Self, Error> {
+        bytes
+            .get(..mem::size_of::<Self>())
+            .and_then(|head| bytemuck::try_from_bytes(head).ok())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid WinCertificateHeader"))
+    }
+}
+
+/// A single `WIN_CERTIFICATE` entry from the PE security directory.
+pub struct WinCertificate<'data> {
+    pub header: &'data WinCertificateHeader,
+    /// The certificate payload, typically a DER-encoded PKCS#7 `SignedData`.
+    pub data: &'data [u8],
+}
+
+/// A single decoded Rich Header record: one MSVC toolchain component
+/// (compiler, linker, or library) and how many times the linker saw it used.
+#[derive(Debug, Clone, Copy)]
+pub struct RichHeaderEntry {
+    pub comp_id: u16,
+    pub build: u16,
+    pub count: u32,
+}
+
+/// The decoded `Rich` header MSVC toolchains embed in the DOS stub, a
+/// compiler-provenance fingerprint useful for clustering samples.
+pub struct RichHeader {
+    pub entries: Vec<RichHeaderEntry>,
+    /// The checksum recomputed from `entries` and the surrounding DOS header
+    /// bytes; should equal the XOR key stored in the file.
+    pub checksum: u32,
+}
+
+fn rotate_left_u32(value: u32, shift: u32) -> u32 {
+    value.rotate_left(shift % 32)
+}
+
+/// A single resolved delay-import thunk: the import address table slot's
+/// virtual address, the owning DLL, and the resolved symbol (a name, or
+/// `#<ordinal>` if the entry is imported by ordinal rather than by name).
+#[derive(Debug, Clone)]
+pub struct DelayImportThunk {
+    pub address: u64,
+    pub dll: String,
+    pub symbol: String,
+}
+
+/// A single `IMAGE_BOUND_IMPORT_DESCRIPTOR` record: the DLL the loader
+/// recorded as already bound at its preferred base, the timestamp it was
+/// bound against, and any forwarder-chain module names that follow it.
+///
+/// Unlike delay imports, the bound-import directory has no import address
+/// table of its own to walk — it records modules, not individual symbol
+/// thunks, so there's no per-symbol address to recover here.
+#[derive(Debug, Clone)]
+pub struct BoundImportEntry {
+    pub dll: String,
+    pub timestamp: u32,
+    pub forwarders: Vec<String>,
+}
+
+/// Reads a nul-terminated ASCII string at file offset `offset`.
+fn read_c_string(data: &[u8], offset: usize) -> Option<String> {
+    let relative_terminator = data.get(offset..)?.iter().position(|&byte| byte == 0)?;
+    std::str::from_utf8(&data[offset..offset + relative_terminator]).ok().map(str::to_string)
+}
+
+/// The subset of `IMAGE_LOAD_CONFIG_DIRECTORY` this crate understands: the
+/// Control Flow Guard function table and, on x86, the SEH handler table.
+///
+/// The full structure has grown new fields across SDK versions and differs
+/// between 32-bit and 64-bit layouts; only the fixed-offset fields needed
+/// for CFG/SEH discovery are decoded here.
+#[derive(Debug, Clone)]
+pub struct LoadConfig {
+    pub size: u32,
+    pub guard_flags: u32,
+    pub guard_cf_function_table: u64,
+    pub guard_cf_function_count: u64,
+    /// x86 only; `None` on other architectures.
+    pub se_handler_table: Option<u64>,
+    pub se_handler_count: Option<u64>,
+}
+
+const OID_MESSAGE_DIGEST: [u8; 11] = [0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x04];
+const OID_SIGNING_TIME: [u8; 11] = [0x06, 0x09, 0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x05];
+
+/// Reads a DER length octet (or long-form length), returning `(length, bytes_consumed)`.
+fn der_read_length(bytes: &[u8]) -> Option<(usize, usize)> {
+    let first = *bytes.first()?;
+    if first & 0x80 == 0 {
+        Some((first as usize, 1))
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 || bytes.len() < 1 + count {
+            return None;
+        }
+        let mut length = 0usize;
+        for byte in &bytes[1..1 + count] {
+            length = (length << 8) | *byte as usize;
+        }
+        Some((length, 1 + count))
+    }
+}
+
+/// Reads one DER tag-length-value, returning `(tag, content, bytes_consumed)`.
+fn der_read_tlv(bytes: &[u8]) -> Option<(u8, &[u8], usize)> {
+    let tag = *bytes.first()?;
+    let (length, length_size) = der_read_length(&bytes[1..])?;
+    let content_start = 1 + length_size;
+    if bytes.len() < content_start + length {
+        return None;
+    }
+    Some((tag, &bytes[content_start..content_start + length], content_start + length))
+}
+
+fn der_find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).position(|window| window == needle)
+}
+
+impl<'data> WinCertificate<'data> {
+    /// The embedded PKCS#7 `SignedData` blob (DER-encoded), padding excluded.
+    pub fn signed_data(&self) -> &'data [u8] {
+        self.data
+    }
+
+    /// Best-effort extraction of the PKCS#9 `messageDigest` authenticated
+    /// attribute (the Authenticode message digest) by locating its OID in
+    /// the `SignedData` blob and reading the `SET{OCTET STRING}` that
+    /// follows it.
+    ///
+    /// This is a minimal OID scan rather than a full CMS/ASN.1 parser — this
+    /// tree has no ASN.1 dependency to parse `SignerInfo` properly — but it
+    /// holds for the standard Authenticode `SignedData` layout.
+    pub fn authenticode_message_digest(&self) -> Option<Vec<u8>> {
+        let oid_offset = der_find(self.data, &OID_MESSAGE_DIGEST)?;
+        let after_oid = &self.data[oid_offset + OID_MESSAGE_DIGEST.len()..];
+        let (set_tag, set_content, _) = der_read_tlv(after_oid)?;
+        if set_tag != 0x31 {
+            return None;
+        }
+        let (octet_tag, octet_content, _) = der_read_tlv(set_content)?;
+        if octet_tag != 0x04 {
+            return None;
+        }
+        Some(octet_content.to_vec())
+    }
+
+    /// Best-effort extraction of the PKCS#9 `signingTime` authenticated
+    /// attribute, as its raw `UTCTime`/`GeneralizedTime` ASCII string. See
+    /// `authenticode_message_digest` for the same minimal-scan caveat.
+    pub fn signing_time(&self) -> Option<String> {
+        let oid_offset = der_find(self.data, &OID_SIGNING_TIME)?;
+        let after_oid = &self.data[oid_offset + OID_SIGNING_TIME.len()..];
+        let (set_tag, set_content, _) = der_read_tlv(after_oid)?;
+        if set_tag != 0x31 {
+            return None;
+        }
+        let (time_tag, time_content, _) = der_read_tlv(set_content)?;
+        if time_tag != 0x17 && time_tag != 0x18 {
+            return None;
+        }
+        String::from_utf8(time_content.to_vec()).ok()
+    }
+}
+
+/// A bounds-checked cursor over a file's raw bytes.
+///
+/// Every `parse_cor20_*` routine used to index `self.file.data()[start..end]`
+/// directly; a crafted or truncated .NET file (a stream header whose
+/// `offset`/`size` point past EOF, or a `version_string_size` that underflows
+/// the rewind arithmetic in `parse_cor20_storage_header`) would panic the
+/// whole process instead of failing to parse. Every read and seek here is
+/// checked against the underlying slice's length and returns a descriptive
+/// `Error` instead, which matters because binlex is run over untrusted
+/// malware corpora.
+struct Cor20Cursor<'data> {
+    data: &'data [u8],
+    position: usize,
+}
+
+impl<'data> Cor20Cursor<'data> {
+    fn new(data: &'data [u8], position: usize) -> Self {
+        Self { data, position }
+    }
+
+    fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Moves the cursor to an absolute offset, rejecting anything past EOF.
+    fn seek(&mut self, position: usize) -> Result<(), Error> {
+        if position > self.data.len() {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "COR20 offset is past end of file"));
+        }
+        self.position = position;
+        Ok(())
+    }
+
+    /// Moves the cursor by a signed offset (e.g. rewinding past a variable-length
+    /// version string), rejecting anything that would land outside the file.
+    fn advance(&mut self, offset: i64) -> Result<(), Error> {
+        let next = self.position as i64 + offset;
+        if next < 0 || next as u64 > self.data.len() as u64 {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "COR20 offset is out of range"));
+        }
+        self.position = next as usize;
+        Ok(())
+    }
+
+    /// Reads `len` bytes at the current position without advancing past them.
+    fn peek(&self, len: usize) -> Result<&'data [u8], Error> {
+        let end = self
+            .position
+            .checked_add(len)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "COR20 read length overflowed"))?;
+        if end > self.data.len() {
+            return Err(Error::new(ErrorKind::UnexpectedEof, "COR20 read extends past end of file"));
+        }
+        Ok(&self.data[self.position..end])
+    }
+
+    /// Reads `len` bytes at the current position and advances past them.
+    fn read(&mut self, len: usize) -> Result<&'data [u8], Error> {
+        let bytes = self.peek(len)?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    fn read_u32(&mut self) -> Result<u32, Error> {
+        Ok(u32::from_le_bytes(self.read(4)?.try_into().unwrap()))
+    }
+}
+
+/// One position in a `BytePattern`: a fixed byte to match exactly, or a
+/// wildcard that matches any byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytePatternToken {
+    Exact(u8),
+    Any,
+}
+
+/// An IDA-style byte pattern for `PE::scan`, e.g. `"48 8B ?? ?? ?? 00 00"`
+/// (parsed via `BytePattern::parse`), or built directly from a
+/// `&[Option<u8>]` mask (`None` = wildcard) via `From`.
+pub struct BytePattern {
+    tokens: Vec<BytePatternToken>,
+}
+
+impl BytePattern {
+    /// Parses a whitespace-separated sequence of hex-byte pairs and
+    /// `?`/`??` wildcards.
+    pub fn parse(pattern: &str) -> Result<Self, Error> {
+        let mut tokens = Vec::new();
+        for token in pattern.split_whitespace() {
+            if token.chars().all(|character| character == '?') {
+                tokens.push(BytePatternToken::Any);
+            } else {
+                let byte = u8::from_str_radix(token, 16).map_err(|error| {
+                    Error::new(ErrorKind::InvalidInput, format!("invalid pattern byte '{token}': {error}"))
+                })?;
+                tokens.push(BytePatternToken::Exact(byte));
+            }
+        }
+        if tokens.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidInput, "pattern is empty"));
+        }
+        Ok(Self { tokens })
+    }
+
+    fn len(&self) -> usize {
+        self.tokens.len()
+    }
+
+    fn matches_at(&self, haystack: &[u8], offset: usize) -> bool {
+        self.tokens.iter().enumerate().all(|(i, token)| match token {
+            BytePatternToken::Any => true,
+            BytePatternToken::Exact(expected) => haystack[offset + i] == *expected,
+        })
+    }
+
+    /// Finds every offset in `haystack` this pattern matches, via a masked
+    /// Boyer-Moore-Horspool search.
+    ///
+    /// The bad-character shift table is built only from the pattern's
+    /// longest wildcard-free suffix (the tokens after its last wildcard):
+    /// those are the only bytes guaranteed present at a matching position,
+    /// so they're the only ones safe to use for skipping past a mismatch. A
+    /// pattern whose last token is itself a wildcard falls back to shifting
+    /// by one, i.e. a plain per-byte scan.
+    fn find_all(&self, haystack: &[u8]) -> Vec<usize> {
+        let pattern_len = self.len();
+        if pattern_len == 0 || haystack.len() < pattern_len {
+            return Vec::new();
+        }
+
+        let last_wildcard = self.tokens.iter().rposition(|token| matches!(token, BytePatternToken::Any));
+        let suffix_start = last_wildcard.map(|index| index + 1).unwrap_or(0);
+        let suffix = &self.tokens[suffix_start..];
+
+        let mut shift = [suffix.len() as u64; 256];
+        for (index, token) in suffix.iter().enumerate() {
+            if let BytePatternToken::Exact(value) = token {
+                shift[*value as usize] = (suffix.len() - 1 - index) as u64;
+            }
+        }
+
+        let mut matches = Vec::new();
+        let mut offset = 0usize;
+        while offset + pattern_len <= haystack.len() {
+            if self.matches_at(haystack, offset) {
+                matches.push(offset);
+            }
+            if suffix.is_empty() {
+                offset += 1;
+                continue;
+            }
+            let last_byte = haystack[offset + pattern_len - 1];
+            let skip = shift[last_byte as usize].max(1);
+            offset += skip as usize;
+        }
+
+        matches
+    }
+}
+
+impl From<&[Option<u8>]> for BytePattern {
+    fn from(mask: &[Option<u8>]) -> Self {
+        Self {
+            tokens: mask
+                .iter()
+                .map(|byte| match byte {
+                    Some(byte) => BytePatternToken::Exact(*byte),
+                    None => BytePatternToken::Any,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Represents a PE (Portable Executable) file, encapsulating the `lief::pe::Binary` and associated metadata.
+pub struct PE {
+    pub pe: lief::pe::Binary,
+    pub file: File,
+    pub config: Config,
+}
+
+impl PE {
+    /// Creates a new `PE` instance by reading a PE file from the provided path.
+    ///
+    /// # Parameters
+    /// - `path`: The file path to the PE file to be loaded.
+    ///
+    /// # Returns
+    /// A `Result` containing the `PE` object on success or an `Error` on failure.
+    pub fn new(path: String, config: Config) -> Result<Self, Error> {
+        let mut file = File::new(path.clone(), config.clone())?;
+        match file.read() {
+            Ok(_) => (),
+            Err(_) => {
+                return Err(Error::new(ErrorKind::InvalidInput, "failed to read file"));
+            }
+        };
+        if let Some(Binary::PE(pe)) = Binary::parse(&path) {
+            return Ok(Self {
+                pe: pe,
+                file: file,
+                config: config,
+            });
+        }
+        return Err(Error::new(ErrorKind::InvalidInput, "invalid pe file"));
+    }
+
+    /// Converts a relative virtual address to a file offset
+    ///
+    /// # Returns
+    /// The file offset as a `Option<u64>`.
+    pub fn relative_virtual_address_to_file_offset(&self, rva: u64) -> Option<u64> {
+        for section in self.pe.sections() {
+            let section_start_rva = section.virtual_address() as u64;
+            let section_end_rva = section_start_rva + section.virtual_size() as u64;
+            if rva >= section_start_rva && rva < section_end_rva {
+                let section_offset = rva - section_start_rva;
+                let file_offset = section.pointerto_raw_data() as u64 + section_offset;
+                return Some(file_offset);
+            }
+        }
+        None
+    }
+
+    fn parse_image_cor20_header(&self) -> Result<(u64, &ImageCor20Header), Error> {
+        if !self.is_dotnet() {
+            return Err(Error::new(ErrorKind::InvalidInput, "not a .NET assembly"));
+        }
+        let clr_runtime_header = self
+            .pe
+            .data_directory_by_type(DATA_DIRECTORY::CLR_RUNTIME_HEADER)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "missing CLR runtime header data directory"))?;
+        let start = self
+            .relative_virtual_address_to_file_offset(clr_runtime_header.rva() as u64)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "CLR runtime header RVA is not mapped"))?;
+        let mut cursor = Cor20Cursor::new(self.file.data(), start as usize);
+        let data = cursor.read(clr_runtime_header.size() as usize)?;
+        let header = ImageCor20Header::from_bytes(data)?;
+        Ok((start, header))
+    }
+
+    pub fn image_cor20_header(&self) -> Option<&ImageCor20Header> {
+        self.parse_image_cor20_header().ok().map(|(_, header)| header)
+    }
+
+    fn parse_cor20_storage_signature_header(&self) -> Result<(u64, &Cor20StorageSignature), Error> {
+        if !self.is_dotnet() {
+            return Err(Error::new(ErrorKind::InvalidInput, "not a .NET assembly"));
+        }
+        let (_, image_cor20_header) = self.parse_image_cor20_header()?;
+        let rva = image_cor20_header.meta_data.virtual_address as u64;
+        let start = self
+            .relative_virtual_address_to_file_offset(rva)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "COR20 metadata root RVA is not mapped"))?;
+        let mut cursor = Cor20Cursor::new(self.file.data(), start as usize);
+        let data = cursor.read(mem::size_of::<Cor20StorageSignature>())?;
+        let header = Cor20StorageSignature::from_bytes(data)?;
+        Ok((start, header))
+    }
+
+    pub fn cor20_storage_signature_header(&self) -> Option<&Cor20StorageSignature> {
+        self.parse_cor20_storage_signature_header().ok().map(|(_, header)| header)
+    }
+
+    fn parse_cor20_storage_header(&self) -> Result<(u64, &Cor20StorageHeader), Error> {
+        if !self.is_dotnet() {
+            return Err(Error::new(ErrorKind::InvalidInput, "not a .NET assembly"));
+        }
+        let (start, cor20_storage_signature_header) = self.parse_cor20_storage_signature_header()?;
+        let mut cursor = Cor20Cursor::new(self.file.data(), start as usize);
+        cursor.advance(mem::size_of::<Cor20StorageSignature>() as i64)?;
+        cursor.advance(cor20_storage_signature_header.version_string_size as i64)?;
+        cursor.advance(-(mem::size_of::<u32>() as i64))?;
+        let header_offset = cursor.position() as u64;
+        let data = cursor.read(mem::size_of::<Cor20StorageHeader>())?;
+        let header = Cor20StorageHeader::from_bytes(data)?;
+        Ok((header_offset, header))
+    }
+
+    pub fn cor20_storage_header(&self) -> Option<&Cor20StorageHeader> {
+        self.parse_cor20_storage_header().ok().map(|(_, header)| header)
+    }
+
+    fn parse_cor20_stream_headers(&self) -> Result<BTreeMap<u64, &Cor20StreamHeader>, Error> {
+        if !self.is_dotnet() {
+            return Err(Error::new(ErrorKind::InvalidInput, "not a .NET assembly"));
+        }
+        let (cor20_storage_header_offset, cor20_storage_header) = self.parse_cor20_storage_header()?;
+        let mut cursor = Cor20Cursor::new(self.file.data(), cor20_storage_header_offset as usize);
+        cursor.advance(mem::size_of::<Cor20StorageHeader>() as i64)?;
+        let mut result = BTreeMap::<u64, &Cor20StreamHeader>::new();
+        for _ in 0..cor20_storage_header.number_of_streams {
+            let header_offset = cursor.position() as u64;
+            let data = cursor.read(mem::size_of::<Cor20StreamHeader>())?;
+            let header = Cor20StreamHeader::from_bytes(data)?;
+            result.insert(header_offset, header);
+            cursor.advance(header.header_size(self.file.data()) as i64 - mem::size_of::<Cor20StreamHeader>() as i64)?;
+        }
+        if result.is_empty() {
+            return Err(Error::new(ErrorKind::InvalidData, "COR20 storage header declares no streams"));
+        }
+        Ok(result)
+    }
+
+    pub fn cor20_stream_headers(&self) -> Vec<&Cor20StreamHeader> {
+        self.parse_cor20_stream_headers()
+            .map(|headers| headers.into_values().collect())
+            .unwrap_or_default()
+    }
+
+    /// Locates and slices the `#Strings`/`#GUID`/`#Blob`/`#US` heaps so
+    /// `StringHeapIndex`/`GuidHeapIndex`/`BlobHeapIndex` values decoded from
+    /// `cor20_metadata_table_entries` can be resolved into real values.
+    pub fn cor20_heaps(&self) -> Option<Cor20Heaps<'_>> {
+        let (metadata_root_offset, _) = self.parse_cor20_storage_signature_header().ok()?;
+        let data = self.file.data();
+
+        let mut heaps = Cor20Heaps {
+            strings: None,
+            guids: None,
+            blobs: None,
+            user_strings: None,
+        };
+
+        for header in self.parse_cor20_stream_headers().ok()?.values() {
+            let start = metadata_root_offset as usize + header.offset as usize;
+            let end = start + header.size as usize;
+            if end > data.len() {
+                continue;
+            }
+            let stream = &data[start..end];
+            match header.name(data) {
+                name if name == STRINGS_STREAM_NAME => heaps.strings = Some(stream),
+                name if name == GUID_STREAM_NAME => heaps.guids = Some(stream),
+                name if name == BLOB_STREAM_NAME => heaps.blobs = Some(stream),
+                name if name == US_STREAM_NAME => heaps.user_strings = Some(stream),
+                _ => {}
+            }
+        }
+
+        Some(heaps)
+    }
+
+    /// The assembly's module name (the `#~` stream's single `Module` row),
+    /// resolved via `cor20_heaps`.
+    pub fn module_name(&self) -> Option<&str> {
+        let heaps = self.cor20_heaps()?;
+        let entries = self.cor20_metadata_table_entries()?;
+        entries.iter().find_map(|entry| match entry {
+            Entry::Module(module) => module.name.resolve(&heaps),
+            _ => None,
+        })
+    }
+
+    /// The fully-qualified `Namespace.Name` of a `TypeDef` row, resolved via `cor20_heaps`.
+    pub fn type_full_name(&self, entry: &TypeDefEntry) -> Option<String> {
+        let heaps = self.cor20_heaps()?;
+        let name = entry.name.resolve(&heaps)?;
+        match entry.namespace.resolve(&heaps) {
+            Some(namespace) if !namespace.is_empty() => Some(format!("{}.{}", namespace, name)),
+            _ => Some(name.to_string()),
+        }
+    }
 
-        let mut valid_index: usize = 0;
+    /// Resolves a raw `#Strings` heap offset (e.g. recovered while decoding a
+    /// signature/blob rather than from a table row's own `StringHeapIndex`).
+    pub fn dotnet_string(&self, index: u32) -> Option<&str> {
+        let heaps = self.cor20_heaps()?;
+        StringHeapIndex { offset: index, size: 0 }.resolve(&heaps)
+    }
 
-        let mut entries = Vec::<Entry>::new();
+    /// Resolves a raw `#US` (user string) heap offset into the UTF-16LE
+    /// string literal it encodes, e.g. a `ldstr` operand. The heap entry's
+    /// trailing byte is a has-extended-character flag, not string data, and
+    /// is dropped here.
+    pub fn dotnet_user_string(&self, index: u32) -> Option<String> {
+        let heaps = self.cor20_heaps()?;
+        let content = read_compressed_blob(heaps.user_strings?, index as usize)?;
+        let utf16_bytes = if content.len() % 2 == 1 { &content[..content.len() - 1] } else { content };
+        let units: Vec<u16> = utf16_bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        String::from_utf16(&units).ok()
+    }
 
-        for i in 0..64 as usize {
+    /// Resolves a raw `#Blob` heap offset (e.g. a method/field signature) into
+    /// its length-prefixed content.
+    pub fn dotnet_blob(&self, index: u32) -> Option<&[u8]> {
+        let heaps = self.cor20_heaps()?;
+        BlobHeapIndex { offset: index, size: 0 }.resolve(&heaps)
+    }
 
-            let entry_offset = cor20_metadata_table_offset as usize
-                + mem::size_of::<Cor20MetadataTable>()
-                + (valid_index * 4);
+    fn parse_cor20_metadata_table(&self) -> Result<(u64, &Cor20MetadataTable), Error> {
+        if !self.is_dotnet() {
+            return Err(Error::new(ErrorKind::InvalidInput, "not a .NET assembly"));
+        }
+        let (mut start, _) = self.parse_cor20_storage_signature_header()?;
+        for (_, header) in self.parse_cor20_stream_headers()? {
+            if header.name(self.file.data()) == vec![0x23, 0x7e, 0x00, 0x00] {
+                start += header.offset as u64;
+            }
+        }
+        let mut cursor = Cor20Cursor::new(self.file.data(), start as usize);
+        let header_offset = cursor.position() as u64;
+        let data = cursor.read(mem::size_of::<Cor20MetadataTable>())?;
+        let header = Cor20MetadataTable::from_bytes(data)?;
+        Ok((header_offset, header))
+    }
 
-            if entry_offset + 4 > self.file.data.len() {
-                return None;
+    pub fn cor20_metadata_table(&self) -> Option<&Cor20MetadataTable> {
+        self.parse_cor20_metadata_table().ok().map(|(_, header)| header)
+    }
+
+    pub fn cor20_metadata_table_entries(&self) -> Option<Vec<Entry>> {
+        if !self.is_dotnet() { return None; }
+
+        let (cor20_metadata_table_offset, cor20_metadata_table) = self.parse_cor20_metadata_table().ok()?;
+        let mut cursor = Cor20Cursor::new(self.file.data(), cor20_metadata_table_offset as usize);
+        cursor.advance(mem::size_of::<Cor20MetadataTable>() as i64).ok()?;
+
+        // Row counts for every present table precede all row data, one 4-byte
+        // count per set bit of `mask_valid`, in table-index order. Every
+        // row's width can reference any table's row count (simple/coded table
+        // indices), so all counts must be read before any row can be sized.
+        let mut row_counts = [0u32; 64];
+        for i in 0..64usize {
+            if cor20_metadata_table.mask_valid & (1u64 << i) == 0 {
+                continue;
+            }
+            row_counts[i] = cursor.read_u32().ok()?;
+        }
+
+        let sizes = TableSizes {
+            heap_sizes: cor20_metadata_table.heap_sizes,
+            row_counts,
+        };
+
+        let mut entries = Vec::<Entry>::new();
+
+        for i in 0..64usize {
+            let row_count = row_counts[i] as usize;
+            if row_count == 0 {
+                continue;
             }
 
-            let entry_count = u32::from_le_bytes(
-                self.file.data[entry_offset..entry_offset + 4].try_into().unwrap(),
-            ) as usize;
-
-            match i {
-                x if x == MetadataToken::Module as usize => {
-                    for _ in 0..entry_count {
-                        let entry = ModuleEntry::from_bytes(
-                            &self.file.data[offset..],
-                            cor20_metadata_table.heap_sizes)?;
-                        offset += entry.size();
-                        entries.push(Entry::Module(entry));
+            let row_size = sizes.row_size(i)?;
+
+            for _ in 0..row_count {
+                let row = cursor.read(row_size).ok()?;
+
+                match i {
+                    x if x == MetadataToken::Module as usize => {
+                        entries.push(Entry::Module(ModuleEntry::from_bytes(row, &sizes)?));
                     }
-                    valid_index += 1;
-                }
-                x if x == MetadataToken::TypeRef as usize => {
-                    for _ in 0..entry_count {
-                        let entry = TypeRefEntry::from_bytes(
-                            &self.file.data[offset..],
-                            cor20_metadata_table.heap_sizes)?;
-                        offset += entry.size();
-                        entries.push(Entry::TypeRef(entry));
+                    x if x == MetadataToken::TypeRef as usize => {
+                        entries.push(Entry::TypeRef(TypeRefEntry::from_bytes(row, &sizes)?));
                     }
-                    valid_index += 1;
-                }
-                x if x == MetadataToken::TypeDef as usize => {
-                    for _ in 0..entry_count {
-                        let entry = TypeDefEntry::from_bytes(
-                            &self.file.data[offset..],
-                            cor20_metadata_table.heap_sizes,
-                        )?;
-                        offset += entry.size();
-                        entries.push(Entry::TypeDef(entry));
+                    x if x == MetadataToken::TypeDef as usize => {
+                        entries.push(Entry::TypeDef(TypeDefEntry::from_bytes(row, &sizes)?));
                     }
-                    valid_index += 1;
-                }
-                x if x == MetadataToken::Field as usize => {
-                    for _ in 0..entry_count {
-                        let entry = FieldEntry::from_bytes(
-                            &self.file.data[offset..],
-                            cor20_metadata_table.heap_sizes,
-                        )?;
-                        offset += entry.size();
-                        entries.push(Entry::Field(entry));
+                    x if x == MetadataToken::Field as usize => {
+                        entries.push(Entry::Field(FieldEntry::from_bytes(row, &sizes)?));
                     }
-                    valid_index += 1;
-                }
-                x if x == MetadataToken::MethodDef as usize => {
-                    for _ in 0..entry_count {
-                        let entry = MethodDefEntry::from_bytes(
-                            &self.file.data[offset..],
-                            cor20_metadata_table.heap_sizes)?;
-                        offset += entry.size();
-                        entries.push(Entry::MethodDef(entry));
+                    x if x == MetadataToken::MethodDef as usize => {
+                        entries.push(Entry::MethodDef(MethodDefEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::Param as usize => {
+                        entries.push(Entry::Param(ParamEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::MemberRef as usize => {
+                        entries.push(Entry::MemberRef(MemberRefEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::Constant as usize => {
+                        entries.push(Entry::Constant(ConstantEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::CustomAttribute as usize => {
+                        entries.push(Entry::CustomAttribute(CustomAttributeEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::StandAloneSig as usize => {
+                        entries.push(Entry::StandAloneSig(StandAloneSigEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::TypeSpec as usize => {
+                        entries.push(Entry::TypeSpec(TypeSpecEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::Assembly as usize => {
+                        entries.push(Entry::Assembly(AssemblyEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::AssemblyRef as usize => {
+                        entries.push(Entry::AssemblyRef(AssemblyRefEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::ModuleRef as usize => {
+                        entries.push(Entry::ModuleRef(ModuleRefEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::ImplMap as usize => {
+                        entries.push(Entry::ImplMap(ImplMapEntry::from_bytes(row, &sizes)?));
+                    }
+                    x if x == MetadataToken::MethodSpec as usize => {
+                        entries.push(Entry::MethodSpec(MethodSpecEntry::from_bytes(row, &sizes)?));
+                    }
+                    _ => {
+                        entries.push(Entry::Raw(metadata_token_from_index(i)?, row.to_vec()));
                     }
                 }
-                _ => {}
             }
         }
 
@@ -872,11 +2478,12 @@ impl PE {
 
     pub fn cor20_method_header(&self, address: u64) -> Result<MethodHeader, Error> {
 
-        let offset = self.virtual_address_to_file_offset(address);
+        let offset = self.virtual_address_to_file_offset(address)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid virtual address"))? as usize;
 
-        if offset.is_none() { return Err(Error::new(ErrorKind::InvalidInput, "invalid virtual address")); }
-
-        let bytes = &self.file.data[offset.unwrap() as usize..offset.unwrap() as usize + 12];
+        let bytes = offset.checked_add(12)
+            .and_then(|end| self.file.data().get(offset..end))
+            .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "method header extends past end of file"))?;
 
         if bytes[0] & 0b11 == 0b10 {
             let code_size = bytes[0] >> 2;
@@ -890,6 +2497,228 @@ impl PE {
         return Err(Error::new(ErrorKind::InvalidData, "invalid method header"));
     }
 
+    /// Disassembles the CIL method body at `address` (typically a
+    /// `MethodDefEntry.rva`), decoding its `MethodHeader` and then its IL
+    /// stream into a flat list of `CilInstruction`s.
+    pub fn cor20_method_instructions(&self, address: u64) -> Result<Vec<CilInstruction>, Error> {
+        let header = self.cor20_method_header(address)?;
+
+        let header_size = header.size()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "unknown method header size"))?;
+
+        let offset = self.virtual_address_to_file_offset(address)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid virtual address"))?
+            as usize;
+
+        let start = offset + header_size;
+        let end = start + header.code_size();
+
+        if end > self.file.data().len() {
+            return Err(Error::new(ErrorKind::InvalidData, "method body extends past end of file"));
+        }
+
+        cil_decode_instructions(&self.file.data()[start..end])
+    }
+
+    /// Locates the PE security directory (the certificate data directory).
+    ///
+    /// Unlike every other data directory, its `rva`/`size` pair is actually
+    /// a raw file offset and length, not an RVA to translate through
+    /// section headers.
+    fn parse_security_directory(&self) -> Option<(u64, u64)> {
+        let directory = self.pe.data_directory_by_type(DATA_DIRECTORY::CERTIFICATE_TABLE)?;
+        if directory.size() == 0 {
+            return None;
+        }
+        Some((directory.rva() as u64, directory.size() as u64))
+    }
+
+    /// Walks the PE security directory's list of `WIN_CERTIFICATE` entries,
+    /// each padded to an 8-byte boundary.
+    pub fn security_directory_entries(&self) -> Option<Vec<WinCertificate<'_>>> {
+        let (offset, size) = self.parse_security_directory()?;
+        let data = self.file.data();
+        let end = offset as usize + size as usize;
+        if end > data.len() {
+            return None;
+        }
+
+        let mut entries = Vec::new();
+        let mut cursor = offset as usize;
+        while cursor + mem::size_of::<WinCertificateHeader>() <= end {
+            let header = WinCertificateHeader::from_bytes(
+                &data[cursor..cursor + mem::size_of::<WinCertificateHeader>()],
+            )
+            .ok()?;
+            let entry_length = header.length as usize;
+            if entry_length < mem::size_of::<WinCertificateHeader>() || cursor + entry_length > end {
+                return None;
+            }
+            let certificate_data = &data[cursor + mem::size_of::<WinCertificateHeader>()..cursor + entry_length];
+            entries.push(WinCertificate { header, data: certificate_data });
+            cursor += (entry_length + 7) & !7;
+        }
+        Some(entries)
+    }
+
+    /// The raw PKCS#7 `SignedData` blob of every `WIN_CERTIFICATE` entry in
+    /// the security directory, for callers that want to verify the
+    /// Authenticode signature themselves rather than just read its digest.
+    pub fn certificates(&self) -> Option<Vec<Vec<u8>>> {
+        Some(
+            self.security_directory_entries()?
+                .into_iter()
+                .map(|certificate| certificate.data.to_vec())
+                .collect(),
+        )
+    }
+
+    /// Computes the Authentihash: the SHA-256 of the file with
+    /// `authenticode_excluded_ranges` skipped, processed in file order. This
+    /// is the digest an Authenticode signer computes and embeds, so comparing
+    /// it against `authenticode_message_digest()` on each of `certificates()`
+    /// tells a caller whether a signed PE has been tampered with since signing.
+    pub fn authentihash(&self) -> Option<String> {
+        let data = self.file.data();
+        let mut excluded = self.authenticode_excluded_ranges()?;
+        excluded.sort_by_key(|&(offset, _)| offset);
+
+        let mut context = digest::Context::new(&digest::SHA256);
+        let mut cursor = 0usize;
+        for (offset, length) in excluded {
+            let offset = offset as usize;
+            let end = offset.saturating_add(length as usize).min(data.len());
+            if offset > cursor {
+                context.update(&data[cursor..offset.min(data.len())]);
+            }
+            cursor = cursor.max(end);
+        }
+        if cursor < data.len() {
+            context.update(&data[cursor..]);
+        }
+
+        Some(crate::models::binary::Binary::to_hex(context.finish().as_ref()))
+    }
+
+    /// Locates and decodes the undocumented `Rich` header MSVC toolchains
+    /// embed between the DOS stub and the PE header.
+    ///
+    /// Scans backward from the `PE\0\0` offset for the `Rich` marker, reads
+    /// the 4-byte XOR key immediately following it, then walks backward
+    /// XOR-decoding 8-byte records until the `DanS` magic is found.
+    ///
+    /// # Returns
+    ///
+    /// The decoded entries plus the recomputed checksum, or `None` if the
+    /// file has no Rich header. `RichHeader::checksum` should equal the XOR
+    /// key found in the file; a mismatch means the header was hand-edited
+    /// rather than produced by the linker.
+    pub fn rich_header(&self) -> Option<RichHeader> {
+        let data = self.file.data();
+        let e_lfanew = u32::from_le_bytes(data.get(0x3C..0x40)?.try_into().ok()?) as usize;
+        if e_lfanew > data.len() {
+            return None;
+        }
+
+        const DANS: u32 = 0x536e6144; // "DanS", little-endian
+
+        let rich_offset = data[..e_lfanew].windows(4).rposition(|window| window == b"Rich")?;
+        let key = u32::from_le_bytes(data.get(rich_offset + 4..rich_offset + 8)?.try_into().ok()?);
+
+        // Walk backward in 4-byte dwords from just before "Rich", XOR-decoding
+        // each, until the decoded dword is "DanS".
+        let mut position = rich_offset;
+        let dans_offset = loop {
+            if position < 4 {
+                return None;
+            }
+            position -= 4;
+            let dword = u32::from_le_bytes(data[position..position + 4].try_into().unwrap()) ^ key;
+            if dword == DANS {
+                break position;
+            }
+        };
+
+        // "DanS" is followed by three XOR-key-only padding dwords before the
+        // entries begin.
+        let entries_start = dans_offset + 4 + 12;
+        if entries_start > rich_offset {
+            return None;
+        }
+
+        let mut entries = Vec::new();
+        let mut cursor = entries_start;
+        while cursor + 8 <= rich_offset {
+            let comp_id_build = u32::from_le_bytes(data[cursor..cursor + 4].try_into().unwrap()) ^ key;
+            let count = u32::from_le_bytes(data[cursor + 4..cursor + 8].try_into().unwrap()) ^ key;
+            entries.push(RichHeaderEntry {
+                comp_id: (comp_id_build >> 16) as u16,
+                build: comp_id_build as u16,
+                count,
+            });
+            cursor += 8;
+        }
+
+        // The reference checksum: the "DanS" offset, plus every DOS header
+        // byte before it (with the `e_lfanew` field treated as zero) rotated
+        // left by its own file offset, plus every comp-id/build dword
+        // rotated left by its use count.
+        let mut checksum = dans_offset as u32;
+        for (i, &byte) in data[..dans_offset].iter().enumerate() {
+            if (0x3C..0x40).contains(&i) {
+                continue;
+            }
+            checksum = checksum.wrapping_add(rotate_left_u32(byte as u32, i as u32));
+        }
+        for entry in &entries {
+            let comp_id_build = ((entry.comp_id as u32) << 16) | entry.build as u32;
+            checksum = checksum.wrapping_add(rotate_left_u32(comp_id_build, entry.count));
+        }
+
+        Some(RichHeader { entries, checksum })
+    }
+
+    /// The file byte ranges Authenticode excludes when computing its PE
+    /// image hash: the `CheckSum` field in the optional header, the
+    /// certificate-table data directory entry itself, and (if present) the
+    /// certificate table's own bytes — all three describe the file's own
+    /// signing state, so including them would make the hash of a signed file
+    /// never match the hash computed before it was signed.
+    ///
+    /// # Returns
+    ///
+    /// `(offset, length)` ranges, not necessarily in file order, for callers
+    /// to skip when recomputing the Authenticode PE hash.
+    pub fn authenticode_excluded_ranges(&self) -> Option<Vec<(u64, u64)>> {
+        let e_lfanew = u32::from_le_bytes(self.file.data().get(0x3C..0x40)?.try_into().ok()?) as u64;
+        // "PE\0\0" (4 bytes) + COFF file header (20 bytes).
+        let optional_header_offset = e_lfanew + 4 + 20;
+
+        // CheckSum sits at the same offset in both PE32 and PE32+: PE32's
+        // extra BaseOfData field (4 bytes) and PE32+'s wider 8-byte ImageBase
+        // (vs PE32's 4-byte ImageBase, with no BaseOfData) add up the same.
+        let checksum_range = (optional_header_offset + 64, 4u64);
+
+        let data_directories_offset = match self.pe.header().machine() {
+            MachineType::AMD64 => 112u64,
+            _ => 96u64,
+        };
+        // Security/Certificate Table is DATA_DIRECTORY entry index 4.
+        let certificate_directory_offset = optional_header_offset + data_directories_offset + 4 * 8;
+        let certificate_directory_range = (certificate_directory_offset, 8u64);
+
+        let mut ranges = vec![checksum_range, certificate_directory_range];
+
+        // The certificate table's own bytes (the WIN_CERTIFICATE entries
+        // themselves) are also excluded: they're the signature's payload,
+        // appended to the file after the hash they cover was computed.
+        if let Some(certificate_table_range) = self.parse_security_directory() {
+            ranges.push(certificate_table_range);
+        }
+
+        Some(ranges)
+    }
+
     /// Checks if the PE file is a .NET assembly.
     ///
     /// This function inspects the imports of the PE file to identify whether it is a .NET application.
@@ -918,7 +2747,7 @@ impl PE {
     #[allow(dead_code)]
     pub fn from_bytes(bytes: Vec<u8>, config: Config) -> Result<Self, Error> {
         let file = File::from_bytes(bytes, config.clone());
-        let mut cursor = Cursor::new(&file.data);
+        let mut cursor = Cursor::new(&file.data());
         if let Some(Binary::PE(pe)) = Binary::from(&mut cursor) {
             return Ok(Self{
                 pe: pe,
@@ -1003,7 +2832,390 @@ impl PE {
             .collect()
     }
 
-    /// Returns a set of function addresses (entry point, exports, TLS callbacks, and Pogo entries) in the PE file.
+    /// Walks the `DELAY_IMPORT_DESCRIPTOR` data directory (a zero-terminated
+    /// array of `IMAGE_DELAYLOAD_DESCRIPTOR`), resolving each descriptor's
+    /// import name table against its import address table to recover every
+    /// delay-loaded thunk's IAT slot VA and target symbol.
+    ///
+    /// # Returns
+    /// One `DelayImportThunk` per resolvable IAT slot; empty if the file has
+    /// no delay-import directory.
+    #[allow(dead_code)]
+    pub fn delay_imports(&self) -> Vec<DelayImportThunk> {
+        let mut thunks = Vec::new();
+        let directory = match self.pe.data_directory_by_type(DATA_DIRECTORY::DELAY_IMPORT_DESCRIPTOR) {
+            Some(directory) if directory.size() > 0 => directory,
+            _ => return thunks,
+        };
+        let data = self.file.data();
+        let is_64bit = matches!(self.pe.header().machine(), MachineType::AMD64);
+        let thunk_size: u64 = if is_64bit { 8 } else { 4 };
+        let ordinal_flag: u64 = if is_64bit { 1 << 63 } else { 1 << 31 };
+
+        const DESCRIPTOR_SIZE: usize = 32;
+        let mut descriptor_rva = directory.rva() as u64;
+        loop {
+            let descriptor_offset = match self.relative_virtual_address_to_file_offset(descriptor_rva) {
+                Some(offset) => offset as usize,
+                None => break,
+            };
+            let descriptor = match data.get(descriptor_offset..descriptor_offset + DESCRIPTOR_SIZE) {
+                Some(bytes) => bytes,
+                None => break,
+            };
+            let dll_name_rva = u32::from_le_bytes(descriptor[4..8].try_into().unwrap());
+            let import_address_table_rva = u32::from_le_bytes(descriptor[12..16].try_into().unwrap());
+            let import_name_table_rva = u32::from_le_bytes(descriptor[16..20].try_into().unwrap());
+            if dll_name_rva == 0 && import_address_table_rva == 0 && import_name_table_rva == 0 {
+                break;
+            }
+
+            let dll = self
+                .relative_virtual_address_to_file_offset(dll_name_rva as u64)
+                .and_then(|offset| read_c_string(data, offset as usize))
+                .unwrap_or_default();
+
+            let mut index: u64 = 0;
+            loop {
+                let name_slot_offset = match self.relative_virtual_address_to_file_offset(
+                    import_name_table_rva as u64 + index * thunk_size,
+                ) {
+                    Some(offset) => offset as usize,
+                    None => break,
+                };
+                let thunk_bytes = match data.get(name_slot_offset..name_slot_offset + thunk_size as usize) {
+                    Some(bytes) => bytes,
+                    None => break,
+                };
+                let thunk_value = if is_64bit {
+                    u64::from_le_bytes(thunk_bytes.try_into().unwrap())
+                } else {
+                    u32::from_le_bytes(thunk_bytes.try_into().unwrap()) as u64
+                };
+                if thunk_value == 0 {
+                    break;
+                }
+
+                let symbol = if thunk_value & ordinal_flag != 0 {
+                    format!("#{}", thunk_value & 0xFFFF)
+                } else {
+                    // A Hint/Name entry: a 2-byte hint followed by the nul-terminated name.
+                    self.relative_virtual_address_to_file_offset(thunk_value)
+                        .and_then(|offset| read_c_string(data, offset as usize + 2))
+                        .unwrap_or_default()
+                };
+
+                let iat_slot_rva = import_address_table_rva as u64 + index * thunk_size;
+                thunks.push(DelayImportThunk {
+                    address: self.relative_virtual_address_to_virtual_address(iat_slot_rva),
+                    dll: dll.clone(),
+                    symbol,
+                });
+
+                index += 1;
+            }
+
+            descriptor_rva += DESCRIPTOR_SIZE as u64;
+        }
+
+        thunks
+    }
+
+    /// Walks the `BOUND_IMPORT` data directory (a zero-terminated array of
+    /// `IMAGE_BOUND_IMPORT_DESCRIPTOR`), one entry per module the loader
+    /// recorded as already bound, plus any forwarder-chain modules it refers to.
+    ///
+    /// # Returns
+    /// One `BoundImportEntry` per descriptor; empty if the file has no
+    /// bound-import directory.
+    #[allow(dead_code)]
+    pub fn bound_imports(&self) -> Vec<BoundImportEntry> {
+        let mut entries = Vec::new();
+        let directory = match self.pe.data_directory_by_type(DATA_DIRECTORY::BOUND_IMPORT) {
+            Some(directory) if directory.size() > 0 => directory,
+            _ => return entries,
+        };
+        let data = self.file.data();
+        let directory_offset = match self.relative_virtual_address_to_file_offset(directory.rva() as u64) {
+            Some(offset) => offset as usize,
+            None => return entries,
+        };
+
+        const DESCRIPTOR_SIZE: usize = 8;
+        const FORWARDER_REF_SIZE: usize = 8;
+        let directory_end = directory_offset + directory.size() as usize;
+        let mut cursor = directory_offset;
+
+        loop {
+            if cursor + DESCRIPTOR_SIZE > directory_end {
+                break;
+            }
+            let descriptor = match data.get(cursor..cursor + DESCRIPTOR_SIZE) {
+                Some(bytes) => bytes,
+                None => break,
+            };
+            let timestamp = u32::from_le_bytes(descriptor[0..4].try_into().unwrap());
+            let offset_module_name = u16::from_le_bytes(descriptor[4..6].try_into().unwrap());
+            let forwarder_count = u16::from_le_bytes(descriptor[6..8].try_into().unwrap());
+            cursor += DESCRIPTOR_SIZE;
+
+            if timestamp == 0 && offset_module_name == 0 && forwarder_count == 0 {
+                break;
+            }
+
+            let dll = read_c_string(data, directory_offset + offset_module_name as usize).unwrap_or_default();
+
+            let mut forwarders = Vec::new();
+            for _ in 0..forwarder_count {
+                let forwarder = match data.get(cursor..cursor + FORWARDER_REF_SIZE) {
+                    Some(bytes) => bytes,
+                    None => break,
+                };
+                let forwarder_offset_module_name = u16::from_le_bytes(forwarder[4..6].try_into().unwrap());
+                if let Some(name) = read_c_string(data, directory_offset + forwarder_offset_module_name as usize) {
+                    forwarders.push(name);
+                }
+                cursor += FORWARDER_REF_SIZE;
+            }
+
+            entries.push(BoundImportEntry { dll, timestamp, forwarders });
+        }
+
+        entries
+    }
+
+    /// Maps every resolvable import address table slot's virtual address to
+    /// the `(dll, symbol)` pair it resolves to, covering both ordinary and
+    /// delay-loaded imports, so disassembly of an indirect call/jump through
+    /// the IAT can be annotated with the target API name.
+    ///
+    /// # Returns
+    /// A `BTreeMap` keyed by IAT slot VA.
+    #[allow(dead_code)]
+    pub fn imports_by_address(&self) -> BTreeMap<u64, (String, String)> {
+        let mut result = BTreeMap::<u64, (String, String)>::new();
+
+        for import in self.pe.imports() {
+            let dll = import.name();
+            for entry in import.entries() {
+                let symbol = if entry.is_ordinal() {
+                    format!("#{}", entry.ordinal())
+                } else {
+                    entry.name()
+                };
+                result.insert(entry.address() + self.imagebase(), (dll.clone(), symbol));
+            }
+        }
+
+        for thunk in self.delay_imports() {
+            result.insert(thunk.address, (thunk.dll, thunk.symbol));
+        }
+
+        result
+    }
+
+    /// Parses the Load Config data directory's `IMAGE_LOAD_CONFIG_DIRECTORY`,
+    /// reading it as the 32-bit or 64-bit layout per the file's machine type.
+    ///
+    /// # Returns
+    /// `None` if there's no Load Config directory, or it's too short to
+    /// contain the `GuardFlags` field this crate reads.
+    #[allow(dead_code)]
+    pub fn load_config(&self) -> Option<LoadConfig> {
+        let directory = self.pe.data_directory_by_type(DATA_DIRECTORY::LOAD_CONFIG_TABLE)?;
+        if directory.size() == 0 {
+            return None;
+        }
+        let offset = self.relative_virtual_address_to_file_offset(directory.rva() as u64)? as usize;
+        let data = self.file.data();
+        let size = u32::from_le_bytes(data.get(offset..offset + 4)?.try_into().ok()?);
+
+        let read_u32 = |field_offset: usize| -> Option<u32> {
+            data.get(offset + field_offset..offset + field_offset + 4)?.try_into().ok().map(u32::from_le_bytes)
+        };
+        let read_u64 = |field_offset: usize| -> Option<u64> {
+            data.get(offset + field_offset..offset + field_offset + 8)?.try_into().ok().map(u64::from_le_bytes)
+        };
+
+        if matches!(self.pe.header().machine(), MachineType::AMD64) {
+            const GUARD_CF_FUNCTION_TABLE_OFFSET: usize = 0x80;
+            const GUARD_CF_FUNCTION_COUNT_OFFSET: usize = 0x88;
+            const GUARD_FLAGS_OFFSET: usize = 0x90;
+            if (size as usize) < GUARD_FLAGS_OFFSET + 4 {
+                return None;
+            }
+            Some(LoadConfig {
+                size,
+                guard_flags: read_u32(GUARD_FLAGS_OFFSET)?,
+                guard_cf_function_table: read_u64(GUARD_CF_FUNCTION_TABLE_OFFSET)?,
+                guard_cf_function_count: read_u64(GUARD_CF_FUNCTION_COUNT_OFFSET)?,
+                se_handler_table: None,
+                se_handler_count: None,
+            })
+        } else {
+            const SE_HANDLER_TABLE_OFFSET: usize = 0x40;
+            const SE_HANDLER_COUNT_OFFSET: usize = 0x44;
+            const GUARD_CF_FUNCTION_TABLE_OFFSET: usize = 0x50;
+            const GUARD_CF_FUNCTION_COUNT_OFFSET: usize = 0x54;
+            const GUARD_FLAGS_OFFSET: usize = 0x58;
+            if (size as usize) < GUARD_FLAGS_OFFSET + 4 {
+                return None;
+            }
+            Some(LoadConfig {
+                size,
+                guard_flags: read_u32(GUARD_FLAGS_OFFSET)?,
+                guard_cf_function_table: read_u32(GUARD_CF_FUNCTION_TABLE_OFFSET)? as u64,
+                guard_cf_function_count: read_u32(GUARD_CF_FUNCTION_COUNT_OFFSET)? as u64,
+                se_handler_table: Some(read_u32(SE_HANDLER_TABLE_OFFSET)? as u64),
+                se_handler_count: Some(read_u32(SE_HANDLER_COUNT_OFFSET)? as u64),
+            })
+        }
+    }
+
+    /// Resolves every Control Flow Guard-valid indirect call target listed in
+    /// the Load Config directory's `GuardCFFunctionTable`.
+    ///
+    /// Each record is `4 + ((GuardFlags >> 28) & 0xF)` bytes: a 4-byte RVA
+    /// followed by that many bytes of per-function metadata, which this only
+    /// needs to skip past to reach the next record.
+    ///
+    /// # Returns
+    /// The resolved virtual addresses; empty if the file has no Load Config
+    /// directory or no CFG function table.
+    #[allow(dead_code)]
+    pub fn guard_cf_functions(&self) -> BTreeSet<u64> {
+        let mut addresses = BTreeSet::<u64>::new();
+        let load_config = match self.load_config() {
+            Some(load_config) => load_config,
+            None => return addresses,
+        };
+        if load_config.guard_cf_function_table == 0 || load_config.guard_cf_function_count == 0 {
+            return addresses;
+        }
+
+        let stride = 4 + ((load_config.guard_flags >> 28) & 0xF) as u64;
+        let data = self.file.data();
+        let table_rva = load_config.guard_cf_function_table.saturating_sub(self.imagebase());
+
+        for i in 0..load_config.guard_cf_function_count {
+            let entry_offset = match self.relative_virtual_address_to_file_offset(table_rva + i * stride) {
+                Some(offset) => offset as usize,
+                None => break,
+            };
+            let rva = match data.get(entry_offset..entry_offset + 4) {
+                Some(bytes) => u32::from_le_bytes(bytes.try_into().unwrap()),
+                None => break,
+            };
+            addresses.insert(self.relative_virtual_address_to_virtual_address(rva as u64));
+        }
+
+        addresses
+    }
+
+    /// Follows the `UNW_FLAG_CHAININFO` bit of the `UNWIND_INFO` at
+    /// `unwind_info_rva`, if set, to the parent `RUNTIME_FUNCTION` a
+    /// fragment's unwind data chains to.
+    ///
+    /// The `UNWIND_INFO` header is 4 bytes (version/flags, size of prolog,
+    /// count of unwind codes, frame register/offset), followed by
+    /// `CountOfCodes` 2-byte `UNWIND_CODE` entries padded to a 4-byte
+    /// boundary, and then, only when chained, the parent's 12-byte
+    /// `RUNTIME_FUNCTION` record.
+    fn chained_runtime_function(&self, unwind_info_rva: u32) -> Option<(u32, u32, u32)> {
+        const UNW_FLAG_CHAININFO: u8 = 0x04;
+
+        let offset = self.relative_virtual_address_to_file_offset(unwind_info_rva as u64)? as usize;
+        let data = self.file.data();
+        let header = data.get(offset..offset + 4)?;
+        if header[0] >> 3 & UNW_FLAG_CHAININFO == 0 {
+            return None;
+        }
+
+        let count_of_codes = header[2] as usize;
+        let chain_offset = offset + 4 + ((count_of_codes * 2 + 3) & !3);
+        let chain = data.get(chain_offset..chain_offset + 12)?;
+        Some((
+            u32::from_le_bytes(chain[0..4].try_into().ok()?),
+            u32::from_le_bytes(chain[4..8].try_into().ok()?),
+            u32::from_le_bytes(chain[8..12].try_into().ok()?),
+        ))
+    }
+
+    /// Parses the AMD64 exception directory's `RUNTIME_FUNCTION` array (three
+    /// 4-byte RVAs per 12-byte record: begin address, end address, unwind
+    /// info) and resolves each record's begin/end RVA to a virtual address.
+    ///
+    /// A fragment whose unwind info carries `UNW_FLAG_CHAININFO` is followed
+    /// up the chain to the parent function it belongs to, so fragmented
+    /// functions are merged into one range instead of being reported as
+    /// several unrelated ones.
+    ///
+    /// # Returns
+    /// A `BTreeMap` of function start address to end address; empty on
+    /// non-AMD64 files or files with no exception directory.
+    #[allow(dead_code)]
+    pub fn runtime_functions(&self) -> BTreeMap<u64, u64> {
+        let mut functions = BTreeMap::<u64, u64>::new();
+        if !matches!(self.pe.header().machine(), MachineType::AMD64) {
+            return functions;
+        }
+
+        let directory = match self.pe.data_directory_by_type(DATA_DIRECTORY::EXCEPTION_TABLE) {
+            Some(directory) if directory.size() > 0 => directory,
+            _ => return functions,
+        };
+        let table_offset = match self.relative_virtual_address_to_file_offset(directory.rva() as u64) {
+            Some(offset) => offset as usize,
+            None => return functions,
+        };
+        let data = self.file.data();
+
+        const RECORD_SIZE: usize = 12;
+        let record_count = directory.size() as usize / RECORD_SIZE;
+
+        for index in 0..record_count {
+            let record_offset = table_offset + index * RECORD_SIZE;
+            let record = match data.get(record_offset..record_offset + RECORD_SIZE) {
+                Some(bytes) => bytes,
+                None => break,
+            };
+            let (mut begin_rva, mut end_rva, mut unwind_info_rva) = (
+                u32::from_le_bytes(record[0..4].try_into().unwrap()),
+                u32::from_le_bytes(record[4..8].try_into().unwrap()),
+                u32::from_le_bytes(record[8..12].try_into().unwrap()),
+            );
+            if begin_rva == 0 && end_rva == 0 {
+                continue;
+            }
+            let fragment_end_rva = end_rva;
+
+            // Bounded to tolerate a malformed or cyclic chain.
+            for _ in 0..64 {
+                match self.chained_runtime_function(unwind_info_rva) {
+                    Some((parent_begin_rva, parent_end_rva, parent_unwind_info_rva)) => {
+                        begin_rva = parent_begin_rva;
+                        end_rva = parent_end_rva;
+                        unwind_info_rva = parent_unwind_info_rva;
+                    }
+                    None => break,
+                }
+            }
+
+            let start_address = self.relative_virtual_address_to_virtual_address(begin_rva as u64);
+            let end_address = self.relative_virtual_address_to_virtual_address(end_rva.max(fragment_end_rva) as u64);
+            functions
+                .entry(start_address)
+                .and_modify(|existing_end| *existing_end = (*existing_end).max(end_address))
+                .or_insert(end_address);
+        }
+
+        functions
+    }
+
+    /// Returns a set of function addresses (entry point, exports, TLS
+    /// callbacks, Pogo entries, resolvable delay-import thunk slots,
+    /// Control Flow Guard function table targets, and x64 exception table
+    /// function starts) in the PE file.
     ///
     /// # Returns
     /// A `BTreeSet` of function addresses in the PE file.
@@ -1013,7 +3225,10 @@ impl PE {
         addresses.insert(self.entrypoint());
         addresses.extend(self.exports());
         addresses.extend(self.tlscallbacks());
+        addresses.extend(self.guard_cf_functions());
         addresses.extend(self.pogos().keys().cloned());
+        addresses.extend(self.delay_imports().into_iter().map(|thunk| thunk.address));
+        addresses.extend(self.runtime_functions().into_keys());
         return addresses;
     }
 
@@ -1107,6 +3322,49 @@ impl PE {
         None
     }
 
+    /// Returns `false` when the raw-file layout of every section already matches where
+    /// that section would land in the reconstructed image, i.e. `image()`'s rewrite
+    /// loop would produce a file byte-identical to the one already on disk.
+    ///
+    /// This is common for unpacked PEs where `section_alignment()` equals
+    /// `file_alignment()`, so `align_section_virtual_address` never inserts padding
+    /// between sections that isn't already present in the raw file.
+    fn image_requires_rewrite(&self) -> bool {
+        if self.section_alignment() != self.file_alignment() {
+            return true;
+        }
+        for section in self.pe.sections() {
+            if section.virtual_size() == 0 { continue; }
+            if section.sizeof_raw_data() == 0 { continue; }
+            let expected = PE::align_section_virtual_address(
+                self.imagebase() + section.pointerto_raw_data() as u64,
+                self.section_alignment(),
+                self.file_alignment());
+            if expected != self.imagebase() + section.pointerto_raw_data() as u64 {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns a zero-copy, memory-mapped view of the PE image.
+    ///
+    /// When no section needs to be relocated or padded to honor its virtual alignment,
+    /// this maps the original file directly rather than duplicating it into a
+    /// reconstructed temporary file, so both the cache and non-cache paths in `main()`
+    /// converge on the same `&[u8]` backed by mapped memory instead of a heap copy.
+    /// Falls back to the existing rewrite-based reconstruction in `image()` when the
+    /// layout actually requires it.
+    #[allow(dead_code)]
+    pub fn image_mapped(&self) -> Result<MemoryMappedFile, Error> {
+        if !self.image_requires_rewrite() {
+            if let Some(path) = &self.file.path {
+                return MemoryMappedFile::new_readonly(PathBuf::from(path));
+            }
+        }
+        self.image()
+    }
+
     /// Caches the PE file contents and returns a `MemoryMappedFile` object.
     ///
     /// # Parameters
@@ -1125,7 +3383,7 @@ impl PE {
         if tempmap.is_cached() {
             return Ok(tempmap);
         }
-        tempmap.write(&self.file.data[0..self.sizeofheaders() as usize])?;
+        tempmap.write(&self.file.data()[0..self.sizeofheaders() as usize])?;
         for section in self.pe.sections() {
             if section.virtual_size() == 0 { continue; }
             if section.sizeof_raw_data() == 0 { continue; }
@@ -1139,11 +3397,47 @@ impl PE {
             }
             let pointerto_raw_data = section.pointerto_raw_data() as usize;
             let sizeof_raw_data = section.sizeof_raw_data() as usize;
-            tempmap.write(&self.file.data[pointerto_raw_data..pointerto_raw_data + sizeof_raw_data])?;
+            tempmap.write(&self.file.data()[pointerto_raw_data..pointerto_raw_data + sizeof_raw_data])?;
         }
         Ok(tempmap)
     }
 
+    /// Searches the reconstructed image's executable sections for `pattern`,
+    /// e.g. a known code stub or constant, for seeding disassembly or
+    /// yara-like triage.
+    ///
+    /// Uses a masked Boyer-Moore-Horspool search (see `BytePattern::find_all`)
+    /// over each range `executable_virtual_address_ranges` reports, so a
+    /// mismatch against a wildcard-free pattern tail skips ahead instead of
+    /// falling back to a byte-by-byte scan of the whole image.
+    ///
+    /// # Returns
+    /// The virtual address of every match, in ascending order.
+    #[allow(dead_code)]
+    pub fn scan(&self, pattern: &BytePattern) -> Result<Vec<u64>, Error> {
+        let image = self.image()?;
+        let mapped = image.mmap()?;
+        let haystack: &[u8] = &mapped;
+
+        let mut matches = Vec::new();
+        for (start_address, end_address) in self.executable_virtual_address_ranges() {
+            let start = start_address.saturating_sub(self.imagebase()) as usize;
+            let end = (end_address.saturating_sub(self.imagebase()) as usize).min(haystack.len());
+            if start >= end {
+                continue;
+            }
+            matches.extend(
+                pattern
+                    .find_all(&haystack[start..end])
+                    .into_iter()
+                    .map(|offset| start_address + offset as u64),
+            );
+        }
+
+        matches.sort_unstable();
+        Ok(matches)
+    }
+
     /// Returns the size of the PE file.
     ///
     /// # Returns
@@ -1200,3 +3494,57 @@ impl PE {
         return addresses;
     }
 }
+
+impl crate::formats::Executable for PE {
+    fn architecture(&self) -> Architecture {
+        PE::architecture(self)
+    }
+
+    fn entrypoints(&self) -> BTreeSet<u64> {
+        PE::entrypoints(self)
+    }
+
+    fn executable_virtual_address_ranges(&self) -> BTreeMap<u64, u64> {
+        PE::executable_virtual_address_ranges(self)
+    }
+
+    fn sha256(&self) -> Option<String> {
+        PE::sha256(self)
+    }
+
+    fn tlsh(&self) -> Option<String> {
+        PE::tlsh(self)
+    }
+
+    fn size(&self) -> u64 {
+        PE::size(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Cor20StreamHeader;
+
+    #[test]
+    fn cor20_stream_header_name_reads_padded_name() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(b"foo\0");
+
+        let header = Cor20StreamHeader::from_bytes(&data).unwrap();
+
+        assert_eq!(header.name(&data), b"foo\0");
+    }
+
+    #[test]
+    fn cor20_stream_header_name_does_not_run_past_truncated_file() {
+        let mut data = Vec::new();
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+
+        let header = Cor20StreamHeader::from_bytes(&data).unwrap();
+
+        assert_eq!(header.name(&data), b"");
+    }
+}
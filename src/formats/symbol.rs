@@ -1,4 +1,4 @@
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::BTreeSet;
@@ -28,6 +28,49 @@ pub struct SymbolJson {
     pub address: u64,
 }
 
+impl SymbolIoJson {
+    /// Converts the function symbol into a CBOR-encoded byte representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` containing the CBOR representation, or an `Err` if
+    /// serialization fails.
+    #[allow(dead_code)]
+    pub fn cbor(&self) -> Result<Vec<u8>, Error> {
+        let mut result = Vec::<u8>::new();
+        ciborium::into_writer(self, &mut result)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        Ok(result)
+    }
+
+    /// Reconstructs a `SymbolIoJson` from the CBOR produced by `cbor()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(SymbolIoJson)` on success, or an `Err` if `data` isn't valid
+    /// CBOR for this shape.
+    #[allow(dead_code)]
+    pub fn from_cbor(data: &[u8]) -> Result<Self, Error> {
+        ciborium::from_reader(data)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+}
+
+impl SymbolJson {
+    /// Reconstructs a `SymbolJson` from the CBOR produced by `Symbol::cbor()`,
+    /// the inverse encoding of `cbor()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(SymbolJson)` on success, or an `Err` if `data` isn't valid
+    /// CBOR for this shape.
+    #[allow(dead_code)]
+    pub fn from_cbor(data: &[u8]) -> Result<Self, Error> {
+        ciborium::from_reader(data)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+}
+
 /// Represents a structure containing metadata about a function symbol.
 #[derive(Clone)]
 pub struct Symbol {
@@ -96,6 +139,21 @@ impl Symbol {
          Ok(result)
      }
 
+    /// Converts the function symbol metadata into a CBOR-encoded byte representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` containing the CBOR representation, or an `Err` if
+    /// serialization fails.
+    #[allow(dead_code)]
+    pub fn cbor(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        let mut result = Vec::<u8>::new();
+        ciborium::into_writer(&raw, &mut result)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        Ok(result)
+    }
+
     /// Demangles a Microsoft Visual C++ (MSVC) mangled symbol name.
     ///
     /// # Arguments
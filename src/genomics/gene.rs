@@ -0,0 +1,18 @@
+/// A single nibble in a `Genome` pattern: either a fixed hex value or a
+/// wildcard that matches any nibble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Gene {
+    Wildcard,
+    Value(u8),
+}
+
+impl Gene {
+    /// Renders this gene as the character used in a genome string (`?` for
+    /// `Wildcard`, a lowercase hex digit for `Value`).
+    pub fn to_char(&self) -> String {
+        match self {
+            Gene::Wildcard => "?".to_string(),
+            Gene::Value(value) => format!("{:x}", value),
+        }
+    }
+}
@@ -164,6 +164,192 @@ impl Genome {
         false
     }
 
+    /// Checks whether the genome matches `data` anchored at offset 0, as
+    /// opposed to `matches_buffer`, which searches every offset.
+    #[allow(dead_code)]
+    pub fn matches_prefix(&self, data: &[u8]) -> bool {
+        if self.genome.len() > data.len() {
+            return false;
+        }
+        self.genome
+            .iter()
+            .enumerate()
+            .all(|(i, byte_pattern)| Self::matches_byte(byte_pattern, data[i]))
+    }
+
+    /// Produces an independent copy of this genome's alleles with a freshly
+    /// seeded RNG and no evaluation history, so a survivor carried into the
+    /// next generation doesn't share RNG state or `is_previous_state` history
+    /// with the original.
+    fn duplicate(&self) -> Genome {
+        let seed = self.rng.lock().unwrap().gen::<u64>();
+        Self::from_genome(self.genome.clone(), seed)
+    }
+
+    fn from_genome(genome: Vec<AllelePair>, seed: u64) -> Self {
+        Self {
+            genome,
+            rng: Arc::new(Mutex::new(StdRng::seed_from_u64(seed))),
+            states: HashSet::<String>::new(),
+        }
+    }
+
+    /// Single-point crossover: cuts both genomes' allele vectors at the same
+    /// random index and swaps tails, producing two offspring.
+    ///
+    /// Genomes shorter than two alleles have no interior cut point, so they're
+    /// returned as fresh, independently-seeded copies instead.
+    #[allow(dead_code)]
+    pub fn crossover(&self, other: &Genome) -> (Genome, Genome) {
+        let shorter = self.genome.len().min(other.genome.len());
+        if shorter < 2 {
+            return (self.duplicate(), other.duplicate());
+        }
+
+        let cut = self.rng.lock().unwrap().gen_range(1..shorter);
+
+        let mut child_a = self.genome[..cut].to_vec();
+        child_a.extend_from_slice(&other.genome[cut..]);
+
+        let mut child_b = other.genome[..cut].to_vec();
+        child_b.extend_from_slice(&self.genome[cut..]);
+
+        let seed_a = self.rng.lock().unwrap().gen::<u64>();
+        let seed_b = self.rng.lock().unwrap().gen::<u64>();
+
+        (Self::from_genome(child_a, seed_a), Self::from_genome(child_b, seed_b))
+    }
+
+    /// Scores this genome against a labeled corpus: the fraction of
+    /// `positives` it matches, minus the fraction of `negatives` it matches,
+    /// minus a penalty for `wildcard_ratio()` so a genome can't win by
+    /// wildcarding itself into a universal match.
+    #[allow(dead_code)]
+    pub fn fitness(&self, positives: &[Vec<u8>], negatives: &[Vec<u8>]) -> f64 {
+        let true_positive_rate = if positives.is_empty() {
+            0.0
+        } else {
+            positives.iter().filter(|buffer| self.matches_buffer(buffer)).count() as f64 / positives.len() as f64
+        };
+
+        let false_positive_rate = if negatives.is_empty() {
+            0.0
+        } else {
+            negatives.iter().filter(|buffer| self.matches_buffer(buffer)).count() as f64 / negatives.len() as f64
+        };
+
+        true_positive_rate - false_positive_rate - (self.wildcard_ratio() * 0.5)
+    }
+
+    /// Scores a candidate genome for `evolve`, rejecting shapes that can
+    /// never be a useful signature outright rather than letting them compete
+    /// on `fitness` alone: an empty genome matches every buffer vacuously
+    /// (its match loop has nothing to fail on), and a genome longer than the
+    /// shortest positive sample can never match that sample, so it can never
+    /// reach a perfect score no matter how the rest of it evolves.
+    fn evolution_fitness(genome: &Genome, positives: &[Vec<u8>], negatives: &[Vec<u8>], max_genome_len: usize) -> f64 {
+        if genome.genome.is_empty() || genome.genome.len() > max_genome_len {
+            return f64::MIN;
+        }
+        genome.fitness(positives, negatives)
+    }
+
+    /// Evolves `population` toward maximizing `fitness` against
+    /// `positives`/`negatives` over up to `generations` rounds, stopping
+    /// early once a genome reaches a perfect score of `1.0`.
+    ///
+    /// Each round scores every genome whose own `is_previous_state` history
+    /// doesn't already hold its current pattern; survivors and offspring start
+    /// with an empty history (see `duplicate`), so only genomes passed in with
+    /// a saved state are ever skipped, and a pattern that reappears in a later
+    /// generation is scored again. It then keeps the top half by fitness, and refills the
+    /// population by crossing survivors and rolling `mutation_rate` against
+    /// each offspring to decide whether to apply one of the existing
+    /// mutation operators (`mutate_wildcard`, `mutate_add_gene`) to it.
+    ///
+    /// # Returns
+    ///
+    /// Returns the string form of the best-scoring genome seen across all
+    /// generations paired with its fitness score, or an empty string and
+    /// `0.0` if `population` is empty or every genome was rejected (see
+    /// `evolution_fitness`).
+    #[allow(dead_code)]
+    pub fn evolve(mut population: Vec<Genome>, positives: &[Vec<u8>], negatives: &[Vec<u8>], generations: usize, mutation_rate: f64) -> (String, f64) {
+        let max_genome_len = positives.iter().map(|buffer| buffer.len()).min().unwrap_or(usize::MAX);
+        let mut best_genome: Option<String> = None;
+        let mut best_score = f64::MIN;
+
+        for _ in 0..generations {
+            let mut scored: Vec<(usize, f64)> = Vec::with_capacity(population.len());
+
+            for (index, genome) in population.iter_mut().enumerate() {
+                if genome.is_previous_state() {
+                    continue;
+                }
+                genome.save();
+                scored.push((index, Self::evolution_fitness(genome, positives, negatives, max_genome_len)));
+            }
+
+            if scored.is_empty() {
+                break;
+            }
+
+            scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+            if scored[0].1 > best_score {
+                best_score = scored[0].1;
+                best_genome = Some(population[scored[0].0].to_string());
+            }
+
+            if best_score >= 1.0 {
+                break;
+            }
+
+            let survivor_count = ((scored.len() + 1) / 2).max(1);
+            let survivors: Vec<Genome> = scored
+                .iter()
+                .take(survivor_count)
+                .map(|&(index, _)| population[index].duplicate())
+                .collect();
+
+            let mut next_generation: Vec<Genome> = survivors.iter().map(Genome::duplicate).collect();
+
+            let mut cursor = 0;
+            while next_generation.len() < population.len() {
+                let parent_a = &survivors[cursor % survivors.len()];
+                let parent_b = &survivors[(cursor + 1) % survivors.len()];
+                let (mut child_a, mut child_b) = parent_a.crossover(parent_b);
+                Self::maybe_mutate(&mut child_a, mutation_rate);
+                Self::maybe_mutate(&mut child_b, mutation_rate);
+
+                next_generation.push(child_a);
+                if next_generation.len() < population.len() {
+                    next_generation.push(child_b);
+                }
+                cursor += 1;
+            }
+
+            population = next_generation;
+        }
+
+        (best_genome.unwrap_or_default(), if best_score == f64::MIN { 0.0 } else { best_score })
+    }
+
+    /// Rolls `mutation_rate` and, on success, applies one of the two mutation
+    /// operators to `genome` at random. Kept as its own helper so `evolve`'s
+    /// generational loop reads as "cross, then maybe mutate" regardless of
+    /// which operator that turns out to be.
+    fn maybe_mutate(genome: &mut Genome, mutation_rate: f64) {
+        if !genome.rng.lock().unwrap().gen_bool(mutation_rate.clamp(0.0, 1.0)) {
+            return;
+        }
+        if genome.rng.lock().unwrap().gen_bool(0.5) {
+            genome.mutate_wildcard();
+        } else {
+            genome.mutate_add_gene();
+        }
+    }
+
     #[allow(dead_code)]
     pub fn matches_file(&self, path: PathBuf) -> Result<bool, IoError> {
         let mapped_file = MemoryMappedFile::new_readonly(path)?;
@@ -171,6 +357,68 @@ impl Genome {
         Ok(self.matches_buffer(&data))
     }
 
+    /// Emits this genome as a complete YARA rule named `name`. `Display`
+    /// already renders each `AllelePair` as two hex-or-`?` characters, which
+    /// is exactly YARA hex-string byte syntax (`XY`, `?X`, `X?`, or `??`), so
+    /// the hex string here is just that rendering with a space between bytes.
+    #[allow(dead_code)]
+    pub fn to_yara_rule(&self, name: &str) -> String {
+        let hex_string: String = self.genome
+            .iter()
+            .map(|pair| format!("{}{}", pair.high.to_char(), pair.low.to_char()))
+            .collect::<Vec<String>>()
+            .join(" ");
+        format!(
+            "rule {} {{\n    strings:\n        $g = {{ {} }}\n    condition:\n        $g\n}}\n",
+            name, hex_string,
+        )
+    }
+
+    /// Parses a YARA hex-string body (the tokens between `{` and `}`, not a
+    /// whole rule) back into a `Genome`. Each whitespace-separated token is
+    /// either a two-character byte (`XY`, `?X`, `X?`, `??`) or a jump
+    /// `[n]`/`[n-m]`, which has no fixed nibble content in YARA itself, so
+    /// it's expanded into `n` (or, for a range, `m`, the widest the jump
+    /// could be) fully-wildcarded `??` byte alleles.
+    #[allow(dead_code)]
+    pub fn from_yara_hex(hex_string: &str) -> Result<Self, Box<dyn Error>> {
+        let mut alleles: Vec<AllelePair> = Vec::new();
+
+        for token in hex_string.split_whitespace() {
+            if let Some(jump) = token.strip_prefix('[').and_then(|t| t.strip_suffix(']')) {
+                let count: usize = match jump.split_once('-') {
+                    Some((_, max)) => max.trim().parse()?,
+                    None => jump.trim().parse()?,
+                };
+                for _ in 0..count {
+                    alleles.push(AllelePair { high: Gene::Wildcard, low: Gene::Wildcard });
+                }
+                continue;
+            }
+
+            let nibbles: Vec<char> = token.chars().collect();
+            if nibbles.len() != 2 {
+                return Err(format!("invalid yara hex token: {}", token).into());
+            }
+            alleles.push(AllelePair {
+                high: Self::parse_yara_nibble(nibbles[0])?,
+                low: Self::parse_yara_nibble(nibbles[1])?,
+            });
+        }
+
+        let seed = rand::thread_rng().gen::<u64>();
+        Ok(Self::from_genome(alleles, seed))
+    }
+
+    /// Parses a single YARA hex-string nibble: `?` (wildcard) or a hex digit.
+    fn parse_yara_nibble(c: char) -> Result<Gene, Box<dyn Error>> {
+        match c {
+            '?' => Ok(Gene::Wildcard),
+            _ if c.is_ascii_hexdigit() => Ok(Gene::Value(u8::from_str_radix(&c.to_string(), 16)?)),
+            _ => Err(format!("invalid character in yara hex token: {}", c).into()),
+        }
+    }
+
     #[allow(dead_code)]
     fn matches_byte(pattern: &AllelePair, data_byte: u8) -> bool {
         let high_nibble = (data_byte >> 4) & 0x0F;
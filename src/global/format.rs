@@ -12,17 +12,22 @@ pub enum Format {
     CODE = 0x00,
     /// Portable Executable
     PE = 0x01,
+    /// Executable and Linkable Format
+    ELF = 0x02,
+    /// Mach-O
+    MACHO = 0x03,
     /// Unknown formats
-    UNKNOWN = 0x02,
+    UNKNOWN = 0x04,
 }
 
 impl Format {
     pub fn from_file(path: String) -> Result<Format, Error> {
         let mut file = File::open(path)?;
-        let mut buffer = [0u8; 2];
+        let mut buffer = [0u8; 4];
         file.seek(SeekFrom::Start(0x00))?;
         file.read_exact(&mut buffer)?;
-        if buffer == [0x4d, 0x5a] {
+
+        if buffer[0..2] == [0x4d, 0x5a] {
             file.seek(SeekFrom::Start(0x3c))?;
             let mut pe_offset = [0u8; 4];
             file.read_exact(&mut pe_offset)?;
@@ -34,6 +39,20 @@ impl Format {
                 return Ok(Format::PE);
             }
         }
+
+        if buffer == [0x7f, 0x45, 0x4c, 0x46] {
+            return Ok(Format::ELF);
+        }
+
+        if buffer == [0xfe, 0xed, 0xfa, 0xce]
+            || buffer == [0xce, 0xfa, 0xed, 0xfe]
+            || buffer == [0xfe, 0xed, 0xfa, 0xcf]
+            || buffer == [0xcf, 0xfa, 0xed, 0xfe]
+            || buffer == [0xca, 0xfe, 0xba, 0xbe]
+            || buffer == [0xbe, 0xba, 0xfe, 0xca] {
+            return Ok(Format::MACHO);
+        }
+
         return Ok(Format::UNKNOWN);
     }
 }
@@ -43,6 +62,8 @@ impl fmt::Display for Format {
         let format: &str = match self {
             Format::CODE => "code",
             Format::PE => "pe",
+            Format::ELF => "elf",
+            Format::MACHO => "macho",
             Format::UNKNOWN => "unknown",
         };
         write!(f, "{}", format)
@@ -55,6 +76,8 @@ impl FromStr for Format {
         match s {
             "code" => Ok(Format::CODE),
             "pe" => Ok(Format::PE),
+            "elf" => Ok(Format::ELF),
+            "macho" => Ok(Format::MACHO),
             "unknown" => Ok(Format::UNKNOWN),
             _ => Err(format!("invalid format")),
         }
@@ -0,0 +1,75 @@
+use crate::io::error::IoError;
+use crate::io::traits::{Read, Write};
+
+/// An in-memory `io::Read`/`io::Write` sink over an owned byte buffer, mirroring
+/// (a small slice of) `std::io::Cursor` without depending on `std::io`.
+///
+/// Used by the streaming JSON writers in `models::controlflow` when the caller
+/// wants an in-memory buffer rather than a file or socket, and by tests/tools
+/// that want to exercise `io::Write` consumers without touching disk.
+pub struct Cursor {
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl Cursor {
+    /// Creates an empty `Cursor`.
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Wraps an existing buffer, positioned at its start for reading.
+    pub fn from_vec(buffer: Vec<u8>) -> Self {
+        Self { buffer, position: 0 }
+    }
+
+    /// Consumes the `Cursor`, returning the underlying buffer.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.buffer
+    }
+
+    /// Borrows the underlying buffer.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+impl Default for Cursor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Read for Cursor {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        let remaining = &self.buffer[self.position..];
+        let n = remaining.len().min(buf.len());
+        buf[..n].copy_from_slice(&remaining[..n]);
+        self.position += n;
+        Ok(n)
+    }
+}
+
+impl Write for Cursor {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError> {
+        self.buffer.extend_from_slice(buf);
+        self.position = self.buffer.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), IoError> {
+        Ok(())
+    }
+}
+
+impl Read for &[u8] {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError> {
+        let n = self.len().min(buf.len());
+        buf[..n].copy_from_slice(&self[..n]);
+        *self = &self[n..];
+        Ok(n)
+    }
+}
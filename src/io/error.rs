@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// Crate-local error type for `io::Read`/`io::Write`, so callers that only need the
+/// `std`-free subset of this module don't have to pull in `std::io::Error`.
+///
+/// When the `std` feature is enabled, `From<std::io::Error>` and
+/// `From<IoError> for std::io::Error` let this type round-trip through code that
+/// still speaks `std::io`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IoError {
+    /// The reader ran out of data before satisfying the request (mirrors
+    /// `std::io::ErrorKind::UnexpectedEof`).
+    UnexpectedEof,
+    /// A `write` call reported writing zero bytes for a non-empty buffer.
+    WriteZero,
+    /// Any other failure, carrying a human-readable description.
+    Other(String),
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IoError::UnexpectedEof => write!(f, "unexpected end of file"),
+            IoError::WriteZero => write!(f, "failed to write whole buffer"),
+            IoError::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for IoError {}
+
+#[cfg(feature = "std")]
+impl From<std::io::Error> for IoError {
+    fn from(error: std::io::Error) -> Self {
+        match error.kind() {
+            std::io::ErrorKind::UnexpectedEof => IoError::UnexpectedEof,
+            std::io::ErrorKind::WriteZero => IoError::WriteZero,
+            _ => IoError::Other(error.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<IoError> for std::io::Error {
+    fn from(error: IoError) -> Self {
+        let kind = match error {
+            IoError::UnexpectedEof => std::io::ErrorKind::UnexpectedEof,
+            IoError::WriteZero => std::io::ErrorKind::WriteZero,
+            IoError::Other(_) => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, error.to_string())
+    }
+}
@@ -0,0 +1,17 @@
+//! A minimal `Read`/`Write` abstraction that mirrors `std::io`'s signatures
+//! without requiring it, plus a crate-local `IoError` and an in-memory `Cursor`.
+//!
+//! This exists so hot paths that only need to stream bytes through (incremental
+//! hashing, JSONL serialization) don't hard-depend on `std::io::Error` or force
+//! data fully into memory first. With the `std` feature enabled (the default),
+//! `impl_io_read_for_std!`/`impl_io_write_for_std!` bridge concrete `std` types
+//! (`File`, `Vec<u8>`) onto these traits, so callers can pass either a `Cursor`
+//! or a real file interchangeably.
+
+pub mod cursor;
+pub mod error;
+pub mod traits;
+
+pub use cursor::Cursor;
+pub use error::IoError;
+pub use traits::{Read, Write};
@@ -0,0 +1,86 @@
+use crate::io::error::IoError;
+
+/// Mirrors `std::io::Read`'s core contract without requiring `std`, so types in
+/// this crate can be written against `io::Read` and still work in a `no_std`
+/// context where the `std` feature (see `impl_io_read_for_std!` below) is disabled.
+pub trait Read {
+    /// Reads into `buf`, returning the number of bytes read (`0` at EOF).
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, IoError>;
+
+    /// Reads exactly `buf.len()` bytes, or fails with `IoError::UnexpectedEof`.
+    fn read_exact(&mut self, mut buf: &mut [u8]) -> Result<(), IoError> {
+        while !buf.is_empty() {
+            match self.read(buf)? {
+                0 => return Err(IoError::UnexpectedEof),
+                n => {
+                    buf = &mut buf[n..];
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Mirrors `std::io::Write`'s core contract without requiring `std`.
+pub trait Write {
+    /// Writes `buf`, returning the number of bytes written.
+    fn write(&mut self, buf: &[u8]) -> Result<usize, IoError>;
+
+    /// Flushes any buffered data.
+    fn flush(&mut self) -> Result<(), IoError>;
+
+    /// Writes the whole of `buf`, or fails with `IoError::WriteZero`.
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<(), IoError> {
+        while !buf.is_empty() {
+            match self.write(buf)? {
+                0 => return Err(IoError::WriteZero),
+                n => {
+                    buf = &buf[n..];
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Implements `io::Write` for a type that already implements `std::io::Write`,
+/// so existing `std` types (`File`, `Vec<u8>`, `Stdout`, ...) can be passed
+/// anywhere an `io::Write` is expected without a blanket impl.
+///
+/// Modeled on the `bitcoin-io` crate's approach of mirroring `std::io`'s traits
+/// and bridging concrete types via a macro rather than a blanket `impl<T:
+/// std::io::Write>`, which would conflict with first-party `io::Write` impls
+/// (e.g. `Cursor`).
+#[macro_export]
+macro_rules! impl_io_write_for_std {
+    ($ty:ty) => {
+        impl $crate::io::Write for $ty {
+            fn write(&mut self, buf: &[u8]) -> Result<usize, $crate::io::IoError> {
+                std::io::Write::write(self, buf).map_err(Into::into)
+            }
+
+            fn flush(&mut self) -> Result<(), $crate::io::IoError> {
+                std::io::Write::flush(self).map_err(Into::into)
+            }
+        }
+    };
+}
+
+/// The `io::Read` counterpart of `impl_io_write_for_std!`.
+#[macro_export]
+macro_rules! impl_io_read_for_std {
+    ($ty:ty) => {
+        impl $crate::io::Read for $ty {
+            fn read(&mut self, buf: &mut [u8]) -> Result<usize, $crate::io::IoError> {
+                std::io::Read::read(self, buf).map_err(Into::into)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "std")]
+impl_io_write_for_std!(std::fs::File);
+#[cfg(feature = "std")]
+impl_io_read_for_std!(std::fs::File);
+#[cfg(feature = "std")]
+impl_io_write_for_std!(Vec<u8>);
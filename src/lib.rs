@@ -6,6 +6,8 @@ pub mod terminal;
 pub mod disassemblers;
 pub mod binary;
 pub mod global;
+pub mod genomics;
+pub mod config;
 
 pub use global::Config;
 pub use binary::Binary;
@@ -1,6 +1,7 @@
 mod models;
 mod formats;
 mod types;
+mod io;
 
 use rayon::ThreadPoolBuilder;
 use formats::pe::PE;
@@ -8,14 +9,16 @@ use models::disassemblers::capstone::disassembler::Disassembler;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde_json::json;
 use std::process;
-use std::fs::File;
-use std::io::Write;
 use std::collections::BTreeSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
 use crate::models::controlflow::graph::Graph;
 use crate::models::controlflow::block::Block;
 use crate::models::controlflow::function::Function;
 use crate::types::lz4string::LZ4String;
+use crate::types::outputstore::OutputStore;
 use crate::models::terminal::args::ARGS;
+use crate::models::config::OutputFormat;
 use crate::models::terminal::io::Stdout;
 use memmap2::Mmap;
 use crate::models::terminal::io::JSON;
@@ -145,6 +148,14 @@ fn main() {
         entrypoints.extend(disassembler.disassemble_linear_pass(ARGS.linear_pass_jump_threshold, ARGS.linear_pass_instruction_threshold));
     }
 
+    if ARGS.enable_superset_pass {
+        entrypoints.extend(disassembler.disassemble_superset_pass(
+            ARGS.superset_pass_jump_threshold,
+            ARGS.superset_pass_instruction_threshold,
+            ARGS.superset_pass_confidence_threshold,
+        ));
+    }
+
     entrypoints.extend(pe.functions());
 
     let function_symbol_addresses: BTreeSet<u64> = function_symbols
@@ -167,6 +178,10 @@ fn main() {
     cfg.options.file_sha256 = pe.sha256();
     cfg.options.file_tlsh = pe.tlsh();
     cfg.options.file_size = Some(pe.size());
+    cfg.options.max_instructions = ARGS.max_instructions;
+    cfg.options.max_blocks = ARGS.max_blocks;
+    cfg.options.max_functions = ARGS.max_functions;
+    cfg.options.timeout_ms = ARGS.timeout_ms;
     cfg.functions.enqueue_extend(entrypoints);
     cfg.functions.insert_symbols_extend(function_symbols);
 
@@ -191,57 +206,104 @@ fn main() {
 
     let cfg = cfg;
 
-    let blocks: Vec<LZ4String> = cfg.blocks.valid()
-        .iter()
-        .map(|entry| *entry)
-        .collect::<Vec<u64>>()
-        .par_iter()
-        .filter_map(|address| Block::new(*address, &cfg).ok())
-        .filter_map(|block|block.json().ok())
-        .map(|js| LZ4String::new(&js))
-        .collect();
-
-    let functions: Vec<LZ4String> = cfg.functions.valid()
-        .iter()
-        .map(|entry| *entry)
-        .collect::<Vec<u64>>()
-        .par_iter()
-        .filter_map(|address| Function::new(*address, &cfg).ok())
-        .filter_map(|function| function.json().ok())
-        .map(|js| LZ4String::new(&js))
-        .collect();
-
-    if ARGS.output.is_none() {
-        functions.iter().for_each(|result| {
-            Stdout.print(result);
-        });
+    if ARGS.format == OutputFormat::Asm {
+        let listing = cfg.functions.valid()
+            .iter()
+            .map(|entry| *entry)
+            .collect::<Vec<u64>>()
+            .iter()
+            .filter_map(|address| Function::new(*address, &cfg).ok())
+            .map(|function| function.to_assembly(ARGS.minimal))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        match &ARGS.output {
+            Some(output_file) => {
+                if let Err(error) = std::fs::write(output_file, listing) {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            }
+            None => Stdout.print(listing),
+        }
 
-        blocks.iter().for_each(|result| {
-            Stdout.print(result);
-        });
+        process::exit(0);
     }
 
-     if let Some(output_file) = &ARGS.output {
-        let mut file = match File::create(output_file) {
-            Ok(file) => file,
+    if let Some(output_file) = &ARGS.output {
+        // Stream each record into the output store as soon as it is produced by the
+        // rayon pipeline instead of collecting every `LZ4String` into a `Vec` first,
+        // so peak memory is bounded by the store's index rather than the full corpus.
+        let store = match OutputStore::new(PathBuf::from(output_file), true) {
+            Ok(store) => Mutex::new(store),
             Err(error) => {
                 eprintln!("{}", error);
                 std::process::exit(1);
             }
         };
 
-        for function in functions {
-            if let Err(error) = writeln!(file, "{}", function) {
-                eprintln!("{}", error);
-                std::process::exit(1);
-            }
-        }
-        for block in blocks {
-            if let Err(error) = writeln!(file, "{}", block) {
-                eprintln!("{}", error);
-                std::process::exit(1);
-            }
+        cfg.functions.valid()
+            .iter()
+            .map(|entry| *entry)
+            .collect::<Vec<u64>>()
+            .par_iter()
+            .filter_map(|address| Function::new(*address, &cfg).ok())
+            .filter_map(|function| function.json().ok())
+            .for_each(|js| {
+                let record = LZ4String::new(&js);
+                if let Err(error) = store.lock().unwrap().append(record.to_string().as_bytes()) {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            });
+
+        cfg.blocks.valid()
+            .iter()
+            .map(|entry| *entry)
+            .collect::<Vec<u64>>()
+            .par_iter()
+            .filter_map(|address| Block::new(*address, &cfg).ok())
+            .filter_map(|block| block.json().ok())
+            .for_each(|js| {
+                let record = LZ4String::new(&js);
+                if let Err(error) = store.lock().unwrap().append(record.to_string().as_bytes()) {
+                    eprintln!("{}", error);
+                    std::process::exit(1);
+                }
+            });
+
+        if let Err(error) = store.into_inner().unwrap().finalize() {
+            eprintln!("{}", error);
+            std::process::exit(1);
         }
+    } else {
+        let blocks: Vec<LZ4String> = cfg.blocks.valid()
+            .iter()
+            .map(|entry| *entry)
+            .collect::<Vec<u64>>()
+            .par_iter()
+            .filter_map(|address| Block::new(*address, &cfg).ok())
+            .filter_map(|block|block.json().ok())
+            .map(|js| LZ4String::new(&js))
+            .collect();
+
+        let functions: Vec<LZ4String> = cfg.functions.valid()
+            .iter()
+            .map(|entry| *entry)
+            .collect::<Vec<u64>>()
+            .par_iter()
+            .filter_map(|address| Function::new(*address, &cfg).ok())
+            .filter_map(|function| function.json().ok())
+            .map(|js| LZ4String::new(&js))
+            .collect();
+
+        functions.iter().for_each(|result| {
+            Stdout.print(result);
+        });
+
+        blocks.iter().for_each(|result| {
+            Stdout.print(result);
+        });
     }
 
     process::exit(0);
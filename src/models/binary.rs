@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use crate::models::hashing::sha256::SHA256;
 pub struct Binary;
 
 #[repr(u16)]
@@ -6,15 +6,22 @@ pub struct Binary;
 pub enum BinaryArchitecture {
     AMD64 = 0x00,
     I386 = 0x01,
+    HOLEYBYTES = 0x02,
     UNKNOWN= 0x03,
+    ARM64 = 0x04,
+    RISCV = 0x05,
+    M68K = 0x06,
 }
 
 impl Binary {
 
+    /// A fixed `[usize; 256]` histogram is used instead of a `HashMap<u8,
+    /// usize>` so this hot loop needs no allocator state, keeping it usable
+    /// from the `no-std` build (see `models::nostd`).
     pub fn entropy(bytes: &Vec<u8>) -> Option<f64> {
-        let mut frequency: HashMap<u8, usize> = HashMap::new();
+        let mut frequency = [0usize; 256];
         for &byte in bytes {
-            *frequency.entry(byte).or_insert(0) += 1;
+            frequency[byte as usize] += 1;
         }
 
         let data_len = bytes.len() as f64;
@@ -22,7 +29,8 @@ impl Binary {
             return None;
         }
 
-        let entropy = frequency.values().fold(0.0, |entropy, &count| {
+        let entropy = frequency.iter().fold(0.0, |entropy, &count| {
+            if count == 0 { return entropy; }
             let probability = count as f64 / data_len;
             entropy - probability * probability.log2()
         });
@@ -36,6 +44,59 @@ impl Binary {
             .collect::<String>()
     }
 
+    /// Reverses `to_hex`, decoding a lowercase or uppercase hex string back into bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(Vec<u8>)` on success, or `None` if `hex` has odd length or
+    /// contains a non-hex-digit character.
+    #[allow(dead_code)]
+    pub fn from_hex(hex: &str) -> Option<Vec<u8>> {
+        if hex.len() % 2 != 0 {
+            return None;
+        }
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+            .collect()
+    }
+
+    /// Computes the SHA-256 digest of `data` as a lowercase hex string.
+    pub fn sha256(data: &[u8]) -> Option<String> {
+        SHA256::new(data).hexdigest()
+    }
+
+    /// Computes a binary Merkle root over `leaves`, repeatedly combining
+    /// adjacent pairs with `sha256(left || right)` and duplicating the last
+    /// node when a level has an odd count, until one 32-byte root remains.
+    ///
+    /// Used by `Block::merkle_root`/`Function::merkle` so two control flow
+    /// graphs can be compared by their roots alone, descending only into the
+    /// subtrees whose hashes differ instead of re-hashing everything.
+    ///
+    /// # Returns
+    ///
+    /// Returns `[0u8; 32]` if `leaves` is empty.
+    pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+        if leaves.is_empty() {
+            return [0u8; 32];
+        }
+        let mut level: Vec<[u8; 32]> = leaves.to_vec();
+        while level.len() > 1 {
+            let mut next_level = Vec::with_capacity((level.len() + 1) / 2);
+            for pair in level.chunks(2) {
+                let left = pair[0];
+                let right = if pair.len() == 2 { pair[1] } else { pair[0] };
+                let mut combined = Vec::with_capacity(64);
+                combined.extend_from_slice(&left);
+                combined.extend_from_slice(&right);
+                next_level.push(SHA256::new(&combined).digest());
+            }
+            level = next_level;
+        }
+        level[0]
+    }
+
     #[allow(dead_code)]
     pub fn hexdump(data: &[u8], address: u64) -> String {
         const BYTES_PER_LINE: usize = 16;
@@ -0,0 +1,55 @@
+pub mod yaz0;
+
+use serde::{Deserialize, Serialize};
+use std::io::{Error, ErrorKind};
+
+/// Compression algorithms available for `FunctionJson.bytes` and container payloads.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum CompressionAlgorithm {
+    /// Store bytes uncompressed, still behind the same header format.
+    None,
+    /// A dependency-free, Yaz0-style LZ77 scheme. See `yaz0`.
+    Yaz0,
+}
+
+const ALGORITHM_NONE: u8 = 0;
+const ALGORITHM_YAZ0: u8 = 1;
+
+/// Size in bytes of the header `compress` prefixes onto its output: a 1-byte
+/// algorithm id followed by the original (uncompressed) length as a little-endian `u64`.
+const HEADER_SIZE: usize = 9;
+
+/// Compresses `data` with `algorithm`, prefixing a small header so `decompress` can
+/// recover both which algorithm was used and how large the original was, without the
+/// caller needing to track either separately (e.g. across a `FunctionJson.bytes`
+/// round-trip or a container payload read back much later).
+pub fn compress(data: &[u8], algorithm: CompressionAlgorithm) -> Vec<u8> {
+    let (algorithm_id, payload) = match algorithm {
+        CompressionAlgorithm::None => (ALGORITHM_NONE, data.to_vec()),
+        CompressionAlgorithm::Yaz0 => (ALGORITHM_YAZ0, yaz0::compress(data)),
+    };
+
+    let mut result = Vec::with_capacity(payload.len() + HEADER_SIZE);
+    result.push(algorithm_id);
+    result.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    result.extend_from_slice(&payload);
+    result
+}
+
+/// Reverses `compress`, reading the algorithm id and original length back out of the
+/// header to dispatch to the right decoder and pre-size the output buffer.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, Error> {
+    if data.len() < HEADER_SIZE {
+        return Err(Error::new(ErrorKind::InvalidData, "compressed payload is too small"));
+    }
+
+    let algorithm_id = data[0];
+    let original_length = u64::from_le_bytes(data[1..HEADER_SIZE].try_into().unwrap()) as usize;
+    let payload = &data[HEADER_SIZE..];
+
+    match algorithm_id {
+        ALGORITHM_NONE => Ok(payload.to_vec()),
+        ALGORITHM_YAZ0 => Ok(yaz0::decompress(payload, original_length)),
+        other => Err(Error::new(ErrorKind::InvalidData, format!("unknown compression algorithm id {}", other))),
+    }
+}
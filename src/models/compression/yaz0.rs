@@ -0,0 +1,147 @@
+/// Maximum back-reference distance: 12 bits of distance-minus-one.
+const WINDOW_SIZE: usize = 4096;
+
+/// Shortest run worth encoding as a back-reference instead of literals.
+const MIN_MATCH: usize = 3;
+
+/// Longest run the short form (length packed into a nibble) can encode.
+const MAX_SHORT_MATCH: usize = 17;
+
+/// Longest run the long form (nibble `0` plus an extra length byte) can encode.
+const MAX_LONG_MATCH: usize = 273;
+
+/// Compresses `data` with a Yaz0-style LZ77 scheme.
+///
+/// The output is a series of groups, each led by a one-byte flag whose 8 bits
+/// (MSB first) say, per following token, whether it's a literal byte (`1`) or a
+/// back-reference (`0`) into the sliding window of already-emitted output.
+/// Back-references are 2 or 3 bytes: the high nibble of the first byte holds
+/// `length - 2` when nonzero (short form, length `3..=17`); when it's `0`, a
+/// third byte holds `length - 0x12` instead (long form, length `18..=273`).
+/// The low 12 bits across the first two bytes hold `distance - 1`.
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let mut output = Vec::new();
+    let mut pos = 0;
+
+    while pos < data.len() {
+        let mut flag_byte = 0u8;
+        let mut group = Vec::new();
+
+        for bit in 0..8 {
+            if pos >= data.len() {
+                break;
+            }
+
+            match find_longest_match(data, pos) {
+                Some((distance, length)) => {
+                    let distance_minus_one = (distance - 1) as u16;
+                    if length <= MAX_SHORT_MATCH {
+                        let byte0 = (((length - 2) as u8) << 4) | ((distance_minus_one >> 8) as u8 & 0x0F);
+                        let byte1 = (distance_minus_one & 0xFF) as u8;
+                        group.push(byte0);
+                        group.push(byte1);
+                    } else {
+                        let byte0 = (distance_minus_one >> 8) as u8 & 0x0F;
+                        let byte1 = (distance_minus_one & 0xFF) as u8;
+                        let byte2 = (length - 0x12) as u8;
+                        group.push(byte0);
+                        group.push(byte1);
+                        group.push(byte2);
+                    }
+                    pos += length;
+                }
+                None => {
+                    flag_byte |= 1 << (7 - bit);
+                    group.push(data[pos]);
+                    pos += 1;
+                }
+            }
+        }
+
+        output.push(flag_byte);
+        output.extend_from_slice(&group);
+    }
+
+    output
+}
+
+/// Reverses `compress`, given the original (uncompressed) length to know when to stop.
+pub fn decompress(data: &[u8], original_length: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(original_length);
+    let mut pos = 0;
+
+    while output.len() < original_length {
+        let flag_byte = data[pos];
+        pos += 1;
+
+        for bit in 0..8 {
+            if output.len() >= original_length {
+                break;
+            }
+
+            let is_literal = (flag_byte & (1 << (7 - bit))) != 0;
+            if is_literal {
+                output.push(data[pos]);
+                pos += 1;
+                continue;
+            }
+
+            let byte0 = data[pos];
+            let byte1 = data[pos + 1];
+            pos += 2;
+
+            let high_nibble = byte0 >> 4;
+            let distance = (((byte0 & 0x0F) as usize) << 8 | byte1 as usize) + 1;
+            let length = if high_nibble == 0 {
+                let byte2 = data[pos];
+                pos += 1;
+                byte2 as usize + 0x12
+            } else {
+                high_nibble as usize + 2
+            };
+
+            let start = output.len() - distance;
+            for i in 0..length {
+                let byte = output[start + i];
+                output.push(byte);
+            }
+        }
+    }
+
+    output
+}
+
+/// Finds the longest run in `data[..pos]` (within `WINDOW_SIZE` bytes) that matches
+/// `data[pos..]`, returning `(distance, length)`. A candidate's match is allowed to
+/// run past `pos` into not-yet-emitted territory, which is what lets a back-reference
+/// encode a repeating run longer than the distance back to its start.
+fn find_longest_match(data: &[u8], pos: usize) -> Option<(usize, usize)> {
+    let max_length = MAX_LONG_MATCH.min(data.len() - pos);
+    if max_length < MIN_MATCH {
+        return None;
+    }
+
+    let window_start = pos.saturating_sub(WINDOW_SIZE);
+    let mut best_length = 0;
+    let mut best_distance = 0;
+
+    for candidate in window_start..pos {
+        let mut length = 0;
+        while length < max_length && data[candidate + length] == data[pos + length] {
+            length += 1;
+        }
+        if length > best_length {
+            best_length = length;
+            best_distance = pos - candidate;
+            if best_length == max_length {
+                break;
+            }
+        }
+    }
+
+    if best_length >= MIN_MATCH {
+        Some((best_distance, best_length))
+    } else {
+        None
+    }
+}
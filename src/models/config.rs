@@ -1,8 +1,18 @@
 use std::process;
 use std::collections::HashSet;
 use clap::Parser;
+use clap::ValueEnum;
 use once_cell::sync::Lazy;
 
+/// Selects how `main` renders disassembled functions and blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// LZ4-compressed JSON records (the default).
+    Json,
+    /// A human-readable assembly listing, via `Function::to_assembly`.
+    Asm,
+}
+
 pub const VERSION: &str = "1.0.0";
 pub const AUTHOR: &str = "@c3rb3ru5d3d53c";
 
@@ -42,6 +52,17 @@ pub struct Args {
     pub tlsh_minimum_byte_size: usize,
     #[arg(long, default_value_t = false)]
     pub disable_linear_pass: bool,
+    /// Runs the heavier superset/shingled sweep alongside the linear pass and
+    /// feeds its high-confidence call targets to `disassemble_control_flow`
+    /// as additional seed functions.
+    #[arg(long, default_value_t = false)]
+    pub enable_superset_pass: bool,
+    #[arg(long, default_value_t = 2)]
+    pub superset_pass_jump_threshold: usize,
+    #[arg(long, default_value_t = 4)]
+    pub superset_pass_instruction_threshold: usize,
+    #[arg(long, default_value_t = 1.0)]
+    pub superset_pass_confidence_threshold: f64,
     #[arg(long, default_value_t = false)]
     pub disable_tlsh: bool,
     #[arg(long, default_value_t = false)]
@@ -54,6 +75,12 @@ pub struct Args {
     pub disable_feature: bool,
     #[arg(long, default_value_t = false)]
     pub disable_hashing: bool,
+    #[arg(long, default_value = None)]
+    pub max_instructions: Option<usize>,
+    #[arg(long, default_value = None)]
+    pub timeout_ms: Option<u64>,
+    #[arg(long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
 }
 
 fn validate(args: &mut Args) {
@@ -73,6 +100,26 @@ fn validate(args: &mut Args) {
         eprintln!("linear jump threshold must be greater than 0");
         process::exit(1);
     }
+    if args.superset_pass_instruction_threshold <= 0 {
+        eprintln!("superset instruction threshold must be greater than 0");
+        process::exit(1);
+    }
+    if args.superset_pass_jump_threshold <= 0 {
+        eprintln!("superset jump threshold must be greater than 0");
+        process::exit(1);
+    }
+    if args.superset_pass_confidence_threshold < 0.0 {
+        eprintln!("superset confidence threshold must be greater than or equal to 0");
+        process::exit(1);
+    }
+    if args.max_instructions == Some(0) {
+        eprintln!("max instructions must be greater than 0");
+        process::exit(1);
+    }
+    if args.timeout_ms == Some(0) {
+        eprintln!("timeout ms must be greater than 0");
+        process::exit(1);
+    }
     if args.disable_hashing {
         args.disable_minhash = true;
         args.disable_sha256 = true;
@@ -1,5 +1,6 @@
 use crate::models::binary::BinaryArchitecture;
 use crate::models::controlflow::instruction::Instruction;
+use crate::models::controlflow::instruction::InstructionJson;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::BTreeMap;
@@ -8,13 +9,18 @@ use std::io::Error;
 use std::io::ErrorKind;
 use crate::models::binary::Binary;
 use crate::models::controlflow::graph::Graph;
+use crate::models::controlflow::graph::HashAlgorithm;
 use crate::models::controlflow::signature::Signature;
 use crate::models::controlflow::signature::SignatureJson;
 use crate::models::controlflow::file::File;
 use crate::models::controlflow::file::FileJson;
+use crate::models::controlflow::rawjson::RawJson;
 use crate::models::hashing::sha256::SHA256;
 use crate::models::hashing::tlsh::TLSH;
-use crate::models::hashing::minhash::MinHash32;
+use crate::models::hashing::minhash::{MinHash32, DEFAULT_SIMILARITY_SEED};
+use crate::models::hashing::ssdeep::SSDEEP;
+use crate::models::controlflow::codec;
+use crate::models::sink::Sink;
 
 /// Represents the JSON-serializable structure of a control flow block.
 #[derive(Serialize, Deserialize)]
@@ -27,8 +33,10 @@ pub struct BlockJson {
     /// The starting address of the block.
     pub address: u64,
     /// The address of the next sequential block, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub next: Option<u64>,
     /// A set of addresses this block may branch or jump to.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     pub to: BTreeSet<u64>,
     /// The number of edges (connections) this block has.
     pub edges: usize,
@@ -43,22 +51,36 @@ pub struct BlockJson {
     /// The raw bytes of the block in hexadecimal format.
     pub bytes: String,
     /// A map of function addresses related to this block.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub functions: BTreeMap<u64, u64>,
     /// The number of instructions in this block.
     pub instructions: usize,
+    /// A human-readable, per-instruction disassembly listing, if
+    /// `GraphOptions::enable_disassembly` is set. See `Block::disassembly`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disassembly: Option<Vec<InstructionJson>>,
     /// The entropy of the block, if enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub entropy: Option<f64>,
     /// The SHA-256 hash of the block, if enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sha256: Option<String>,
-    /// The MinHash of the block, if enabled.
-    pub minhash: Option<String>,
-    /// The TLSH of the block, if enabled.
-    pub tlsh: Option<String>,
+    /// The Merkle root over the block's instructions, hex-encoded. Two blocks
+    /// with identical instructions always produce the same root, regardless
+    /// of surrounding code, letting a caller diffing two CFGs skip blocks
+    /// whose roots already match.
+    pub merkle: String,
+    /// Fuzzy/locality-sensitive hashes of the block, keyed by algorithm name
+    /// (e.g. `"tlsh"`, `"minhash"`, `"ssdeep"`), per `GraphOptions::hash_algorithms`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, String>,
     /// Indicates whether the block is contiguous.
     pub contiguous: bool,
     /// File metadata related to the block.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file: Option<FileJson>,
     /// Tags associated with the block.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
 
@@ -136,15 +158,279 @@ impl<'block> Block<'block> {
 
     /// Converts the block into a JSON string representation.
     ///
+    /// The result is cached on `cfg.block_json_cache` by address, so repeated
+    /// calls (e.g. from multiple function views referencing the same block)
+    /// reuse the already-encoded fragment instead of re-serializing it.
+    ///
     /// # Returns
     ///
     /// Returns `Ok(String)` containing the JSON representation, or an `Err` if serialization fails.
     pub fn json(&self) -> Result<String, Error> {
+        if let Some(entry) = self.cfg.block_json_cache.get(&self.address) {
+            return Ok(entry.value().as_str().to_string());
+        }
         let raw = self.process();
+        if let Some(sink) = &self.cfg.sink {
+            if let Ok(mut sink) = sink.lock() {
+                let _ = sink.send_block(&raw);
+            }
+        }
         let result = serde_json::to_string(&raw)?;
+        let fragment = RawJson::new(result.clone())?;
+        self.cfg.block_json_cache.insert(self.address, fragment);
         Ok(result)
     }
 
+    /// Converts the block metadata into a compact binary representation.
+    ///
+    /// This is a much smaller, faster-to-decode alternative to `json()` for consumers
+    /// that don't need a human-readable format (e.g. writing large corpora to disk).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` containing the bincode-encoded `BlockJson`, or an `Err`
+    /// if serialization fails.
+    pub fn bincode(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        bincode::serialize(&raw).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+
+    /// Encodes this block into a compact binary record: varint integer
+    /// fields, the `to`/`functions` address sets delta-encoded relative to
+    /// `self.address`, and the fuzzy/SHA-256 hashes stored as raw bytes
+    /// behind a 1-byte presence tag instead of hex text. Much smaller and
+    /// faster to decode than `json()`/`bincode()` for a corpus with millions
+    /// of blocks.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` with the encoded record.
+    #[allow(dead_code)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        let mut out = Vec::new();
+
+        codec::write_uvarint(raw.address, &mut out);
+        codec::write_string(&raw.architecture, &mut out);
+
+        match raw.next {
+            Some(value) => { out.push(1); codec::write_uvarint(codec::zigzag_encode(value as i64 - raw.address as i64), &mut out); }
+            None => out.push(0),
+        }
+
+        codec::write_uvarint(raw.to.len() as u64, &mut out);
+        for address in &raw.to {
+            codec::write_uvarint(codec::zigzag_encode(*address as i64 - raw.address as i64), &mut out);
+        }
+
+        codec::write_uvarint(raw.edges as u64, &mut out);
+
+        let mut flags: u8 = 0;
+        if raw.prologue { flags |= 0b001; }
+        if raw.conditional { flags |= 0b010; }
+        if raw.contiguous { flags |= 0b100; }
+        out.push(flags);
+
+        codec::write_bytes(&Signature::new(self.address, self.end(), &self.cfg).to_bytes()?, &mut out);
+
+        codec::write_uvarint(raw.size as u64, &mut out);
+        codec::write_bytes(&self.bytes(), &mut out);
+
+        codec::write_uvarint(raw.instructions as u64, &mut out);
+
+        codec::write_uvarint(raw.functions.len() as u64, &mut out);
+        for (instruction_address, function_address) in &raw.functions {
+            codec::write_uvarint(codec::zigzag_encode(*instruction_address as i64 - raw.address as i64), &mut out);
+            codec::write_uvarint(codec::zigzag_encode(*function_address as i64 - raw.address as i64), &mut out);
+        }
+
+        match raw.entropy {
+            Some(value) => { out.push(1); out.extend_from_slice(&value.to_le_bytes()); }
+            None => out.push(0),
+        }
+
+        match &raw.sha256 {
+            Some(hex) => {
+                out.push(1);
+                let bytes = Binary::from_hex(hex)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid sha256 hex"))?;
+                codec::write_bytes(&bytes, &mut out);
+            }
+            None => out.push(0),
+        }
+
+        out.extend_from_slice(&self.merkle_root());
+
+        codec::write_uvarint(raw.hashes.len() as u64, &mut out);
+        for (name, hex) in &raw.hashes {
+            codec::write_string(name, &mut out);
+            let bytes = Binary::from_hex(hex)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("invalid {} hex", name)))?;
+            codec::write_bytes(&bytes, &mut out);
+        }
+
+        let file_blob = bincode::serialize(&raw.file)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        codec::write_bytes(&file_blob, &mut out);
+
+        codec::write_uvarint(raw.tags.len() as u64, &mut out);
+        for tag in &raw.tags {
+            codec::write_string(tag, &mut out);
+        }
+
+        Ok(out)
+    }
+
+    /// Decodes a record written by `to_bytes` back into a `BlockJson`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `bytes` is truncated or malformed.
+    #[allow(dead_code)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<BlockJson, Error> {
+        let mut cursor = 0usize;
+
+        let address = codec::read_uvarint(bytes, &mut cursor)?;
+        let architecture = codec::read_string(bytes, &mut cursor)?;
+
+        let next = match bytes.get(cursor).copied() {
+            Some(1) => {
+                cursor += 1;
+                let delta = codec::zigzag_decode(codec::read_uvarint(bytes, &mut cursor)?);
+                Some((address as i64 + delta) as u64)
+            }
+            Some(0) => { cursor += 1; None }
+            _ => return Err(Error::new(ErrorKind::UnexpectedEof, "truncated block record")),
+        };
+
+        let to_count = codec::read_uvarint(bytes, &mut cursor)?;
+        let mut to = BTreeSet::new();
+        for _ in 0..to_count {
+            let delta = codec::zigzag_decode(codec::read_uvarint(bytes, &mut cursor)?);
+            to.insert((address as i64 + delta) as u64);
+        }
+
+        let edges = codec::read_uvarint(bytes, &mut cursor)? as usize;
+
+        let flags = *bytes.get(cursor).ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated block record"))?;
+        cursor += 1;
+        let prologue = flags & 0b001 != 0;
+        let conditional = flags & 0b010 != 0;
+        let contiguous = flags & 0b100 != 0;
+
+        let signature_bytes = codec::read_bytes(bytes, &mut cursor)?;
+        let signature = Signature::from_bytes(&signature_bytes)?;
+
+        let size = codec::read_uvarint(bytes, &mut cursor)? as usize;
+        let block_bytes = codec::read_bytes(bytes, &mut cursor)?;
+
+        let instructions = codec::read_uvarint(bytes, &mut cursor)? as usize;
+
+        let functions_count = codec::read_uvarint(bytes, &mut cursor)?;
+        let mut functions = BTreeMap::new();
+        for _ in 0..functions_count {
+            let instruction_delta = codec::zigzag_decode(codec::read_uvarint(bytes, &mut cursor)?);
+            let function_delta = codec::zigzag_decode(codec::read_uvarint(bytes, &mut cursor)?);
+            functions.insert((address as i64 + instruction_delta) as u64, (address as i64 + function_delta) as u64);
+        }
+
+        let entropy = match bytes.get(cursor).copied() {
+            Some(1) => {
+                cursor += 1;
+                let end = cursor + 8;
+                if end > bytes.len() { return Err(Error::new(ErrorKind::UnexpectedEof, "truncated entropy")); }
+                let value = f64::from_le_bytes(bytes[cursor..end].try_into().unwrap());
+                cursor = end;
+                Some(value)
+            }
+            Some(0) => { cursor += 1; None }
+            _ => return Err(Error::new(ErrorKind::UnexpectedEof, "truncated block record")),
+        };
+
+        let sha256 = match bytes.get(cursor).copied() {
+            Some(1) => { cursor += 1; Some(Binary::to_hex(&codec::read_bytes(bytes, &mut cursor)?)) }
+            Some(0) => { cursor += 1; None }
+            _ => return Err(Error::new(ErrorKind::UnexpectedEof, "truncated block record")),
+        };
+
+        let merkle_end = cursor + 32;
+        if merkle_end > bytes.len() { return Err(Error::new(ErrorKind::UnexpectedEof, "truncated merkle root")); }
+        let merkle = Binary::to_hex(&bytes[cursor..merkle_end]);
+        cursor = merkle_end;
+
+        let hash_count = codec::read_uvarint(bytes, &mut cursor)?;
+        let mut hashes = BTreeMap::new();
+        for _ in 0..hash_count {
+            let name = codec::read_string(bytes, &mut cursor)?;
+            let digest = Binary::to_hex(&codec::read_bytes(bytes, &mut cursor)?);
+            hashes.insert(name, digest);
+        }
+
+        let file_blob = codec::read_bytes(bytes, &mut cursor)?;
+        let file: Option<FileJson> = bincode::deserialize(&file_blob)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+
+        let tags_count = codec::read_uvarint(bytes, &mut cursor)?;
+        let mut tags = Vec::new();
+        for _ in 0..tags_count {
+            tags.push(codec::read_string(bytes, &mut cursor)?);
+        }
+
+        Ok(BlockJson {
+            type_: "block".to_string(),
+            architecture,
+            address,
+            next,
+            to,
+            edges,
+            prologue,
+            conditional,
+            signature,
+            size,
+            bytes: Binary::to_hex(&block_bytes),
+            functions,
+            instructions,
+            // Not encoded by `to_bytes`: the disassembly listing is large,
+            // optional, and easily re-derived from `instructions()`, so the
+            // compact codec doesn't carry it.
+            disassembly: None,
+            entropy,
+            sha256,
+            merkle,
+            hashes,
+            contiguous,
+            file,
+            tags,
+        })
+    }
+
+    /// Appends this block's `to_bytes` record to `writer`, length-prefixed,
+    /// so a whole CFG's blocks can be written to one file without holding
+    /// them all in memory at once.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Err` if encoding or the write fails.
+    #[allow(dead_code)]
+    pub fn write_bytes<W: crate::io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        codec::write_record(writer, &self.to_bytes()?)
+    }
+
+    /// Reads one record written by `write_bytes` back into a `BlockJson`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(None)` at a clean end of stream, so callers can loop until
+    /// this returns `None` to scan every block without knowing the count
+    /// up front.
+    #[allow(dead_code)]
+    pub fn read_bytes<R: crate::io::Read>(reader: &mut R) -> Result<Option<BlockJson>, Error> {
+        match codec::read_record(reader)? {
+            Some(bytes) => Ok(Some(Self::from_bytes(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Processes the block into its JSON-serializable representation.
     ///
     /// # Returns
@@ -164,11 +450,12 @@ impl<'block> Block<'block> {
             size: self.size(),
             bytes: Binary::to_hex(&self.bytes()),
             instructions: self.instruction_count(),
+            disassembly: if self.cfg.options.enable_disassembly { Some(self.disassembly()) } else { None },
             functions: self.functions(),
             entropy: self.entropy(),
             sha256: self.sha256(),
-            minhash: self.minhash(),
-            tlsh: self.tlsh(),
+            merkle: self.merkle(),
+            hashes: self.hashes(),
             contiguous: true,
             file: self.file(),
             tags: self.cfg.options.tags.clone(),
@@ -182,7 +469,7 @@ impl<'block> Block<'block> {
     ///
     /// Returns an `Option<FileJson>` containing file metadata if available, or `None` otherwise.
     pub fn file(&self) -> Option<FileJson> {
-        Some(File::new(self.cfg.options.clone()).process())
+        Some(File::new(self.cfg.options.clone(), self.cfg.truncated).process())
     }
 
     /// Determines whether the block starts with a function prologue.
@@ -279,6 +566,7 @@ impl<'block> Block<'block> {
     /// Returns `Some(String)` containing the TLSH, or `None` if TLSH is disabled or the block size is too small.
     pub fn tlsh(&self) -> Option<String> {
         if !self.cfg.options.enable_tlsh { return None; }
+        if !self.cfg.options.hash_algorithms.contains(&HashAlgorithm::Tlsh) { return None; }
         return TLSH::new(&self.bytes(), self.cfg.options.tlsh_mininum_byte_size).hexdigest();
     }
 
@@ -289,6 +577,7 @@ impl<'block> Block<'block> {
     /// Returns `Some(String)` containing the MinHash, or `None` if MinHash is disabled or the block's size exceeds the configured maximum.
     pub fn minhash(&self) -> Option<String> {
         if !self.cfg.options.enable_minhash { return None; }
+        if !self.cfg.options.hash_algorithms.contains(&HashAlgorithm::MinHash) { return None; }
         if self.bytes().len() > self.cfg.options.minhash_maximum_byte_size { return None; }
         return MinHash32::new(
             &self.bytes(),
@@ -298,6 +587,43 @@ impl<'block> Block<'block> {
         ).hexdigest();
     }
 
+    /// Computes a ssdeep-style fuzzy hash of the block's bytes, if
+    /// `HashAlgorithm::Ssdeep` is in `GraphOptions::hash_algorithms`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(String)` containing the signature, or `None` if ssdeep
+    /// isn't selected or the block is empty.
+    pub fn ssdeep(&self) -> Option<String> {
+        if !self.cfg.options.hash_algorithms.contains(&HashAlgorithm::Ssdeep) { return None; }
+        SSDEEP::new(&self.bytes()).hexdigest()
+    }
+
+    /// Computes every fuzzy/locality-sensitive hash selected by
+    /// `GraphOptions::hash_algorithms`, keyed by algorithm name.
+    /// `HashAlgorithm::Custom` entries are skipped: there's nothing in this
+    /// crate to dispatch them to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BTreeMap<String, String>` with one entry per algorithm that
+    /// both is selected and actually produced a digest.
+    pub fn hashes(&self) -> BTreeMap<String, String> {
+        let mut result = BTreeMap::new();
+        for algorithm in &self.cfg.options.hash_algorithms {
+            let digest = match algorithm {
+                HashAlgorithm::Tlsh => self.tlsh(),
+                HashAlgorithm::MinHash => self.minhash(),
+                HashAlgorithm::Ssdeep => self.ssdeep(),
+                HashAlgorithm::Custom(_) => None,
+            };
+            if let Some(digest) = digest {
+                result.insert(algorithm.name().to_string(), digest);
+            }
+        }
+        result
+    }
+
     /// Computes the SHA-256 hash of the block's bytes, if enabled.
     ///
     /// # Returns
@@ -308,6 +634,86 @@ impl<'block> Block<'block> {
         return SHA256::new(&self.bytes()).hexdigest();
     }
 
+    /// Retrieves the block's instructions in address order.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<Instruction>` covering `self.address..=self.terminator.address`.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        self.cfg.instructions
+            .range(self.address..=self.terminator.address)
+            .map(|entry| entry.value().clone())
+            .collect()
+    }
+
+    /// Renders a human-readable, per-instruction disassembly listing for this
+    /// block: each entry carries the instruction's address, raw bytes (hex),
+    /// mnemonic/operand text (if the decoding backend surfaced one), and the
+    /// same `is_jump`/`is_conditional`/`is_return`/`next`/`to` control-flow
+    /// annotations already tracked per instruction, so the terminator's entry
+    /// alone shows where the block ends and which blocks it edges to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<InstructionJson>` covering `self.instructions()`, in
+    /// address order.
+    pub fn disassembly(&self) -> Vec<InstructionJson> {
+        self.instructions()
+            .iter()
+            .map(|instruction| instruction.process())
+            .collect()
+    }
+
+    /// Computes this block's Merkle root as raw bytes: a leaf hash per
+    /// instruction (`sha256` of its normalized bytes, via `Signature::normalize`
+    /// so wildcarded operands don't disturb the hash), combined pairwise up to
+    /// one root via `Binary::merkle_root`. Because each internal node covers a
+    /// contiguous instruction range, a caller diffing two blocks can tell
+    /// they're identical (or find where they diverge) from the roots alone.
+    ///
+    /// # Returns
+    ///
+    /// Returns `[0u8; 32]` if the block has no instructions.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        let leaves: Vec<[u8; 32]> = self.instructions()
+            .iter()
+            .map(|instruction| {
+                let normalized = Signature::new(instruction.address, instruction.address, &self.cfg).normalize();
+                SHA256::new(&normalized).digest()
+            })
+            .collect();
+        Binary::merkle_root(&leaves)
+    }
+
+    /// Hex-encoded form of `merkle_root`, for `BlockJson` and other external consumers.
+    ///
+    /// # Returns
+    ///
+    /// Returns the Merkle root as a lowercase hex string.
+    pub fn merkle(&self) -> String {
+        Binary::to_hex(&self.merkle_root())
+    }
+
+    /// Computes a `k`-permutation MinHash similarity signature over `ngram`-byte
+    /// shingles of the block's bytes, using a fixed seed so it's directly
+    /// comparable against another block's or instruction's signature of the same
+    /// `(k, ngram)`. Unlike `minhash()`, this isn't gated by `enable_minhash` or
+    /// `minhash_maximum_byte_size`, since its purpose is ad-hoc similarity
+    /// comparison rather than the persisted, config-driven MinHash digest.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u32>` of length `k`; all-`SENTINEL_HASH` if the block is shorter than `ngram`.
+    pub fn minhash_signature(&self, k: usize, ngram: usize) -> Vec<u32> {
+        MinHash32::new(&self.bytes(), k, ngram, DEFAULT_SIMILARITY_SEED).hash_or_sentinel()
+    }
+
+    /// Estimated Jaccard similarity between this block and `other`, via their
+    /// `minhash_signature(k, ngram)` signatures.
+    pub fn similarity(&self, other: &Block<'_>, k: usize, ngram: usize) -> f64 {
+        MinHash32::similarity(&self.minhash_signature(k, ngram), &other.minhash_signature(k, ngram))
+    }
+
     /// Retrieves the size of the block in bytes.
     ///
     /// # Returns
@@ -354,4 +760,48 @@ impl<'block> Block<'block> {
         return self.terminator.address;
     }
 
+    /// Renders a human-readable assembly listing for this block: an address
+    /// label, then one line per instruction as `virtual_address: bytes  text`,
+    /// followed by `-> 0x... (fallthrough|branch|call)` lines for the edges
+    /// leaving the block. When `minimal` is set, the raw byte column is
+    /// omitted to keep the listing terse.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `String` suitable for triage or diffing without parsing JSON.
+    pub fn to_assembly(&self, minimal: bool) -> String {
+        let mut result = String::new();
+        result.push_str(&format!("  block 0x{:x}:\n", self.address));
+
+        for instruction in self.instructions() {
+            let text = instruction.text.clone().unwrap_or_default();
+            if minimal {
+                result.push_str(&format!("    0x{:x}: {}\n", instruction.address, text));
+            } else {
+                result.push_str(&format!(
+                    "    0x{:x}: {:<24} {}\n",
+                    instruction.address,
+                    Binary::to_hex(&instruction.bytes),
+                    text,
+                ));
+            }
+
+            for target in instruction.to() {
+                result.push_str(&format!("        -> 0x{:x} (branch)\n", target));
+            }
+
+            for function_address in instruction.functions.iter() {
+                result.push_str(&format!("        -> 0x{:x} (call)\n", function_address));
+            }
+
+            if instruction.address == self.terminator.address {
+                if let Some(next) = instruction.next() {
+                    result.push_str(&format!("        -> 0x{:x} (fallthrough)\n", next));
+                }
+            }
+        }
+
+        result
+    }
+
 }
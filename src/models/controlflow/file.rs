@@ -1,7 +1,9 @@
-use std::io::Error;
+use std::io::{Error, ErrorKind};
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 use serde_json;
-use crate::models::controlflow::graph::GraphOptions;
+use crate::models::controlflow::graph::{GraphOptions, Truncation};
+use crate::models::hashing::tlsh::TLSH;
 
 /// Represents a JSON-serializable structure containing file metadata.
 #[derive(Serialize, Deserialize)]
@@ -12,12 +14,24 @@ pub struct FileJson {
     pub tlsh: Option<String>,
     /// The size of the file in bytes, if available.
     pub size: Option<u64>,
+    /// `true` if the `Graph` this file metadata was derived from stopped
+    /// early because one of its analysis budgets was exceeded (see
+    /// `Graph::truncated`), i.e. this block/function's view of the binary
+    /// may be incomplete.
+    pub truncated: bool,
+    /// Which budget stopped the traversal, named via `Truncation::name`, or
+    /// `None` if `truncated` is `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<String>,
 }
 
 /// Represents file metadata derived from `GraphOptions`.
 pub struct File {
     /// Options containing file-specific metadata, such as hashes and size.
     pub options: GraphOptions,
+    /// Mirrors `Graph::truncated` for the graph this file metadata was
+    /// derived from.
+    pub truncated: Option<Truncation>,
 }
 
 impl File {
@@ -26,13 +40,16 @@ impl File {
     /// # Arguments
     ///
     /// * `options` - A `GraphOptions` instance containing the file metadata.
+    /// * `truncated` - Which budget stopped the originating `Graph`'s
+    ///   traversal early, if any; see `Graph::truncated`.
     ///
     /// # Returns
     ///
     /// Returns a new `File` instance.
-    pub fn new(options: GraphOptions) -> Self {
+    pub fn new(options: GraphOptions, truncated: Option<Truncation>) -> Self {
         Self {
             options: options,
+            truncated: truncated,
         }
     }
 
@@ -76,6 +93,8 @@ impl File {
             sha256: self.sha256(),
             tlsh: self.tlsh(),
             size: self.size(),
+            truncated: self.truncated.is_some(),
+            truncation: self.truncated.map(|reason| reason.name().to_string()),
         }
     }
 
@@ -99,4 +118,94 @@ impl File {
         Ok(result)
     }
 
+    /// Converts the file metadata into a CBOR-encoded byte representation.
+    ///
+    /// Encodes the same `FileJson` model `json()` does, so the two are
+    /// interchangeable on the wire; CBOR is smaller and faster to parse than
+    /// JSON for large corpora, at the cost of not being human-readable.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` containing the CBOR representation, or an `Err` if
+    /// serialization fails.
+    pub fn cbor(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        let mut result = Vec::<u8>::new();
+        ciborium::into_writer(&raw, &mut result)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        Ok(result)
+    }
+
+    /// Computes the TLSH distance between this file and `other`. Lower
+    /// scores mean more similar; `0` means identical digests.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if either file is missing a TLSH digest.
+    #[allow(dead_code)]
+    pub fn tlsh_distance(&self, other: &File) -> Option<u32> {
+        TLSH::distance(&self.tlsh()?, &other.tlsh()?)
+    }
+
+}
+
+impl FileJson {
+    /// Reconstructs a `FileJson` from the CBOR produced by `File::cbor()`, the
+    /// inverse encoding of `cbor()`. Lets downstream tools reload emitted file
+    /// metadata without re-disassembling.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(FileJson)` on success, or an `Err` if `data` isn't valid CBOR
+    /// for this shape.
+    pub fn from_cbor(data: &[u8]) -> Result<Self, Error> {
+        ciborium::from_reader(data)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+
+    /// Groups `files` (by index into the slice) into near-duplicate families:
+    /// files sharing an exact `sha256` collapse into one group each, then
+    /// those groups are merged by TLSH distance between representatives, joining
+    /// a group into the first existing group whose representative digest is
+    /// within `tlsh_threshold` of its own. Files missing both hashes end up
+    /// as singleton groups.
+    ///
+    /// # Returns
+    ///
+    /// Returns one `Vec<usize>` of `files` indices per family.
+    #[allow(dead_code)]
+    pub fn cluster(files: &[FileJson], tlsh_threshold: u32) -> Vec<Vec<usize>> {
+        let mut groups_by_sha256: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut initial_groups: Vec<Vec<usize>> = Vec::new();
+
+        for (index, file) in files.iter().enumerate() {
+            match &file.sha256 {
+                Some(sha256) => groups_by_sha256.entry(sha256.clone()).or_default().push(index),
+                None => initial_groups.push(vec![index]),
+            }
+        }
+        initial_groups.extend(groups_by_sha256.into_values());
+
+        let representative_tlsh = |group: &[usize]| -> Option<&str> {
+            group.iter().find_map(|&index| files[index].tlsh.as_deref())
+        };
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for group in initial_groups {
+            let joinable = representative_tlsh(&group).and_then(|tlsh| {
+                groups.iter_mut().find(|existing| {
+                    representative_tlsh(existing)
+                        .and_then(|other| TLSH::distance(tlsh, other))
+                        .is_some_and(|distance| distance <= tlsh_threshold)
+                })
+            });
+
+            match joinable {
+                Some(existing) => existing.extend(group),
+                None => groups.push(group),
+            }
+        }
+
+        groups
+    }
 }
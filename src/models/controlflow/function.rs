@@ -1,24 +1,27 @@
 
 use crate::models::binary::BinaryArchitecture;
+use crate::models::compression;
 use crate::models::controlflow::instruction::Instruction;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
-use std::io::Error;
-use std::io::ErrorKind;
+use crate::models::nostd::Error;
+use crate::models::nostd::ErrorKind;
 use crate::models::binary::Binary;
 use crate::models::controlflow::graph::Graph;
 use crate::models::controlflow::graph::GraphQueue;
+use crate::models::controlflow::graph::TrapReason;
 use crate::models::controlflow::block::Block;
 use crate::models::controlflow::signature::Signature;
 use crate::models::controlflow::signature::SignatureJson;
 use crate::models::controlflow::symbol::Symbol;
 use crate::models::controlflow::file::FileJson;
 use crate::models::controlflow::file::File;
+use crate::models::controlflow::rawjson::RawJson;
 use crate::models::hashing::sha256::SHA256;
 use crate::models::hashing::tlsh::TLSH;
-use crate::models::hashing::minhash::MinHash32;
+use crate::models::hashing::minhash::{MinHash32, DEFAULT_SIMILARITY_SEED};
 
 /// Represents a JSON-serializable structure containing metadata about a function.
 #[derive(Serialize, Deserialize)]
@@ -35,32 +38,52 @@ pub struct FunctionJson {
     /// Indicates whether this function starts with a prologue.
     pub prologue: bool,
     /// The signature of the function in JSON format.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub signature: Option<SignatureJson>,
+    /// A wildcard-gap signature pattern spanning the function's entire address
+    /// range, populated even when the function is non-contiguous.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<String>,
     /// The symbol names representing the function, if available.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     pub names: BTreeSet<String>,
     /// The size of the function in bytes, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<usize>,
     /// The raw bytes of the function in hexadecimal format, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub bytes: Option<String>,
     /// A map of functions associated with the function.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
     pub functions: BTreeMap<u64, u64>,
     /// The set of blocks contained within the function.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
     pub blocks: BTreeSet<u64>,
     /// File metadata associated with the function, if available.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub file: Option<FileJson>,
     /// The number of instructions in the function.
     pub instructions: usize,
     /// The entropy of the function, if enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub entropy: Option<f64>,
     /// The SHA-256 hash of the function, if enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub sha256: Option<String>,
+    /// The Merkle root over the function's blocks (in address order), hex-encoded.
+    /// Each leaf is a block's own `Block::merkle_root`, so a caller diffing two
+    /// functions can descend only into the blocks whose roots differ.
+    pub merkle: String,
     /// The MinHash of the function, if enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub minhash: Option<String>,
     /// The TLSH of the function, if enabled.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub tlsh: Option<String>,
     /// Indicates whether the function is contiguous.
     pub contiguous: bool,
     /// Tags associated with the function.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 }
 
@@ -113,7 +136,7 @@ impl<'function> Function<'function> {
         let mut size: usize = 0;
         let symbol = cfg.functions.get_symbol(address);
 
-        let mut queue = GraphQueue::new();
+        let queue = GraphQueue::new();
 
         queue.enqueue(address);
 
@@ -172,6 +195,7 @@ impl<'function> Function<'function> {
             edges: self.edges(),
             prologue: self.is_prologue(),
             signature: self.signature(),
+            pattern: self.pattern(),
             bytes: self.bytes_to_hex(),
             size: self.size(),
             functions: self.functions(),
@@ -179,6 +203,7 @@ impl<'function> Function<'function> {
             instructions: self.instruction_count(),
             entropy: self.entropy(),
             sha256: self.sha256(),
+            merkle: self.merkle(),
             minhash: self.minhash(),
             tlsh: self.tlsh(),
             contiguous: self.is_contiguous(),
@@ -205,7 +230,7 @@ impl<'function> Function<'function> {
     ///
     /// Returns an `Option<FileJson>` containing file metadata if available, or `None` otherwise.
     pub fn file(&self) -> Option<FileJson> {
-        Some(File::new(self.cfg.options.clone()).process())
+        Some(File::new(self.cfg.options.clone(), self.cfg.truncated).process())
     }
 
     /// Prints the JSON representation of the function to standard output.
@@ -218,15 +243,55 @@ impl<'function> Function<'function> {
 
     /// Converts the function metadata into a JSON string representation.
     ///
+    /// The result is cached on `cfg.function_json_cache` by address, mirroring
+    /// `Block::json`, so re-requesting the same function's JSON reuses the
+    /// already-encoded fragment instead of re-serializing it.
+    ///
     /// # Returns
     ///
     /// Returns `Ok(String)` containing the JSON representation, or an `Err` if serialization fails.
     pub fn json(&self) -> Result<String, Error> {
+        if let Some(entry) = self.cfg.function_json_cache.get(&self.address) {
+            return Ok(entry.value().as_str().to_string());
+        }
         let raw = self.process();
         let result = serde_json::to_string(&raw)?;
+        let fragment = RawJson::new(result.clone())?;
+        self.cfg.function_json_cache.insert(self.address, fragment);
         Ok(result)
     }
 
+    /// Writes the function metadata as a single line of JSON to `writer`, without
+    /// requiring `std::io` on the sink side.
+    ///
+    /// Intended for JSONL output over millions of functions: callers append a
+    /// newline between records as they stream them out, rather than collecting
+    /// every `json()` string into memory first.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an `Err` if serialization or the write fails.
+    pub fn write_json<W: crate::io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let json = self.json()?;
+        writer
+            .write_all(json.as_bytes())
+            .map_err(|error| Error::new(ErrorKind::Other, error.to_string()))
+    }
+
+    /// Converts the function metadata into a compact binary representation.
+    ///
+    /// This is a much smaller, faster-to-decode alternative to `json()` for consumers
+    /// that don't need a human-readable format (e.g. writing large corpora to disk).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` containing the bincode-encoded `FunctionJson`, or an `Err`
+    /// if serialization fails.
+    pub fn bincode(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        bincode::serialize(&raw).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+
     /// Generates the function's signature if the function is contiguous.
     ///
     /// # Returns
@@ -237,6 +302,41 @@ impl<'function> Function<'function> {
         return Some(Signature::new(self.address, self.end().unwrap(), &self.cfg, self.cfg.options.clone()).process());
     }
 
+    /// Builds a wildcard-gap signature pattern across the function's entire
+    /// address span, even when the function is non-contiguous.
+    ///
+    /// Bytes covered by a decoded instruction are rendered as concrete hex
+    /// nibbles; bytes in the gap between one block's end and the next block's
+    /// start (e.g. after compiler hot/cold splitting) are rendered as
+    /// wildcard nibbles (`?`), so non-contiguous functions still produce a
+    /// matchable signature instead of being dropped by `signature()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the function has no blocks.
+    pub fn pattern(&self) -> Option<String> {
+        let mut result = String::new();
+        let mut previous_end: Option<u64> = None;
+
+        for (block_address, terminator) in self.blocks() {
+            if let Some(previous_end) = previous_end {
+                if *block_address > previous_end {
+                    result.push_str(&"?".repeat(((*block_address - previous_end) * 2) as usize));
+                }
+            }
+
+            let block_end = terminator.address + terminator.size() as u64;
+            for entry in self.cfg.instructions.range(*block_address..block_end) {
+                result.push_str(&Binary::to_hex(&entry.value().bytes));
+            }
+
+            previous_end = Some(block_end);
+        }
+
+        if result.is_empty() { return None; }
+        Some(result)
+    }
+
     /// Retrieves the total number of instructions in the function.
     ///
     /// # Returns
@@ -275,14 +375,32 @@ impl<'function> Function<'function> {
 
     /// Converts the function's bytes to a hexadecimal string, if available.
     ///
+    /// When `GraphOptions::enable_compression` is set, this hex-encodes
+    /// `compressed_bytes()` (compressed payload plus its algorithm/length header)
+    /// instead of the raw bytes; `compression::decompress` reverses it to recover
+    /// `bytes()` for hashing and signature generation downstream.
+    ///
     /// # Returns
     ///
     /// Returns `Some(String)` containing the hexadecimal representation of the bytes, or `None` if unavailable.
     pub fn bytes_to_hex(&self) -> Option<String> {
-        if let Some(bytes) = self.bytes() {
-            return Some(Binary::to_hex(&bytes));
+        self.compressed_bytes().map(|bytes| Binary::to_hex(&bytes))
+    }
+
+    /// Retrieves the function's bytes, compressed per `GraphOptions::enable_compression`/
+    /// `compression_algorithm` with a small header recording the algorithm id and
+    /// original length, or unmodified when compression is disabled.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(Vec<u8>)` containing the (possibly compressed) bytes, or `None`
+    /// if the function is not contiguous.
+    pub fn compressed_bytes(&self) -> Option<Vec<u8>> {
+        let bytes = self.bytes()?;
+        if !self.cfg.options.enable_compression {
+            return Some(bytes);
         }
-        return None;
+        Some(compression::compress(&bytes, self.cfg.options.compression_algorithm))
     }
 
     /// Retrieves the size of the function in bytes, if contiguous.
@@ -362,6 +480,35 @@ impl<'function> Function<'function> {
         return None;
     }
 
+    /// Computes the Merkle root over this function's blocks, in address order:
+    /// each leaf is a block's own `Block::merkle_root`, combined pairwise via
+    /// `Binary::merkle_root` up to one root. A block that fails to reconstruct
+    /// (should not happen for blocks already validated by `Function::new`) is
+    /// skipped rather than failing the whole function's root.
+    ///
+    /// # Returns
+    ///
+    /// Returns the Merkle root as a lowercase hex string.
+    pub fn merkle(&self) -> String {
+        let leaves: Vec<[u8; 32]> = self.blocks.keys()
+            .filter_map(|block_address| Block::new(*block_address, &self.cfg).ok())
+            .map(|block| block.merkle_root())
+            .collect();
+        Binary::to_hex(&Binary::merkle_root(&leaves))
+    }
+
+    /// Computes the TLSH distance between this function's TLSH and `other_digest`.
+    /// Lower scores mean more similar; `0` means identical. This enables
+    /// threshold-based clustering of functions across a corpus.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if this function's TLSH is unavailable (disabled, not
+    /// contiguous, or below the minimum byte size) or `other_digest` is malformed.
+    pub fn tlsh_distance(&self, other_digest: &str) -> Option<u32> {
+        TLSH::distance(&self.tlsh()?, other_digest)
+    }
+
     /// Computes the MinHash of the function's bytes, if enabled and contiguous.
     ///
     /// # Returns
@@ -381,6 +528,29 @@ impl<'function> Function<'function> {
         return None;
     }
 
+    /// Computes a `k`-permutation MinHash similarity signature over `ngram`-byte
+    /// shingles of the function's bytes, using a fixed seed so it's directly
+    /// comparable against another function's, block's, or instruction's
+    /// signature of the same `(k, ngram)`. Unlike `minhash()`, this isn't gated
+    /// by `enable_minhash` or `minhash_maximum_byte_size`.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u32>` of length `k`; all-`SENTINEL_HASH` if the function is not
+    /// contiguous or is shorter than `ngram`.
+    pub fn minhash_signature(&self, k: usize, ngram: usize) -> Vec<u32> {
+        match self.bytes() {
+            Some(bytes) => MinHash32::new(&bytes, k, ngram, DEFAULT_SIMILARITY_SEED).hash_or_sentinel(),
+            None => vec![crate::models::hashing::minhash::SENTINEL_HASH; k],
+        }
+    }
+
+    /// Estimated Jaccard similarity between this function and `other`, via their
+    /// `minhash_signature(k, ngram)` signatures.
+    pub fn similarity(&self, other: &Function<'_>, k: usize, ngram: usize) -> f64 {
+        MinHash32::similarity(&self.minhash_signature(k, ngram), &other.minhash_signature(k, ngram))
+    }
+
     /// Retrieves the blocks that make up the function.
     ///
     /// # Returns
@@ -399,20 +569,96 @@ impl<'function> Function<'function> {
         return self.functions.clone();
     }
 
+    /// Renders the function's control flow graph as a Graphviz DOT `digraph`.
+    ///
+    /// Each block is a node labeled with its start address, and directed edges
+    /// connect a block to the successors derived from its terminator's branch
+    /// targets (`Instruction::to`/`Instruction::next`). Edges that leave the
+    /// function (targets not present in `self.blocks`) are omitted.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `String` containing the DOT source, suitable for piping into
+    /// `dot`/`xdot` for visual triage.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph {\n");
+
+        for block_address in self.blocks().keys() {
+            dot.push_str(&format!("    \"0x{:x}\"\n", block_address));
+        }
+
+        for (block_address, terminator) in self.blocks() {
+            let mut successors: BTreeSet<u64> = terminator.to();
+            if let Some(next) = terminator.next() {
+                successors.insert(next);
+            }
+            for successor in successors {
+                if self.blocks.contains_key(&successor) {
+                    dot.push_str(&format!("    \"0x{:x}\" -> \"0x{:x}\"\n", block_address, successor));
+                }
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Renders the function as a human-readable assembly listing: a header
+    /// with the function's address and any resolved `Symbol` names, followed
+    /// by each block's `Block::to_assembly` listing in address order. When
+    /// `minimal` is set, each instruction's raw byte column is omitted.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `String` suitable for triage or diffing without parsing JSON.
+    pub fn to_assembly(&self, minimal: bool) -> String {
+        let mut result = String::new();
+
+        let names = self.names();
+        if names.is_empty() {
+            result.push_str(&format!("function 0x{:x}\n", self.address));
+        } else {
+            let names = names.into_iter().collect::<Vec<String>>().join(", ");
+            result.push_str(&format!("function 0x{:x} <{}>\n", self.address, names));
+        }
+
+        for block_address in self.blocks().keys() {
+            if let Ok(block) = Block::new(*block_address, &self.cfg) {
+                result.push_str(&block.to_assembly(minimal));
+            }
+        }
+
+        result
+    }
+
     /// Checks whether the function is contiguous in memory.
     ///
+    /// Also returns `false` if any block's successor was never explored
+    /// because the traversal hit an analysis budget (`TrapReason::
+    /// DecodeLimitExceeded`): the blocks actually discovered might look
+    /// contiguous, but whether the function truly is can't be verified
+    /// without the blocks the budget cut off.
+    ///
     /// # Returns
     ///
     /// Returns `true` if the function is contiguous; otherwise, `false`.
     pub fn is_contiguous(&self) -> bool {
         let mut block_previous_end: Option<u64> = None;
-        for (block_start_address, terminator )in self.blocks() {
+        for (block_start_address, terminator) in self.blocks() {
             if let Some(previous_end) = block_previous_end {
                 if previous_end != *block_start_address {
                     return false;
                 }
             }
             block_previous_end = Some(terminator.address + terminator.size() as u64);
+
+            let successors = terminator.to.iter().chain(terminator.next.iter());
+            for successor in successors {
+                if self.cfg.blocks.trap_reason(*successor) == Some(TrapReason::DecodeLimitExceeded) {
+                    return false;
+                }
+            }
         }
         return true;
     }
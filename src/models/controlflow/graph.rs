@@ -1,8 +1,130 @@
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::io::Error;
+use std::io::ErrorKind;
+use std::fs::File;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use crate::models::binary::BinaryArchitecture;
+use crate::models::compression::CompressionAlgorithm;
+use crate::models::controlflow::codec;
+use crate::models::controlflow::function::Function;
 use crate::models::controlflow::instruction::Instruction;
+use crate::models::controlflow::profile::load_profile;
+use crate::models::controlflow::rawjson::RawJson;
+use crate::models::controlflow::symbol::Symbol;
+use crate::models::sink::{self, Sink, SinkKind};
 use crossbeam::queue::SegQueue;
 use crossbeam_skiplist::SkipMap;
 use crossbeam_skiplist::SkipSet;
+use std::time::{Duration, Instant};
+
+/// Why an address was rejected during disassembly.
+///
+/// `GraphQueue::insert_invalid` alone only says an address didn't pan out;
+/// `TrapReason` records which of the disassembler's checks actually rejected
+/// it, mirroring the explicit typed traps a register-VM decoder emits instead
+/// of silently aborting. Any trapped address is always also invalid, so
+/// `GraphQueue::is_invalid` semantics are unaffected by using this.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TrapReason {
+    /// The byte at this address has no opcode table entry.
+    IllegalOpcode,
+    /// A computed jump/call/branch target falls outside `executable_address_ranges`.
+    OutOfBoundsTarget,
+    /// A relative branch target is not aligned to a valid instruction boundary.
+    MisalignedTarget,
+    /// This address lands in the middle of an already-decoded instruction.
+    OverlappingInstruction,
+    /// Decoding exceeded the backend's configured instruction/recursion limit.
+    DecodeLimitExceeded,
+}
+
+/// Which analysis budget stopped a `Graph` traversal early, if any.
+///
+/// `Graph::truncated` carries this instead of a bare `bool` so a caller
+/// inspecting a partial result (e.g. from an adversarial, deliberately
+/// packed sample that would otherwise enqueue unbounded bogus work) can
+/// tell which ceiling was hit rather than just that one was.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Truncation {
+    /// `GraphOptions::max_instructions` was reached.
+    Instructions,
+    /// `GraphOptions::max_blocks` was reached.
+    Blocks,
+    /// `GraphOptions::max_functions` was reached.
+    Functions,
+    /// `GraphOptions::timeout_ms` elapsed.
+    Timeout,
+}
+
+impl Truncation {
+    /// The name this reason is reported under in `FileJson::truncation`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Truncation::Instructions => "instructions",
+            Truncation::Blocks => "blocks",
+            Truncation::Functions => "functions",
+            Truncation::Timeout => "timeout",
+        }
+    }
+}
+
+/// Selects a locality-sensitive/fuzzy hash algorithm `Signature`/`Block` dispatch
+/// to, modeled on the `CompressionAlgorithm` enum-plus-dispatch pattern: adding a
+/// new algorithm is a new variant and a match arm, not a new `enable_*` flag and
+/// method pair. `GraphOptions::hash_algorithms` carries a list of these so a run
+/// can emit several fuzzy hashes (e.g. both TLSH and ssdeep) in one pass.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum HashAlgorithm {
+    /// Trend Micro Locality Sensitive Hash.
+    Tlsh,
+    /// Context-triggered piecewise hash (ssdeep-style fuzzy hash).
+    Ssdeep,
+    /// MinHash, as produced by `models::hashing::minhash::MinHash32`.
+    MinHash,
+    /// An algorithm not built into this crate, identified by name. `Signature`/
+    /// `Block` have nothing to dispatch to for this variant and skip it, but it
+    /// still round-trips through configuration so a downstream consumer can
+    /// recognize its own extension among the configured list.
+    Custom(String),
+}
+
+impl HashAlgorithm {
+    /// The name this algorithm's digest is keyed under in `SignatureJson`/
+    /// `BlockJson`'s `hashes` map.
+    pub fn name(&self) -> &str {
+        match self {
+            HashAlgorithm::Tlsh => "tlsh",
+            HashAlgorithm::Ssdeep => "ssdeep",
+            HashAlgorithm::MinHash => "minhash",
+            HashAlgorithm::Custom(name) => name,
+        }
+    }
+}
+
+impl std::fmt::Display for HashAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for HashAlgorithm {
+    type Err = std::convert::Infallible;
+
+    /// Parses an algorithm name case-insensitively; any name that isn't one
+    /// of the built-in algorithms is accepted as `Custom`, since the whole
+    /// point of this enum is that new algorithms don't need a crate change to
+    /// be named in configuration.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.to_ascii_lowercase().as_str() {
+            "tlsh" => HashAlgorithm::Tlsh,
+            "ssdeep" => HashAlgorithm::Ssdeep,
+            "minhash" => HashAlgorithm::MinHash,
+            _ => HashAlgorithm::Custom(s.to_string()),
+        })
+    }
+}
 
 /// Configuration options for the `Graph` structure, specifying settings for hashing, entropy, and other metadata.
 #[derive(Clone)]
@@ -37,6 +159,61 @@ pub struct GraphOptions {
     pub file_size: Option<u64>,
     /// Tags associated with the graph.
     pub tags: Vec<String>,
+    /// Enables or disables compressing large byte blobs (e.g. `FunctionJson.bytes`)
+    /// with `compression_algorithm` instead of storing them raw/hex.
+    pub enable_compression: bool,
+    /// Which compression algorithm to use when `enable_compression` is set.
+    pub compression_algorithm: CompressionAlgorithm,
+    /// Which fuzzy/locality-sensitive hash algorithms `Signature::hashes`/
+    /// `Block::hashes` compute, in addition to the dedicated `enable_tlsh`/
+    /// `enable_minhash` gates. Defaults to `[Tlsh, MinHash]`, matching the
+    /// pre-existing behavior; add `HashAlgorithm::Ssdeep` to also emit a
+    /// ssdeep-style digest.
+    pub hash_algorithms: Vec<HashAlgorithm>,
+    /// Where `Graph::open_sink` streams `Block`/`Signature` records to as
+    /// they are produced. Defaults to `SinkKind::None`, matching the
+    /// pre-existing behavior of only collecting `BlockJson`/`SignatureJson`
+    /// in memory for the caller to write out itself.
+    pub sink: SinkKind,
+    /// Enables or disables `Block::disassembly()` being attached to
+    /// `BlockJson.disassembly`. Off by default since most consumers only
+    /// want the signature/hash metadata, not a full per-instruction listing.
+    pub enable_disassembly: bool,
+    /// Maximum number of instructions a `DisassemblerBackend` traversal may
+    /// write into a `Graph` before `Graph::enforce_budget` stops it early and
+    /// sets `Graph::truncated`. `None` (the default) leaves traversal
+    /// unbounded, matching the pre-existing behavior.
+    pub max_instructions: Option<usize>,
+    /// Maximum number of blocks a traversal may confirm valid (see
+    /// `GraphQueue::valid`) before `Graph::enforce_budget` stops it early.
+    /// Checked independently of `max_instructions`. `None` (the default)
+    /// leaves traversal unbounded.
+    pub max_blocks: Option<usize>,
+    /// Maximum number of functions a traversal may confirm valid, mirroring
+    /// `max_blocks` at the function level. `None` (the default) leaves
+    /// traversal unbounded.
+    pub max_functions: Option<usize>,
+    /// Wall-clock budget in milliseconds for the same traversal, checked
+    /// independently of the other budgets against `Graph::started_at`.
+    /// `None` (the default) leaves traversal unbounded.
+    pub timeout_ms: Option<u64>,
+    /// Maximum number of entries a `DisassemblerBackend` will read out of an
+    /// indirect jump's table when recovering switch-statement targets, so a
+    /// misidentified or unbounded table can't be walked forever.
+    pub jump_table_maximum_entries: usize,
+    /// Opt-in, intra-block register constant propagation: tracks `mov
+    /// reg,imm`/`lea reg,[rip+disp]`/`xor reg,reg` as it decodes a block so
+    /// an indirect `call reg`/`jmp reg` whose register was just loaded with
+    /// a constant can still resolve to a concrete target, rather than
+    /// leaving the block's outgoing edge unresolved. `false` (the default)
+    /// since it costs a per-block register map even when nothing in the
+    /// image needs it.
+    pub enable_register_constant_propagation: bool,
+    /// Maximum number of instructions `Disassembler::resolve_switch_table`
+    /// will replay from a block's start when forward-emulating registers to
+    /// resolve a register-dispatched switch table, so a malformed or
+    /// pathologically long block can't be walked forever.
+    pub indirect_resolution_maximum_instructions: usize,
 }
 
 impl GraphOptions {
@@ -62,8 +239,368 @@ impl GraphOptions {
             file_tlsh: None,
             file_size: None,
             tags: vec![],
+            enable_compression: false,
+            compression_algorithm: CompressionAlgorithm::Yaz0,
+            hash_algorithms: vec![HashAlgorithm::Tlsh, HashAlgorithm::MinHash],
+            sink: SinkKind::None,
+            enable_disassembly: false,
+            max_instructions: None,
+            max_blocks: None,
+            max_functions: None,
+            timeout_ms: None,
+            jump_table_maximum_entries: 256,
+            enable_register_constant_propagation: false,
+            indirect_resolution_maximum_instructions: 64,
         };
     }
+
+    /// Builds `GraphOptions` from a layered, INI-style analysis profile (see
+    /// `profile::load_profile`), starting from `GraphOptions::new()` and
+    /// applying whichever of the `[minhash]`, `[tlsh]`, `[entropy]`,
+    /// `[hashing]`, `[sink]`, `[disassembly]`, and `[tags]` sections/keys the
+    /// merged profile sets:
+    ///
+    /// ```ini
+    /// [minhash]
+    /// enabled = true
+    /// maximum_byte_size = 50
+    /// number_of_hashes = 64
+    /// shingle_size = 4
+    /// seed = 0
+    ///
+    /// [tlsh]
+    /// enabled = true
+    /// minimum_byte_size = 50
+    ///
+    /// [entropy]
+    /// enabled = true
+    ///
+    /// [hashing]
+    /// algorithms = tlsh,minhash,ssdeep
+    ///
+    /// [sink]
+    /// type = file
+    /// target = /var/log/binlex/blocks.ndjson
+    ///
+    /// [disassembly]
+    /// enabled = false
+    ///
+    /// [tags]
+    /// items = malware,packed
+    /// ```
+    ///
+    /// Keys the profile doesn't mention keep their compiled default.
+    pub fn from_config(path: &Path) -> Result<Self, Error> {
+        let layers = load_profile(path)?;
+        let mut options = Self::new();
+
+        if let Some(section) = layers.get("minhash") {
+            if let Some(value) = section.get("enabled") { options.enable_minhash = parse_bool(value)?; }
+            if let Some(value) = section.get("maximum_byte_size") { options.minhash_maximum_byte_size = parse_number(value)?; }
+            if let Some(value) = section.get("number_of_hashes") { options.minhash_number_of_hashes = parse_number(value)?; }
+            if let Some(value) = section.get("shingle_size") { options.minhash_shingle_size = parse_number(value)?; }
+            if let Some(value) = section.get("seed") { options.minhash_seed = parse_number(value)?; }
+        }
+
+        if let Some(section) = layers.get("tlsh") {
+            if let Some(value) = section.get("enabled") { options.enable_tlsh = parse_bool(value)?; }
+            if let Some(value) = section.get("minimum_byte_size") { options.tlsh_mininum_byte_size = parse_number(value)?; }
+        }
+
+        if let Some(section) = layers.get("entropy") {
+            if let Some(value) = section.get("enabled") { options.enable_entropy = parse_bool(value)?; }
+        }
+
+        if let Some(section) = layers.get("hashing") {
+            if let Some(value) = section.get("algorithms") {
+                options.hash_algorithms = value
+                    .split(',')
+                    .map(|name| name.trim())
+                    .filter(|name| !name.is_empty())
+                    .map(|name| name.parse::<HashAlgorithm>().unwrap())
+                    .collect();
+            }
+        }
+
+        if let Some(section) = layers.get("sink") {
+            if let Some(value) = section.get("type") {
+                let target = section.get("target").cloned().unwrap_or_default();
+                options.sink = match value.to_ascii_lowercase().as_str() {
+                    "none" => SinkKind::None,
+                    "file" => SinkKind::File(target),
+                    "stdout" => SinkKind::Stdout,
+                    "tcp" => SinkKind::Tcp(target),
+                    other => return Err(Error::new(std::io::ErrorKind::InvalidData, format!("unknown sink type: {}", other))),
+                };
+            }
+        }
+
+        if let Some(section) = layers.get("disassembly") {
+            if let Some(value) = section.get("enabled") { options.enable_disassembly = parse_bool(value)?; }
+        }
+
+        if let Some(section) = layers.get("tags") {
+            if let Some(value) = section.get("items") {
+                options.tags = value.split(',').map(|tag| tag.trim().to_string()).filter(|tag| !tag.is_empty()).collect();
+            }
+        }
+
+        Ok(options)
+    }
+}
+
+fn parse_bool(value: &str) -> Result<bool, Error> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "1" | "yes" | "on" => Ok(true),
+        "false" | "0" | "no" | "off" => Ok(false),
+        _ => Err(Error::new(std::io::ErrorKind::InvalidData, format!("invalid boolean value: {}", value))),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, Error> {
+    value.parse::<T>().map_err(|_| Error::new(std::io::ErrorKind::InvalidData, format!("invalid numeric value: {}", value)))
+}
+
+/// The most instructions `Graph::recognize_import_thunks` will consider a
+/// single-block candidate function before ruling it out as too large to be a
+/// thunk. In practice a matched thunk is always exactly one instruction (see
+/// `recognize_import_thunks`'s doc comment), so this is a generous ceiling
+/// rather than a tight bound.
+const THUNK_MAX_INSTRUCTIONS: usize = 3;
+
+/// Reads the 32-bit little-endian displacement trailing the opcode/ModRM
+/// byte at `bytes[modrm_offset]`, if `bytes` is long enough to hold one.
+fn trailing_disp32(bytes: &[u8], modrm_offset: usize) -> Option<i32> {
+    let start = modrm_offset + 1;
+    bytes.get(start..start + 4).map(|slice| i32::from_le_bytes(slice.try_into().unwrap()))
+}
+
+/// Resolves the absolute GOT/IAT slot address(es) `terminator` might read
+/// through, for the `FF 25 disp32` encoding shared by:
+///
+/// - AMD64 `jmp qword [rip + disp32]` (slot = end of this instruction + disp32).
+/// - I386 `jmp dword [disp32]` (slot = disp32, an absolute address with no base register).
+///
+/// Both interpretations of the same bytes are returned, since this graph-only
+/// pass has no record of the file's bitness; `recognize_import_thunks` picks
+/// whichever (if any) resolves to a known import. The PIC `jmp dword [ebx +
+/// disp32]` form isn't included here: resolving it needs the runtime GOT
+/// base, which isn't available at this layer, so those stubs are never
+/// matched rather than guessed at.
+fn resolve_thunk_slot_candidates(terminator: &Instruction) -> Vec<u64> {
+    let bytes = &terminator.bytes;
+    if bytes.len() < 6 || bytes[0] != 0xFF || bytes[1] != 0x25 {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::new();
+    if let Some(disp) = trailing_disp32(bytes, 1) {
+        candidates.push(disp as u32 as u64);
+        if let Some(next) = terminator.address.checked_add(terminator.size() as u64) {
+            candidates.push(next.wrapping_add(disp as i64 as u64));
+        }
+    }
+    candidates
+}
+
+/// `Graph::to_packed`/`from_packed`'s field ids. Unrecognized ids are simply
+/// skipped by `Graph::from_packed`, so new fields can be added here without
+/// breaking readers built against an older field list.
+const PACKED_FIELD_ARCHITECTURE: u8 = 1;
+const PACKED_FIELD_INSTRUCTIONS: u8 = 2;
+const PACKED_FIELD_BLOCKS: u8 = 3;
+const PACKED_FIELD_FUNCTIONS: u8 = 4;
+
+/// `Graph::to_packed`/`from_packed`'s type tags: what shape follows a field
+/// id, so a reader can skip a field's payload without knowing what the field
+/// id means.
+const PACKED_TAG_VARINT: u8 = 0;
+const PACKED_TAG_BYTES: u8 = 1;
+
+fn write_packed_varint(field: u8, value: u64, out: &mut Vec<u8>) {
+    out.push(field);
+    out.push(PACKED_TAG_VARINT);
+    codec::write_uvarint(value, out);
+}
+
+fn write_packed_bytes(field: u8, payload: &[u8], out: &mut Vec<u8>) {
+    out.push(field);
+    out.push(PACKED_TAG_BYTES);
+    codec::write_bytes(payload, out);
+}
+
+fn decode_architecture(value: u64) -> BinaryArchitecture {
+    match value {
+        0x00 => BinaryArchitecture::AMD64,
+        0x01 => BinaryArchitecture::I386,
+        0x02 => BinaryArchitecture::HOLEYBYTES,
+        0x04 => BinaryArchitecture::ARM64,
+        0x05 => BinaryArchitecture::RISCV,
+        0x06 => BinaryArchitecture::M68K,
+        _ => BinaryArchitecture::UNKNOWN,
+    }
+}
+
+/// Encodes a single `Instruction`'s full state (not just the summary
+/// `InstructionJson` carries), so `Graph::from_packed` can rebuild the exact
+/// `instructions` map without re-disassembling.
+fn encode_instruction(instruction: &Instruction, out: &mut Vec<u8>) {
+    codec::write_uvarint(instruction.address, out);
+    codec::write_bytes(&instruction.bytes, out);
+
+    let mut flags = 0u8;
+    if instruction.is_jump { flags |= 0x01; }
+    if instruction.is_conditional { flags |= 0x02; }
+    if instruction.is_call { flags |= 0x04; }
+    if instruction.is_return { flags |= 0x08; }
+    if instruction.is_trap { flags |= 0x10; }
+    if instruction.is_block_start { flags |= 0x20; }
+    if instruction.is_prologue { flags |= 0x40; }
+    out.push(flags);
+
+    codec::write_uvarint(instruction.edges as u64, out);
+
+    match instruction.next {
+        Some(next) => { out.push(1); codec::write_uvarint(next, out); }
+        None => out.push(0),
+    }
+
+    codec::write_uvarint(instruction.to.len() as u64, out);
+    for &address in &instruction.to {
+        codec::write_uvarint(address, out);
+    }
+
+    codec::write_uvarint(instruction.functions.len() as u64, out);
+    for &address in &instruction.functions {
+        codec::write_uvarint(address, out);
+    }
+
+    match &instruction.text {
+        Some(text) => { out.push(1); codec::write_string(text, out); }
+        None => out.push(0),
+    }
+}
+
+/// Reverses `encode_instruction`.
+fn decode_instruction(bytes: &[u8], cursor: &mut usize) -> Result<Instruction, Error> {
+    let address = codec::read_uvarint(bytes, cursor)?;
+    let raw_bytes = codec::read_bytes(bytes, cursor)?;
+    let mut instruction = Instruction::new(address, raw_bytes);
+
+    let flags = *bytes.get(*cursor).ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated instruction flags"))?;
+    *cursor += 1;
+    instruction.is_jump = flags & 0x01 != 0;
+    instruction.is_conditional = flags & 0x02 != 0;
+    instruction.is_call = flags & 0x04 != 0;
+    instruction.is_return = flags & 0x08 != 0;
+    instruction.is_trap = flags & 0x10 != 0;
+    instruction.is_block_start = flags & 0x20 != 0;
+    instruction.is_prologue = flags & 0x40 != 0;
+
+    instruction.edges = codec::read_uvarint(bytes, cursor)? as usize;
+
+    let has_next = *bytes.get(*cursor).ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated instruction next marker"))?;
+    *cursor += 1;
+    instruction.next = if has_next != 0 { Some(codec::read_uvarint(bytes, cursor)?) } else { None };
+
+    let to_count = codec::read_uvarint(bytes, cursor)?;
+    for _ in 0..to_count {
+        instruction.to.insert(codec::read_uvarint(bytes, cursor)?);
+    }
+
+    let functions_count = codec::read_uvarint(bytes, cursor)?;
+    for _ in 0..functions_count {
+        instruction.functions.insert(codec::read_uvarint(bytes, cursor)?);
+    }
+
+    let has_text = *bytes.get(*cursor).ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated instruction text marker"))?;
+    *cursor += 1;
+    instruction.text = if has_text != 0 { Some(codec::read_string(bytes, cursor)?) } else { None };
+
+    Ok(instruction)
+}
+
+fn encode_address_set(set: &SkipSet<u64>, out: &mut Vec<u8>) {
+    codec::write_uvarint(set.len() as u64, out);
+    for address in set.iter() {
+        codec::write_uvarint(*address, out);
+    }
+}
+
+fn decode_address_set(bytes: &[u8], cursor: &mut usize) -> Result<SkipSet<u64>, Error> {
+    let count = codec::read_uvarint(bytes, cursor)?;
+    let set = SkipSet::<u64>::new();
+    for _ in 0..count {
+        set.insert(codec::read_uvarint(bytes, cursor)?);
+    }
+    Ok(set)
+}
+
+fn encode_symbols(symbols: &SkipMap<u64, Symbol>, out: &mut Vec<u8>) {
+    codec::write_uvarint(symbols.len() as u64, out);
+    for entry in symbols.iter() {
+        codec::write_uvarint(*entry.key(), out);
+        let symbol = entry.value();
+        codec::write_uvarint(symbol.names.len() as u64, out);
+        for name in &symbol.names {
+            codec::write_string(name, out);
+        }
+    }
+}
+
+fn decode_symbols(bytes: &[u8], cursor: &mut usize) -> Result<SkipMap<u64, Symbol>, Error> {
+    let count = codec::read_uvarint(bytes, cursor)?;
+    let map = SkipMap::<u64, Symbol>::new();
+    for _ in 0..count {
+        let address = codec::read_uvarint(bytes, cursor)?;
+        let mut symbol = Symbol::new(address);
+        let name_count = codec::read_uvarint(bytes, cursor)?;
+        for _ in 0..name_count {
+            symbol.insert_name(codec::read_string(bytes, cursor)?);
+        }
+        map.insert(address, symbol);
+    }
+    Ok(map)
+}
+
+/// Encodes a `GraphQueue`'s `processed`/`valid`/`invalid` address sets and
+/// `symbols` map. `queue` and `traps` aren't carried: `queue` is pending,
+/// not-yet-processed work a checkpoint has no use for, and `traps` is
+/// diagnostic detail about rejected addresses rather than analysis state.
+fn encode_graph_queue(queue: &GraphQueue, out: &mut Vec<u8>) {
+    encode_address_set(&queue.processed, out);
+    encode_address_set(&queue.valid, out);
+    encode_address_set(&queue.invalid, out);
+    encode_symbols(&queue.symbols, out);
+}
+
+/// Reverses `encode_graph_queue`.
+///
+/// # Returns
+///
+/// Returns `Err` if any decoded `valid` address isn't also `processed`, the
+/// invariant `GraphQueue::insert_valid` enforces when building one normally.
+fn decode_graph_queue(bytes: &[u8]) -> Result<GraphQueue, Error> {
+    let mut cursor = 0;
+    let processed = decode_address_set(bytes, &mut cursor)?;
+    let valid = decode_address_set(bytes, &mut cursor)?;
+    for entry in valid.iter() {
+        let address = *entry;
+        if !processed.contains(&address) {
+            return Err(Error::new(ErrorKind::InvalidData, format!("packed graph queue: valid address 0x{:x} is not processed", address)));
+        }
+    }
+    let invalid = decode_address_set(bytes, &mut cursor)?;
+    let symbols = decode_symbols(bytes, &mut cursor)?;
+
+    Ok(GraphQueue {
+        queue: SegQueue::<u64>::new(),
+        processed,
+        valid,
+        invalid,
+        traps: SkipMap::<u64, TrapReason>::new(),
+        symbols,
+    })
 }
 
 /// Queue structure used within `Graph` for managing addresses in processing stages.
@@ -76,6 +613,12 @@ pub struct GraphQueue {
     pub valid: SkipSet<u64>,
     /// Set of invalid addresses in the graph.
     pub invalid: SkipSet<u64>,
+    /// Why each invalid address was rejected, for addresses rejected with a known reason.
+    pub traps: SkipMap<u64, TrapReason>,
+    /// Map of symbol addresses in the graph, populated directly from a file
+    /// format's own symbol table or by a recognizer pass (e.g.
+    /// `Graph::recognize_import_thunks`).
+    pub symbols: SkipMap<u64, Symbol>,
 }
 
 impl Clone for GraphQueue {
@@ -102,11 +645,21 @@ impl Clone for GraphQueue {
         for item in self.invalid.iter() {
             cloned_invalid.insert(*item);
         }
+        let cloned_traps = SkipMap::new();
+        for entry in self.traps.iter() {
+            cloned_traps.insert(*entry.key(), *entry.value());
+        }
+        let cloned_symbols = SkipMap::<u64, Symbol>::new();
+        for entry in self.symbols.iter() {
+            cloned_symbols.insert(*entry.key(), entry.value().clone());
+        }
         GraphQueue {
             queue: cloned_queue,
             processed: cloned_processed,
             valid: cloned_valid,
             invalid: cloned_invalid,
+            traps: cloned_traps,
+            symbols: cloned_symbols,
         }
     }
 }
@@ -124,7 +677,126 @@ impl GraphQueue {
             processed: SkipSet::<u64>::new(),
             valid: SkipSet::<u64>::new(),
             invalid: SkipSet::<u64>::new(),
+            traps: SkipMap::<u64, TrapReason>::new(),
+            symbols: SkipMap::<u64, Symbol>::new(),
+        }
+    }
+
+    /// Retrieves the symbol at `address`, if one has been recorded.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(Symbol)` if a symbol is recorded at `address`, otherwise `None`.
+    pub fn get_symbol(&self, address: u64) -> Option<Symbol> {
+        self.symbols.get(&address).map(|entry| entry.value().clone())
+    }
+
+    /// Checks if a symbol has been recorded at `address`.
+    #[allow(dead_code)]
+    pub fn is_symbol(&self, address: u64) -> bool {
+        self.symbols.contains_key(&address)
+    }
+
+    /// Records `symbol` at its own address, merging its names into any symbol
+    /// already recorded there instead of overwriting it.
+    pub fn insert_symbol(&self, mut symbol: Symbol) {
+        if let Some(existing) = self.symbols.get(&symbol.address) {
+            symbol.insert_name_entend(existing.value().names.clone());
         }
+        self.symbols.insert(symbol.address, symbol);
+    }
+
+    /// Records each of `symbols` via `insert_symbol`.
+    #[allow(dead_code)]
+    pub fn insert_symbols_extend(&self, symbols: Vec<Symbol>) {
+        for symbol in symbols {
+            self.insert_symbol(symbol);
+        }
+    }
+
+    /// Writes a non-destructive snapshot of this queue's pending work, its
+    /// `processed`/`valid`/`invalid` address sets, and `symbols` to `writer`,
+    /// so an interrupted run can pick back up with `GraphQueue::restore`
+    /// instead of starting over.
+    ///
+    /// `queue` is snapshotted the same lossless way `Clone` does: drained
+    /// into a buffer, encoded, then pushed straight back so the live queue
+    /// is left exactly as it was found.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if the write fails.
+    #[allow(dead_code)]
+    pub fn checkpoint<W: crate::io::Write>(&self, writer: &mut W) -> Result<(), Error> {
+        let mut pending = Vec::new();
+        while let Some(address) = self.queue.pop() {
+            pending.push(address);
+        }
+        for &address in &pending {
+            self.queue.push(address);
+        }
+
+        let mut payload = Vec::new();
+        codec::write_uvarint(pending.len() as u64, &mut payload);
+        for address in &pending {
+            codec::write_uvarint(*address, &mut payload);
+        }
+        encode_address_set(&self.processed, &mut payload);
+        encode_address_set(&self.valid, &mut payload);
+        encode_address_set(&self.invalid, &mut payload);
+        encode_symbols(&self.symbols, &mut payload);
+
+        codec::write_record(writer, &payload)
+    }
+
+    /// Reads a snapshot written by `checkpoint` back into a fresh
+    /// `GraphQueue`, re-enqueueing every address that was still pending
+    /// except those already `processed` — matching the guard `enqueue`
+    /// already enforces, so a restored queue can never re-process an
+    /// address the run already finished.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `reader` has no record to read, or the record is malformed.
+    #[allow(dead_code)]
+    pub fn restore<R: crate::io::Read>(reader: &mut R) -> Result<Self, Error> {
+        let payload = codec::read_record(reader)?
+            .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "empty graph queue checkpoint"))?;
+        let mut cursor = 0;
+
+        let pending_count = codec::read_uvarint(&payload, &mut cursor)?;
+        let mut pending = Vec::with_capacity(pending_count as usize);
+        for _ in 0..pending_count {
+            pending.push(codec::read_uvarint(&payload, &mut cursor)?);
+        }
+
+        let processed = decode_address_set(&payload, &mut cursor)?;
+        let valid = decode_address_set(&payload, &mut cursor)?;
+        for entry in valid.iter() {
+            let address = *entry;
+            if !processed.contains(&address) {
+                return Err(Error::new(ErrorKind::InvalidData, format!("graph queue checkpoint: valid address 0x{:x} is not processed", address)));
+            }
+        }
+        let invalid = decode_address_set(&payload, &mut cursor)?;
+        let symbols = decode_symbols(&payload, &mut cursor)?;
+
+        let restored = Self {
+            queue: SegQueue::<u64>::new(),
+            processed,
+            valid,
+            invalid,
+            traps: SkipMap::<u64, TrapReason>::new(),
+            symbols,
+        };
+
+        for address in pending {
+            if !restored.is_processed(address) {
+                restored.queue.push(address);
+            }
+        }
+
+        Ok(restored)
     }
 
     /// Marks an address as invalid if it has not been marked as valid.
@@ -132,7 +804,7 @@ impl GraphQueue {
     /// # Arguments
     ///
     /// * `address` - The address to mark as invalid.
-    pub fn insert_invalid(&mut self, address: u64) {
+    pub fn insert_invalid(&self, address: u64) {
         if !self.is_invalid(address) {
             if !self.is_valid(address) {
                 self.invalid.insert(address);
@@ -140,6 +812,39 @@ impl GraphQueue {
         }
     }
 
+    /// Marks an address as invalid and records why it was rejected.
+    ///
+    /// Equivalent to `insert_invalid` but additionally populates the `traps`
+    /// map, so disassembler backends that know *why* an address was rejected
+    /// can record that instead of just discarding the reason.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address to mark as invalid.
+    /// * `reason` - Why the address was rejected.
+    pub fn insert_trap(&self, address: u64, reason: TrapReason) {
+        self.insert_invalid(address);
+        self.traps.insert(address, reason);
+    }
+
+    /// Retrieves why `address` was rejected, if it was trapped with a known reason.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(TrapReason)` if `address` was trapped, otherwise `None`.
+    pub fn trap_reason(&self, address: u64) -> Option<TrapReason> {
+        self.traps.get(&address).map(|entry| *entry.value())
+    }
+
+    /// Retrieves a reference to the trap-reason map.
+    ///
+    /// # Returns
+    ///
+    /// Returns a reference to the `SkipMap` of invalid addresses to their `TrapReason`.
+    pub fn traps(&self) -> &SkipMap<u64, TrapReason> {
+        return &self.traps;
+    }
+
     /// Checks if an address is marked as invalid.
     ///
     /// # Returns
@@ -191,7 +896,7 @@ impl GraphQueue {
     /// # Arguments
     ///
     /// * `address` - The address to mark as valid.
-    pub fn insert_valid(&mut self, address: u64) {
+    pub fn insert_valid(&self, address: u64) {
         if self.is_processed(address) {
             self.valid.insert(address);
         }
@@ -202,7 +907,7 @@ impl GraphQueue {
     /// # Arguments
     ///
     /// * `addresses` - A set of addresses to mark as processed.
-    pub fn insert_processed_extend(&mut self, addresses: BTreeSet<u64>) {
+    pub fn insert_processed_extend(&self, addresses: BTreeSet<u64>) {
         for address in addresses {
             self.insert_processed(address);
         }
@@ -213,7 +918,7 @@ impl GraphQueue {
     /// # Arguments
     ///
     /// * `address` - The address to mark as processed.
-    pub fn insert_processed(&mut self, address: u64) {
+    pub fn insert_processed(&self, address: u64) {
         self.processed.insert(address);
     }
 
@@ -231,7 +936,7 @@ impl GraphQueue {
     /// # Arguments
     ///
     /// * `addresses` - A set of addresses to enqueue.
-    pub fn enqueue_extend(&mut self, addresses: BTreeSet<u64>) {
+    pub fn enqueue_extend(&self, addresses: BTreeSet<u64>) {
         for address in addresses {
             self.enqueue(address);
         }
@@ -242,7 +947,7 @@ impl GraphQueue {
     /// # Returns
     ///
     /// Returns `true` if the address was enqueued, otherwise `false`.
-    pub fn enqueue(&mut self, address: u64) -> bool {
+    pub fn enqueue(&self, address: u64) -> bool {
         if self.is_processed(address) { return false; }
         self.queue.push(address);
         return true;
@@ -253,7 +958,7 @@ impl GraphQueue {
     /// # Returns
     ///
     /// Returns `Some(u64)` containing the dequeued address if available, otherwise `None`.
-    pub fn dequeue(&mut self) -> Option<u64> {
+    pub fn dequeue(&self) -> Option<u64> {
         self.queue.pop()
     }
 
@@ -262,7 +967,7 @@ impl GraphQueue {
     /// # Returns
     ///
     /// Returns a `BTreeSet<u64>` containing all dequeued addresses.
-    pub fn dequeue_all(&mut self) -> BTreeSet<u64> {
+    pub fn dequeue_all(&self) -> BTreeSet<u64> {
         let mut set = BTreeSet::new();
         while let Some(address) = self.queue.pop() {
             set.insert(address);
@@ -273,6 +978,8 @@ impl GraphQueue {
 
 /// Represents a control flow graph with instructions, blocks, and functions.
 pub struct Graph {
+    /// The architecture every `Instruction` in this graph was decoded as.
+    pub architecture: BinaryArchitecture,
     /// A map of instruction addresses to `Instruction` instances.
     pub instructions: SkipMap<u64, Instruction>,
     /// Queue for managing basic blocks within the graph.
@@ -281,6 +988,26 @@ pub struct Graph {
     pub functions: GraphQueue,
     /// Configuration options for the graph.
     pub options: GraphOptions,
+    /// Caches each block's already-serialized JSON fragment by address, so a
+    /// block referenced from multiple views is only encoded once.
+    pub block_json_cache: SkipMap<u64, RawJson>,
+    /// Caches each function's already-serialized JSON fragment by address,
+    /// mirroring `block_json_cache`.
+    pub function_json_cache: SkipMap<u64, RawJson>,
+    /// The sink `Block::json`/`Signature::json` stream their output to, once
+    /// `open_sink` has materialized `options.sink`. `None` until then, or if
+    /// `options.sink` is `SinkKind::None`.
+    pub sink: Option<Arc<Mutex<dyn Sink>>>,
+    /// `Some(reason)` once `enforce_budget` has stopped a
+    /// `DisassemblerBackend` traversal early because one of
+    /// `options.max_instructions`/`max_blocks`/`max_functions`/`timeout_ms`
+    /// was exceeded, so callers emitting this graph's JSON know the result
+    /// is only partial rather than a clean, complete analysis, and which
+    /// budget forced the cutoff.
+    pub truncated: Option<Truncation>,
+    /// When this graph was created; the clock `options.timeout_ms` is
+    /// measured against.
+    started_at: Instant,
 }
 
 impl Graph {
@@ -290,15 +1017,36 @@ impl Graph {
     ///
     /// Returns a `Graph` instance with empty instructions, blocks, and functions.
     #[allow(dead_code)]
-    pub fn new() -> Self  {
+    pub fn new(architecture: BinaryArchitecture) -> Self  {
         return Self{
+            architecture,
             instructions: SkipMap::<u64, Instruction>::new(),
             blocks: GraphQueue::new(),
             functions: GraphQueue::new(),
             options: GraphOptions::new(),
+            block_json_cache: SkipMap::<u64, RawJson>::new(),
+            function_json_cache: SkipMap::<u64, RawJson>::new(),
+            sink: None,
+            truncated: None,
+            started_at: Instant::now(),
         };
     }
 
+    /// Materializes `options.sink` into a live `Sink` handle stored on
+    /// `self.sink`, so subsequent `Block::json`/`Signature::json` calls
+    /// stream their output as they are produced. A no-op (leaves `self.sink`
+    /// as `None`) when `options.sink` is `SinkKind::None`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if the selected sink can't be opened (e.g. the file path
+    /// is not writable).
+    #[allow(dead_code)]
+    pub fn open_sink(&mut self) -> Result<(), Error> {
+        self.sink = sink::open(&self.options.sink)?.map(|sink| Arc::new(Mutex::new(sink)) as Arc<Mutex<dyn Sink>>);
+        Ok(())
+    }
+
     pub fn instructions(&self) -> &SkipMap<u64, Instruction> {
         return &self.instructions;
     }
@@ -321,6 +1069,57 @@ impl Graph {
     pub fn get_instruction(&self, address: u64) -> Option<Instruction> {
         self.instructions.get(&address).map(|entry|entry.value().clone())
     }
+
+    /// Returns which budget, if any, this graph has exceeded: decoded
+    /// instruction count, confirmed-valid block count, confirmed-valid
+    /// function count, or elapsed wall-clock time, checked in that order
+    /// against `options.max_instructions`/`max_blocks`/`max_functions`/
+    /// `timeout_ms`. Returns `None` when none of those are set, i.e.
+    /// unbounded traversal.
+    pub fn is_budget_exceeded(&self) -> Option<Truncation> {
+        if let Some(max_instructions) = self.options.max_instructions {
+            if self.instructions.len() >= max_instructions { return Some(Truncation::Instructions); }
+        }
+        if let Some(max_blocks) = self.options.max_blocks {
+            if self.blocks.valid().len() >= max_blocks { return Some(Truncation::Blocks); }
+        }
+        if let Some(max_functions) = self.options.max_functions {
+            if self.functions.valid().len() >= max_functions { return Some(Truncation::Functions); }
+        }
+        if let Some(timeout_ms) = self.options.timeout_ms {
+            if self.started_at.elapsed() >= Duration::from_millis(timeout_ms) { return Some(Truncation::Timeout); }
+        }
+        None
+    }
+
+    /// Checks `is_budget_exceeded` and, the first time it trips, marks every
+    /// address still queued in `blocks`/`functions` invalid with
+    /// `TrapReason::DecodeLimitExceeded` and records `truncated`, leaving
+    /// whatever is already `processed`/`valid`/`invalid` untouched so those
+    /// sets stay mutually consistent.
+    ///
+    /// A `DisassemblerBackend` traversal loop calls this ahead of each
+    /// dequeue so adversarial or pathological input can't run it unbounded:
+    /// once the budget trips, no further work is dequeued and the partial
+    /// graph built so far is returned instead of hanging or erroring out.
+    ///
+    /// # Returns
+    ///
+    /// Returns `true` if the budget has been exceeded (whether by this call
+    /// or an earlier one), `false` otherwise.
+    pub fn enforce_budget(&mut self) -> bool {
+        let Some(reason) = self.is_budget_exceeded() else { return false; };
+        if self.truncated.is_none() {
+            self.truncated = Some(reason);
+            for address in self.blocks.dequeue_all() {
+                self.blocks.insert_trap(address, TrapReason::DecodeLimitExceeded);
+            }
+            for address in self.functions.dequeue_all() {
+                self.functions.insert_trap(address, TrapReason::DecodeLimitExceeded);
+            }
+        }
+        true
+    }
     pub fn absorb(&mut self, graph: &mut Graph) {
 
         for entry in graph.instructions() {
@@ -347,6 +1146,10 @@ impl Graph {
             self.blocks.insert_invalid(entry.value().clone());
         }
 
+        for entry in graph.blocks.traps() {
+            self.blocks.insert_trap(*entry.key(), *entry.value());
+        }
+
         for entry in graph.functions.valid() {
             self.functions.insert_valid(entry.value().clone());
         }
@@ -355,6 +1158,272 @@ impl Graph {
             self.functions.insert_invalid(entry.value().clone());
         }
 
+        for entry in graph.functions.traps() {
+            self.functions.insert_trap(*entry.key(), *entry.value());
+        }
+
+        if let Some(reason) = graph.truncated {
+            if self.truncated.is_none() {
+                self.truncated = Some(reason);
+            }
+        }
+    }
+
+    /// Recognizes PLT/IAT import thunks and records the imported name each
+    /// resolves to as a `Symbol` in `self.functions`, so calls into them stop
+    /// showing up as anonymous functions.
+    ///
+    /// A candidate is a function made up of exactly one block terminated by
+    /// an unconditional jump through an unresolved memory operand (`is_jump`,
+    /// not `is_conditional`, `edges == 1`, `to` empty) — the shape a GOT/IAT
+    /// stub's `jmp [slot]` decodes to when the disassembler can't see past
+    /// the indirection. Because `Block::blocks()` only follows conditional
+    /// fallthrough, this also naturally excludes the `push imm; jmp plt0`
+    /// tail of a lazy-binding stub from the function body, so the function
+    /// this sees is always exactly that one instruction.
+    ///
+    /// `imports`, keyed by the absolute GOT/IAT slot address a thunk reads
+    /// through, supplies the `(module, symbol)` pair to name it with; see
+    /// `PE::imports_by_address` for one way to build it. A stub whose slot
+    /// can't be computed, or doesn't appear in `imports`, is left unnamed
+    /// rather than risk inventing a name.
+    ///
+    /// # Returns
+    ///
+    /// Returns the number of thunks matched and recorded.
+    #[allow(dead_code)]
+    pub fn recognize_import_thunks(&self, imports: &BTreeMap<u64, (String, String)>) -> usize {
+        let mut matched = 0;
+
+        for entry in self.functions.valid() {
+            let address = *entry.value();
+
+            let function = match Function::new(address, self) {
+                Ok(function) => function,
+                Err(_) => continue,
+            };
+
+            if function.block_addresses().len() != 1 || function.instruction_count() > THUNK_MAX_INSTRUCTIONS {
+                continue;
+            }
+
+            let terminator = match function.blocks.get(&address) {
+                Some(terminator) => terminator,
+                None => continue,
+            };
+
+            if !terminator.is_jump || terminator.is_conditional || terminator.edges != 1 || !terminator.to.is_empty() {
+                continue;
+            }
+
+            let resolved = resolve_thunk_slot_candidates(terminator)
+                .into_iter()
+                .find_map(|slot| imports.get(&slot));
+
+            let (_module, name) = match resolved {
+                Some(import) => import,
+                None => continue,
+            };
+
+            let mut symbol = Symbol::new(address);
+            symbol.insert_name(name.clone());
+            self.functions.insert_symbol(symbol);
+            matched += 1;
+        }
+
+        matched
+    }
+
+    /// Serializes this graph's control-flow analysis state (`architecture`,
+    /// every `instructions` entry, and `blocks`/`functions`' processed/valid/
+    /// invalid sets and symbols) into a compact, self-describing binary
+    /// stream, so large samples can be checkpointed to disk instead of
+    /// re-disassembled on every run.
+    ///
+    /// The stream is a sequence of `(field id, type tag, payload)` entries,
+    /// where the type tag alone (a varint value, or a length-prefixed byte
+    /// string) is enough for a reader to skip a field it doesn't recognize,
+    /// keeping the format forward-compatible with fields a newer writer adds.
+    ///
+    /// # Returns
+    ///
+    /// The encoded bytes.
+    #[allow(dead_code)]
+    pub fn to_packed(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+
+        write_packed_varint(PACKED_FIELD_ARCHITECTURE, self.architecture as u64, &mut out);
+
+        let mut instructions = Vec::new();
+        codec::write_uvarint(self.instructions.len() as u64, &mut instructions);
+        for entry in self.instructions.iter() {
+            encode_instruction(entry.value(), &mut instructions);
+        }
+        write_packed_bytes(PACKED_FIELD_INSTRUCTIONS, &instructions, &mut out);
+
+        let mut blocks = Vec::new();
+        encode_graph_queue(&self.blocks, &mut blocks);
+        write_packed_bytes(PACKED_FIELD_BLOCKS, &blocks, &mut out);
+
+        let mut functions = Vec::new();
+        encode_graph_queue(&self.functions, &mut functions);
+        write_packed_bytes(PACKED_FIELD_FUNCTIONS, &functions, &mut out);
+
+        out
+    }
+
+    /// Reconstructs a `Graph` from bytes produced by `to_packed`, reusing
+    /// `options` for the fields `to_packed` doesn't carry (e.g. hashing and
+    /// compression settings, which are run configuration rather than
+    /// analysis state).
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `bytes` is truncated, malformed, or carries a
+    /// `valid` address that isn't also `processed`.
+    #[allow(dead_code)]
+    pub fn from_packed(bytes: &[u8], options: GraphOptions) -> Result<Self, Error> {
+        let mut cursor = 0;
+        let mut architecture = BinaryArchitecture::UNKNOWN;
+        let mut instructions = SkipMap::<u64, Instruction>::new();
+        let mut blocks: Option<GraphQueue> = None;
+        let mut functions: Option<GraphQueue> = None;
+
+        while cursor < bytes.len() {
+            let field = *bytes.get(cursor).ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated packed graph field id"))?;
+            cursor += 1;
+            let tag = *bytes.get(cursor).ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated packed graph type tag"))?;
+            cursor += 1;
+
+            match tag {
+                PACKED_TAG_VARINT => {
+                    let value = codec::read_uvarint(bytes, &mut cursor)?;
+                    if field == PACKED_FIELD_ARCHITECTURE {
+                        architecture = decode_architecture(value);
+                    }
+                }
+                PACKED_TAG_BYTES => {
+                    let payload = codec::read_bytes(bytes, &mut cursor)?;
+                    match field {
+                        PACKED_FIELD_INSTRUCTIONS => {
+                            let mut payload_cursor = 0;
+                            let count = codec::read_uvarint(&payload, &mut payload_cursor)?;
+                            for _ in 0..count {
+                                let instruction = decode_instruction(&payload, &mut payload_cursor)?;
+                                instructions.insert(instruction.address, instruction);
+                            }
+                        }
+                        PACKED_FIELD_BLOCKS => blocks = Some(decode_graph_queue(&payload)?),
+                        PACKED_FIELD_FUNCTIONS => functions = Some(decode_graph_queue(&payload)?),
+                        // Unknown field: already consumed via its type tag, skip it.
+                        _ => {}
+                    }
+                }
+                _ => return Err(Error::new(ErrorKind::InvalidData, format!("packed graph: unknown type tag {}", tag))),
+            }
+        }
+
+        Ok(Self {
+            architecture,
+            instructions,
+            blocks: blocks.unwrap_or_else(GraphQueue::new),
+            functions: functions.unwrap_or_else(GraphQueue::new),
+            options,
+            block_json_cache: SkipMap::<u64, RawJson>::new(),
+            function_json_cache: SkipMap::<u64, RawJson>::new(),
+            sink: None,
+        })
+    }
+
+    /// Writes this graph's full resumable work state to `path`: `architecture`,
+    /// then `instructions`, then `blocks`, then `functions`, the latter two
+    /// each via `GraphQueue::checkpoint` — the counterpart `resume_from`
+    /// reads back.
+    ///
+    /// Unlike `to_packed`, this is meant for an analysis run that's still in
+    /// progress: it carries the pending queues so discovery can continue, as
+    /// well as every instruction decoded so far. Persisting `instructions`
+    /// alongside the queues matters, not just for completeness: an address
+    /// already marked `processed` is never re-enqueued on restore (see
+    /// `GraphQueue::restore`), so if its instructions weren't also restored
+    /// the resumed graph would have a block/function address with no
+    /// instructions backing it and `Block::new`/`Function::new` would simply
+    /// drop it from the output.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `path` can't be created or a write fails.
+    #[allow(dead_code)]
+    pub fn checkpoint_to(&self, path: &Path) -> Result<(), Error> {
+        let mut file = File::create(path)?;
+
+        let mut architecture = Vec::new();
+        codec::write_uvarint(self.architecture as u64, &mut architecture);
+        codec::write_record(&mut file, &architecture)?;
+
+        let mut instructions = Vec::new();
+        codec::write_uvarint(self.instructions.len() as u64, &mut instructions);
+        for entry in self.instructions.iter() {
+            encode_instruction(entry.value(), &mut instructions);
+        }
+        codec::write_record(&mut file, &instructions)?;
+
+        self.blocks.checkpoint(&mut file)?;
+        self.functions.checkpoint(&mut file)?;
+
+        Ok(())
+    }
+
+    /// Rebuilds a `Graph` from a checkpoint written by `checkpoint_to`, so an
+    /// interrupted large-binary run can resume instead of starting over.
+    ///
+    /// `config` supplies the run options `checkpoint_to` doesn't carry (e.g.
+    /// hashing and compression settings), the same way `options` is passed
+    /// into `from_packed` instead of being part of the packed bytes.
+    ///
+    /// Only addresses still pending in `blocks`/`functions` and not already
+    /// `processed` are re-enqueued, matching `GraphQueue::restore`'s guard;
+    /// every previously-decoded instruction is restored alongside them so a
+    /// `processed` block/function address still has the instructions
+    /// `Block::new`/`Function::new` need to build it.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `path` is missing, truncated, or malformed, or if
+    /// `config` fails to load.
+    #[allow(dead_code)]
+    pub fn resume_from(path: &Path, config: &Path) -> Result<Self, Error> {
+        let mut file = File::open(path)?;
+
+        let architecture_bytes = codec::read_record(&mut file)?
+            .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "missing architecture record in graph checkpoint"))?;
+        let mut architecture_cursor = 0;
+        let architecture = decode_architecture(codec::read_uvarint(&architecture_bytes, &mut architecture_cursor)?);
+
+        let instructions_bytes = codec::read_record(&mut file)?
+            .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "missing instructions record in graph checkpoint"))?;
+        let mut instructions_cursor = 0;
+        let instructions = SkipMap::<u64, Instruction>::new();
+        let count = codec::read_uvarint(&instructions_bytes, &mut instructions_cursor)?;
+        for _ in 0..count {
+            let instruction = decode_instruction(&instructions_bytes, &mut instructions_cursor)?;
+            instructions.insert(instruction.address, instruction);
+        }
+
+        let blocks = GraphQueue::restore(&mut file)?;
+        let functions = GraphQueue::restore(&mut file)?;
+        let options = GraphOptions::from_config(config)?;
+
+        Ok(Self {
+            architecture,
+            instructions,
+            blocks,
+            functions,
+            options,
+            block_json_cache: SkipMap::<u64, RawJson>::new(),
+            function_json_cache: SkipMap::<u64, RawJson>::new(),
+            sink: None,
+        })
     }
 
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+//! Declarative instruction classification, generated at build time from
+//! `instructions.in` (crate root; one `architecture mnemonic operand_shape
+//! flags` fact per line) by `build.rs` into `$OUT_DIR/instrs.rs`.
+//!
+//! Backends consult `classify` instead of hand-written per-mnemonic match
+//! arms, so teaching binlex a new architecture's prologue shape, or tuning
+//! which mnemonics should be wildcard-masked during signature
+//! normalization, is a data edit to `instructions.in`, not a code change.
+use crate::models::binary::BinaryArchitecture;
+
+/// The generated-table classification of one `(architecture, mnemonic)` pair.
+///
+/// `operand_shape` is carried through from `instructions.in` for future
+/// operand-aware refinement (e.g. distinguishing `sub esp, imm8` from `sub
+/// esp, eax`); `classify` does not yet filter on decoded operands, only the
+/// mnemonic text. `normalize` mirrors `instructions.in`'s `normalize` flag
+/// but is not yet consumed by `Signature::normalize`, which still masks
+/// purely from the backend's own wildcard pattern string.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InstructionClass {
+    pub operand_shape: &'static str,
+    pub is_prologue: bool,
+    pub is_call: bool,
+    pub is_branch: bool,
+    pub is_nop: bool,
+    pub normalize: bool,
+}
+
+/// Maps a `BinaryArchitecture` to the lowercase name used as the first field
+/// of each `instructions.in` line.
+fn architecture_name(architecture: BinaryArchitecture) -> &'static str {
+    match architecture {
+        BinaryArchitecture::AMD64 => "amd64",
+        BinaryArchitecture::I386 => "i386",
+        BinaryArchitecture::ARM64 => "arm64",
+        BinaryArchitecture::HOLEYBYTES => "holeybytes",
+        BinaryArchitecture::RISCV => "riscv",
+        BinaryArchitecture::M68K => "m68k",
+        BinaryArchitecture::UNKNOWN => "unknown",
+    }
+}
+
+/// Classifies `mnemonic` for `architecture` using the table `build.rs`
+/// generated from `instructions.in`, returning `InstructionClass::default()`
+/// (all flags `false`) for any mnemonic the table doesn't list.
+///
+/// `mnemonic` is matched as given; callers should pass their decoder's own
+/// mnemonic text lowercased (Capstone's already is).
+pub fn classify(architecture: BinaryArchitecture, mnemonic: &str) -> InstructionClass {
+    classify_raw(architecture_name(architecture), mnemonic)
+}
+
+include!(concat!(env!("OUT_DIR"), "/instrs.rs"));
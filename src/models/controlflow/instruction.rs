@@ -0,0 +1,225 @@
+use std::collections::BTreeSet;
+use std::io::{Error, ErrorKind};
+use serde::{Deserialize, Serialize};
+use serde_json;
+use crate::models::binary::Binary;
+use crate::models::hashing::minhash::{MinHash32, DEFAULT_SIMILARITY_SEED};
+
+/// Represents the JSON-serializable structure of an `Instruction`.
+///
+/// Unlike `BlockJson`/`FunctionJson`, this is a lossless, direct mirror of
+/// `Instruction`'s own fields (no `cfg`-derived metadata), so `Instruction::from_json`
+/// can fully reconstruct the original instruction rather than only its summary.
+#[derive(Serialize, Deserialize)]
+pub struct InstructionJson {
+    /// The address of this instruction.
+    pub address: u64,
+    /// The raw bytes of this instruction, in hexadecimal format.
+    pub bytes: String,
+    /// `true` if this instruction is an unconditional or conditional jump.
+    pub is_jump: bool,
+    /// `true` if this instruction is a conditional jump.
+    pub is_conditional: bool,
+    /// `true` if this instruction is a call.
+    pub is_call: bool,
+    /// `true` if this instruction is a return.
+    pub is_return: bool,
+    /// `true` if this instruction is a trap (illegal/privileged/halt) instruction.
+    pub is_trap: bool,
+    /// `true` if this instruction is known to start a block.
+    pub is_block_start: bool,
+    /// `true` if this instruction is a function prologue.
+    pub is_prologue: bool,
+    /// The number of outgoing edges this instruction terminates its block with.
+    pub edges: usize,
+    /// The address of the fallthrough instruction, if this instruction is a conditional branch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next: Option<u64>,
+    /// The set of addresses this instruction may branch, jump, or call to.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub to: BTreeSet<u64>,
+    /// Function addresses this instruction references.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub functions: BTreeSet<u64>,
+    /// Mnemonic and operand text (e.g. `"mov eax, ebx"`), if the backend that
+    /// decoded this instruction surfaced one. `None` for backends that only
+    /// populate the control-flow flags above.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// A hex-with-`?`-wildcard rendering of `bytes`, the same length as
+    /// `bytes * 2`, masking address-dependent nibbles (immediates,
+    /// displacements) so two otherwise-identical instructions at different
+    /// addresses or before/after relocation produce the same pattern. Falls
+    /// back to the unmasked hex of `bytes` for backends that don't compute
+    /// one. `Signature::pattern` concatenates this across a block's or
+    /// function's instructions; `Function::pattern` uses the unmasked bytes.
+    pub pattern: String,
+}
+
+/// A single decoded instruction within a `Graph`.
+///
+/// This is the unit both the Capstone-backed disassembler and any other
+/// `DisassemblerBackend` write into the graph via `Graph::insert_instruction`;
+/// `Block`/`Function` then stitch ranges of these together purely from the
+/// flags below, without needing to know which backend produced them.
+#[derive(Clone)]
+pub struct Instruction {
+    /// The address of this instruction.
+    pub address: u64,
+    /// The raw bytes of this instruction.
+    pub bytes: Vec<u8>,
+    /// `true` if this instruction is an unconditional or conditional jump.
+    pub is_jump: bool,
+    /// `true` if this instruction is a conditional jump.
+    pub is_conditional: bool,
+    /// `true` if this instruction is a call.
+    pub is_call: bool,
+    /// `true` if this instruction is a return.
+    pub is_return: bool,
+    /// `true` if this instruction is a trap (illegal/privileged/halt) instruction.
+    pub is_trap: bool,
+    /// `true` if this instruction is known to start a block (e.g. a prologue or a jump target).
+    pub is_block_start: bool,
+    /// `true` if this instruction is a function prologue.
+    pub is_prologue: bool,
+    /// The number of outgoing edges this instruction terminates its block with.
+    pub edges: usize,
+    /// The address of the fallthrough instruction, if this instruction is a conditional branch.
+    pub next: Option<u64>,
+    /// The set of addresses this instruction may branch, jump, or call to.
+    pub to: BTreeSet<u64>,
+    /// Function addresses this instruction references (e.g. the target of a `call`).
+    pub functions: BTreeSet<u64>,
+    /// Mnemonic and operand text, if the backend that decoded this
+    /// instruction surfaced one. See `InstructionJson::text`.
+    pub text: Option<String>,
+    /// A hex-with-`?`-wildcard rendering of `bytes`. See `InstructionJson::pattern`.
+    pub pattern: String,
+}
+
+impl Instruction {
+    /// Creates a new, minimal `Instruction` at `address` covering `bytes`.
+    ///
+    /// All control-flow flags default to `false`/empty; callers set the
+    /// relevant ones once decoding determines the instruction's shape.
+    pub fn new(address: u64, bytes: Vec<u8>) -> Self {
+        let pattern = Binary::to_hex(&bytes);
+        Self {
+            address,
+            bytes,
+            is_jump: false,
+            is_conditional: false,
+            is_call: false,
+            is_return: false,
+            is_trap: false,
+            is_block_start: false,
+            is_prologue: false,
+            edges: 0,
+            next: None,
+            to: BTreeSet::new(),
+            functions: BTreeSet::new(),
+            text: None,
+            pattern,
+        }
+    }
+
+    /// The size of this instruction in bytes.
+    pub fn size(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// The address of the fallthrough instruction, if any.
+    pub fn next(&self) -> Option<u64> {
+        self.next
+    }
+
+    /// The set of addresses this instruction may branch, jump, or call to.
+    pub fn to(&self) -> BTreeSet<u64> {
+        self.to.clone()
+    }
+
+    /// Computes a `k`-permutation MinHash signature over `ngram`-byte shingles of
+    /// this instruction's bytes, using a fixed seed so signatures built from the
+    /// same `(k, ngram)` are directly comparable across instructions.
+    ///
+    /// # Returns
+    ///
+    /// A `Vec<u32>` of length `k`; all-`SENTINEL_HASH` if `bytes` is shorter than
+    /// `ngram`, which `similarity` treats as similar to nothing.
+    pub fn minhash(&self, k: usize, ngram: usize) -> Vec<u32> {
+        MinHash32::new(&self.bytes, k, ngram, DEFAULT_SIMILARITY_SEED).hash_or_sentinel()
+    }
+
+    /// Estimated Jaccard similarity between this instruction and `other`, via
+    /// their `minhash(k, ngram)` signatures.
+    ///
+    /// # Returns
+    ///
+    /// A similarity in `0.0..=1.0`; `0.0` if either instruction is shorter than `ngram`.
+    pub fn similarity(&self, other: &Instruction, k: usize, ngram: usize) -> f64 {
+        MinHash32::similarity(&self.minhash(k, ngram), &other.minhash(k, ngram))
+    }
+
+    /// Converts this instruction into its JSON-serializable `InstructionJson` form.
+    pub fn process(&self) -> InstructionJson {
+        InstructionJson {
+            address: self.address,
+            bytes: Binary::to_hex(&self.bytes),
+            is_jump: self.is_jump,
+            is_conditional: self.is_conditional,
+            is_call: self.is_call,
+            is_return: self.is_return,
+            is_trap: self.is_trap,
+            is_block_start: self.is_block_start,
+            is_prologue: self.is_prologue,
+            edges: self.edges,
+            next: self.next,
+            to: self.to.clone(),
+            functions: self.functions.clone(),
+            text: self.text.clone(),
+            pattern: self.pattern.clone(),
+        }
+    }
+
+    /// Converts the instruction into a JSON string representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(String)` containing the JSON representation, or an `Err` if serialization fails.
+    pub fn json(&self) -> Result<String, Error> {
+        let raw = self.process();
+        let result = serde_json::to_string(&raw)?;
+        Ok(result)
+    }
+
+    /// Reconstructs an `Instruction` from the JSON produced by `json()`, the
+    /// inverse of `process()`. Enables on-disk trait databases and
+    /// multiprocessing fan-out without re-disassembling.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Instruction)` on success, or an `Err` if `data` isn't valid
+    /// `InstructionJson` or its `bytes` field isn't valid hex.
+    pub fn from_json(data: &str) -> Result<Self, Error> {
+        let raw: InstructionJson = serde_json::from_str(data)?;
+        let bytes = Binary::from_hex(&raw.bytes)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "instruction bytes is not valid hex"))?;
+        Ok(Self {
+            address: raw.address,
+            bytes,
+            is_jump: raw.is_jump,
+            is_conditional: raw.is_conditional,
+            is_call: raw.is_call,
+            is_return: raw.is_return,
+            is_trap: raw.is_trap,
+            is_block_start: raw.is_block_start,
+            is_prologue: raw.is_prologue,
+            edges: raw.edges,
+            next: raw.next,
+            to: raw.to,
+            functions: raw.functions,
+            text: raw.text,
+            pattern: raw.pattern,
+        })
+    }
+}
@@ -0,0 +1,11 @@
+pub mod block;
+pub mod codec;
+pub mod file;
+pub mod function;
+pub mod graph;
+pub mod instruction;
+pub mod instrs;
+pub mod signature;
+pub mod profile;
+pub mod rawjson;
+pub mod symbol;
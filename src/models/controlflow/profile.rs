@@ -0,0 +1,115 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io::{Error, ErrorKind};
+use std::path::{Path, PathBuf};
+
+/// A layered, INI-style profile: one `HashMap` of key/value pairs per section.
+pub type ProfileLayers = HashMap<String, HashMap<String, String>>;
+
+/// Parses `path` as an INI-style analysis profile, recursively resolving
+/// `%include <path>` directives so teams can layer a shared base profile with
+/// per-job overrides.
+///
+/// The file is a sequence of `[section]` headers, `key = value` items, and
+/// two directives:
+///
+/// * `%include <path>` - merges in another profile, resolved relative to the
+///   including file's directory, before continuing with the rest of this file.
+///   Because later values override earlier ones, anything after the
+///   `%include` in this file wins over what it pulled in.
+/// * `%unset <key>` - drops a value inherited from an earlier layer back to
+///   the compiled default. `<key>` may be `section.key` to unset from any
+///   section, or a bare `key` to unset from the current (most recently
+///   opened) section.
+///
+/// Includes are resolved with cycle detection: re-including a file already
+/// on the current include path returns an error instead of recursing forever.
+pub fn load_profile(path: &Path) -> Result<ProfileLayers, Error> {
+    let mut visiting = HashSet::new();
+    load_profile_inner(path, &mut visiting)
+}
+
+fn load_profile_inner(path: &Path, visiting: &mut HashSet<PathBuf>) -> Result<ProfileLayers, Error> {
+    let canonical = fs::canonicalize(path)
+        .map_err(|error| Error::new(error.kind(), format!("{}: {}", path.display(), error)))?;
+
+    if !visiting.insert(canonical.clone()) {
+        return Err(Error::new(ErrorKind::InvalidData, format!("%include cycle detected at {}", path.display())));
+    }
+
+    let contents = fs::read_to_string(&canonical)?;
+    let base_dir = canonical.parent().map(Path::to_path_buf).unwrap_or_else(|| PathBuf::from("."));
+
+    let mut layers: ProfileLayers = HashMap::new();
+    let mut current_section = String::new();
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(argument) = line.strip_prefix("%include") {
+            let included_path = argument.trim();
+            if included_path.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidData, "%include requires a path"));
+            }
+            let resolved = base_dir.join(included_path);
+            let included_layers = load_profile_inner(&resolved, visiting)?;
+            merge_layers(&mut layers, included_layers);
+            continue;
+        }
+
+        if let Some(argument) = line.strip_prefix("%unset") {
+            let key = argument.trim();
+            if key.is_empty() {
+                return Err(Error::new(ErrorKind::InvalidData, "%unset requires a key"));
+            }
+            unset_key(&mut layers, &current_section, key);
+            continue;
+        }
+
+        if line.starts_with('[') && line.ends_with(']') {
+            current_section = line[1..line.len() - 1].trim().to_string();
+            layers.entry(current_section.clone()).or_default();
+            continue;
+        }
+
+        if let Some((key, value)) = line.split_once('=') {
+            layers.entry(current_section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+            continue;
+        }
+
+        return Err(Error::new(ErrorKind::InvalidData, format!("unrecognized profile line: {}", raw_line)));
+    }
+
+    visiting.remove(&canonical);
+    Ok(layers)
+}
+
+/// Layers `incoming` on top of the profile built so far, so values the
+/// included file sets replace keys already present in `target` (from lines
+/// that appear before the `%include` in the including file); lines after the
+/// `%include` are applied afterwards and replace these in turn.
+fn merge_layers(target: &mut ProfileLayers, incoming: ProfileLayers) {
+    for (section, items) in incoming {
+        let existing = target.entry(section).or_default();
+        for (key, value) in items {
+            existing.insert(key, value);
+        }
+    }
+}
+
+/// Removes `key` (either `section.key`, or a bare `key` resolved against
+/// `current_section`) from `layers`.
+fn unset_key(layers: &mut ProfileLayers, current_section: &str, key: &str) {
+    let (section, key) = match key.split_once('.') {
+        Some((section, key)) => (section, key),
+        None => (current_section, key),
+    };
+    if let Some(items) = layers.get_mut(section) {
+        items.remove(key);
+    }
+}
@@ -0,0 +1,38 @@
+use serde::Serialize;
+use serde_json::value::RawValue;
+use std::io::Error;
+
+/// A pre-serialized JSON fragment. Wrapping an already-valid JSON string in
+/// `RawJson` lets a parent object embed it verbatim during serialization
+/// instead of re-parsing and re-encoding it, so a block or function that has
+/// already been serialized once (e.g. through a cache) doesn't pay that cost
+/// again on every subsequent view.
+#[derive(Clone)]
+pub struct RawJson(Box<RawValue>);
+
+impl RawJson {
+    /// Wraps an already-serialized JSON string.
+    ///
+    /// # Returns
+    ///
+    /// Returns an `Err` if `json` is not syntactically valid JSON.
+    pub fn new(json: String) -> Result<Self, Error> {
+        let raw = RawValue::from_string(json)
+            .map_err(|error| Error::new(std::io::ErrorKind::InvalidData, error.to_string()))?;
+        Ok(Self(raw))
+    }
+
+    /// Returns the wrapped JSON as a `&str`, without re-encoding it.
+    pub fn as_str(&self) -> &str {
+        self.0.get()
+    }
+}
+
+impl Serialize for RawJson {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
@@ -1,11 +1,16 @@
 use serde::{Deserialize, Serialize};
 use serde_json;
-use std::io::Error;
+use std::collections::BTreeMap;
+use std::io::{Error, ErrorKind};
 use crate::models::binary::Binary;
+use crate::models::controlflow::codec;
+use crate::models::sink::Sink;
 use crate::models::controlflow::graph::Graph;
+use crate::models::controlflow::graph::HashAlgorithm;
 use crate::models::hashing::sha256::SHA256;
 use crate::models::hashing::tlsh::TLSH;
 use crate::models::hashing::minhash::MinHash32;
+use crate::models::hashing::ssdeep::SSDEEP;
 
 /// Represents a JSON-serializable structure containing metadata about a signature.
 #[derive(Serialize, Deserialize)]
@@ -20,10 +25,12 @@ pub struct SignatureJson {
     pub entropy: Option<f64>,
     /// The SHA-256 hash of the normalized signature, if enabled.
     pub sha256: Option<String>,
-    /// The MinHash of the normalized signature, if enabled.
-    pub minhash: Option<String>,
-    /// The TLSH (Locality Sensitive Hash) of the normalized signature, if enabled.
-    pub tlsh: Option<String>,
+    /// Fuzzy/locality-sensitive hashes of the normalized signature, keyed by
+    /// algorithm name (e.g. `"tlsh"`, `"minhash"`, `"ssdeep"`), per
+    /// `GraphOptions::hash_algorithms`. Coexists instead of one field per
+    /// algorithm so adding a new one doesn't grow this struct.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub hashes: BTreeMap<String, String>,
 }
 
 /// Represents a signature within a control flow graph.
@@ -91,7 +98,7 @@ impl<'a> Signature<'a> {
     ///
     /// Returns a `Vec<u8>` containing the feature vector, or an empty vector if feature extraction is disabled.
     pub fn feature(&self) -> Vec<u8> {
-        if !self.cfg.config.heuristics.features.enabled { return Vec::<u8>::new(); }
+        if !self.cfg.options.enable_feature { return Vec::<u8>::new(); }
         self.normalize()
             .iter()
             .flat_map(|byte| vec![((byte & 0xf0) >> 4) as u8, (byte & 0x0f) as u8])
@@ -133,7 +140,7 @@ impl<'a> Signature<'a> {
     ///
     /// Returns `Some(String)` containing the normalized hexadecimal representation, or `None` if normalization is disabled.
     pub fn normalized(&self) -> Option<String> {
-        if !self.cfg.config.heuristics.normalization.enabled{ return None; }
+        if !self.cfg.options.enable_normalized { return None; }
         Some(Binary::to_hex(&self.normalize()))
     }
 
@@ -141,26 +148,68 @@ impl<'a> Signature<'a> {
     ///
     /// # Returns
     ///
-    /// Returns `Some(String)` containing the TLSH, or `None` if TLSH is disabled.
+    /// Returns `Some(String)` containing the TLSH, or `None` if TLSH is disabled or
+    /// `HashAlgorithm::Tlsh` isn't in `GraphOptions::hash_algorithms`.
     pub fn tlsh(&self) -> Option<String> {
-        if !self.cfg.config.hashing.tlsh.enabled { return None; }
-        return TLSH::new(&self.normalize(), self.cfg.config.hashing.tlsh.minimum_byte_size).hexdigest();
+        if !self.cfg.options.enable_tlsh { return None; }
+        if !self.cfg.options.hash_algorithms.contains(&HashAlgorithm::Tlsh) { return None; }
+        return TLSH::new(&self.normalize(), self.cfg.options.tlsh_mininum_byte_size).hexdigest();
     }
 
     /// Computes the MinHash of the normalized signature, if enabled.
     ///
     /// # Returns
     ///
-    /// Returns `Some(String)` containing the MinHash, or `None` if MinHash is disabled.
+    /// Returns `Some(String)` containing the MinHash, or `None` if MinHash is disabled or
+    /// `HashAlgorithm::MinHash` isn't in `GraphOptions::hash_algorithms`.
     #[allow(dead_code)]
     pub fn minhash(&self) -> Option<String> {
-        if !self.cfg.config.hashing.minhash.enabled { return None; }
-        if self.normalize().len() > self.cfg.config.hashing.minhash.maximum_byte_size { return None; }
+        if !self.cfg.options.enable_minhash { return None; }
+        if !self.cfg.options.hash_algorithms.contains(&HashAlgorithm::MinHash) { return None; }
+        if self.normalize().len() > self.cfg.options.minhash_maximum_byte_size { return None; }
         return MinHash32::new(
             &self.normalize(),
-            self.cfg.config.hashing.minhash.number_of_hashes,
-            self.cfg.config.hashing.minhash.shingle_size,
-            self.cfg.config.hashing.minhash.seed).hexdigest();
+            self.cfg.options.minhash_number_of_hashes,
+            self.cfg.options.minhash_shingle_size,
+            self.cfg.options.minhash_seed).hexdigest();
+    }
+
+    /// Computes a ssdeep-style fuzzy hash of the normalized signature, if
+    /// `HashAlgorithm::Ssdeep` is in `GraphOptions::hash_algorithms`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(String)` containing the signature, or `None` if ssdeep
+    /// isn't selected or the normalized signature is empty.
+    #[allow(dead_code)]
+    pub fn ssdeep(&self) -> Option<String> {
+        if !self.cfg.options.hash_algorithms.contains(&HashAlgorithm::Ssdeep) { return None; }
+        SSDEEP::new(&self.normalize()).hexdigest()
+    }
+
+    /// Computes every fuzzy/locality-sensitive hash selected by
+    /// `GraphOptions::hash_algorithms`, keyed by algorithm name.
+    /// `HashAlgorithm::Custom` entries are skipped: there's nothing in this
+    /// crate to dispatch them to.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `BTreeMap<String, String>` with one entry per algorithm that
+    /// both is selected and actually produced a digest.
+    pub fn hashes(&self) -> BTreeMap<String, String> {
+        let mut result = BTreeMap::new();
+        for algorithm in &self.cfg.options.hash_algorithms {
+            let digest = match algorithm {
+                HashAlgorithm::Tlsh => self.tlsh(),
+                HashAlgorithm::MinHash => self.minhash(),
+                HashAlgorithm::Ssdeep => self.ssdeep(),
+                HashAlgorithm::Custom(_) => None,
+            };
+            if let Some(digest) = digest {
+                result.insert(algorithm.name().to_string(), digest);
+            }
+        }
+        result
     }
 
     /// Computes the SHA-256 hash of the normalized signature, if enabled.
@@ -169,7 +218,7 @@ impl<'a> Signature<'a> {
     ///
     /// Returns `Some(String)` containing the SHA-256 hash, or `None` if SHA-256 is disabled.
     pub fn sha256(&self) -> Option<String> {
-        if !self.cfg.config.hashing.sha256.enabled { return None; }
+        if !self.cfg.options.enable_sha256 { return None; }
         SHA256::new(&self.normalize()).hexdigest()
     }
 
@@ -179,7 +228,7 @@ impl<'a> Signature<'a> {
     ///
     /// Returns `Some(f64)` containing the entropy, or `None` if entropy calculation is disabled.
     pub fn entropy(&self) -> Option<f64> {
-        if !self.cfg.config.heuristics.entropy.enabled { return None; }
+        if !self.cfg.options.enable_entropy { return None; }
         Binary::entropy(&self.normalize())
     }
 
@@ -195,8 +244,7 @@ impl<'a> Signature<'a> {
             feature: self.feature(),
             sha256: self.sha256(),
             entropy: self.entropy(),
-            minhash: self.minhash(),
-            tlsh: self.tlsh(),
+            hashes: self.hashes(),
         }
     }
 
@@ -209,8 +257,116 @@ impl<'a> Signature<'a> {
     #[allow(dead_code)]
     pub fn json(&self) -> Result<String, Error> {
         let raw = self.process();
+        if let Some(sink) = &self.cfg.sink {
+            if let Ok(mut sink) = sink.lock() {
+                let _ = sink.send_signature(&raw);
+            }
+        }
         let result =  serde_json::to_string(&raw)?;
         Ok(result)
     }
 
+    /// Encodes this signature into the compact binary format `from_bytes`
+    /// reverses: varint-prefixed fields instead of JSON, and the fuzzy/SHA-256
+    /// hashes stored as raw bytes behind a presence tag instead of hex text.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` with the encoded record.
+    #[allow(dead_code)]
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        let mut out = Vec::new();
+
+        codec::write_string(&raw.pattern, &mut out);
+
+        match &raw.normalized {
+            Some(value) => { out.push(1); codec::write_string(value, &mut out); }
+            None => out.push(0),
+        }
+
+        codec::write_bytes(&raw.feature, &mut out);
+
+        match raw.entropy {
+            Some(value) => { out.push(1); out.extend_from_slice(&value.to_le_bytes()); }
+            None => out.push(0),
+        }
+
+        match &raw.sha256 {
+            Some(hex) => {
+                out.push(1);
+                let bytes = Binary::from_hex(hex)
+                    .ok_or_else(|| Error::new(ErrorKind::InvalidData, "invalid sha256 hex"))?;
+                codec::write_bytes(&bytes, &mut out);
+            }
+            None => out.push(0),
+        }
+
+        codec::write_uvarint(raw.hashes.len() as u64, &mut out);
+        for (name, hex) in &raw.hashes {
+            codec::write_string(name, &mut out);
+            let bytes = Binary::from_hex(hex)
+                .ok_or_else(|| Error::new(ErrorKind::InvalidData, format!("invalid {} hex", name)))?;
+            codec::write_bytes(&bytes, &mut out);
+        }
+
+        Ok(out)
+    }
+
+    /// Decodes a record written by `to_bytes` back into a `SignatureJson`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Err` if `bytes` is truncated or malformed.
+    #[allow(dead_code)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<SignatureJson, Error> {
+        let mut cursor = 0usize;
+
+        let pattern = codec::read_string(bytes, &mut cursor)?;
+
+        let normalized = match bytes.get(cursor).copied() {
+            Some(1) => { cursor += 1; Some(codec::read_string(bytes, &mut cursor)?) }
+            Some(0) => { cursor += 1; None }
+            _ => return Err(Error::new(ErrorKind::UnexpectedEof, "truncated signature record")),
+        };
+
+        let feature = codec::read_bytes(bytes, &mut cursor)?;
+
+        let entropy = match bytes.get(cursor).copied() {
+            Some(1) => {
+                cursor += 1;
+                let end = cursor + 8;
+                if end > bytes.len() { return Err(Error::new(ErrorKind::UnexpectedEof, "truncated entropy")); }
+                let value = f64::from_le_bytes(bytes[cursor..end].try_into().unwrap());
+                cursor = end;
+                Some(value)
+            }
+            Some(0) => { cursor += 1; None }
+            _ => return Err(Error::new(ErrorKind::UnexpectedEof, "truncated signature record")),
+        };
+
+        let sha256 = match bytes.get(cursor).copied() {
+            Some(1) => { cursor += 1; Some(Binary::to_hex(&codec::read_bytes(bytes, &mut cursor)?)) }
+            Some(0) => { cursor += 1; None }
+            _ => return Err(Error::new(ErrorKind::UnexpectedEof, "truncated signature record")),
+        };
+
+        let hash_count = codec::read_uvarint(bytes, &mut cursor)?;
+        let mut hashes = BTreeMap::new();
+        for _ in 0..hash_count {
+            let name = codec::read_string(bytes, &mut cursor)?;
+            let digest = Binary::to_hex(&codec::read_bytes(bytes, &mut cursor)?);
+            hashes.insert(name, digest);
+        }
+
+        Ok(SignatureJson {
+            pattern,
+            normalized,
+            feature,
+            entropy,
+            sha256,
+            hashes,
+        })
+    }
+
 }
@@ -0,0 +1,118 @@
+use std::io::{Error, ErrorKind};
+use serde::{Deserialize, Serialize};
+use serde_json;
+use std::collections::BTreeSet;
+
+/// Represents a JSON-serializable structure containing metadata about a function symbol.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SymbolJson {
+    /// Names associated with the function symbol.
+    pub names: BTreeSet<String>,
+    /// The virtual address of the function symbol.
+    pub address: u64,
+}
+
+/// Represents a structure containing metadata about a function symbol, keyed
+/// by address within a `GraphQueue`.
+///
+/// Unlike `formats::symbol::Symbol`, which a file format parses directly out
+/// of its own symbol table, this is the graph-side representation any
+/// recognizer pass (e.g. import thunk recognition) can insert into
+/// `GraphQueue::symbols` regardless of which format produced the name.
+#[derive(Clone)]
+pub struct Symbol {
+    /// Names associated with the function symbol.
+    pub names: BTreeSet<String>,
+    /// The virtual address of the function symbol.
+    pub address: u64,
+}
+
+impl Symbol {
+    /// Creates a new, nameless `Symbol` at `address`.
+    pub fn new(address: u64) -> Self {
+        Self {
+            names: BTreeSet::<String>::new(),
+            address,
+        }
+    }
+
+    /// Inserts many names for a symbol given a set of names.
+    pub fn insert_name_entend(&mut self, names: BTreeSet<String>) {
+        for name in names {
+            self.insert_name(name);
+        }
+    }
+
+    /// Inserts a single function name associated with the address, along with
+    /// its demangled form if `Symbols::demangle_with_scheme` recognizes `name`
+    /// as mangled (MSVC, Itanium, or either Rust scheme). Storing both forms
+    /// lets a function be matched by whichever one a caller has on hand,
+    /// without callers needing to demangle names themselves first.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `bool` indicating if `name` itself (the raw form) was newly inserted.
+    #[allow(dead_code)]
+    pub fn insert_name(&mut self, name: String) -> bool {
+        let (_, demangled) = crate::models::symbols::Symbols::demangle_with_scheme(&name);
+        let inserted = self.names.insert(name.clone());
+        if demangled != name {
+            self.names.insert(demangled);
+        }
+        inserted
+    }
+
+    /// Processes the symbol into its JSON-serializable representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `SymbolJson` struct containing metadata about the symbol.
+    pub fn process(&self) -> SymbolJson {
+        SymbolJson {
+            names: self.names.clone(),
+            address: self.address,
+        }
+    }
+
+    /// Converts the symbol metadata into a JSON string representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(String)` containing the JSON representation, or an `Err` if serialization fails.
+    #[allow(dead_code)]
+    pub fn json(&self) -> Result<String, Error> {
+        let raw = self.process();
+        let result = serde_json::to_string(&raw)?;
+        Ok(result)
+    }
+
+    /// Converts the symbol metadata into a CBOR-encoded byte representation.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(Vec<u8>)` containing the CBOR representation, or an `Err` if
+    /// serialization fails.
+    #[allow(dead_code)]
+    pub fn cbor(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        let mut result = Vec::<u8>::new();
+        ciborium::into_writer(&raw, &mut result)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        Ok(result)
+    }
+}
+
+impl SymbolJson {
+    /// Reconstructs a `SymbolJson` from the CBOR produced by `Symbol::cbor()`,
+    /// the inverse encoding of `cbor()`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(SymbolJson)` on success, or an `Err` if `data` isn't valid
+    /// CBOR for this shape.
+    #[allow(dead_code)]
+    pub fn from_cbor(data: &[u8]) -> Result<Self, Error> {
+        ciborium::from_reader(data)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Error, ErrorKind};
+use crate::models::binary::BinaryArchitecture;
+use crate::models::controlflow::graph::Graph;
+
+/// A pluggable instruction-decoding backend that lifts raw bytes into a `Graph`.
+///
+/// Each implementation owns whatever decoder state it needs (a Capstone handle,
+/// a bytecode opcode table, ...) and is architecture-specific; callers pick a
+/// concrete backend based on `binlex::models::binary::BinaryArchitecture`
+/// rather than the `Graph`/`GraphQueue` APIs knowing anything about how the
+/// bytes were decoded.
+pub trait DisassemblerBackend {
+    /// The `BinaryArchitecture`s this backend knows how to decode, so a
+    /// caller selecting a backend for a given architecture (e.g. from
+    /// `global::Mode`) doesn't have to hard-code which concrete backend
+    /// covers which ISA.
+    fn architectures(&self) -> &[BinaryArchitecture];
+
+    /// Returns `true` if this backend advertises support for `architecture`.
+    fn supports(&self, architecture: BinaryArchitecture) -> bool {
+        self.architectures().contains(&architecture)
+    }
+
+    /// Recursively disassembles the function starting at `address`, writing every
+    /// instruction it reaches into `graph` and returning the number of
+    /// instructions written.
+    fn disassemble_function(&self, address: u64, graph: &mut Graph) -> Result<u64, Error>;
+
+    /// Recursively disassembles the block starting at `address`, writing every
+    /// instruction in the block into `graph` and returning the number of
+    /// instructions written.
+    fn disassemble_block(&self, address: u64, graph: &mut Graph) -> Result<u64, Error>;
+
+    /// Disassembles every function and block reachable from `addresses`,
+    /// writing every instruction discovered into `graph`.
+    fn disassemble_control_flow(&self, addresses: BTreeSet<u64>, graph: &mut Graph) -> Result<(), Error>;
+
+    /// Performs a linear sweep over the backend's executable address ranges,
+    /// returning the set of addresses that look like function prologues.
+    fn disassemble_linear_pass(&self, valid_jump_threshold: usize, valid_instruction_threshold: usize) -> BTreeSet<u64>;
+}
+
+/// Builds the `DisassemblerBackend` that advertises support for
+/// `architecture`, so callers that only know "I'm analyzing a
+/// `BinaryArchitecture::ARM64` image" (e.g. `global::Mode`-driven selection)
+/// never have to name a concrete backend type themselves.
+///
+/// Each backend is gated behind its own cargo feature (`capstone`,
+/// `holeybytes`, ...) so a binary that cannot link the Capstone C library can
+/// still be built with only the pure-Rust backends compiled in; `capstone` is
+/// on by default.
+pub fn for_architecture(
+    architecture: BinaryArchitecture,
+    image: Vec<u8>,
+    executable_address_ranges: BTreeMap<u64, u64>,
+) -> Result<Box<dyn DisassemblerBackend>, Error> {
+    #[cfg(feature = "capstone")]
+    {
+        if matches!(
+            architecture,
+            BinaryArchitecture::AMD64 | BinaryArchitecture::I386 | BinaryArchitecture::ARM64
+        ) {
+            let backend = crate::models::disassemblers::capstone::disassembler::Disassembler::new(
+                architecture,
+                image,
+                executable_address_ranges,
+            )?;
+            return Ok(Box::new(backend));
+        }
+    }
+
+    #[cfg(feature = "holeybytes")]
+    {
+        if architecture == BinaryArchitecture::HOLEYBYTES {
+            return Ok(Box::new(crate::models::disassemblers::holeybytes::disassembler::Disassembler::new(
+                image,
+                executable_address_ranges,
+            )));
+        }
+    }
+
+    let _ = (&image, &executable_address_ranges);
+    Err(Error::new(ErrorKind::Other, format!("no disassembler backend compiled in for architecture 0x{:02x}", architecture as u16)))
+}
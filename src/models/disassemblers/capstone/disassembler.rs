@@ -0,0 +1,1465 @@
+extern crate capstone;
+
+use capstone::prelude::*;
+use capstone::arch::x86::{X86Insn, X86OperandType};
+use capstone::arch::x86::X86Reg::{X86_REG_RIP, X86_REG_RSP, X86_REG_RBP, X86_REG_ESP, X86_REG_EBP, X86_REG_EFLAGS};
+use capstone::arch::arm64::{Arm64Insn, Arm64OperandType, Arm64CC};
+use capstone::arch::{ArchDetail, ArchOperand};
+use capstone::{Insn, InsnId, InsnGroupId, InsnGroupType, RegId};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::io::{Error, ErrorKind};
+use crate::models::binary::{Binary, BinaryArchitecture};
+use crate::models::controlflow::graph::{Graph, GraphOptions, TrapReason};
+use crate::models::controlflow::instruction::Instruction;
+use crate::models::controlflow::instrs;
+use crate::models::disassemblers::backend::DisassemblerBackend;
+
+/// Returns `true` if `address` falls inside an instruction already decoded into `graph`.
+fn overlaps_instruction(graph: &Graph, address: u64) -> bool {
+    graph.instructions
+        .range(..address)
+        .next_back()
+        .map(|entry| entry.value().address + entry.value().size() as u64 > address)
+        .unwrap_or(false)
+}
+
+/// A coarse instruction category, derived from Capstone's own
+/// `insn_detail().groups()` rather than a hand-maintained `InsnId`
+/// allow-list, so a mnemonic Capstone already classifies (e.g. a new call
+/// or jump variant) is picked up automatically instead of silently falling
+/// through to `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category {
+    Trap,
+    Return,
+    Call,
+    UnconditionalJump,
+    ConditionalJump,
+    Privileged,
+    Other,
+}
+
+/// The SIMD/crypto instruction-set extension a vector instruction belongs
+/// to, tagged from its operand register widths and mnemonic rather than a
+/// 3-entry denylist of specific mnemonics (`MOVUPS`/`MOVAPS`/`XORPS`), so
+/// the rest of SSE/AVX/AVX-512 is covered without listing every mnemonic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IsaSet {
+    None,
+    Sse,
+    Avx,
+    Avx512,
+    Fma,
+    Aes,
+}
+
+/// An individual x86 RFLAGS bit, tracked separately from Capstone's combined
+/// `EFLAGS` pseudo-register so `Disassembler::instruction_liveness` can tell
+/// a flag that's still needed from one that isn't.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Flag {
+    Cf,
+    Pf,
+    Af,
+    Zf,
+    Sf,
+    Of,
+}
+
+/// A register or flag an instruction reads or writes, as tracked by
+/// `Disassembler::instruction_liveness`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LiveValue {
+    Register(RegId),
+    Flag(Flag),
+}
+
+/// The live-in/live-out sets `Disassembler::instruction_liveness` computes
+/// for one instruction in a block: `live_out` is what's still needed by the
+/// rest of the block (or is live across its terminator), `live_in` is that
+/// plus whatever this instruction itself uses.
+#[derive(Debug, Clone, Default)]
+pub struct LiveSet {
+    pub live_in: HashSet<LiveValue>,
+    pub live_out: HashSet<LiveValue>,
+}
+
+const ALL_FLAGS: [Flag; 6] = [Flag::Cf, Flag::Pf, Flag::Af, Flag::Zf, Flag::Sf, Flag::Of];
+
+/// The individual RFLAGS bits a handful of common x86 instruction classes
+/// define and use, since Capstone only reports `EFLAGS` as a single combined
+/// register rather than per-bit detail. Anything Capstone reports as
+/// touching `EFLAGS` that isn't one of these known classes (`Jcc`, `SETcc`,
+/// `CMOVcc`, and the like) is treated as reading and writing every flag --
+/// conservative, since guessing the wrong condition code's flag subset would
+/// make `instruction_liveness` wildcard a flag that's actually still live.
+fn x86_flags_def_use(cs: &Capstone, instruction: &Insn) -> (Vec<Flag>, Vec<Flag>) {
+    const ARITHMETIC: [InsnId; 8] = [
+        InsnId(X86Insn::X86_INS_ADD as u32),
+        InsnId(X86Insn::X86_INS_SUB as u32),
+        InsnId(X86Insn::X86_INS_AND as u32),
+        InsnId(X86Insn::X86_INS_OR as u32),
+        InsnId(X86Insn::X86_INS_XOR as u32),
+        InsnId(X86Insn::X86_INS_NEG as u32),
+        InsnId(X86Insn::X86_INS_CMP as u32),
+        InsnId(X86Insn::X86_INS_TEST as u32),
+    ];
+    const ARITHMETIC_WITH_CARRY_IN: [InsnId; 2] = [
+        InsnId(X86Insn::X86_INS_ADC as u32),
+        InsnId(X86Insn::X86_INS_SBB as u32),
+    ];
+    const NO_CARRY_OUT: [InsnId; 2] = [
+        InsnId(X86Insn::X86_INS_INC as u32),
+        InsnId(X86Insn::X86_INS_DEC as u32),
+    ];
+
+    if ARITHMETIC.contains(&instruction.id()) {
+        return (ALL_FLAGS.to_vec(), Vec::new());
+    }
+    if ARITHMETIC_WITH_CARRY_IN.contains(&instruction.id()) {
+        return (ALL_FLAGS.to_vec(), vec![Flag::Cf]);
+    }
+    if NO_CARRY_OUT.contains(&instruction.id()) {
+        return (vec![Flag::Pf, Flag::Af, Flag::Zf, Flag::Sf, Flag::Of], Vec::new());
+    }
+
+    let touches_eflags = match cs.insn_detail(instruction) {
+        Ok(detail) => detail
+            .regs_read()
+            .chain(detail.regs_write())
+            .any(|register| register == RegId(X86_REG_EFLAGS as u16)),
+        Err(_) => false,
+    };
+    if touches_eflags {
+        return (ALL_FLAGS.to_vec(), ALL_FLAGS.to_vec());
+    }
+
+    (Vec::new(), Vec::new())
+}
+
+/// One decode site found by `Disassembler::disassemble_superset_pass`: its
+/// length and the addresses control flow can continue to from here
+/// (fallthrough, plus any resolved branch target).
+struct SupersetCandidate {
+    length: u64,
+    successors: BTreeSet<u64>,
+    call_target: Option<u64>,
+    terminal: bool,
+}
+
+/// A `DisassemblerBackend` that decodes instructions with Capstone.
+///
+/// This is the original binlex backend, now writing directly into a `Graph`
+/// instead of the bespoke `CFG`/`Function`/`Block` types it used before the
+/// `Graph`-based control flow model existed, so it can sit behind
+/// `DisassemblerBackend` alongside other architectures.
+pub struct Disassembler {
+    cs: Capstone,
+    image: Vec<u8>,
+    machine: BinaryArchitecture,
+    executable_address_ranges: BTreeMap<u64, u64>,
+}
+
+impl Disassembler {
+    pub fn new(machine: BinaryArchitecture, image: Vec<u8>, executable_address_ranges: BTreeMap<u64, u64>) -> Result<Self, Error> {
+        let cs = Disassembler::cs_new(machine, true)?;
+        Ok(Self {
+            cs,
+            image,
+            machine,
+            executable_address_ranges,
+        })
+    }
+
+    pub fn is_executable_address(&self, address: u64) -> bool {
+        self.executable_address_ranges
+            .iter()
+            .any(|(start, end)| address >= *start && address <= *end)
+    }
+
+    fn cs_new(machine: BinaryArchitecture, detail: bool) -> Result<Capstone, Error> {
+        match machine {
+            BinaryArchitecture::AMD64 => {
+                Capstone::new()
+                    .x86()
+                    .mode(arch::x86::ArchMode::Mode64)
+                    .syntax(arch::x86::ArchSyntax::Intel)
+                    .detail(detail)
+                    .build()
+                    .map_err(|error| Error::new(ErrorKind::Other, format!("capstone error: {:?}", error)))
+            },
+            BinaryArchitecture::I386 => {
+                Capstone::new()
+                    .x86()
+                    .mode(arch::x86::ArchMode::Mode32)
+                    .syntax(arch::x86::ArchSyntax::Intel)
+                    .detail(detail)
+                    .build()
+                    .map_err(|error| Error::new(ErrorKind::Other, format!("capstone error: {:?}", error)))
+            },
+            BinaryArchitecture::ARM64 => {
+                Capstone::new()
+                    .arm64()
+                    .mode(arch::arm64::ArchMode::Arm)
+                    .detail(detail)
+                    .build()
+                    .map_err(|error| Error::new(ErrorKind::Other, format!("capstone error: {:?}", error)))
+            },
+            _ => Err(Error::new(ErrorKind::Other, "unsupported architecture")),
+        }
+    }
+
+    fn disassemble_instruction(&self, address: u64) -> Result<Insn, Error> {
+        if address as usize >= self.image.len() {
+            return Err(Error::new(ErrorKind::Other, format!("Instruction -> 0x{:x}: is out of bounds", address)));
+        }
+        let instructions = self.cs
+            .disasm_count(&self.image[address as usize..], address, 1)
+            .map_err(|_| Error::new(ErrorKind::Other, format!("Instruction -> 0x{:x}: failed to disassemble", address)))?;
+        instructions.iter().next().cloned().ok_or_else(|| {
+            Error::new(ErrorKind::Other, format!("Instruction -> 0x{:x}: no instruction decoded", address))
+        })
+    }
+
+    fn get_operands(&self, instruction: &Insn) -> Vec<ArchOperand> {
+        match self.cs.insn_detail(instruction) {
+            Ok(detail) => detail.arch_detail().operands(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    fn get_operand_immutable(&self, instruction: &Insn, index: usize) -> Option<u64> {
+        let operands = self.get_operands(instruction);
+        let operand = operands.get(index)?;
+        match operand {
+            ArchOperand::X86Operand(op) => {
+                if let X86OperandType::Imm(imm) = op.op_type {
+                    return Some(imm as u64);
+                }
+                None
+            },
+            ArchOperand::Arm64Operand(op) => {
+                if let Arm64OperandType::Imm(imm) = op.op_type {
+                    return Some(imm as u64);
+                }
+                None
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the index of the operand carrying `instruction`'s branch target.
+    ///
+    /// x86 call/jump instructions always encode the target as their first operand.
+    /// AArch64 `CBZ`/`CBNZ`/`TBZ`/`TBNZ` encode it as their last operand, after the
+    /// register (and, for `TBZ`/`TBNZ`, the bit position) being tested.
+    fn branch_operand_index(&self, instruction: &Insn) -> usize {
+        if self.machine == BinaryArchitecture::ARM64 {
+            let operand_count = self.get_operands(instruction).len();
+            if operand_count > 0 {
+                return operand_count - 1;
+            }
+        }
+        0
+    }
+
+    /// Returns the AArch64 condition code `instruction` executes under, if any.
+    fn arm64_condition_code(&self, instruction: &Insn) -> Option<Arm64CC> {
+        let detail = self.cs.insn_detail(instruction).ok()?;
+        match detail.arch_detail() {
+            ArchDetail::Arm64Detail(arm64_detail) => Some(arm64_detail.cc()),
+            _ => None,
+        }
+    }
+
+    /// `true` if `instruction` carries Capstone's own `group` in its detail,
+    /// the generic mechanism `instruction_category`/`is_call_instruction`/
+    /// `is_return_instruction`/`is_*_jump_instruction` build on instead of a
+    /// hand-maintained `InsnId` allow-list, so any mnemonic Capstone itself
+    /// already classifies into that group is covered automatically.
+    fn instruction_has_group(&self, instruction: &Insn, group: InsnGroupType) -> bool {
+        match self.cs.insn_detail(instruction) {
+            Ok(detail) => detail.groups().iter().any(|id| *id == InsnGroupId(group as u8)),
+            Err(_) => false,
+        }
+    }
+
+    fn is_trap_instruction(&self, instruction: &Insn) -> bool {
+        if self.machine == BinaryArchitecture::ARM64 {
+            return [
+                InsnId(Arm64Insn::ARM64_INS_BRK as u32),
+                InsnId(Arm64Insn::ARM64_INS_UDF as u32),
+                InsnId(Arm64Insn::ARM64_INS_HLT as u32),
+            ].contains(&instruction.id());
+        }
+        [
+            InsnId(X86Insn::X86_INS_INT3 as u32),
+            InsnId(X86Insn::X86_INS_UD2 as u32),
+            InsnId(X86Insn::X86_INS_INT1 as u32),
+            InsnId(X86Insn::X86_INS_INTO as u32),
+            InsnId(X86Insn::X86_INS_HLT as u32),
+        ].contains(&instruction.id())
+    }
+
+    /// `true` if `instruction` requires a privilege level the disassembled
+    /// image wouldn't run under (e.g. `HLT`, `IN`/`OUT`, `LGDT`).
+    #[allow(dead_code)]
+    fn is_privilege_instruction(&self, instruction: &Insn) -> bool {
+        self.instruction_has_group(instruction, InsnGroupType::CS_GRP_PRIVILEGE)
+    }
+
+    fn is_return_instruction(&self, instruction: &Insn) -> bool {
+        self.instruction_has_group(instruction, InsnGroupType::CS_GRP_RET)
+            || self.instruction_has_group(instruction, InsnGroupType::CS_GRP_IRET)
+    }
+
+    fn is_call_instruction(&self, instruction: &Insn) -> bool {
+        self.instruction_has_group(instruction, InsnGroupType::CS_GRP_CALL)
+    }
+
+    fn is_unconditional_jump_instruction(&self, instruction: &Insn) -> bool {
+        self.instruction_has_group(instruction, InsnGroupType::CS_GRP_JUMP)
+            && !self.is_conditional_jump_instruction(instruction)
+    }
+
+    /// `true` if `instruction` is a jump (`CS_GRP_JUMP`) that doesn't always
+    /// take its branch. Which specific mnemonics are conditional isn't
+    /// something Capstone's groups expose on their own, so this still needs
+    /// per-architecture refinement: AArch64 `CBZ`/`CBNZ`/`TBZ`/`TBNZ` are
+    /// always conditional, and a conditionally-coded `B` depends on its
+    /// condition code; x86 has exactly one unconditional jump mnemonic
+    /// (`JMP`/`LJMP`), so any other `CS_GRP_JUMP` instruction -- including
+    /// ones not in any hand-written list -- is conditional by elimination.
+    fn is_conditional_jump_instruction(&self, instruction: &Insn) -> bool {
+        if self.machine == BinaryArchitecture::ARM64 {
+            if !self.instruction_has_group(instruction, InsnGroupType::CS_GRP_JUMP) {
+                return false;
+            }
+            if [
+                InsnId(Arm64Insn::ARM64_INS_CBZ as u32),
+                InsnId(Arm64Insn::ARM64_INS_CBNZ as u32),
+                InsnId(Arm64Insn::ARM64_INS_TBZ as u32),
+                InsnId(Arm64Insn::ARM64_INS_TBNZ as u32),
+            ].contains(&instruction.id()) {
+                return true;
+            }
+            if InsnId(Arm64Insn::ARM64_INS_B as u32) == instruction.id() {
+                return matches!(
+                    self.arm64_condition_code(instruction),
+                    Some(cc) if cc != Arm64CC::ARM64_CC_INVALID && cc != Arm64CC::ARM64_CC_AL
+                );
+            }
+            return false;
+        }
+        self.instruction_has_group(instruction, InsnGroupType::CS_GRP_JUMP)
+            && instruction.id() != InsnId(X86Insn::X86_INS_JMP as u32)
+            && instruction.id() != InsnId(X86Insn::X86_INS_LJMP as u32)
+    }
+
+    /// Classifies `instruction` into a `Category`, deferring to
+    /// `is_trap_instruction`/`is_privilege_instruction`/the call/return/jump
+    /// predicates above.
+    #[allow(dead_code)]
+    pub fn instruction_category(&self, instruction: &Insn) -> Category {
+        if self.is_trap_instruction(instruction) {
+            return Category::Trap;
+        }
+        if self.is_return_instruction(instruction) {
+            return Category::Return;
+        }
+        if self.is_call_instruction(instruction) {
+            return Category::Call;
+        }
+        if self.is_conditional_jump_instruction(instruction) {
+            return Category::ConditionalJump;
+        }
+        if self.is_unconditional_jump_instruction(instruction) {
+            return Category::UnconditionalJump;
+        }
+        if self.is_privilege_instruction(instruction) {
+            return Category::Privileged;
+        }
+        Category::Other
+    }
+
+    /// The name Capstone reports for a register operand, lowercased, or
+    /// `""` if `instruction` doesn't have one at `index`.
+    fn operand_register_name(&self, instruction: &Insn, index: usize) -> String {
+        let operands = self.get_operands(instruction);
+        let reg_id = match operands.get(index) {
+            Some(ArchOperand::X86Operand(op)) => match op.op_type {
+                X86OperandType::Reg(reg_id) => reg_id,
+                _ => return String::new(),
+            },
+            _ => return String::new(),
+        };
+        self.cs.reg_name(reg_id).unwrap_or_default().to_ascii_lowercase()
+    }
+
+    /// Tags `instruction` with the SIMD/crypto instruction-set extension its
+    /// widest vector register operand or mnemonic implies: `zmm` operands
+    /// mean AVX-512, `ymm` operands mean AVX, and an `xmm` operand means AVX
+    /// (VEX-encoded, mnemonic starts with `v`) or plain SSE otherwise.
+    /// `vfmadd`/`vfmsub`-family and `aes`-family mnemonics are called out
+    /// ahead of the width check since they're a distinct extension rather
+    /// than "wider SSE". x86-only; every other architecture reports `None`.
+    pub fn instruction_isa_set(&self, instruction: &Insn) -> IsaSet {
+        if self.machine != BinaryArchitecture::AMD64 && self.machine != BinaryArchitecture::I386 {
+            return IsaSet::None;
+        }
+
+        let mnemonic = instruction.mnemonic().unwrap_or_default().to_ascii_lowercase();
+        if mnemonic.starts_with("vfmadd") || mnemonic.starts_with("vfmsub")
+            || mnemonic.starts_with("vfnmadd") || mnemonic.starts_with("vfnmsub") {
+            return IsaSet::Fma;
+        }
+        if mnemonic.contains("aes") {
+            return IsaSet::Aes;
+        }
+
+        let operands = self.get_operands(instruction);
+        let is_vector_register = |name: &str| {
+            name.starts_with("xmm") || name.starts_with("ymm") || name.starts_with("zmm")
+        };
+        let widest = (0..operands.len())
+            .map(|index| self.operand_register_name(instruction, index))
+            .filter(|name| is_vector_register(name))
+            .max_by_key(|name| ["xmm", "ymm", "zmm"].iter().position(|prefix| name.starts_with(prefix)));
+
+        match widest {
+            Some(name) if name.starts_with("zmm") => IsaSet::Avx512,
+            Some(name) if name.starts_with("ymm") => IsaSet::Avx,
+            Some(name) if name.starts_with("xmm") => {
+                if mnemonic.starts_with('v') { IsaSet::Avx } else { IsaSet::Sse }
+            },
+            _ => IsaSet::None,
+        }
+    }
+
+    /// `true` if `instruction` belongs to a vector ISA set that signature
+    /// generation doesn't model the operand encoding of yet, so callers
+    /// building a byte-pattern signature should wildcard the whole
+    /// instruction rather than emit its raw bytes.
+    fn is_unsupported_signature_instruction(&self, instruction: &Insn) -> bool {
+        self.instruction_isa_set(instruction) != IsaSet::None
+    }
+
+    /// Finds function pointers `instruction` loads via a position-independent
+    /// code convention, generalized per architecture: x86-64 has no absolute
+    /// addressing mode, so PIC code loads a callback/vtable/GOT entry with a
+    /// RIP-relative `lea`; AArch64 has no RIP-relative addressing at all, so
+    /// the same pattern is split across an `adrp` (page base) and a
+    /// following `add` (page offset) sharing a destination register.
+    fn instruction_executable_addresses(&self, instruction: &Insn) -> BTreeSet<u64> {
+        match self.machine {
+            BinaryArchitecture::AMD64 => self.x86_rip_relative_addresses(instruction),
+            BinaryArchitecture::ARM64 => self.arm64_adrp_add_addresses(instruction),
+            _ => BTreeSet::new(),
+        }
+    }
+
+    fn x86_rip_relative_addresses(&self, instruction: &Insn) -> BTreeSet<u64> {
+        let mut result = BTreeSet::new();
+        for operand in self.get_operands(instruction) {
+            if let ArchOperand::X86Operand(op) = operand {
+                if let X86OperandType::Mem(mem) = op.op_type {
+                    if mem.base() != RegId(X86_REG_RIP as u16) || mem.index() != RegId(0) {
+                        continue;
+                    }
+                    let address = (instruction.address() as i64 + mem.disp() + instruction.bytes().len() as i64) as u64;
+                    if self.is_executable_address(address) {
+                        result.insert(address);
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn arm64_adrp_add_addresses(&self, instruction: &Insn) -> BTreeSet<u64> {
+        let mut result = BTreeSet::new();
+
+        if instruction.id() != InsnId(Arm64Insn::ARM64_INS_ADRP as u32) {
+            return result;
+        }
+
+        let operands = self.get_operands(instruction);
+        let destination = match operands.first() {
+            Some(ArchOperand::Arm64Operand(op)) => match op.op_type {
+                Arm64OperandType::Reg(reg) => reg,
+                _ => return result,
+            },
+            _ => return result,
+        };
+        let page_base = match operands.get(1) {
+            Some(ArchOperand::Arm64Operand(op)) => match op.op_type {
+                Arm64OperandType::Imm(imm) => imm,
+                _ => return result,
+            },
+            _ => return result,
+        };
+
+        let next_address = instruction.address() + instruction.bytes().len() as u64;
+        let next = match self.disassemble_instruction(next_address) {
+            Ok(next) => next,
+            Err(_) => return result,
+        };
+        if next.id() != InsnId(Arm64Insn::ARM64_INS_ADD as u32) {
+            return result;
+        }
+
+        let next_operands = self.get_operands(&next);
+        let same_destination = matches!(
+            next_operands.first(),
+            Some(ArchOperand::Arm64Operand(op)) if matches!(op.op_type, Arm64OperandType::Reg(reg) if reg == destination)
+        );
+        if !same_destination {
+            return result;
+        }
+
+        let page_offset = match next_operands.get(2) {
+            Some(ArchOperand::Arm64Operand(op)) => match op.op_type {
+                Arm64OperandType::Imm(imm) => imm,
+                _ => return result,
+            },
+            _ => return result,
+        };
+
+        let address = (page_base + page_offset) as u64;
+        if self.is_executable_address(address) {
+            result.insert(address);
+        }
+        result
+    }
+
+    fn read_image_pointer(&self, address: u64, size: usize) -> Option<u64> {
+        let start = address as usize;
+        let bytes = self.image.get(start..start.checked_add(size)?)?;
+        match size {
+            4 => Some(u32::from_le_bytes(bytes.try_into().ok()?) as u64),
+            8 => Some(u64::from_le_bytes(bytes.try_into().ok()?)),
+            _ => None,
+        }
+    }
+
+    fn read_image_i32(&self, address: u64) -> Option<i32> {
+        let start = address as usize;
+        let bytes = self.image.get(start..start.checked_add(4)?)?;
+        Some(i32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Recovers `jmp [base + index*scale]`-style jump-table targets that
+    /// `get_operand_immutable` can't resolve, since the jump's target comes
+    /// from memory rather than an immediate operand.
+    ///
+    /// Reads consecutive pointer-sized entries out of `self.image` starting
+    /// at the memory operand's displacement (the table base), accepting both
+    /// a plain table of absolute pointers (4 bytes for I386, 8 for AMD64) and
+    /// a "base+offset" table of 4-byte relative displacements added back to
+    /// the table base, as PIC compilers emit for `.rodata`-relative switch
+    /// tables. Stops at the first entry that isn't an executable address or
+    /// once `max_entries` is reached, so a misidentified or unbounded table
+    /// can't run away.
+    fn resolve_jump_table(&self, instruction: &Insn, max_entries: usize) -> BTreeSet<u64> {
+        let mut targets = BTreeSet::new();
+
+        if self.machine != BinaryArchitecture::AMD64 && self.machine != BinaryArchitecture::I386 {
+            return targets;
+        }
+
+        let mem = self.get_operands(instruction).into_iter().find_map(|operand| match operand {
+            ArchOperand::X86Operand(op) => match op.op_type {
+                X86OperandType::Mem(mem) if mem.index() != RegId(0) => Some(mem),
+                _ => None,
+            },
+            _ => None,
+        });
+
+        let mem = match mem {
+            Some(mem) => mem,
+            None => return targets,
+        };
+
+        let disp = mem.disp();
+        if disp < 0 {
+            return targets;
+        }
+        let table_base = disp as u64;
+        if !self.is_executable_address(table_base) {
+            return targets;
+        }
+
+        let entry_size: u64 = if self.machine == BinaryArchitecture::AMD64 { 8 } else { 4 };
+
+        for i in 0..max_entries as u64 {
+            let entry_address = table_base + i * entry_size;
+
+            let direct = self.read_image_pointer(entry_address, entry_size as usize)
+                .filter(|&address| address != 0 && self.is_executable_address(address));
+
+            let relative = self.read_image_i32(entry_address)
+                .map(|offset| (table_base as i64 + offset as i64) as u64)
+                .filter(|&address| self.is_executable_address(address));
+
+            match direct.or(relative) {
+                Some(address) => { targets.insert(address); },
+                None => break,
+            }
+        }
+
+        targets
+    }
+
+    /// `true` if `instruction` has an immediate operand, x86-only.
+    fn instruction_contains_immutable_operand(&self, instruction: &Insn) -> bool {
+        self.get_operands(instruction).iter().any(|operand| {
+            matches!(operand, ArchOperand::X86Operand(op) if matches!(op.op_type, X86OperandType::Imm(_)))
+        })
+    }
+
+    /// `true` if `instruction` has a memory operand, x86-only.
+    fn instruction_contains_memory_operand(&self, instruction: &Insn) -> bool {
+        self.get_operands(instruction).iter().any(|operand| {
+            matches!(operand, ArchOperand::X86Operand(op) if matches!(op.op_type, X86OperandType::Mem(_)))
+        })
+    }
+
+    /// `true` if `instruction`'s immediate operand should be wildcarded
+    /// rather than kept concrete when building a signature: always for a
+    /// call/jump target (address-dependent by definition), and otherwise
+    /// when `live` shows the register this instruction writes is dead
+    /// immediately afterward -- a write nothing downstream reads is as
+    /// layout-dependent-and-uninteresting as a stack adjustment, without
+    /// hand-listing `mov`/`sub`/`add`/`inc`/`dec` against `rsp`/`rbp`
+    /// specifically. x86-only.
+    fn is_immutable_instruction_to_signature(&self, instruction: &Insn, live: Option<&LiveSet>) -> bool {
+        if !self.instruction_contains_immutable_operand(instruction) {
+            return false;
+        }
+        if self.is_call_instruction(instruction)
+            || self.is_unconditional_jump_instruction(instruction)
+            || self.is_conditional_jump_instruction(instruction) {
+            return true;
+        }
+        let live = match live {
+            Some(live) => live,
+            None => return false,
+        };
+        let destination = match self.get_operands(instruction).first() {
+            Some(ArchOperand::X86Operand(op)) => match op.op_type {
+                X86OperandType::Reg(reg_id) => reg_id,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        !live.live_out.contains(&LiveValue::Register(destination))
+    }
+
+    /// The registers and individual RFLAGS bits `instruction` defines
+    /// (writes) and uses (reads), combining Capstone's own
+    /// `regs_write()`/`regs_read()` with `x86_flags_def_use`'s
+    /// mnemonic-specific flag modeling on x86 (other architectures get only
+    /// the register-level detail Capstone reports).
+    fn instruction_def_use(&self, instruction: &Insn) -> (HashSet<LiveValue>, HashSet<LiveValue>) {
+        let mut defs = HashSet::new();
+        let mut uses = HashSet::new();
+
+        if let Ok(detail) = self.cs.insn_detail(instruction) {
+            for register in detail.regs_write() {
+                defs.insert(LiveValue::Register(register));
+            }
+            for register in detail.regs_read() {
+                uses.insert(LiveValue::Register(register));
+            }
+        }
+
+        if self.machine == BinaryArchitecture::AMD64 || self.machine == BinaryArchitecture::I386 {
+            let (flag_defs, flag_uses) = x86_flags_def_use(&self.cs, instruction);
+            defs.extend(flag_defs.into_iter().map(LiveValue::Flag));
+            uses.extend(flag_uses.into_iter().map(LiveValue::Flag));
+        }
+
+        (defs, uses)
+    }
+
+    /// Computes per-instruction register/flag liveness for the block
+    /// starting at `block_start`, via the classic backward dataflow pass:
+    /// `live_out[i] = live_in[i+1]`, `live_in[i] = uses[i] ∪ (live_out[i] \
+    /// defs[i])`. Decodes the block the same way `disassemble_block` does
+    /// (stopping at the first trap/return/jump terminator; a call does not
+    /// end the block), so the
+    /// result lines up one-to-one with the instructions `disassemble_block`
+    /// will itself visit.
+    #[allow(dead_code)]
+    pub fn instruction_liveness(&self, block_start: u64) -> Vec<LiveSet> {
+        let mut def_use = Vec::new();
+        let mut pc = block_start;
+
+        loop {
+            let instruction = match self.disassemble_instruction(pc) {
+                Ok(instruction) => instruction,
+                Err(_) => break,
+            };
+
+            let is_terminator = self.is_trap_instruction(&instruction)
+                || self.is_return_instruction(&instruction)
+                || self.is_unconditional_jump_instruction(&instruction)
+                || self.is_conditional_jump_instruction(&instruction);
+
+            def_use.push(self.instruction_def_use(&instruction));
+            pc += instruction.bytes().len() as u64;
+
+            if is_terminator {
+                break;
+            }
+        }
+
+        let mut live_sets = vec![LiveSet::default(); def_use.len()];
+        let mut live_out = HashSet::<LiveValue>::new();
+
+        for index in (0..def_use.len()).rev() {
+            let (defs, uses) = &def_use[index];
+            let live_in: HashSet<LiveValue> = uses
+                .iter()
+                .cloned()
+                .chain(live_out.iter().filter(|value| !defs.contains(value)).cloned())
+                .collect();
+            live_sets[index] = LiveSet { live_in: live_in.clone(), live_out: live_out.clone() };
+            live_out = live_in;
+        }
+
+        live_sets
+    }
+
+    /// The number of bytes Capstone's x86 encoder spends on a displacement
+    /// of `displacement`: `0` for no displacement, `1` for one that fits in
+    /// a signed byte, `4` otherwise (x86 has no 2-byte displacement form).
+    fn displacement_size(displacement: u64) -> usize {
+        if displacement == 0 {
+            0
+        } else if (displacement as i64) >= i8::MIN as i64 && (displacement as i64) <= i8::MAX as i64 {
+            1
+        } else {
+            4
+        }
+    }
+
+    /// Builds a hex-with-`?`-wildcard signature pattern for `instruction`,
+    /// masking the bytes its immediate/displacement operands occupy so that
+    /// two otherwise-identical instructions referencing different addresses,
+    /// offsets, or relocation-dependent constants produce the same pattern.
+    ///
+    /// Traps and `nop`s are wildcarded in full, since padding/filler bytes
+    /// carry no signal. Instructions with neither an immediate nor a memory
+    /// operand are returned unmasked, since there's nothing operand-encoded
+    /// to wildcard. x86-only (AMD64/I386); every other architecture falls
+    /// back to the unmasked hex of `instruction`'s bytes. `live`, when
+    /// available, is this instruction's entry from `instruction_liveness`,
+    /// used to additionally wildcard an immediate whose destination
+    /// register is dead.
+    fn wildcard_mask(&self, instruction: &Insn, live: Option<&LiveSet>) -> String {
+        if self.machine != BinaryArchitecture::AMD64 && self.machine != BinaryArchitecture::I386 {
+            return Binary::to_hex(instruction.bytes());
+        }
+
+        if self.is_trap_instruction(instruction) || InsnId(X86Insn::X86_INS_NOP as u32) == instruction.id() {
+            return "?".repeat(instruction.bytes().len() * 2);
+        }
+
+        if self.is_unsupported_signature_instruction(instruction) {
+            return "?".repeat(instruction.bytes().len() * 2);
+        }
+
+        if !self.instruction_contains_immutable_operand(instruction)
+            && !self.instruction_contains_memory_operand(instruction) {
+            return Binary::to_hex(instruction.bytes());
+        }
+
+        let instruction_size = instruction.bytes().len() * 8;
+        let mut wildcarded = vec![false; instruction_size];
+        let is_immutable_signature = self.is_immutable_instruction_to_signature(instruction, live);
+
+        for operand in self.get_operands(instruction) {
+            if let ArchOperand::X86Operand(op) = operand {
+                let should_wildcard = match op.op_type {
+                    X86OperandType::Imm(_) => is_immutable_signature,
+                    X86OperandType::Mem(mem) => mem.index() == RegId(0),
+                    _ => false,
+                };
+
+                let displacement_size = match op.op_type {
+                    X86OperandType::Mem(op_mem) => Self::displacement_size(op_mem.disp() as u64) * 8,
+                    _ => 0,
+                };
+
+                let op_size = (op.size as usize * 8).max(displacement_size).min(instruction_size);
+                let operand_offset = instruction_size - op_size;
+
+                if should_wildcard {
+                    for i in 0..op_size {
+                        if operand_offset + i < wildcarded.len() {
+                            wildcarded[operand_offset + i] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        let instruction_hex = Binary::to_hex(instruction.bytes());
+        instruction_hex
+            .chars()
+            .enumerate()
+            .map(|(index, ch)| {
+                let start = index * 4;
+                let end = start + 4;
+                if wildcarded[start..end].iter().all(|&x| x) { '?' } else { ch }
+            })
+            .collect()
+    }
+
+    /// Updates `registers`, a per-block map from register id to known
+    /// constant value, for `mov reg,imm`, `mov reg,[abs]` (reading the
+    /// pointer-sized value at the absolute address straight out of
+    /// `self.image`, when that address is itself a known executable
+    /// address), `lea reg,[rip+disp]` (using the instruction's end address
+    /// as the RIP base), `add reg,imm`/`sub reg,imm` (adjusting an
+    /// already-known value), and `xor reg,reg` (-> 0), invalidating the
+    /// destination register on any other write to it. Deliberately
+    /// intra-block only (`registers` is fresh per block/scan) so this stays
+    /// a cheap, sound approximation rather than a real dataflow pass.
+    /// x86-only; only called when
+    /// `GraphOptions::enable_register_constant_propagation` is set.
+    fn track_register_constant(&self, instruction: &Insn, registers: &mut HashMap<RegId, u64>) {
+        let operands = self.get_operands(instruction);
+        let destination = match operands.first() {
+            Some(ArchOperand::X86Operand(op)) => match op.op_type {
+                X86OperandType::Reg(reg_id) => reg_id,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        if instruction.id() == InsnId(X86Insn::X86_INS_MOV as u32) {
+            if let Some(ArchOperand::X86Operand(op)) = operands.get(1) {
+                match op.op_type {
+                    X86OperandType::Imm(imm) => {
+                        registers.insert(destination, imm as u64);
+                        return;
+                    },
+                    X86OperandType::Mem(mem) if mem.base() == RegId(0) && mem.index() == RegId(0) => {
+                        let address = mem.disp() as u64;
+                        if self.is_executable_address(address) {
+                            let pointer_size = if self.machine == BinaryArchitecture::AMD64 { 8 } else { 4 };
+                            if let Some(value) = self.read_image_pointer(address, pointer_size) {
+                                registers.insert(destination, value);
+                                return;
+                            }
+                        }
+                    },
+                    _ => {},
+                }
+            }
+            registers.remove(&destination);
+            return;
+        }
+
+        if instruction.id() == InsnId(X86Insn::X86_INS_LEA as u32) {
+            if let Some(ArchOperand::X86Operand(op)) = operands.get(1) {
+                if let X86OperandType::Mem(mem) = op.op_type {
+                    if mem.base() == RegId(X86_REG_RIP as u16) && mem.index() == RegId(0) {
+                        let address = (instruction.address() as i64 + mem.disp() + instruction.bytes().len() as i64) as u64;
+                        registers.insert(destination, address);
+                        return;
+                    }
+                }
+            }
+            registers.remove(&destination);
+            return;
+        }
+
+        if instruction.id() == InsnId(X86Insn::X86_INS_XOR as u32) {
+            if let Some(ArchOperand::X86Operand(op)) = operands.get(1) {
+                if let X86OperandType::Reg(source) = op.op_type {
+                    if source == destination {
+                        registers.insert(destination, 0);
+                        return;
+                    }
+                }
+            }
+            registers.remove(&destination);
+            return;
+        }
+
+        if instruction.id() == InsnId(X86Insn::X86_INS_ADD as u32) || instruction.id() == InsnId(X86Insn::X86_INS_SUB as u32) {
+            if let Some(known) = registers.get(&destination).copied() {
+                if let Some(ArchOperand::X86Operand(op)) = operands.get(1) {
+                    if let X86OperandType::Imm(imm) = op.op_type {
+                        let value = if instruction.id() == InsnId(X86Insn::X86_INS_ADD as u32) {
+                            known.wrapping_add(imm as u64)
+                        } else {
+                            known.wrapping_sub(imm as u64)
+                        };
+                        registers.insert(destination, value);
+                        return;
+                    }
+                }
+            }
+            registers.remove(&destination);
+            return;
+        }
+
+        registers.remove(&destination);
+    }
+
+    /// Resolves `branch_insn`'s (`call`/`jmp`) register or memory operand
+    /// against `registers`, returning the target only if it falls inside
+    /// `executable_address_ranges`. A register operand's known value is the
+    /// target directly; a memory operand with no index (a scaled/indexed
+    /// target isn't modeled) is treated as a pointer slot, so the value read
+    /// through `self.image` at the resolved address is the target.
+    fn resolve_indirect_operand(&self, branch_insn: &Insn, registers: &HashMap<RegId, u64>) -> Option<u64> {
+        let value = match self.get_operands(branch_insn).first()? {
+            ArchOperand::X86Operand(op) => match op.op_type {
+                X86OperandType::Reg(reg_id) => *registers.get(&reg_id)?,
+                X86OperandType::Mem(mem) if mem.index() == RegId(0) => {
+                    let base = if mem.base() == RegId(0) { 0 } else { *registers.get(&mem.base())? };
+                    let address = (base as i64 + mem.disp()) as u64;
+                    let pointer_size = if self.machine == BinaryArchitecture::AMD64 { 8 } else { 4 };
+                    self.read_image_pointer(address, pointer_size)?
+                },
+                _ => return None,
+            },
+            _ => return None,
+        };
+        if self.is_executable_address(value) { Some(value) } else { None }
+    }
+
+    /// Recovers the full case-target set of a `jmp reg`-style switch
+    /// dispatcher -- the `-fPIC` idiom `lea table, [rip+disp]` /
+    /// `movsxd reg, [table+index*scale]` / `add reg, table` / `jmp reg`,
+    /// where `resolve_jump_table` doesn't apply because the jump's own
+    /// operand is a bare register rather than a memory expression, and
+    /// `resolve_indirect_operand` can only resolve the one case the
+    /// decode-order register constants happen to encode rather than
+    /// enumerate every case.
+    ///
+    /// Replays the block from `block_start`, alongside
+    /// `track_register_constant`'s register constants, tracking via
+    /// `track_table_load` which register last loaded a table entry (a
+    /// `mov`/`movsx`/`movsxd` from `[known_base + index*scale]`) and
+    /// survives unmodified through a later `add` back to that same base --
+    /// the signature of the relative-offset-table idiom. If `branch_insn`'s
+    /// register operand matches, reads every `[0,
+    /// options.jump_table_maximum_entries)` entry from the table directly
+    /// (rather than following the one path this block's registers encode),
+    /// stopping at the first entry that isn't an executable address. Doesn't
+    /// model a preceding bounds check (`cmp`/`ja`) living in a predecessor
+    /// block, so `jump_table_maximum_entries` is the only cap. x86-only
+    /// (AMD64/I386), and only useful when
+    /// `options.enable_register_constant_propagation` is set.
+    #[allow(dead_code)]
+    pub fn resolve_switch_table(&self, block_start: u64, branch_insn: &Insn, options: &GraphOptions) -> HashSet<u64> {
+        let mut targets = HashSet::<u64>::new();
+
+        if self.machine != BinaryArchitecture::AMD64 && self.machine != BinaryArchitecture::I386 {
+            return targets;
+        }
+
+        let mut registers = HashMap::<RegId, u64>::new();
+        let mut tables = HashMap::<RegId, (u64, u64)>::new();
+        let mut pc = block_start;
+        let mut steps: usize = 0;
+
+        while pc < branch_insn.address() {
+            if steps >= options.indirect_resolution_maximum_instructions {
+                return targets;
+            }
+
+            let instruction = match self.disassemble_instruction(pc) {
+                Ok(instruction) => instruction,
+                Err(_) => return targets,
+            };
+
+            self.track_table_load(&instruction, &registers, &mut tables);
+            self.track_register_constant(&instruction, &mut registers);
+
+            pc += instruction.bytes().len() as u64;
+            steps += 1;
+        }
+
+        let target_register = match self.get_operands(branch_insn).first() {
+            Some(ArchOperand::X86Operand(op)) => match op.op_type {
+                X86OperandType::Reg(reg_id) => reg_id,
+                _ => return targets,
+            },
+            _ => return targets,
+        };
+        let (table_base, entry_size) = match tables.get(&target_register) {
+            Some(table) => *table,
+            None => return targets,
+        };
+
+        for i in 0..options.jump_table_maximum_entries as u64 {
+            let entry_address = table_base + i * entry_size;
+            let offset = match self.read_image_i32(entry_address) {
+                Some(offset) => offset,
+                None => break,
+            };
+            let address = (table_base as i64 + offset as i64) as u64;
+            if !self.is_executable_address(address) {
+                break;
+            }
+            targets.insert(address);
+        }
+
+        targets
+    }
+
+    /// Updates `tables`, a map from register id to `(table_base,
+    /// entry_size)`, for the two steps of the relative-table switch idiom
+    /// `resolve_switch_table` looks for: a `mov`/`movsx`/`movsxd reg, [base +
+    /// index*scale]` where `base` is a known constant in `registers` (the
+    /// register now holds a raw table entry), and a later `add reg,
+    /// base_reg` back to that same base register (the register still
+    /// identifies the same table, now holding a fully-resolved case target
+    /// rather than a raw entry -- which is exactly what `resolve_switch_table`
+    /// wants to recognize at the final `jmp reg`). Any other write to a
+    /// tracked destination register drops it.
+    fn track_table_load(&self, instruction: &Insn, registers: &HashMap<RegId, u64>, tables: &mut HashMap<RegId, (u64, u64)>) {
+        let operands = self.get_operands(instruction);
+        let destination = match operands.first() {
+            Some(ArchOperand::X86Operand(op)) => match op.op_type {
+                X86OperandType::Reg(reg_id) => reg_id,
+                _ => return,
+            },
+            _ => return,
+        };
+
+        let is_mov_class = instruction.id() == InsnId(X86Insn::X86_INS_MOV as u32)
+            || instruction.id() == InsnId(X86Insn::X86_INS_MOVSX as u32)
+            || instruction.id() == InsnId(X86Insn::X86_INS_MOVSXD as u32);
+
+        if is_mov_class {
+            if let Some(ArchOperand::X86Operand(op)) = operands.get(1) {
+                if let X86OperandType::Mem(mem) = op.op_type {
+                    if mem.index() != RegId(0) {
+                        if let Some(&base) = registers.get(&mem.base()) {
+                            let entry_size = if mem.scale() > 0 { mem.scale() as u64 } else { 4 };
+                            tables.insert(destination, (base.wrapping_add(mem.disp() as u64), entry_size));
+                            return;
+                        }
+                    }
+                }
+            }
+            tables.remove(&destination);
+            return;
+        }
+
+        if instruction.id() == InsnId(X86Insn::X86_INS_ADD as u32) {
+            if let Some((table_base, _)) = tables.get(&destination).copied() {
+                if let Some(ArchOperand::X86Operand(op)) = operands.get(1) {
+                    if let X86OperandType::Reg(source) = op.op_type {
+                        if registers.get(&source) == Some(&table_base) {
+                            return;
+                        }
+                    }
+                }
+            }
+            tables.remove(&destination);
+            return;
+        }
+
+        tables.remove(&destination);
+    }
+
+    /// Decodes the instruction at `address` and converts it into a `Graph` `Instruction`.
+    fn decode(&self, block_start: u64, address: u64, options: &GraphOptions, registers: &mut HashMap<RegId, u64>, live: Option<&LiveSet>) -> Result<Instruction, Error> {
+        let insn = self.disassemble_instruction(address)?;
+        let mut instruction = Instruction::new(insn.address(), insn.bytes().to_vec());
+        instruction.text = match (insn.mnemonic(), insn.op_str()) {
+            (Some(mnemonic), Some(op_str)) if !op_str.is_empty() => Some(format!("{} {}", mnemonic, op_str)),
+            (Some(mnemonic), _) => Some(mnemonic.to_string()),
+            (None, _) => None,
+        };
+        instruction.pattern = self.wildcard_mask(&insn, live);
+
+        if self.is_trap_instruction(&insn) {
+            instruction.is_trap = true;
+            instruction.edges = 0;
+            return Ok(instruction);
+        }
+
+        if self.is_return_instruction(&insn) {
+            instruction.is_return = true;
+            instruction.edges = 1;
+            return Ok(instruction);
+        }
+
+        if self.is_call_instruction(&insn) {
+            instruction.is_call = true;
+            let target = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)).or_else(|| {
+                if options.enable_register_constant_propagation {
+                    self.resolve_indirect_operand(&insn, registers)
+                } else {
+                    None
+                }
+            });
+            if let Some(target) = target {
+                instruction.functions.insert(target);
+            }
+            instruction.next = Some(address + insn.bytes().len() as u64);
+            if options.enable_register_constant_propagation {
+                self.track_register_constant(&insn, registers);
+            }
+            return Ok(instruction);
+        }
+
+        if self.is_conditional_jump_instruction(&insn) {
+            instruction.is_jump = true;
+            instruction.is_conditional = true;
+            instruction.edges = 2;
+            instruction.next = Some(address + insn.bytes().len() as u64);
+            if let Some(target) = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)) {
+                instruction.to.insert(target);
+            }
+            return Ok(instruction);
+        }
+
+        if self.is_unconditional_jump_instruction(&insn) {
+            instruction.is_jump = true;
+            if let Some(target) = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)) {
+                instruction.edges = 1;
+                instruction.to.insert(target);
+                return Ok(instruction);
+            }
+
+            if options.enable_register_constant_propagation {
+                let switch_targets = self.resolve_switch_table(block_start, &insn, options);
+                if !switch_targets.is_empty() {
+                    instruction.edges = switch_targets.len();
+                    instruction.to.extend(switch_targets);
+                    return Ok(instruction);
+                }
+
+                if let Some(target) = self.resolve_indirect_operand(&insn, registers) {
+                    instruction.edges = 1;
+                    instruction.to.insert(target);
+                    return Ok(instruction);
+                }
+            }
+
+            let jump_table_targets = self.resolve_jump_table(&insn, options.jump_table_maximum_entries);
+            if !jump_table_targets.is_empty() {
+                instruction.edges = jump_table_targets.len();
+                instruction.to.extend(jump_table_targets);
+                return Ok(instruction);
+            }
+
+            instruction.edges = 1;
+            instruction.next = Some(address + insn.bytes().len() as u64);
+            return Ok(instruction);
+        }
+
+        if let Some(mnemonic) = insn.mnemonic() {
+            instruction.is_prologue = instrs::classify(self.machine, mnemonic).is_prologue;
+        }
+
+        instruction.functions.extend(self.instruction_executable_addresses(&insn));
+        instruction.next = Some(address + insn.bytes().len() as u64);
+        if options.enable_register_constant_propagation {
+            self.track_register_constant(&insn, registers);
+        }
+        Ok(instruction)
+    }
+
+    /// Alternative to `disassemble_linear_pass` for packed, hand-written, or
+    /// interleaved-data code: rather than walking each executable range once
+    /// and resetting its counters on the first decode error, decodes an
+    /// instruction starting at *every* byte offset in the range, then scores
+    /// each candidate start by how consistent its successor chain is with
+    /// other candidate starts. A start gains confidence for each successor
+    /// (fallthrough or resolved branch/call target) that is itself a valid
+    /// decode site, and a lower-scoring start that falls inside the bytes
+    /// of a higher-scoring one already kept is discarded -- recovering entries the
+    /// single-pass sweep drops when misalignment or embedded data throws it
+    /// off. Only call targets reached by a chain meeting both
+    /// `valid_jump_threshold` and `valid_instruction_threshold`, scored at
+    /// or above `confidence_threshold`, are returned as seeds for
+    /// `disassemble_control_flow`.
+    ///
+    /// Unlike `resolve_jump_table`, it has no architecture check and sweeps
+    /// whatever architecture this `Disassembler` was built for. Not part of
+    /// `DisassemblerBackend` since it's a heavier, opt-in alternative to
+    /// `disassemble_linear_pass` rather than every backend's default sweep.
+    /// Gated behind `--enable-superset-pass` in `main`, with its thresholds
+    /// exposed as the `superset_pass_*` CLI options.
+    pub fn disassemble_superset_pass(&self, valid_jump_threshold: usize, valid_instruction_threshold: usize, confidence_threshold: f64) -> BTreeSet<u64> {
+        let mut decoded: BTreeMap<u64, SupersetCandidate> = BTreeMap::new();
+
+        for (start, end) in self.executable_address_ranges.clone() {
+            let mut pc = start;
+            while pc < end {
+                if let Ok(insn) = self.disassemble_instruction(pc) {
+                    let length = insn.bytes().len() as u64;
+                    let terminal = self.is_trap_instruction(&insn) || self.is_return_instruction(&insn);
+
+                    let mut successors = BTreeSet::<u64>::new();
+                    if !terminal {
+                        let fallthrough = pc + length;
+                        if self.is_conditional_jump_instruction(&insn) {
+                            if let Some(target) = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)) {
+                                successors.insert(target);
+                            }
+                            successors.insert(fallthrough);
+                        } else if self.is_unconditional_jump_instruction(&insn) {
+                            if let Some(target) = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)) {
+                                successors.insert(target);
+                            }
+                        } else {
+                            successors.insert(fallthrough);
+                        }
+                    }
+
+                    let call_target = if self.is_call_instruction(&insn) {
+                        self.get_operand_immutable(&insn, self.branch_operand_index(&insn))
+                    } else {
+                        None
+                    };
+
+                    decoded.insert(pc, SupersetCandidate { length, successors, call_target, terminal });
+                }
+                pc += 1;
+            }
+        }
+
+        let mut scored: Vec<(u64, f64)> = decoded.iter().map(|(&address, candidate)| {
+            let mut score = 1.0;
+            score += candidate.successors.iter().filter(|successor| decoded.contains_key(successor)).count() as f64;
+            if candidate.call_target.is_some_and(|target| decoded.contains_key(&target)) {
+                score += 1.0;
+            }
+            (address, score)
+        }).collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
+
+        let mut covered = BTreeSet::<u64>::new();
+        let mut functions = BTreeSet::<u64>::new();
+
+        for (address, score) in scored {
+            if score < confidence_threshold || covered.contains(&address) {
+                continue;
+            }
+
+            let candidate = &decoded[&address];
+            for offset in 0..candidate.length {
+                covered.insert(address + offset);
+            }
+
+            let valid_jumps = candidate.successors.iter().filter(|successor| decoded.contains_key(successor)).count();
+            let chain_length = self.superset_chain_length(&decoded, address);
+
+            if let Some(call_target) = candidate.call_target {
+                if valid_jumps >= valid_jump_threshold
+                    && chain_length >= valid_instruction_threshold
+                    && self.is_executable_address(call_target) {
+                    functions.insert(call_target);
+                }
+            }
+        }
+
+        functions
+    }
+
+    /// Length of the fallthrough-only run starting at `address`, stopping
+    /// after the first terminal instruction or unconditional jump (a
+    /// conditional branch or call continues the run), or at an address
+    /// `disassemble_superset_pass` didn't decode -- the superset-sweep
+    /// analogue of `disassemble_linear_pass`'s `valid_instructions` counter.
+    fn superset_chain_length(&self, decoded: &BTreeMap<u64, SupersetCandidate>, mut address: u64) -> usize {
+        let mut length = 0;
+        let mut visited = BTreeSet::<u64>::new();
+        while let Some(candidate) = decoded.get(&address) {
+            if !visited.insert(address) {
+                break;
+            }
+            length += 1;
+            if candidate.terminal {
+                break;
+            }
+            let fallthrough = address + candidate.length;
+            if !candidate.successors.contains(&fallthrough) {
+                break;
+            }
+            address = fallthrough;
+        }
+        length
+    }
+}
+
+impl DisassemblerBackend for Disassembler {
+    fn architectures(&self) -> &[BinaryArchitecture] {
+        const SUPPORTED: [BinaryArchitecture; 3] = [
+            BinaryArchitecture::AMD64,
+            BinaryArchitecture::I386,
+            BinaryArchitecture::ARM64,
+        ];
+        &SUPPORTED
+    }
+
+    fn disassemble_block(&self, address: u64, graph: &mut Graph) -> Result<u64, Error> {
+        if !self.is_executable_address(address) {
+            graph.blocks.insert_invalid(address);
+            return Err(Error::new(ErrorKind::Other, format!("Block -> 0x{:x}: does not start in executable memory", address)));
+        }
+
+        let mut written: u64 = 0;
+        let mut pc = address;
+        let mut registers = HashMap::<RegId, u64>::new();
+        let liveness = self.instruction_liveness(address);
+        let mut instruction_index: usize = 0;
+
+        loop {
+            if overlaps_instruction(graph, pc) {
+                graph.blocks.insert_trap(pc, TrapReason::OverlappingInstruction);
+                return Err(Error::new(ErrorKind::Other, format!("Block -> 0x{:x}: overlaps an already-decoded instruction", pc)));
+            }
+
+            let instruction = match self.decode(address, pc, &graph.options, &mut registers, liveness.get(instruction_index)) {
+                Ok(instruction) => instruction,
+                Err(error) => {
+                    graph.blocks.insert_trap(pc, TrapReason::IllegalOpcode);
+                    return Err(error);
+                }
+            };
+
+            for &function_address in &instruction.functions {
+                graph.functions.enqueue(function_address);
+            }
+
+            let size = instruction.size() as u64;
+            let is_terminator = instruction.is_trap || instruction.is_return || instruction.is_jump;
+
+            if is_terminator {
+                for &target in &instruction.to {
+                    if self.is_executable_address(target) {
+                        graph.blocks.enqueue(target);
+                    } else {
+                        graph.blocks.insert_trap(target, TrapReason::OutOfBoundsTarget);
+                    }
+                }
+                if let Some(next) = instruction.next {
+                    if self.is_executable_address(next) {
+                        graph.blocks.enqueue(next);
+                    } else {
+                        graph.blocks.insert_trap(next, TrapReason::OutOfBoundsTarget);
+                    }
+                }
+            }
+
+            graph.insert_instruction(instruction.clone());
+            written += 1;
+
+            if is_terminator {
+                graph.blocks.insert_processed(address);
+                graph.blocks.insert_valid(address);
+                return Ok(written);
+            }
+
+            pc += size;
+            instruction_index += 1;
+        }
+    }
+
+    fn disassemble_function(&self, address: u64, graph: &mut Graph) -> Result<u64, Error> {
+        let mut written: u64 = 0;
+
+        graph.blocks.enqueue(address);
+
+        loop {
+            if graph.enforce_budget() { break; }
+            let Some(pc) = graph.blocks.dequeue() else { break; };
+            if graph.blocks.is_processed(pc) {
+                continue;
+            }
+            match self.disassemble_block(pc, graph) {
+                Ok(count) => written += count,
+                Err(_) => continue,
+            }
+        }
+
+        graph.functions.insert_processed(address);
+        graph.functions.insert_valid(address);
+
+        Ok(written)
+    }
+
+    fn disassemble_control_flow(&self, addresses: BTreeSet<u64>, graph: &mut Graph) -> Result<(), Error> {
+        graph.functions.enqueue_extend(addresses);
+
+        loop {
+            if graph.enforce_budget() { break; }
+            let Some(address) = graph.functions.dequeue() else { break; };
+            if graph.functions.is_processed(address) {
+                continue;
+            }
+            let _ = self.disassemble_function(address, graph);
+        }
+
+        Ok(())
+    }
+
+    fn disassemble_linear_pass(&self, valid_jump_threshold: usize, valid_instruction_threshold: usize) -> BTreeSet<u64> {
+        let mut functions = BTreeSet::<u64>::new();
+
+        for (start, end) in self.executable_address_ranges.clone() {
+            let mut pc = start;
+            let mut valid_instructions = 0;
+            let mut valid_jumps = 0;
+
+            while pc < end {
+                let insn = match self.disassemble_instruction(pc) {
+                    Ok(insn) => insn,
+                    Err(_) => {
+                        pc += 1;
+                        valid_instructions = 0;
+                        valid_jumps = 0;
+                        continue;
+                    }
+                };
+
+                if self.is_trap_instruction(&insn) {
+                    pc += insn.bytes().len() as u64;
+                    continue;
+                }
+
+                if self.is_conditional_jump_instruction(&insn) || self.is_unconditional_jump_instruction(&insn) {
+                    if let Some(target) = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)) {
+                        if self.is_executable_address(target) {
+                            valid_jumps += 1;
+                        } else {
+                            valid_instructions = 0;
+                            valid_jumps = 0;
+                            pc += 1;
+                            continue;
+                        }
+                    }
+                }
+
+                if self.is_call_instruction(&insn) {
+                    if valid_jumps >= valid_jump_threshold && valid_instructions >= valid_instruction_threshold {
+                        if let Some(target) = self.get_operand_immutable(&insn, self.branch_operand_index(&insn)) {
+                            if self.is_executable_address(target) {
+                                functions.insert(target);
+                            }
+                        }
+                    }
+                }
+
+                valid_instructions += 1;
+                pc += insn.bytes().len() as u64;
+            }
+        }
+
+        functions
+    }
+}
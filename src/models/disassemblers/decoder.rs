@@ -0,0 +1,279 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Error, ErrorKind};
+use crate::models::binary::BinaryArchitecture;
+use crate::models::controlflow::graph::{Graph, TrapReason};
+use crate::models::controlflow::instruction::Instruction;
+use crate::models::disassemblers::backend::DisassemblerBackend;
+
+/// A single instruction lifted by an `InstructionDecoder`, carrying only
+/// what the generic traversal in `GenericDisassembler` needs to keep
+/// walking the control flow -- not a full `Instruction`, so a decoder for a
+/// bespoke ISA (e.g. a table-driven VM bytecode) doesn't have to know
+/// anything about `Graph`/`Block`/`Function`.
+pub struct DecodedInstruction {
+    /// The raw bytes this instruction occupies; its length is the decoder's
+    /// own notion of instruction size.
+    pub bytes: Vec<u8>,
+    /// Mnemonic and operand text, if the decoder surfaces one.
+    pub mnemonic: Option<String>,
+    /// The address of the fallthrough instruction, if execution can
+    /// continue past this one (absent for an unconditional jump, call, or
+    /// return).
+    pub fallthrough: Option<u64>,
+    /// Addresses this instruction may jump or branch to.
+    pub branches: BTreeSet<u64>,
+    /// Addresses this instruction calls as functions.
+    pub calls: BTreeSet<u64>,
+    /// `true` if this instruction only conditionally takes `branches`
+    /// (i.e. `fallthrough` is also a real successor).
+    pub is_conditional: bool,
+    /// `true` if this instruction returns from the current function.
+    pub is_return: bool,
+    /// `true` if this instruction halts execution (illegal opcode,
+    /// privileged instruction, trap) and has no successors.
+    pub is_halt: bool,
+}
+
+/// A pluggable per-instruction decoder for `GenericDisassembler`.
+///
+/// Unlike `DisassemblerBackend`, which owns the entire recursive-descent
+/// traversal, an `InstructionDecoder` only lifts one instruction at a time
+/// from raw bytes -- so a custom ISA (e.g. the kind of compact register
+/// bytecode seen in VM-based packers) can plug its opcode table in here and
+/// get binlex's block/function discovery, hashing, and JSON emission for
+/// free via `GenericDisassembler`, without reimplementing the traversal
+/// that `capstone::Disassembler`/`holeybytes::Disassembler` each do by hand.
+pub trait InstructionDecoder {
+    /// Decodes the instruction starting at `address`, whose bytes are the
+    /// image's remaining contents from `address` onward. Returns `None` if
+    /// `bytes` doesn't begin with a valid instruction (illegal opcode,
+    /// truncated operands).
+    fn decode(&self, address: u64, bytes: &[u8]) -> Option<DecodedInstruction>;
+
+    /// The `BinaryArchitecture`s this decoder's opcode table covers; see
+    /// `DisassemblerBackend::architectures`.
+    fn architectures(&self) -> &[BinaryArchitecture];
+}
+
+/// Returns `true` if `address` falls inside an instruction already decoded into `graph`.
+fn overlaps_instruction(graph: &Graph, address: u64) -> bool {
+    graph.instructions
+        .range(..address)
+        .next_back()
+        .map(|entry| entry.value().address + entry.value().size() as u64 > address)
+        .unwrap_or(false)
+}
+
+/// A `DisassemblerBackend` that drives `Graph` construction from any
+/// `InstructionDecoder` rather than a hardwired Capstone handle.
+///
+/// This is the generic counterpart to `capstone::Disassembler` and
+/// `holeybytes::Disassembler`: those two own their own decode-and-traverse
+/// logic end to end, while `GenericDisassembler` implements the traversal
+/// once and defers only the byte-to-`DecodedInstruction` step to `D`.
+pub struct GenericDisassembler<D: InstructionDecoder> {
+    decoder: D,
+    image: Vec<u8>,
+    executable_address_ranges: BTreeMap<u64, u64>,
+}
+
+impl<D: InstructionDecoder> GenericDisassembler<D> {
+    pub fn new(decoder: D, image: Vec<u8>, executable_address_ranges: BTreeMap<u64, u64>) -> Self {
+        Self {
+            decoder,
+            image,
+            executable_address_ranges,
+        }
+    }
+
+    pub fn is_executable_address(&self, address: u64) -> bool {
+        self.executable_address_ranges
+            .iter()
+            .any(|(start, end)| address >= *start && address <= *end)
+    }
+
+    /// Decodes the instruction at `address` via `self.decoder` and folds its
+    /// `DecodedInstruction` into the `Instruction` shape `Graph` expects.
+    fn decode_instruction(&self, address: u64) -> Result<Instruction, Error> {
+        let offset = address as usize;
+        let remaining = self.image.get(offset..).ok_or_else(|| {
+            Error::new(ErrorKind::Other, format!("Instruction -> 0x{:x}: is out of bounds", address))
+        })?;
+
+        let decoded = self.decoder.decode(address, remaining).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, format!("Instruction -> 0x{:x}: decoder rejected instruction", address))
+        })?;
+
+        let mut instruction = Instruction::new(address, decoded.bytes);
+        instruction.text = decoded.mnemonic;
+        instruction.is_conditional = decoded.is_conditional;
+        instruction.is_return = decoded.is_return;
+        instruction.is_trap = decoded.is_halt;
+        instruction.is_call = !decoded.calls.is_empty();
+        instruction.functions = decoded.calls;
+        instruction.is_jump = !decoded.branches.is_empty();
+        instruction.to = decoded.branches;
+        instruction.next = decoded.fallthrough;
+        instruction.edges = instruction.to.len() + instruction.next.is_some() as usize;
+
+        Ok(instruction)
+    }
+}
+
+impl<D: InstructionDecoder> DisassemblerBackend for GenericDisassembler<D> {
+    fn architectures(&self) -> &[BinaryArchitecture] {
+        self.decoder.architectures()
+    }
+
+    fn disassemble_block(&self, address: u64, graph: &mut Graph) -> Result<u64, Error> {
+        if !self.is_executable_address(address) {
+            graph.blocks.insert_invalid(address);
+            return Err(Error::new(ErrorKind::Other, format!("Block -> 0x{:x}: does not start in executable memory", address)));
+        }
+
+        let mut written: u64 = 0;
+        let mut pc = address;
+
+        loop {
+            if overlaps_instruction(graph, pc) {
+                graph.blocks.insert_trap(pc, TrapReason::OverlappingInstruction);
+                return Err(Error::new(ErrorKind::Other, format!("Block -> 0x{:x}: overlaps an already-decoded instruction", pc)));
+            }
+
+            let instruction = match self.decode_instruction(pc) {
+                Ok(instruction) => instruction,
+                Err(error) => {
+                    graph.blocks.insert_trap(pc, TrapReason::IllegalOpcode);
+                    return Err(error);
+                }
+            };
+
+            for &function_address in &instruction.functions {
+                if self.is_executable_address(function_address) {
+                    graph.functions.enqueue(function_address);
+                } else {
+                    graph.functions.insert_trap(function_address, TrapReason::OutOfBoundsTarget);
+                }
+            }
+
+            let size = instruction.size() as u64;
+            let is_terminator = instruction.is_trap || instruction.is_return || instruction.is_jump;
+
+            if is_terminator {
+                for &target in &instruction.to {
+                    if self.is_executable_address(target) {
+                        graph.blocks.enqueue(target);
+                    } else {
+                        graph.blocks.insert_trap(target, TrapReason::OutOfBoundsTarget);
+                    }
+                }
+                if let Some(next) = instruction.next {
+                    if self.is_executable_address(next) {
+                        graph.blocks.enqueue(next);
+                    } else {
+                        graph.blocks.insert_trap(next, TrapReason::OutOfBoundsTarget);
+                    }
+                }
+            }
+
+            graph.insert_instruction(instruction.clone());
+            written += 1;
+
+            if is_terminator {
+                graph.blocks.insert_processed(address);
+                graph.blocks.insert_valid(address);
+                return Ok(written);
+            }
+
+            pc += size;
+        }
+    }
+
+    fn disassemble_function(&self, address: u64, graph: &mut Graph) -> Result<u64, Error> {
+        let mut written: u64 = 0;
+
+        graph.blocks.enqueue(address);
+
+        loop {
+            if graph.enforce_budget() { break; }
+            let Some(pc) = graph.blocks.dequeue() else { break; };
+            if graph.blocks.is_processed(pc) {
+                continue;
+            }
+            match self.disassemble_block(pc, graph) {
+                Ok(count) => written += count,
+                Err(_) => continue,
+            }
+        }
+
+        graph.functions.insert_processed(address);
+        graph.functions.insert_valid(address);
+
+        Ok(written)
+    }
+
+    fn disassemble_control_flow(&self, addresses: BTreeSet<u64>, graph: &mut Graph) -> Result<(), Error> {
+        graph.functions.enqueue_extend(addresses);
+
+        loop {
+            if graph.enforce_budget() { break; }
+            let Some(address) = graph.functions.dequeue() else { break; };
+            if graph.functions.is_processed(address) {
+                continue;
+            }
+            let _ = self.disassemble_function(address, graph);
+        }
+
+        Ok(())
+    }
+
+    fn disassemble_linear_pass(&self, valid_jump_threshold: usize, valid_instruction_threshold: usize) -> BTreeSet<u64> {
+        let mut functions = BTreeSet::<u64>::new();
+
+        for (start, end) in self.executable_address_ranges.clone() {
+            let mut pc = start;
+            let mut valid_instructions = 0;
+            let mut valid_jumps = 0;
+
+            while pc < end {
+                let instruction = match self.decode_instruction(pc) {
+                    Ok(instruction) => instruction,
+                    Err(_) => {
+                        pc += 1;
+                        valid_instructions = 0;
+                        valid_jumps = 0;
+                        continue;
+                    }
+                };
+
+                if instruction.is_jump {
+                    if let Some(&target) = instruction.to.iter().next() {
+                        if self.is_executable_address(target) {
+                            valid_jumps += 1;
+                        } else {
+                            valid_instructions = 0;
+                            valid_jumps = 0;
+                            pc += 1;
+                            continue;
+                        }
+                    }
+                }
+
+                if instruction.is_call {
+                    if valid_jumps >= valid_jump_threshold && valid_instructions >= valid_instruction_threshold {
+                        if let Some(&target) = instruction.functions.iter().next() {
+                            if self.is_executable_address(target) {
+                                functions.insert(target);
+                            }
+                        }
+                    }
+                }
+
+                valid_instructions += 1;
+                pc += instruction.size() as u64;
+            }
+        }
+
+        functions
+    }
+}
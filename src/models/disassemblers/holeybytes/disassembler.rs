@@ -0,0 +1,266 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Error, ErrorKind};
+use crate::models::binary::BinaryArchitecture;
+use crate::models::controlflow::graph::{Graph, TrapReason};
+use crate::models::controlflow::instruction::Instruction;
+use crate::models::disassemblers::backend::DisassemblerBackend;
+use crate::models::disassemblers::holeybytes::opcode::Opcode;
+
+/// Returns `true` if `address` falls inside an instruction already decoded into `graph`.
+fn overlaps_instruction(graph: &Graph, address: u64) -> bool {
+    graph.instructions
+        .range(..address)
+        .next_back()
+        .map(|entry| entry.value().address + entry.value().size() as u64 > address)
+        .unwrap_or(false)
+}
+
+/// A `DisassemblerBackend` for HoleyBytes, a little-endian, fixed-width
+/// register-VM bytecode ISA, so analysts can lift custom VM payloads the same
+/// way binlex lifts native Capstone-decodable code.
+///
+/// Decoding is recursive descent over a static opcode -> encoding table
+/// (`Opcode::layout`): each opcode fixes its own operand layout and therefore
+/// its instruction length, so there is never a need to speculatively try
+/// multiple encodings for the same byte.
+pub struct Disassembler {
+    image: Vec<u8>,
+    executable_address_ranges: BTreeMap<u64, u64>,
+}
+
+impl Disassembler {
+    pub fn new(image: Vec<u8>, executable_address_ranges: BTreeMap<u64, u64>) -> Self {
+        Self {
+            image,
+            executable_address_ranges,
+        }
+    }
+
+    pub fn is_executable_address(&self, address: u64) -> bool {
+        self.executable_address_ranges
+            .iter()
+            .any(|(start, end)| address >= *start && address <= *end)
+    }
+
+    fn read_i32(&self, offset: usize) -> Option<i32> {
+        let bytes = self.image.get(offset..offset + 4)?;
+        Some(i32::from_le_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Decodes the instruction at `address`.
+    ///
+    /// Returns `Err` if `address` has no opcode table entry or its operand
+    /// bytes run past the end of the image; callers are expected to mark the
+    /// address invalid in that case rather than treat it as a hard failure.
+    fn decode(&self, address: u64) -> Result<Instruction, Error> {
+        let offset = address as usize;
+        let byte = *self.image.get(offset).ok_or_else(|| {
+            Error::new(ErrorKind::Other, format!("Instruction -> 0x{:x}: is out of bounds", address))
+        })?;
+
+        let opcode = Opcode::decode(byte).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, format!("Instruction -> 0x{:x}: illegal opcode 0x{:02x}", address, byte))
+        })?;
+
+        let length = opcode.layout().length();
+        if offset + length > self.image.len() {
+            return Err(Error::new(ErrorKind::Other, format!("Instruction -> 0x{:x}: truncated operands", address)));
+        }
+
+        let bytes = self.image[offset..offset + length].to_vec();
+        let mut instruction = Instruction::new(address, bytes);
+
+        match opcode {
+            Opcode::Jump => {
+                let displacement = self.read_i32(offset + 1).unwrap();
+                instruction.is_jump = true;
+                instruction.edges = 1;
+                instruction.to.insert((address as i64 + displacement as i64) as u64);
+            },
+            Opcode::BranchNotEqual => {
+                // r0 (offset + 1), r1 (offset + 2), then the displacement.
+                let displacement = self.read_i32(offset + 3).unwrap();
+                instruction.is_jump = true;
+                instruction.is_conditional = true;
+                instruction.edges = 2;
+                instruction.to.insert((address as i64 + displacement as i64) as u64);
+                instruction.next = Some(address + length as u64);
+            },
+            Opcode::Call => {
+                // rd (offset + 1) receives the return address, then the displacement.
+                let displacement = self.read_i32(offset + 2).unwrap();
+                instruction.is_call = true;
+                instruction.functions.insert((address as i64 + displacement as i64) as u64);
+                instruction.next = Some(address + length as u64);
+            },
+            Opcode::Return => {
+                instruction.is_return = true;
+                instruction.edges = 1;
+            },
+            Opcode::Nop | Opcode::Add | Opcode::Sub | Opcode::Mov | Opcode::LoadImmediate => {
+                instruction.next = Some(address + length as u64);
+            },
+        }
+
+        Ok(instruction)
+    }
+}
+
+impl DisassemblerBackend for Disassembler {
+    fn architectures(&self) -> &[BinaryArchitecture] {
+        const SUPPORTED: [BinaryArchitecture; 1] = [BinaryArchitecture::HOLEYBYTES];
+        &SUPPORTED
+    }
+
+    fn disassemble_block(&self, address: u64, graph: &mut Graph) -> Result<u64, Error> {
+        if !self.is_executable_address(address) {
+            graph.blocks.insert_invalid(address);
+            return Err(Error::new(ErrorKind::Other, format!("Block -> 0x{:x}: does not start in executable memory", address)));
+        }
+
+        let mut written: u64 = 0;
+        let mut pc = address;
+
+        loop {
+            if overlaps_instruction(graph, pc) {
+                graph.blocks.insert_trap(pc, TrapReason::OverlappingInstruction);
+                return Err(Error::new(ErrorKind::Other, format!("Block -> 0x{:x}: overlaps an already-decoded instruction", pc)));
+            }
+
+            let instruction = match self.decode(pc) {
+                Ok(instruction) => instruction,
+                Err(error) => {
+                    graph.blocks.insert_trap(pc, TrapReason::IllegalOpcode);
+                    return Err(error);
+                }
+            };
+
+            for &function_address in &instruction.functions {
+                if self.is_executable_address(function_address) {
+                    graph.functions.enqueue(function_address);
+                } else {
+                    graph.functions.insert_trap(function_address, TrapReason::OutOfBoundsTarget);
+                }
+            }
+
+            let size = instruction.size() as u64;
+            let is_terminator = instruction.is_return || instruction.is_jump;
+
+            if is_terminator {
+                for &target in &instruction.to {
+                    if self.is_executable_address(target) {
+                        graph.blocks.enqueue(target);
+                    } else {
+                        graph.blocks.insert_trap(target, TrapReason::OutOfBoundsTarget);
+                    }
+                }
+                if let Some(next) = instruction.next {
+                    if self.is_executable_address(next) {
+                        graph.blocks.enqueue(next);
+                    } else {
+                        graph.blocks.insert_trap(next, TrapReason::OutOfBoundsTarget);
+                    }
+                }
+            }
+
+            graph.insert_instruction(instruction.clone());
+            written += 1;
+
+            if is_terminator {
+                graph.blocks.insert_processed(address);
+                graph.blocks.insert_valid(address);
+                return Ok(written);
+            }
+
+            pc += size;
+        }
+    }
+
+    fn disassemble_function(&self, address: u64, graph: &mut Graph) -> Result<u64, Error> {
+        let mut written: u64 = 0;
+
+        graph.blocks.enqueue(address);
+
+        loop {
+            if graph.enforce_budget() { break; }
+            let Some(pc) = graph.blocks.dequeue() else { break; };
+            if graph.blocks.is_processed(pc) {
+                continue;
+            }
+            match self.disassemble_block(pc, graph) {
+                Ok(count) => written += count,
+                Err(_) => continue,
+            }
+        }
+
+        graph.functions.insert_processed(address);
+        graph.functions.insert_valid(address);
+
+        Ok(written)
+    }
+
+    fn disassemble_control_flow(&self, addresses: BTreeSet<u64>, graph: &mut Graph) -> Result<(), Error> {
+        graph.functions.enqueue_extend(addresses);
+
+        loop {
+            if graph.enforce_budget() { break; }
+            let Some(address) = graph.functions.dequeue() else { break; };
+            if graph.functions.is_processed(address) {
+                continue;
+            }
+            let _ = self.disassemble_function(address, graph);
+        }
+
+        Ok(())
+    }
+
+    fn disassemble_linear_pass(&self, valid_jump_threshold: usize, valid_instruction_threshold: usize) -> BTreeSet<u64> {
+        let mut functions = BTreeSet::<u64>::new();
+
+        for (start, end) in self.executable_address_ranges.clone() {
+            let mut pc = start;
+            let mut valid_instructions = 0;
+            let mut valid_jumps = 0;
+
+            while pc < end {
+                let instruction = match self.decode(pc) {
+                    Ok(instruction) => instruction,
+                    Err(_) => {
+                        pc += 1;
+                        valid_instructions = 0;
+                        valid_jumps = 0;
+                        continue;
+                    }
+                };
+
+                if instruction.is_jump {
+                    if let Some(&target) = instruction.to.iter().next() {
+                        if self.is_executable_address(target) {
+                            valid_jumps += 1;
+                        } else {
+                            valid_instructions = 0;
+                            valid_jumps = 0;
+                            pc += 1;
+                            continue;
+                        }
+                    }
+                }
+
+                if instruction.is_call {
+                    if valid_jumps >= valid_jump_threshold && valid_instructions >= valid_instruction_threshold {
+                        if let Some(&target) = instruction.functions.iter().next() {
+                            if self.is_executable_address(target) {
+                                functions.insert(target);
+                            }
+                        }
+                    }
+                }
+
+                valid_instructions += 1;
+                pc += instruction.size() as u64;
+            }
+        }
+
+        functions
+    }
+}
@@ -0,0 +1,2 @@
+pub mod disassembler;
+pub mod opcode;
@@ -0,0 +1,84 @@
+/// The operand layout a HoleyBytes opcode is encoded with.
+///
+/// Every layout has a fixed length: register operands select `r0`-`r255`
+/// with a single byte, immediates are 8 bytes, and relative branch
+/// displacements are a signed 4-byte offset applied to the branching
+/// instruction's own address.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OperandLayout {
+    /// No operands.
+    None,
+    /// `rd <- rs1 op rs2`: three register operands.
+    ThreeRegisters,
+    /// `rd <- op rs1`: two register operands.
+    TwoRegisters,
+    /// `rd <- imm`: one register operand and an 8-byte immediate.
+    RegisterImmediate,
+    /// An unconditional relative jump: a 4-byte signed displacement.
+    Branch,
+    /// A conditional relative branch: two register operands and a 4-byte signed displacement.
+    ConditionalBranch,
+    /// A call: one register operand to receive the return address and a 4-byte signed displacement.
+    Call,
+}
+
+impl OperandLayout {
+    /// The total instruction length in bytes, including the 1-byte opcode.
+    pub fn length(&self) -> usize {
+        match self {
+            OperandLayout::None => 1,
+            OperandLayout::ThreeRegisters => 4,
+            OperandLayout::TwoRegisters => 3,
+            OperandLayout::RegisterImmediate => 10,
+            OperandLayout::Branch => 5,
+            OperandLayout::ConditionalBranch => 7,
+            OperandLayout::Call => 6,
+        }
+    }
+}
+
+/// The HoleyBytes opcodes binlex's recursive-descent decoder understands.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Opcode {
+    Nop,
+    Add,
+    Sub,
+    Mov,
+    LoadImmediate,
+    Jump,
+    BranchNotEqual,
+    Call,
+    Return,
+}
+
+impl Opcode {
+    /// Decodes a 1-byte opcode value, returning `None` for opcodes with no table entry.
+    pub fn decode(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(Opcode::Nop),
+            0x01 => Some(Opcode::Add),
+            0x02 => Some(Opcode::Sub),
+            0x03 => Some(Opcode::Mov),
+            0x04 => Some(Opcode::LoadImmediate),
+            0x05 => Some(Opcode::Jump),
+            0x06 => Some(Opcode::BranchNotEqual),
+            0x07 => Some(Opcode::Call),
+            0x08 => Some(Opcode::Return),
+            _ => None,
+        }
+    }
+
+    /// The operand layout this opcode is encoded with.
+    pub fn layout(&self) -> OperandLayout {
+        match self {
+            Opcode::Nop => OperandLayout::None,
+            Opcode::Add | Opcode::Sub => OperandLayout::ThreeRegisters,
+            Opcode::Mov => OperandLayout::TwoRegisters,
+            Opcode::LoadImmediate => OperandLayout::RegisterImmediate,
+            Opcode::Jump => OperandLayout::Branch,
+            Opcode::BranchNotEqual => OperandLayout::ConditionalBranch,
+            Opcode::Call => OperandLayout::Call,
+            Opcode::Return => OperandLayout::None,
+        }
+    }
+}
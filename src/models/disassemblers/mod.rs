@@ -0,0 +1,9 @@
+pub mod backend;
+pub mod decoder;
+
+// `capstone` is on by default; disable default features and opt into
+// `holeybytes` alone to build without linking the Capstone C library.
+#[cfg(feature = "capstone")]
+pub mod capstone;
+#[cfg(feature = "holeybytes")]
+pub mod holeybytes;
@@ -0,0 +1,66 @@
+use twox_hash::XxHash64;
+use std::hash::{Hash, Hasher};
+
+/// Number of registers, as a power of two. 2^`PRECISION` registers gives a standard
+/// error of roughly `1.04 / sqrt(2^PRECISION)`; at 10 that's ~3.25%, accurate enough
+/// to compare shingle diversity between signatures without keeping every shingle.
+const PRECISION: u32 = 10;
+const NUM_REGISTERS: usize = 1 << PRECISION;
+
+/// Estimates the number of distinct shingles that went into a signature using the
+/// HyperLogLog algorithm, so two signatures of the same byte length can still be told
+/// apart by how diverse their shingle content is (e.g. a padded or repetitive
+/// function will under-report relative to its raw size).
+pub struct HyperLogLog {
+    registers: Vec<u8>,
+}
+
+impl HyperLogLog {
+    pub fn new() -> Self {
+        Self {
+            registers: vec![0u8; NUM_REGISTERS],
+        }
+    }
+
+    /// Builds an estimator directly from a sequence of byte shingles, as produced by
+    /// `bytes.windows(shingle_size)`.
+    #[allow(dead_code)]
+    pub fn from_shingles<'a, I: IntoIterator<Item = &'a [u8]>>(shingles: I) -> Self {
+        let mut hll = Self::new();
+        for shingle in shingles {
+            hll.insert(shingle);
+        }
+        hll
+    }
+
+    /// Hashes `item` and folds it into the estimator's registers.
+    pub fn insert<T: Hash>(&mut self, item: T) {
+        let mut hasher = XxHash64::default();
+        item.hash(&mut hasher);
+        let hash = hasher.finish();
+
+        let index = (hash & (NUM_REGISTERS as u64 - 1)) as usize;
+        let remaining = hash >> PRECISION;
+        let rank = (remaining.trailing_zeros() + 1).min(64 - PRECISION) as u8;
+
+        if rank > self.registers[index] {
+            self.registers[index] = rank;
+        }
+    }
+
+    /// Returns the estimated number of distinct items inserted so far.
+    pub fn estimate(&self) -> f64 {
+        let m = NUM_REGISTERS as f64;
+        let alpha = 0.7213 / (1.0 + 1.079 / m);
+
+        let sum: f64 = self.registers.iter().map(|&rank| 2f64.powi(-(rank as i32))).sum();
+        let raw_estimate = alpha * m * m / sum;
+
+        let zero_registers = self.registers.iter().filter(|&&rank| rank == 0).count();
+        if raw_estimate <= 2.5 * m && zero_registers > 0 {
+            return m * (m / zero_registers as f64).ln();
+        }
+
+        raw_estimate
+    }
+}
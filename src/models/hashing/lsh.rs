@@ -0,0 +1,160 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use twox_hash::XxHash64;
+
+/// An LSH (locality-sensitive hashing) banding index over `MinHash32` signatures.
+///
+/// Each signature of `bands * rows` hashes is split into `bands` bands of `rows`
+/// rows each; every band is hashed down to a single bucket key. Two signatures
+/// that land in the same bucket in at least one band are candidate near-duplicates.
+/// This turns an O(n^2) all-pairs MinHash comparison into a near-linear indexing
+/// pass, at the cost of the usual LSH false-negative/false-positive tradeoff: a
+/// pair with Jaccard similarity `s` becomes a candidate with probability
+/// `1 - (1 - s^rows)^bands` (see `candidate_probability`).
+pub struct MinHashLSH {
+    bands: usize,
+    rows: usize,
+    buckets: HashMap<(usize, u64), Vec<u64>>,
+}
+
+impl MinHashLSH {
+    pub fn new(bands: usize, rows: usize) -> Self {
+        Self {
+            bands,
+            rows,
+            buckets: HashMap::<(usize, u64), Vec<u64>>::new(),
+        }
+    }
+
+    /// Picks `(bands, rows)` for a `num_hashes`-length signature whose S-curve
+    /// threshold `(1 / bands) ^ (1 / rows)` is closest to `target` similarity,
+    /// restricted to factorizations of `num_hashes` so every band is full.
+    pub fn for_similarity_threshold(num_hashes: usize, target: f64) -> (usize, usize) {
+        let mut best = (1, num_hashes);
+        let mut best_distance = f64::MAX;
+        for rows in 1..=num_hashes {
+            if num_hashes % rows != 0 { continue; }
+            let bands = num_hashes / rows;
+            let threshold = (1.0 / bands as f64).powf(1.0 / rows as f64);
+            let distance = (threshold - target).abs();
+            if distance < best_distance {
+                best_distance = distance;
+                best = (bands, rows);
+            }
+        }
+        best
+    }
+
+    /// Hashes each of this index's `bands` bands of `minhash` into a bucket
+    /// key, returning `None` if `minhash` doesn't contain exactly
+    /// `bands * rows` hashes.
+    fn band_buckets(&self, minhash: &[u32]) -> Option<Vec<(usize, u64)>> {
+        if minhash.len() != self.bands * self.rows { return None; }
+        let mut keys = Vec::with_capacity(self.bands);
+        for band_index in 0..self.bands {
+            let start = band_index * self.rows;
+            let band = &minhash[start..start + self.rows];
+            let mut hasher = XxHash64::default();
+            band.hash(&mut hasher);
+            keys.push((band_index, hasher.finish()));
+        }
+        Some(keys)
+    }
+
+    /// Indexes `address`'s MinHash signature under this function's address.
+    ///
+    /// Does nothing if `minhash` doesn't contain exactly `bands * rows` hashes.
+    pub fn insert(&mut self, address: u64, minhash: &[u32]) {
+        let Some(keys) = self.band_buckets(minhash) else { return; };
+        for key in keys {
+            self.buckets
+                .entry(key)
+                .or_insert_with(Vec::new)
+                .push(address);
+        }
+    }
+
+    /// Returns every address already indexed that shares a bucket with
+    /// `minhash` in at least one band, without inserting `minhash` itself.
+    ///
+    /// Unlike `candidate_pairs`/`clusters`, which only relate addresses that
+    /// have both been inserted, this lets a caller probe the index with a
+    /// signature that isn't (yet, or ever going to be) part of it -- e.g.
+    /// checking a newly-analyzed function against a corpus already indexed.
+    /// Returns an empty set if `minhash` doesn't contain exactly
+    /// `bands * rows` hashes.
+    #[allow(dead_code)]
+    pub fn query(&self, minhash: &[u32]) -> BTreeSet<u64> {
+        let Some(keys) = self.band_buckets(minhash) else { return BTreeSet::new(); };
+        let mut matches = BTreeSet::new();
+        for key in keys {
+            if let Some(addresses) = self.buckets.get(&key) {
+                matches.extend(addresses.iter().copied());
+            }
+        }
+        matches
+    }
+
+    /// Returns every pair of addresses that shared a bucket in at least one
+    /// band, i.e. the candidate near-duplicate pairs this index found.
+    pub fn candidate_pairs(&self) -> HashSet<(u64, u64)> {
+        let mut pairs = HashSet::new();
+        for addresses in self.buckets.values() {
+            if addresses.len() < 2 { continue; }
+            for i in 0..addresses.len() {
+                for j in (i + 1)..addresses.len() {
+                    let (a, b) = (addresses[i], addresses[j]);
+                    pairs.insert(if a < b { (a, b) } else { (b, a) });
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Groups candidate pairs into clusters via union-find over every address
+    /// that shares at least one bucket with another address.
+    ///
+    /// # Returns
+    ///
+    /// Returns a `Vec<Vec<u64>>` of clusters of two or more addresses; addresses
+    /// with no candidate partner are omitted.
+    pub fn clusters(&self) -> Vec<Vec<u64>> {
+        let mut parent: HashMap<u64, u64> = HashMap::new();
+
+        for addresses in self.buckets.values() {
+            for &address in addresses {
+                parent.entry(address).or_insert(address);
+            }
+            for window in addresses.windows(2) {
+                let root_a = Self::find(&mut parent, window[0]);
+                let root_b = Self::find(&mut parent, window[1]);
+                if root_a != root_b {
+                    parent.insert(root_a, root_b);
+                }
+            }
+        }
+
+        let addresses: Vec<u64> = parent.keys().cloned().collect();
+        let mut groups: HashMap<u64, Vec<u64>> = HashMap::new();
+        for address in addresses {
+            let root = Self::find(&mut parent, address);
+            groups.entry(root).or_insert_with(Vec::new).push(address);
+        }
+
+        groups.into_values().filter(|group| group.len() > 1).collect()
+    }
+
+    fn find(parent: &mut HashMap<u64, u64>, x: u64) -> u64 {
+        let p = *parent.get(&x).unwrap_or(&x);
+        if p == x { return x; }
+        let root = Self::find(parent, p);
+        parent.insert(x, root);
+        root
+    }
+
+    /// Estimates the probability that two signatures with Jaccard similarity
+    /// `similarity` become a candidate pair under this index's configuration.
+    pub fn candidate_probability(&self, similarity: f64) -> f64 {
+        1.0 - (1.0 - similarity.powi(self.rows as i32)).powi(self.bands as i32)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::models::binary::Binary;
+
+/// Per-round left-rotation amounts for the standard MD5 compression function.
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22,
+    5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9, 14, 20,
+    4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23,
+    6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// Per-round additive constants, the integer part of `abs(sin(i + 1)) * 2^32`.
+const CONSTANTS: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee,
+    0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be,
+    0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa,
+    0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed,
+    0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c,
+    0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05,
+    0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039,
+    0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1,
+    0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+/// A dependency-free MD5 implementation, kept for legacy/compatibility hashing
+/// (e.g. matching corpora keyed by MD5 elsewhere) rather than anything security
+/// sensitive; prefer `SHA256` when collision resistance matters.
+pub struct MD5<'md5> {
+    pub bytes: &'md5 [u8],
+}
+
+impl<'md5> MD5<'md5> {
+    #[allow(dead_code)]
+    pub fn new(bytes: &'md5 [u8]) -> Self {
+        Self { bytes }
+    }
+
+    #[allow(dead_code)]
+    pub fn hexdigest(&self) -> Option<String> {
+        Some(Binary::to_hex(&self.digest()))
+    }
+
+    fn digest(&self) -> [u8; 16] {
+        let mut a0: u32 = 0x67452301;
+        let mut b0: u32 = 0xefcdab89;
+        let mut c0: u32 = 0x98badcfe;
+        let mut d0: u32 = 0x10325476;
+
+        let message = Self::pad(self.bytes);
+
+        for chunk in message.chunks(64) {
+            let mut words = [0u32; 16];
+            for (i, word) in words.iter_mut().enumerate() {
+                *word = u32::from_le_bytes(chunk[i * 4..i * 4 + 4].try_into().unwrap());
+            }
+
+            let (mut a, mut b, mut c, mut d) = (a0, b0, c0, d0);
+
+            for i in 0..64 {
+                let (f, g) = match i {
+                    0..=15 => ((b & c) | (!b & d), i),
+                    16..=31 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                    32..=47 => (b ^ c ^ d, (3 * i + 5) % 16),
+                    _ => (c ^ (b | !d), (7 * i) % 16),
+                };
+
+                let f = f
+                    .wrapping_add(a)
+                    .wrapping_add(CONSTANTS[i])
+                    .wrapping_add(words[g]);
+                a = d;
+                d = c;
+                c = b;
+                b = b.wrapping_add(f.rotate_left(SHIFTS[i]));
+            }
+
+            a0 = a0.wrapping_add(a);
+            b0 = b0.wrapping_add(b);
+            c0 = c0.wrapping_add(c);
+            d0 = d0.wrapping_add(d);
+        }
+
+        let mut digest = [0u8; 16];
+        digest[0..4].copy_from_slice(&a0.to_le_bytes());
+        digest[4..8].copy_from_slice(&b0.to_le_bytes());
+        digest[8..12].copy_from_slice(&c0.to_le_bytes());
+        digest[12..16].copy_from_slice(&d0.to_le_bytes());
+        digest
+    }
+
+    /// Pads `data` to a multiple of 64 bytes per the MD5 spec: a `1` bit, zero
+    /// bits up to `56 mod 64`, then the original bit length as a little-endian `u64`.
+    fn pad(data: &[u8]) -> Vec<u8> {
+        let bit_length = (data.len() as u64).wrapping_mul(8);
+        let mut padded = data.to_vec();
+        padded.push(0x80);
+        while padded.len() % 64 != 56 {
+            padded.push(0x00);
+        }
+        padded.extend_from_slice(&bit_length.to_le_bytes());
+        padded
+    }
+}
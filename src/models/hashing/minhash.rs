@@ -2,17 +2,125 @@ use rand::{Rng, SeedableRng};
 use rand::rngs::SmallRng;
 use twox_hash::XxHash32;
 use std::hash::{Hash, Hasher};
+use xxhash_rust::xxh3::xxh3_64_with_seed;
 
 const PRIME_MODULUS: u32 = 4294967291;
 
+/// Fixed seed for similarity signatures (`hash_or_sentinel`/`similarity`) that are
+/// meant to be comparable across separately-analyzed code units. Unlike
+/// `GraphOptions::minhash_seed`, which callers may vary per run, this stays
+/// constant so two signatures built from the same `(num_hashes, shingle_size)`
+/// always used the same hash permutations.
+pub const DEFAULT_SIMILARITY_SEED: u64 = 0;
+
+/// Sentinel signature value MinHash32 starts each permutation's minimum at;
+/// reused by `hash_or_sentinel` to stand in for "no shingle observed" so an
+/// empty-input signature still has the right length and compares as 0.0
+/// similarity against anything, including another empty one.
+pub const SENTINEL_HASH: u32 = u32::MAX;
+
+/// Selects the hash function used to digest each shingle before it's fed into the
+/// permutation (`hash`) or threshold (`frac_hash`) step. `Xxh3` trades the default's
+/// per-byte `std::hash::Hash` combinator for a single-pass XXH3 digest, which is
+/// substantially faster on the high-volume shingle stream of large binaries.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MinhashBackend {
+    Default,
+    Xxh3,
+}
+
+impl Default for MinhashBackend {
+    fn default() -> Self {
+        MinhashBackend::Default
+    }
+}
+
+impl std::fmt::Display for MinhashBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let backend = match self {
+            MinhashBackend::Default => "default",
+            MinhashBackend::Xxh3 => "xxh3",
+        };
+        write!(f, "{}", backend)
+    }
+}
+
+impl std::str::FromStr for MinhashBackend {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "default" => Ok(MinhashBackend::Default),
+            "xxh3" => Ok(MinhashBackend::Xxh3),
+            _ => Err(format!("invalid minhash hash backend")),
+        }
+    }
+}
+
 pub struct MinHash32 <'minhash32> {
     a_coefficients: Vec<u32>,
     b_coefficients: Vec<u32>,
     num_hashes: usize,
     shingle_size: usize,
+    seed: u64,
+    backend: MinhashBackend,
     bytes: &'minhash32 [u8],
 }
 
+/// A FracMinHash sketch produced by `MinHash32::frac_hash`: every shingle hash at or
+/// below the `scaled` threshold, instead of a fixed-size bottom-k sample. Unlike the
+/// bottom-k signature, its cardinality grows with the input. `element_count` (the
+/// number of shingles the sketch was built from, regardless of how many passed the
+/// threshold) is carried alongside it; `containment` and `jaccard` are computed from
+/// `hashes` alone and do not use it.
+#[derive(Clone)]
+pub struct FracMinHashSketch {
+    pub hashes: Vec<u64>,
+    pub element_count: usize,
+    pub scaled: u64,
+    pub seed: u64,
+    pub shingle_size: usize,
+    pub backend: MinhashBackend,
+}
+
+impl FracMinHashSketch {
+    /// Two sketches are only comparable if they were built with the same shingling
+    /// parameters and hash backend; otherwise their hash sets aren't drawn from the
+    /// same space and a containment/Jaccard score between them would be meaningless.
+    fn is_comparable_to(&self, other: &FracMinHashSketch) -> bool {
+        self.seed == other.seed
+            && self.shingle_size == other.shingle_size
+            && self.scaled == other.scaled
+            && self.backend == other.backend
+    }
+
+    /// Containment of `self` within `reference`: `|self ∩ reference| / |self|`,
+    /// computed by intersecting the two hash sets. Unlike `MinHash32::jaccard_similarity`,
+    /// this isn't dominated by a size disparity between the sketches, which makes it the
+    /// right metric for matching a small function's sketch against a large file's.
+    /// Returns `None` if the sketches weren't built with matching seed/shingle_size/scaled,
+    /// or if `self` is empty.
+    pub fn containment(&self, reference: &FracMinHashSketch) -> Option<f64> {
+        if !self.is_comparable_to(reference) { return None; }
+        if self.hashes.is_empty() { return None; }
+        let reference_set: std::collections::HashSet<u64> = reference.hashes.iter().copied().collect();
+        let contained = self.hashes.iter().filter(|hash| reference_set.contains(hash)).count();
+        Some(contained as f64 / self.hashes.len() as f64)
+    }
+
+    /// Jaccard similarity `|self ∩ reference| / |self ∪ reference|` between two
+    /// FracMinHash sketches. Returns `None` if the sketches aren't comparable.
+    #[allow(dead_code)]
+    pub fn jaccard(&self, reference: &FracMinHashSketch) -> Option<f64> {
+        if !self.is_comparable_to(reference) { return None; }
+        let self_set: std::collections::HashSet<u64> = self.hashes.iter().copied().collect();
+        let reference_set: std::collections::HashSet<u64> = reference.hashes.iter().copied().collect();
+        let intersection = self_set.intersection(&reference_set).count();
+        let union = self_set.union(&reference_set).count();
+        if union == 0 { return Some(0.0); }
+        Some(intersection as f64 / union as f64)
+    }
+}
+
 impl <'minhash32> MinHash32 <'minhash32> {
 
     pub fn new(bytes: &'minhash32 [u8], num_hashes: usize, shingle_size: usize, seed: u64) -> Self {
@@ -31,17 +139,64 @@ impl <'minhash32> MinHash32 <'minhash32> {
             b_coefficients: b_coefficients,
             num_hashes: num_hashes,
             shingle_size: shingle_size,
+            seed: seed,
+            backend: MinhashBackend::default(),
             bytes: bytes,
         }
     }
 
+    /// Selects the hash function used to digest each shingle, overriding the
+    /// default `std::hash::Hash`-based combinator with e.g. `MinhashBackend::Xxh3`.
+    #[allow(dead_code)]
+    pub fn with_backend(mut self, backend: MinhashBackend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Digests a single shingle to a `u32` using the selected `backend`.
+    fn shingle_hash(&self, shingle: &[u8]) -> u32 {
+        match self.backend {
+            MinhashBackend::Default => {
+                let mut hasher = XxHash32::default();
+                shingle.hash(&mut hasher);
+                hasher.finish() as u32
+            }
+            MinhashBackend::Xxh3 => xxh3_64_with_seed(shingle, self.seed) as u32,
+        }
+    }
+
+    /// Builds a FracMinHash sketch: every shingle whose XXH3 hash is at or below
+    /// `max_hash = u64::MAX / scaled` is kept, rather than retaining only the
+    /// `num_hashes` smallest per-permutation values. `scaled` is clamped to `1` if
+    /// `0` is passed in, since the threshold would otherwise divide by zero; callers
+    /// should check `ConfigMinhash::scaled != 0` before choosing this over `hash`.
+    #[allow(dead_code)]
+    pub fn frac_hash(&self, scaled: u64) -> FracMinHashSketch {
+        let max_hash = u64::MAX / scaled.max(1);
+        let mut hashes = Vec::new();
+        let mut element_count = 0;
+        for shingle in self.bytes.windows(self.shingle_size) {
+            element_count += 1;
+            let hash_value = xxh3_64_with_seed(shingle, self.seed);
+            if hash_value <= max_hash {
+                hashes.push(hash_value);
+            }
+        }
+        FracMinHashSketch {
+            hashes: hashes,
+            element_count: element_count,
+            scaled: scaled,
+            seed: self.seed,
+            shingle_size: self.shingle_size,
+            backend: MinhashBackend::Xxh3,
+        }
+    }
+
     pub fn hash(&self) -> Option<Vec<u32>> {
         if self.bytes.len() < self.shingle_size { return None; }
         let mut min_hashes = vec![u32::MAX; self.num_hashes];
         for shingle in self.bytes.windows(self.shingle_size) {
-            let mut hasher = XxHash32::default();
-            shingle.hash(&mut hasher);
-            let shingle_hash = hasher.finish() as u32;
+            let shingle_hash = self.shingle_hash(shingle);
             for i in 0..self.num_hashes {
                 let a = self.a_coefficients[i];
                 let b = self.b_coefficients[i];
@@ -54,6 +209,27 @@ impl <'minhash32> MinHash32 <'minhash32> {
         Some(min_hashes)
     }
 
+    /// Like `hash`, but returns an all-`SENTINEL_HASH` signature of length
+    /// `num_hashes` instead of `None` when there are fewer than `shingle_size`
+    /// bytes to shingle, so callers building comparable signatures (e.g. across
+    /// many functions of varying size) always get a fixed-length vector back.
+    #[allow(dead_code)]
+    pub fn hash_or_sentinel(&self) -> Vec<u32> {
+        self.hash().unwrap_or_else(|| vec![SENTINEL_HASH; self.num_hashes])
+    }
+
+    /// Estimated Jaccard similarity between two `hash_or_sentinel` signatures,
+    /// treating an all-sentinel signature (too little data to shingle) as
+    /// similar to nothing, including another all-sentinel signature.
+    #[allow(dead_code)]
+    pub fn similarity(signature_a: &[u32], signature_b: &[u32]) -> f64 {
+        let is_sentinel = |signature: &[u32]| signature.iter().all(|&hash| hash == SENTINEL_HASH);
+        if is_sentinel(signature_a) || is_sentinel(signature_b) {
+            return 0.0;
+        }
+        Self::jaccard_similarity(signature_a, signature_b)
+    }
+
     #[allow(dead_code)]
     pub fn jaccard_similarity(hash1: &[u32], hash2: &[u32]) -> f64 {
         if hash1.len() != hash2.len() { return 0.0; }
@@ -66,6 +242,62 @@ impl <'minhash32> MinHash32 <'minhash32> {
         intersection as f64 / hash1.len() as f64
     }
 
+    /// Estimates the containment of `query` within `reference`: the fraction of
+    /// `query`'s per-permutation minimum hashes that also appear anywhere among
+    /// `reference`'s. Unlike `jaccard_similarity`, this is asymmetric and tolerant of
+    /// size differences between the two signatures, which makes it the better choice
+    /// when checking whether a small function's signature is embedded in a larger one
+    /// rather than comparing two signatures of comparable size.
+    #[allow(dead_code)]
+    pub fn containment_similarity(query: &[u32], reference: &[u32]) -> f64 {
+        if query.is_empty() { return 0.0; }
+        let reference_set: std::collections::HashSet<u32> = reference.iter().copied().collect();
+        let contained = query.iter().filter(|value| reference_set.contains(value)).count();
+        contained as f64 / query.len() as f64
+    }
+
+    /// Computes the pairwise Jaccard similarity matrix across every signature in
+    /// `signatures`, for callers that need an all-vs-all comparison (e.g. clustering)
+    /// rather than a single pair.
+    #[allow(dead_code)]
+    pub fn pairwise_jaccard_similarity(signatures: &[Vec<u32>]) -> Vec<Vec<f64>> {
+        let count = signatures.len();
+        let mut matrix = vec![vec![0.0; count]; count];
+        for i in 0..count {
+            matrix[i][i] = 1.0;
+            for j in (i + 1)..count {
+                let score = Self::jaccard_similarity(&signatures[i], &signatures[j]);
+                matrix[i][j] = score;
+                matrix[j][i] = score;
+            }
+        }
+        matrix
+    }
+
+    /// Splits `signature` into `bands` equal-length bands and hashes each band's
+    /// rows together into a single 64-bit bucket key, for an `LSHIndex`-style
+    /// caller that wants to bucket signatures without an O(n^2) all-pairs
+    /// comparison. Returns an error if `signature.len()` isn't evenly divisible
+    /// by `bands`.
+    #[allow(dead_code)]
+    pub fn band_keys(signature: &[u32], bands: usize) -> Result<Vec<u64>, std::io::Error> {
+        if bands == 0 || signature.len() % bands != 0 {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("signature length {} is not evenly divisible by {} bands", signature.len(), bands),
+            ));
+        }
+        let rows = signature.len() / bands;
+        let mut keys = Vec::with_capacity(bands);
+        for band_index in 0..bands {
+            let start = band_index * rows;
+            let mut hasher = XxHash32::default();
+            signature[start..start + rows].hash(&mut hasher);
+            keys.push(((band_index as u64) << 32) | hasher.finish() as u64);
+        }
+        Ok(keys)
+    }
+
     pub fn hexdigest(&self) -> Option<String> {
         self.hash().map(|minhash| {
             minhash.iter()
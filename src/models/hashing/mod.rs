@@ -0,0 +1,103 @@
+pub mod sha256;
+pub mod sha1;
+pub mod md5;
+pub mod tlsh;
+pub mod minhash;
+pub mod xxhash;
+pub mod sbt;
+pub mod hyperloglog;
+pub mod lsh;
+pub mod ssdeep;
+
+use std::fmt;
+use std::str::FromStr;
+use sha256::SHA256;
+use sha1::SHA1;
+use md5::MD5;
+use tlsh::TLSH;
+use xxhash::XXHash;
+
+/// Minimum byte size `Hasher::Tlsh` requires before TLSH will emit a digest,
+/// matching the default used elsewhere in the crate (see `formats::file`).
+const TLSH_MININUM_BYTE_SIZE: usize = 50;
+
+/// Default seed `Hasher::Xxhash` uses, matching the `ConfigXXHash` seed `Config::new` sets.
+const XXHASH_SEED: u64 = 0;
+
+/// Selects which hashing algorithm to run, so callers (e.g. the `--hash` CLI flag
+/// or the Python bindings) can pick one by name instead of calling each struct
+/// under `models::hashing` directly. Adding a new algorithm is a one-variant
+/// change: a new enum case, a `from_str` alias, a `Display` name, and a
+/// `hexdigest` arm.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Hasher {
+    Sha256,
+    Sha1,
+    Md5,
+    /// TLSH, a fuzzy/similarity hash rather than a cryptographic one.
+    Tlsh,
+    /// XXH3, a fast non-cryptographic hash suited to cheap dedup/bucketing keys.
+    Xxhash,
+}
+
+impl Hasher {
+    /// Computes the hex digest of `bytes` under this algorithm.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Some(String)` with the hex digest, or `None` if the algorithm
+    /// declines to hash `bytes` (e.g. `Tlsh` below its minimum byte size).
+    pub fn hexdigest(&self, bytes: &[u8]) -> Option<String> {
+        match self {
+            Hasher::Sha256 => SHA256::new(bytes).hexdigest(),
+            Hasher::Sha1 => SHA1::new(bytes).hexdigest(),
+            Hasher::Md5 => MD5::new(bytes).hexdigest(),
+            Hasher::Tlsh => TLSH::new(bytes, TLSH_MININUM_BYTE_SIZE).hexdigest(),
+            Hasher::Xxhash => XXHash::new(bytes, XXHASH_SEED, 0).hexdigest(),
+        }
+    }
+}
+
+impl fmt::Display for Hasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Hasher::Sha256 => "sha256",
+            Hasher::Sha1 => "sha1",
+            Hasher::Md5 => "md5",
+            Hasher::Tlsh => "tlsh",
+            Hasher::Xxhash => "xxhash",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for Hasher {
+    type Err = UnknownHasher;
+
+    /// Parses an algorithm name, accepting a small set of common aliases
+    /// (`"sha-256"` alongside `"sha256"`, etc.) case-insensitively.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "sha256" | "sha-256" => Ok(Hasher::Sha256),
+            "sha1" | "sha-1" => Ok(Hasher::Sha1),
+            "md5" => Ok(Hasher::Md5),
+            "tlsh" => Ok(Hasher::Tlsh),
+            "xxhash" | "xxh3" | "xxh3-64" => Ok(Hasher::Xxhash),
+            _ => Err(UnknownHasher { name: s.to_string() }),
+        }
+    }
+}
+
+/// Returned by `Hasher::from_str` when `name` doesn't match any known algorithm.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnknownHasher {
+    pub name: String,
+}
+
+impl fmt::Display for UnknownHasher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown hash algorithm '{}'", self.name)
+    }
+}
+
+impl std::error::Error for UnknownHasher {}
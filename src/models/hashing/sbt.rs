@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+/// Number of bits backing each node's Bloom filter. Sized for the handful of MinHash
+/// values (tens to low hundreds) a single signature contributes.
+const FILTER_BITS: usize = 2048;
+
+/// A fixed-size Bloom filter over `u32` MinHash values, used as the per-node
+/// membership summary in a `SequenceBloomTree`.
+///
+/// A single hash (the value's own `u32`, reduced mod the bit count) is enough here:
+/// the values entering the filter are themselves already well-distributed MinHash
+/// outputs, so a second internal hash buys little at the cost of extra shifts.
+#[derive(Clone)]
+struct BloomFilter {
+    bits: Vec<u64>,
+}
+
+impl BloomFilter {
+    fn new() -> Self {
+        Self {
+            bits: vec![0u64; FILTER_BITS / 64],
+        }
+    }
+
+    fn index(value: u32) -> (usize, u64) {
+        let bit = value as usize % FILTER_BITS;
+        (bit / 64, 1u64 << (bit % 64))
+    }
+
+    fn insert(&mut self, value: u32) {
+        let (word, mask) = Self::index(value);
+        self.bits[word] |= mask;
+    }
+
+    fn contains(&self, value: u32) -> bool {
+        let (word, mask) = Self::index(value);
+        self.bits[word] & mask != 0
+    }
+
+    /// Merges `other` into `self` in place, producing the union filter a parent node
+    /// uses to summarize its children.
+    fn union_with(&mut self, other: &BloomFilter) {
+        for (a, b) in self.bits.iter_mut().zip(other.bits.iter()) {
+            *a |= b;
+        }
+    }
+}
+
+/// A single node in the `SequenceBloomTree`: either a leaf carrying a signature's id,
+/// or an internal node summarizing its two children's filters.
+enum SbtNode {
+    Leaf {
+        id: String,
+        filter: BloomFilter,
+    },
+    Internal {
+        filter: BloomFilter,
+        left: Box<SbtNode>,
+        right: Box<SbtNode>,
+    },
+}
+
+impl SbtNode {
+    fn filter(&self) -> &BloomFilter {
+        match self {
+            SbtNode::Leaf { filter, .. } => filter,
+            SbtNode::Internal { filter, .. } => filter,
+        }
+    }
+
+    /// Descends the node, collecting the ids of every leaf whose filter contains at
+    /// least `threshold` of `query`'s distinct values. A node whose own filter
+    /// already fails the threshold is pruned without visiting its children, since no
+    /// leaf beneath it can contain more set bits than its own union filter does.
+    fn query(&self, query: &[u32], threshold: f64, out: &mut Vec<String>) {
+        let required = ((query.len() as f64) * threshold).ceil() as usize;
+        let hits = query.iter().filter(|value| self.filter().contains(**value)).count();
+        if hits < required {
+            return;
+        }
+
+        match self {
+            SbtNode::Leaf { id, .. } => out.push(id.clone()),
+            SbtNode::Internal { left, right, .. } => {
+                left.query(query, threshold, out);
+                right.query(query, threshold, out);
+            }
+        }
+    }
+}
+
+/// A Sequence Bloom Tree over MinHash signatures: a binary tree of Bloom filters,
+/// where every internal node's filter is the union of its children's, letting a
+/// similarity query prune whole subtrees that can't possibly contain a hit instead
+/// of scanning every signature linearly.
+///
+/// Construction is batched: signatures accumulate via `insert` and the tree itself is
+/// assembled once by `build`, since a Sequence Bloom Tree is normally built over a
+/// corpus collected up front rather than rebalanced on every insert.
+pub struct SequenceBloomTree {
+    pending: Vec<(String, Vec<u32>)>,
+    root: Option<SbtNode>,
+}
+
+impl SequenceBloomTree {
+    pub fn new() -> Self {
+        Self {
+            pending: Vec::new(),
+            root: None,
+        }
+    }
+
+    /// Queues a signature for inclusion the next time `build` runs.
+    #[allow(dead_code)]
+    pub fn insert(&mut self, id: String, minhash: &[u32]) {
+        self.pending.push((id, minhash.to_vec()));
+    }
+
+    /// Assembles the tree from every signature queued by `insert` so far, replacing
+    /// any previously built tree.
+    #[allow(dead_code)]
+    pub fn build(&mut self) {
+        let mut nodes: Vec<SbtNode> = self.pending.iter().map(|(id, minhash)| {
+            let mut filter = BloomFilter::new();
+            let unique: HashSet<u32> = minhash.iter().copied().collect();
+            for value in unique {
+                filter.insert(value);
+            }
+            SbtNode::Leaf { id: id.clone(), filter }
+        }).collect();
+
+        while nodes.len() > 1 {
+            let mut next = Vec::with_capacity((nodes.len() + 1) / 2);
+            let mut iter = nodes.into_iter();
+            while let Some(left) = iter.next() {
+                match iter.next() {
+                    Some(right) => {
+                        let mut filter = left.filter().clone();
+                        filter.union_with(right.filter());
+                        next.push(SbtNode::Internal {
+                            filter,
+                            left: Box::new(left),
+                            right: Box::new(right),
+                        });
+                    },
+                    None => next.push(left),
+                }
+            }
+            nodes = next;
+        }
+
+        self.root = nodes.into_iter().next();
+    }
+
+    /// Returns the ids of every indexed signature estimated to share at least
+    /// `threshold` (0.0-1.0) of `minhash`'s distinct values, without linearly
+    /// scanning signatures that the tree can rule out by their ancestors' filters.
+    #[allow(dead_code)]
+    pub fn query(&self, minhash: &[u32], threshold: f64) -> Vec<String> {
+        let unique: Vec<u32> = minhash.iter().copied().collect::<HashSet<u32>>().into_iter().collect();
+        let mut out = Vec::new();
+        if let Some(root) = &self.root {
+            root.query(&unique, threshold, &mut out);
+        }
+        out
+    }
+}
@@ -0,0 +1,22 @@
+use ring::digest;
+use crate::models::binary::Binary;
+
+/// SHA-1, kept for compatibility with corpora/tooling keyed by it; `ring` marks
+/// the underlying algorithm "legacy use only" and `SHA256` should be preferred
+/// for anything where collision resistance matters.
+pub struct SHA1<'sha1> {
+    pub bytes: &'sha1 [u8],
+}
+
+impl<'sha1> SHA1<'sha1> {
+    #[allow(dead_code)]
+    pub fn new(bytes: &'sha1 [u8]) -> Self {
+        Self { bytes }
+    }
+
+    #[allow(dead_code)]
+    pub fn hexdigest(&self) -> Option<String> {
+        let digest = digest::digest(&digest::SHA1_FOR_LEGACY_USE_ONLY, self.bytes);
+        Some(Binary::to_hex(digest.as_ref()))
+    }
+}
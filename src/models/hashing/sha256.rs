@@ -1,5 +1,10 @@
 use ring::digest;
 use crate::models::binary::Binary;
+use crate::io::{IoError, Read};
+
+/// Chunk size used when hashing incrementally from a `Read` rather than a
+/// fully-materialized slice.
+const READER_CHUNK_SIZE: usize = 64 * 1024;
 
 pub struct SHA256 <'sha256> {
     pub bytes: &'sha256 [u8],
@@ -16,7 +21,33 @@ impl <'sha256> SHA256 <'sha256> {
 
     #[allow(dead_code)]
     pub fn hexdigest(&self) -> Option<String> {
+        return Some(Binary::to_hex(&self.digest()));
+    }
+
+    /// Computes the raw 32-byte SHA-256 digest, for callers building further
+    /// binary structures (e.g. Merkle tree nodes) that need the bytes rather
+    /// than a hex string.
+    pub fn digest(&self) -> [u8; 32] {
         let digest = digest::digest(&digest::SHA256, &self.bytes);
-        return Some(Binary::to_hex(digest.as_ref()));
+        let mut result = [0u8; 32];
+        result.copy_from_slice(digest.as_ref());
+        result
+    }
+
+    /// Computes a SHA-256 hex digest incrementally over `reader`, in fixed-size
+    /// chunks, so the caller never needs to hold the whole input in memory at
+    /// once (e.g. hashing a `CachedFile`-backed payload larger than RAM).
+    #[allow(dead_code)]
+    pub fn hexdigest_reader<R: Read>(reader: &mut R) -> Result<String, IoError> {
+        let mut context = digest::Context::new(&digest::SHA256);
+        let mut chunk = [0u8; READER_CHUNK_SIZE];
+        loop {
+            let n = reader.read(&mut chunk)?;
+            if n == 0 {
+                break;
+            }
+            context.update(&chunk[..n]);
+        }
+        Ok(Binary::to_hex(context.finish().as_ref()))
     }
 }
@@ -0,0 +1,209 @@
+/// Base64-style alphabet ssdeep-style signatures are built from.
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Smallest block size a signature will use; doubled until the input no
+/// longer produces an overlong signature at that size.
+const MIN_BLOCKSIZE: u64 = 3;
+
+/// Target signature length `block_size` is chosen to aim for, mirroring
+/// ssdeep's own `SPAMSUM_LENGTH`.
+const SPAMSUM_LENGTH: usize = 64;
+
+/// Width of the rolling checksum's trailing window.
+const ROLLING_WINDOW: usize = 7;
+
+/// Seed each block hash accumulator starts from, so an empty run between
+/// trigger points still contributes a deterministic character.
+const HASH_SEED: u32 = 0x28021967;
+
+/// A rolling checksum over the trailing `ROLLING_WINDOW` bytes, used to find
+/// context-triggered reset points independent of their absolute position in
+/// the input (the same local context always triggers at the same points,
+/// which is what makes the resulting signature tolerant of insertions and
+/// deletions elsewhere in the file).
+struct RollingChecksum {
+    window: [u8; ROLLING_WINDOW],
+    position: usize,
+    h1: u32,
+    h2: u32,
+    h3: u32,
+}
+
+impl RollingChecksum {
+    fn new() -> Self {
+        Self {
+            window: [0u8; ROLLING_WINDOW],
+            position: 0,
+            h1: 0,
+            h2: 0,
+            h3: 0,
+        }
+    }
+
+    /// Feeds one more byte in and returns the updated checksum.
+    fn update(&mut self, byte: u8) -> u32 {
+        let dropped = self.window[self.position];
+        self.window[self.position] = byte;
+        self.position = (self.position + 1) % ROLLING_WINDOW;
+
+        self.h2 = self.h2.wrapping_sub(self.h1);
+        self.h2 = self.h2.wrapping_add(ROLLING_WINDOW as u32 * byte as u32);
+
+        self.h1 = self.h1.wrapping_add(byte as u32);
+        self.h1 = self.h1.wrapping_sub(dropped as u32);
+
+        self.h3 = (self.h3 << 5) ^ (self.h3 >> 27);
+        self.h3 ^= byte as u32;
+
+        self.h1.wrapping_add(self.h2).wrapping_add(self.h3)
+    }
+}
+
+/// A context-triggered piecewise hash (CTPH), in the spirit of ssdeep: a
+/// rolling checksum over the input picks reset points that depend only on
+/// local context, and a separate accumulator hashes each piece between
+/// resets down to one base64 character, at two block sizes (`block_size` and
+/// `block_size * 2`) so the comparison in `compare` can line either up
+/// against a signature computed from a slightly different-sized input.
+///
+/// This is this crate's own implementation and is not guaranteed to be
+/// bit-compatible with the reference `ssdeep` tool's output, mirroring the
+/// `ConfigTLSH` distance/decode duplication elsewhere in this codebase.
+pub struct SSDEEP<'ssdeep> {
+    pub bytes: &'ssdeep [u8],
+}
+
+impl<'ssdeep> SSDEEP<'ssdeep> {
+    #[allow(dead_code)]
+    pub fn new(bytes: &'ssdeep [u8]) -> Self {
+        Self { bytes }
+    }
+
+    /// Picks the block size so the piecewise signature at that size lands
+    /// near `SPAMSUM_LENGTH` characters, doubling from `MIN_BLOCKSIZE` until
+    /// it does.
+    fn block_size(&self) -> u64 {
+        let mut block_size = MIN_BLOCKSIZE;
+        while (self.bytes.len() as u64) / block_size > SPAMSUM_LENGTH as u64 {
+            block_size *= 2;
+        }
+        block_size
+    }
+
+    /// Builds the piecewise signature for one block size: the rolling
+    /// checksum is reset to look for `checksum % block_size == block_size - 1`,
+    /// and each time it fires, the block hash accumulated since the last
+    /// trigger is appended as one base64 character and reset.
+    fn piece_hash(&self, block_size: u64) -> String {
+        let mut signature = String::new();
+        let mut rolling = RollingChecksum::new();
+        let mut block_hash: u32 = HASH_SEED;
+
+        for &byte in self.bytes {
+            block_hash = block_hash.wrapping_mul(63).wrapping_add(byte as u32);
+            let checksum = rolling.update(byte);
+            if (checksum as u64) % block_size == block_size - 1 {
+                signature.push(BASE64[(block_hash & 0x3f) as usize] as char);
+                block_hash = HASH_SEED;
+            }
+        }
+
+        if block_hash != HASH_SEED || signature.is_empty() {
+            signature.push(BASE64[(block_hash & 0x3f) as usize] as char);
+        }
+
+        signature
+    }
+
+    /// Computes the ssdeep-style signature `"block_size:piece_hash:piece_hash_2x"`.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if the input is empty, since there is nothing to
+    /// fuzzy-hash.
+    #[allow(dead_code)]
+    pub fn hexdigest(&self) -> Option<String> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+        let block_size = self.block_size();
+        Some(format!(
+            "{}:{}:{}",
+            block_size,
+            self.piece_hash(block_size),
+            self.piece_hash(block_size * 2)
+        ))
+    }
+
+    /// Compares two `"block_size:hash:hash"` signatures, returning a 0-100
+    /// similarity score. Signatures whose block sizes aren't equal or one
+    /// double the other aren't comparable (they were built from pieces of
+    /// unrelated sizes), matching ssdeep's own block-size alignment rule.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if either signature is malformed or the block sizes
+    /// aren't alignable.
+    #[allow(dead_code)]
+    pub fn compare(signature_a: &str, signature_b: &str) -> Option<u32> {
+        let (block_size_a, hash_a1, hash_a2) = Self::parse(signature_a)?;
+        let (block_size_b, hash_b1, hash_b2) = Self::parse(signature_b)?;
+
+        let score = if block_size_a == block_size_b {
+            Self::piece_similarity(hash_a1, hash_b1)
+        } else if block_size_a == block_size_b * 2 {
+            Self::piece_similarity(hash_a1, hash_b2)
+        } else if block_size_b == block_size_a * 2 {
+            Self::piece_similarity(hash_a2, hash_b1)
+        } else {
+            return None;
+        };
+
+        Some(score)
+    }
+
+    fn parse(signature: &str) -> Option<(u64, &str, &str)> {
+        let mut parts = signature.splitn(3, ':');
+        let block_size = parts.next()?.parse::<u64>().ok()?;
+        let hash1 = parts.next()?;
+        let hash2 = parts.next()?;
+        Some((block_size, hash1, hash2))
+    }
+
+    /// Normalized similarity between two piece hashes, via their edit
+    /// distance relative to the longer string's length.
+    fn piece_similarity(a: &str, b: &str) -> u32 {
+        if a.is_empty() && b.is_empty() {
+            return 100;
+        }
+        let distance = Self::edit_distance(a, b);
+        let longest = a.len().max(b.len());
+        if longest == 0 {
+            return 100;
+        }
+        (100 - ((distance * 100) / longest).min(100)) as u32
+    }
+
+    /// Standard Levenshtein edit distance between two character sequences.
+    fn edit_distance(a: &str, b: &str) -> usize {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let mut row: Vec<usize> = (0..=b.len()).collect();
+
+        for i in 1..=a.len() {
+            let mut previous_diagonal = row[0];
+            row[0] = i;
+            for j in 1..=b.len() {
+                let previous_above = row[j];
+                row[j] = if a[i - 1] == b[j - 1] {
+                    previous_diagonal
+                } else {
+                    1 + previous_diagonal.min(row[j]).min(row[j - 1])
+                };
+                previous_diagonal = previous_above;
+            }
+        }
+
+        row[b.len()]
+    }
+}
@@ -52,4 +52,70 @@ impl <'tlsh> TLSH <'tlsh> {
         tlsh::hash_buf(&self.bytes).ok().map(|h| h.to_string())
     }
 
+    /// Decodes a TLSH hex digest into its header fields (length bucket, the two
+    /// Q-ratio quartiles) and its 128 2-bit body buckets.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if `digest` isn't a well-formed 70-character TLSH hex digest.
+    fn decode(digest: &str) -> Option<(u8, u8, u8, [u8; 128])> {
+        if digest.len() != 70 { return None; }
+        let mut bytes = [0u8; 35];
+        for (index, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&digest[index * 2..index * 2 + 2], 16).ok()?;
+        }
+        let length_bucket = bytes[1];
+        let q1_ratio = bytes[2] >> 4;
+        let q2_ratio = bytes[2] & 0x0f;
+        let mut buckets = [0u8; 128];
+        for (index, body_byte) in bytes[3..35].iter().enumerate() {
+            for nibble in 0..4 {
+                buckets[index * 4 + nibble] = (body_byte >> (6 - nibble * 2)) & 0x3;
+            }
+        }
+        Some((length_bucket, q1_ratio, q2_ratio, buckets))
+    }
+
+    /// Wrapped distance between two 4-bit Q-ratio quartile values, scaled as the
+    /// TLSH header component does.
+    fn qratio_distance(a: u8, b: u8) -> u32 {
+        let diff = (a as i32 - b as i32).unsigned_abs();
+        diff.min(16 - diff) * 12
+    }
+
+    /// Computes the TLSH distance between this hash's digest and `other_digest`.
+    /// Lower scores mean more similar; `0` means identical.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if either digest is missing or malformed.
+    #[allow(dead_code)]
+    pub fn compare(&self, other_digest: &str) -> Option<u32> {
+        let digest = self.hexdigest()?;
+        Self::distance(&digest, other_digest)
+    }
+
+    /// Computes the TLSH distance between two hex digests directly, without
+    /// needing a `TLSH` instance over the original bytes.
+    ///
+    /// # Returns
+    ///
+    /// Returns `None` if either digest is missing or malformed.
+    #[allow(dead_code)]
+    pub fn distance(digest_a: &str, digest_b: &str) -> Option<u32> {
+        let (length_a, q1_a, q2_a, body_a) = Self::decode(digest_a)?;
+        let (length_b, q1_b, q2_b, body_b) = Self::decode(digest_b)?;
+
+        let mut distance = (length_a as i32 - length_b as i32).unsigned_abs() * 12;
+        distance += Self::qratio_distance(q1_a, q1_b);
+        distance += Self::qratio_distance(q2_a, q2_b);
+
+        for (value_a, value_b) in body_a.iter().zip(body_b.iter()) {
+            let diff = (*value_a as i32 - *value_b as i32).unsigned_abs();
+            distance += if diff > 1 { 6 } else { diff };
+        }
+
+        Some(distance)
+    }
+
 }
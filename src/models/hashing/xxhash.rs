@@ -0,0 +1,42 @@
+use xxhash_rust::xxh3::{xxh3_64_with_seed, xxh3_128_with_seed};
+use crate::models::binary::Binary;
+
+/// A fast, non-cryptographic XXH3 hash backend, offered as a cheaper alternative to
+/// SHA-256/TLSH/MinHash when a quick dedup key is enough. XXH3 processes input in
+/// 16-byte lanes, mixing each lane with a secret/seed via multiply-and-xor
+/// accumulators and folding the accumulator lanes at the end.
+pub struct XXHash <'xxhash> {
+    pub bytes: &'xxhash [u8],
+    pub seed: u64,
+    pub minimum_byte_size: usize,
+}
+
+impl <'xxhash> XXHash <'xxhash> {
+
+    #[allow(dead_code)]
+    pub fn new(bytes: &'xxhash [u8], seed: u64, minimum_byte_size: usize) -> Self {
+        Self {
+            bytes: bytes,
+            seed: seed,
+            minimum_byte_size: minimum_byte_size,
+        }
+    }
+
+    /// Computes the 64-bit XXH3 hex digest, or `None` if `bytes` is shorter than
+    /// `minimum_byte_size`.
+    #[allow(dead_code)]
+    pub fn hexdigest(&self) -> Option<String> {
+        if self.bytes.len() < self.minimum_byte_size { return None; }
+        let digest = xxh3_64_with_seed(self.bytes, self.seed).to_be_bytes();
+        return Some(Binary::to_hex(&digest));
+    }
+
+    /// Computes the 128-bit XXH3 hex digest, or `None` if `bytes` is shorter than
+    /// `minimum_byte_size`.
+    #[allow(dead_code)]
+    pub fn hexdigest128(&self) -> Option<String> {
+        if self.bytes.len() < self.minimum_byte_size { return None; }
+        let digest = xxh3_128_with_seed(self.bytes, self.seed).to_be_bytes();
+        return Some(Binary::to_hex(&digest));
+    }
+}
@@ -1,4 +1,4 @@
-use std::io::Error;
+use std::io::{Error, ErrorKind};
 use crate::models::binary::Binary;
 use serde::{Deserialize, Serialize};
 use serde_json;
@@ -49,6 +49,14 @@ impl Instruction {
         Ok(result)
     }
 
+    /// Converts the instruction into a compact binary representation, cheaper to
+    /// decode than `json()` when writing large numbers of instructions to disk.
+    #[allow(dead_code)]
+    pub fn bincode(&self) -> Result<Vec<u8>, Error> {
+        let raw = self.process();
+        bincode::serialize(&raw).map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))
+    }
+
     #[allow(dead_code)]
     pub fn print(&self) {
         if let Ok(json) = self.json() {
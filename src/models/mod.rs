@@ -5,4 +5,11 @@ pub mod binary;
 pub mod minhash;
 pub mod debug;
 pub mod terminal;
-pub mod symbols;
\ No newline at end of file
+pub mod symbols;
+pub mod hashing;
+pub mod cfg;
+pub mod pattern;
+pub mod compression;
+pub mod sink;
+pub mod nostd;
+pub mod serialization;
\ No newline at end of file
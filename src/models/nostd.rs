@@ -0,0 +1,66 @@
+//! `std`/`no-std` error shim, introduced so the core analysis path (starting
+//! with `Function::json`) can compile under a `no-std` build for embedding in
+//! sandboxes, firmware-analysis agents, or WASM scanners that have no OS.
+//! Behind the (default) `std` feature this is just `std::io::{Error,
+//! ErrorKind}`; under `no-std` it's a minimal, `alloc`-only stand-in with the
+//! same construction API so call sites don't have to change beyond their
+//! `use`.
+//!
+//! `global::Mode` is left on `std::io::Error` and is not ported. File I/O (`models::terminal::
+//! config`'s TOML loading, `Graph::checkpoint_to`/`resume_from`) and the pyo3
+//! `Function` wrapper are also out of scope here and stay `std`-only; porting
+//! `models::hashing`'s `HashMap`-based shingle/band maps to `no-std` is
+//! tracked as follow-on work, not done in this pass.
+
+#[cfg(feature = "std")]
+pub use std::io::{Error, ErrorKind};
+
+#[cfg(not(feature = "std"))]
+pub use self::no_std_error::{Error, ErrorKind};
+
+#[cfg(not(feature = "std"))]
+mod no_std_error {
+    extern crate alloc;
+    use alloc::string::String;
+    use core::fmt;
+
+    /// Mirrors the subset of `std::io::ErrorKind` this crate's core analysis
+    /// path actually constructs.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum ErrorKind {
+        InvalidInput,
+        InvalidData,
+        UnexpectedEof,
+        Other,
+    }
+
+    /// A minimal, allocator-only stand-in for `std::io::Error`, carrying just
+    /// a kind and a message string.
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+        message: String,
+    }
+
+    impl Error {
+        pub fn new<M: Into<String>>(kind: ErrorKind, message: M) -> Self {
+            Self { kind, message: message.into() }
+        }
+
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}", self.message)
+        }
+    }
+
+    impl From<serde_json::Error> for Error {
+        fn from(error: serde_json::Error) -> Self {
+            Self::new(ErrorKind::InvalidData, alloc::format!("{}", error))
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use lief::pe::headers::MachineType;
+use crate::config::Config;
+use crate::genomics::Genome;
+
+pub const PATTERN_MAX_MATCH_SIZE: usize = 32;
+
+/// Matches function prologues against the `Genome` patterns configured for
+/// each architecture, instead of a hardcoded set of `regex::bytes::Regex`.
+///
+/// Patterns are expressed in the nibble-with-wildcard syntax `Genome` already
+/// parses (`?` = wildcard nibble, hex digit = fixed nibble) and come from
+/// `Config::disassembler.prologues`, so adding an architecture or a custom
+/// prologue is a configuration change rather than a code change.
+pub struct Pattern {
+    machine: MachineType,
+    amd64: Vec<Genome>,
+    i386: Vec<Genome>,
+}
+
+impl Pattern {
+    pub fn new(machine: MachineType, config: &Config) -> Self {
+        let amd64 = config.disassembler.prologues.amd64
+            .iter()
+            .map(|pattern| Genome::new(pattern, 0).unwrap_or_else(|e| panic!("AMD64 Pattern: {}", e)))
+            .collect();
+
+        let i386 = config.disassembler.prologues.i386
+            .iter()
+            .map(|pattern| Genome::new(pattern, 0).unwrap_or_else(|e| panic!("I386 Pattern: {}", e)))
+            .collect();
+
+        Self {
+            machine,
+            amd64,
+            i386,
+        }
+    }
+
+    pub fn is_prologue(&self, bytes: &[u8]) -> bool {
+        match self.machine {
+            MachineType::AMD64 => {
+                self.amd64.iter().any(|genome| genome.matches_prefix(bytes))
+            },
+            MachineType::I386 => {
+                self.i386.iter().any(|genome| genome.matches_prefix(bytes))
+            },
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// Selects which wire encoding a `*Json` struct's `cbor()`/`json()` pair (or
+/// an output-layer caller juggling both) should use. JSON stays the default
+/// everywhere for readability; CBOR is opt-in for callers processing large
+/// corpora of functions/blocks who want a smaller, faster-to-parse artifact
+/// from the same serde model with no schema duplication.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum SerializationFormat {
+    /// Pretty/compact JSON via `serde_json`. The default.
+    Json,
+    /// Binary CBOR via `ciborium`.
+    Cbor,
+}
@@ -0,0 +1,69 @@
+use std::fs::{File, OpenOptions};
+use std::io::{Error, ErrorKind, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use crate::models::controlflow::block::BlockJson;
+use crate::models::controlflow::signature::SignatureJson;
+use crate::models::sink::Sink;
+
+/// Streams block/signature records as newline-delimited JSON into a local
+/// file, batching writes and flushing every `flush_count` records or
+/// `flush_interval` elapsed, whichever comes first.
+pub struct FileSink {
+    file: File,
+    buffer: Vec<u8>,
+    pending: usize,
+    flush_count: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
+}
+
+impl FileSink {
+    /// Opens (creating if necessary) `path` for appending.
+    pub fn new(path: &Path, flush_count: usize, flush_interval: Duration) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file,
+            buffer: Vec::new(),
+            pending: 0,
+            flush_count,
+            flush_interval,
+            last_flush: Instant::now(),
+        })
+    }
+
+    fn enqueue(&mut self, line: &[u8]) -> Result<(), Error> {
+        self.buffer.extend_from_slice(line);
+        self.buffer.push(b'\n');
+        self.pending += 1;
+        if self.pending >= self.flush_count || self.last_flush.elapsed() >= self.flush_interval {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for FileSink {
+    fn send_block(&mut self, block: &BlockJson) -> Result<(), Error> {
+        let line = serde_json::to_vec(block)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        self.enqueue(&line)
+    }
+
+    fn send_signature(&mut self, signature: &SignatureJson) -> Result<(), Error> {
+        let line = serde_json::to_vec(signature)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        self.enqueue(&line)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.buffer.is_empty() {
+            self.file.write_all(&self.buffer)?;
+            self.buffer.clear();
+            self.pending = 0;
+        }
+        self.file.flush()?;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+pub mod file;
+pub mod stdout;
+pub mod tcp;
+
+use std::io::Error;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::models::controlflow::block::BlockJson;
+use crate::models::controlflow::signature::SignatureJson;
+use file::FileSink;
+use stdout::StdoutSink;
+use tcp::TcpSink;
+
+/// How many records a batching sink buffers before flushing, unless the
+/// caller overrides it.
+pub const DEFAULT_FLUSH_COUNT: usize = 128;
+/// How long a batching sink waits before flushing a non-empty, below-count
+/// buffer, unless the caller overrides it.
+pub const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A destination `Block`/`Signature` records are streamed to as they are
+/// produced, instead of being buffered into one JSON blob and written out at
+/// the end of a run. Mirrors the sync/async client split used for talking to
+/// a remote server: `send_block`/`send_signature` block until the record is
+/// written or batched for the next flush, while `send_block_async` hands it to a background thread
+/// and returns immediately.
+pub trait Sink: Send {
+    /// Sends a block record, blocking until it is written (or batched for the
+    /// next flush).
+    fn send_block(&mut self, block: &BlockJson) -> Result<(), Error>;
+    /// Sends a signature record, blocking until it is written (or batched for
+    /// the next flush).
+    fn send_signature(&mut self, signature: &SignatureJson) -> Result<(), Error>;
+    /// Flushes any buffered records immediately instead of waiting for the
+    /// configured batch count/interval.
+    fn flush(&mut self) -> Result<(), Error>;
+
+    /// Non-blocking counterpart to `send_block`: hands the record to a
+    /// background thread and returns immediately, for callers that can't
+    /// afford to stall a disassembly pipeline on sink I/O (e.g. a slow or
+    /// retrying network sink).
+    fn send_block_async(self: Arc<Mutex<Self>>, block: BlockJson)
+    where
+        Self: 'static + Sized,
+    {
+        std::thread::spawn(move || {
+            if let Ok(mut sink) = self.lock() {
+                let _ = sink.send_block(&block);
+            }
+        });
+    }
+}
+
+/// Which `Sink` (if any) a `Graph` streams its `Block`/`Signature` output to.
+/// Selected on `GraphOptions` so it round-trips through configuration the
+/// same way `compression_algorithm`/`hash_algorithms` do.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum SinkKind {
+    /// Don't stream; callers collect `BlockJson`/`SignatureJson` the usual way.
+    None,
+    /// Append newline-delimited JSON records to a local file at this path.
+    File(String),
+    /// Write newline-delimited JSON records to stdout.
+    Stdout,
+    /// Batch-POST newline-delimited JSON records to a remote corpus server
+    /// reachable at this `host:port` address.
+    Tcp(String),
+}
+
+/// Opens the concrete `Sink` implementation selected by `kind`, or `None` if
+/// streaming isn't configured.
+///
+/// # Returns
+///
+/// Returns `Err` if the selected sink can't be opened (e.g. the file path is
+/// not writable).
+pub fn open(kind: &SinkKind) -> Result<Option<Box<dyn Sink>>, Error> {
+    match kind {
+        SinkKind::None => Ok(None),
+        SinkKind::File(path) => Ok(Some(Box::new(FileSink::new(
+            Path::new(path),
+            DEFAULT_FLUSH_COUNT,
+            DEFAULT_FLUSH_INTERVAL,
+        )?))),
+        SinkKind::Stdout => Ok(Some(Box::new(StdoutSink::new(
+            DEFAULT_FLUSH_COUNT,
+            DEFAULT_FLUSH_INTERVAL,
+        )))),
+        SinkKind::Tcp(address) => Ok(Some(Box::new(TcpSink::new(
+            address,
+            "/",
+            DEFAULT_FLUSH_COUNT,
+            DEFAULT_FLUSH_INTERVAL,
+        )))),
+    }
+}
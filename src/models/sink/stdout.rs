@@ -0,0 +1,62 @@
+use std::io::{Error, ErrorKind, Write};
+use std::time::{Duration, Instant};
+use crate::models::controlflow::block::BlockJson;
+use crate::models::controlflow::signature::SignatureJson;
+use crate::models::sink::Sink;
+
+/// Streams block/signature records as newline-delimited JSON to stdout,
+/// batching writes the same way `FileSink` does.
+pub struct StdoutSink {
+    buffer: Vec<u8>,
+    pending: usize,
+    flush_count: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
+}
+
+impl StdoutSink {
+    pub fn new(flush_count: usize, flush_interval: Duration) -> Self {
+        Self {
+            buffer: Vec::new(),
+            pending: 0,
+            flush_count,
+            flush_interval,
+            last_flush: Instant::now(),
+        }
+    }
+
+    fn enqueue(&mut self, line: &[u8]) -> Result<(), Error> {
+        self.buffer.extend_from_slice(line);
+        self.buffer.push(b'\n');
+        self.pending += 1;
+        if self.pending >= self.flush_count || self.last_flush.elapsed() >= self.flush_interval {
+            self.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Sink for StdoutSink {
+    fn send_block(&mut self, block: &BlockJson) -> Result<(), Error> {
+        let line = serde_json::to_vec(block)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        self.enqueue(&line)
+    }
+
+    fn send_signature(&mut self, signature: &SignatureJson) -> Result<(), Error> {
+        let line = serde_json::to_vec(signature)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        self.enqueue(&line)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.buffer.is_empty() {
+            std::io::stdout().write_all(&self.buffer)?;
+            self.buffer.clear();
+            self.pending = 0;
+        }
+        std::io::stdout().flush()?;
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
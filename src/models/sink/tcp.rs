@@ -0,0 +1,116 @@
+use std::io::{Error, ErrorKind, Read, Write};
+use std::net::TcpStream;
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+use crate::models::controlflow::block::BlockJson;
+use crate::models::controlflow::signature::SignatureJson;
+use crate::models::sink::Sink;
+
+/// How many times `TcpSink` retries a batch after a transient connection
+/// failure before giving up, with a linearly-increasing backoff between
+/// attempts.
+const MAX_RETRIES: u32 = 3;
+
+/// Streams batched block/signature records to a remote corpus server as
+/// newline-delimited JSON, over a minimal hand-rolled HTTP/1.1 POST (this
+/// crate has no HTTP client dependency). Retries a fixed number of times with
+/// a short backoff on a transient connection failure before giving up.
+pub struct TcpSink {
+    address: String,
+    path: String,
+    buffer: Vec<u8>,
+    pending: usize,
+    flush_count: usize,
+    flush_interval: Duration,
+    last_flush: Instant,
+}
+
+impl TcpSink {
+    pub fn new(address: &str, path: &str, flush_count: usize, flush_interval: Duration) -> Self {
+        Self {
+            address: address.to_string(),
+            path: path.to_string(),
+            buffer: Vec::new(),
+            pending: 0,
+            flush_count,
+            flush_interval,
+            last_flush: Instant::now(),
+        }
+    }
+
+    fn enqueue(&mut self, line: &[u8]) -> Result<(), Error> {
+        self.buffer.extend_from_slice(line);
+        self.buffer.push(b'\n');
+        self.pending += 1;
+        if self.pending >= self.flush_count || self.last_flush.elapsed() >= self.flush_interval {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn send_batch(&self, payload: &[u8]) -> Result<(), Error> {
+        let mut attempt = 0;
+        loop {
+            match self.post(payload) {
+                Ok(()) => return Ok(()),
+                Err(error) if attempt < MAX_RETRIES => {
+                    attempt += 1;
+                    sleep(Duration::from_millis(200 * attempt as u64));
+                    let _ = error;
+                }
+                Err(error) => return Err(error),
+            }
+        }
+    }
+
+    fn post(&self, payload: &[u8]) -> Result<(), Error> {
+        let mut stream = TcpStream::connect(&self.address)?;
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/x-ndjson\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            self.path,
+            self.address,
+            payload.len(),
+        );
+        stream.write_all(request.as_bytes())?;
+        stream.write_all(payload)?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        let status_line = response
+            .split(|&byte| byte == b'\n')
+            .next()
+            .unwrap_or(&[]);
+        let status_line = String::from_utf8_lossy(status_line);
+        if !status_line.contains(" 200 ") && !status_line.contains(" 204 ") && !status_line.trim_end().ends_with("200") {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("sink server responded with {}", status_line.trim()),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Sink for TcpSink {
+    fn send_block(&mut self, block: &BlockJson) -> Result<(), Error> {
+        let line = serde_json::to_vec(block)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        self.enqueue(&line)
+    }
+
+    fn send_signature(&mut self, signature: &SignatureJson) -> Result<(), Error> {
+        let line = serde_json::to_vec(signature)
+            .map_err(|error| Error::new(ErrorKind::InvalidData, error.to_string()))?;
+        self.enqueue(&line)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        if !self.buffer.is_empty() {
+            self.send_batch(&self.buffer)?;
+            self.buffer.clear();
+            self.pending = 0;
+        }
+        self.last_flush = Instant::now();
+        Ok(())
+    }
+}
@@ -1,19 +1,370 @@
+use std::collections::BTreeMap;
+use crate::models::controlflow::symbol::Symbol;
+
+/// Which mangling scheme a symbol name was encoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManglingScheme {
+    /// Microsoft Visual C++ (`?name@@...`).
+    Msvc,
+    /// Itanium C++ ABI, used by GCC/Clang (`_Z...`).
+    Itanium,
+    /// rustc's legacy mangling (`_ZN...17h<16 hex digits>E`), a restricted
+    /// form of Itanium where the final path component is always a hash
+    /// disambiguator rather than a real identifier.
+    RustLegacy,
+    /// rustc's v0 mangling (`_R...`). Only a best-effort path/identifier
+    /// decode is performed; see `demangle_rust_v0`.
+    RustV0,
+    /// Not recognized as mangled by any known scheme.
+    None,
+}
+
+/// A demangled symbol, decomposed into the pieces callers care about instead
+/// of just the rendered display string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DemangledSymbol {
+    /// The fully-qualified, human-readable name (`ns::name(args)`).
+    pub display: String,
+    /// Enclosing namespaces/classes, outermost first.
+    pub namespaces: Vec<String>,
+    /// The unqualified function or method name.
+    pub name: String,
+    /// Argument types recovered from the mangling, if any were encoded.
+    pub arguments: Vec<String>,
+}
+
 pub struct Symbols;
 
 impl Symbols {
+    /// Detects which mangling scheme, if any, `name` was encoded with by its prefix.
+    ///
+    /// Rust legacy names are a restricted form of Itanium nested names, so
+    /// they're distinguished from plain Itanium by also requiring the
+    /// trailing `17h<16 hex digits>` hash component Rust's legacy mangler
+    /// always appends; an `_ZN...E` name missing that hash is treated as
+    /// ordinary Itanium.
+    pub fn scheme(name: &str) -> ManglingScheme {
+        if name.starts_with('?') {
+            ManglingScheme::Msvc
+        } else if name.starts_with("_R") {
+            ManglingScheme::RustV0
+        } else if name.starts_with("_ZN") && name.ends_with('E') && Self::has_rust_legacy_hash(name) {
+            ManglingScheme::RustLegacy
+        } else if name.starts_with("_Z") {
+            ManglingScheme::Itanium
+        } else {
+            ManglingScheme::None
+        }
+    }
+
+    /// Checks whether `name`'s final Itanium `<source-name>` path component
+    /// is a Rust legacy hash disambiguator (`17h` followed by 16 lowercase
+    /// hex digits).
+    fn has_rust_legacy_hash(name: &str) -> bool {
+        let body = &name[3..name.len() - 1];
+        let mut cursor = 0usize;
+        let mut last: Option<String> = None;
+        while let Some(component) = Self::read_source_name(body, &mut cursor) {
+            last = Some(component);
+        }
+        last.map(|component| {
+            component.len() == 17
+                && component.starts_with('h')
+                && component[1..].chars().all(|c| c.is_ascii_hexdigit())
+        }).unwrap_or(false)
+    }
+
+    /// Demangles `name`, detecting the scheme from its prefix. Names that
+    /// aren't mangled by any known scheme are returned unchanged.
+    pub fn demangle(name: &str) -> DemangledSymbol {
+        match Self::scheme(name) {
+            ManglingScheme::Msvc => Self::demangle_msvc(name),
+            ManglingScheme::Itanium => Self::demangle_itanium(name),
+            ManglingScheme::RustLegacy => Self::demangle_rust_legacy(name),
+            ManglingScheme::RustV0 => Self::demangle_rust_v0(name),
+            ManglingScheme::None => DemangledSymbol {
+                display: name.to_string(),
+                namespaces: Vec::new(),
+                name: name.to_string(),
+                arguments: Vec::new(),
+            },
+        }
+    }
+
+    /// Demangles an MSVC name (`?name@namespace@@...`). Recognizes the `?0`/`?1`
+    /// constructor/destructor name codes; the trailing type-encoding block
+    /// (calling convention, return type, parameters) is not decoded into
+    /// `arguments` since MSVC's type grammar is considerably more involved
+    /// than Itanium's, but the namespace/name split alone is enough to make
+    /// most symbols readable.
+    fn demangle_msvc(name: &str) -> DemangledSymbol {
+        let body = name.trim_start_matches('?');
+        let parts: Vec<&str> = body.split('@').collect();
+
+        let raw_name = parts.first().copied().unwrap_or(name);
+        let special = name.starts_with("??");
+        let (function_name, class_name) = match (special, raw_name.get(..1)) {
+            (true, Some("0")) => ("constructor".to_string(), Some(&raw_name[1..])),
+            (true, Some("1")) => ("destructor".to_string(), Some(&raw_name[1..])),
+            _ => (raw_name.to_string(), None),
+        };
+
+        let mut namespaces: Vec<String> = parts
+            .iter()
+            .skip(1)
+            .take_while(|part| !part.is_empty())
+            .map(|part| part.to_string())
+            .rev()
+            .collect();
+        if let Some(class_name) = class_name {
+            namespaces.push(class_name.to_string());
+        }
+
+        let display = if namespaces.is_empty() {
+            function_name.clone()
+        } else {
+            format!("{}::{}", namespaces.join("::"), function_name)
+        };
+
+        DemangledSymbol { display, namespaces, name: function_name, arguments: Vec::new() }
+    }
+
+    /// Demangles an Itanium name (`_Z[N<namespace><name>...E]<name><args>`).
+    /// Covers nested names and the builtin scalar/pointer/reference/const
+    /// type codes; anything else (templates, substitutions, function types)
+    /// stops argument decoding early rather than guessing.
+    fn demangle_itanium(name: &str) -> DemangledSymbol {
+        let body = &name[2..];
+        let bytes = body.as_bytes();
+        let mut cursor = 0usize;
+        let mut namespaces: Vec<String> = Vec::new();
+
+        if bytes.first() == Some(&b'N') {
+            cursor += 1;
+            while bytes.get(cursor).is_some_and(|b| *b != b'E') {
+                match Self::read_source_name(body, &mut cursor) {
+                    Some(part) => namespaces.push(part),
+                    None => break,
+                }
+            }
+            if bytes.get(cursor) == Some(&b'E') {
+                cursor += 1;
+            }
+        } else if let Some(part) = Self::read_source_name(body, &mut cursor) {
+            namespaces.push(part);
+        }
+
+        let function_name = namespaces.pop().unwrap_or_else(|| name.to_string());
+        let arguments = Self::read_itanium_arguments(body, cursor);
+
+        let display = if namespaces.is_empty() {
+            format!("{}({})", function_name, arguments.join(", "))
+        } else {
+            format!("{}::{}({})", namespaces.join("::"), function_name, arguments.join(", "))
+        };
+
+        DemangledSymbol { display, namespaces, name: function_name, arguments }
+    }
+
+    /// Demangles a Rust legacy name (`_ZN<source-name>+E`), rustc's historical
+    /// mangling: a plain Itanium nested name whose final path component is
+    /// always a `17h<16 hex digits>` disambiguator rather than a real
+    /// identifier, so it's dropped here instead of rendered as a namespace.
+    fn demangle_rust_legacy(name: &str) -> DemangledSymbol {
+        let body = &name[3..name.len() - 1];
+        let mut cursor = 0usize;
+        let mut components: Vec<String> = Vec::new();
+        while let Some(component) = Self::read_source_name(body, &mut cursor) {
+            components.push(component);
+        }
+        components.pop();
+
+        let function_name = components.pop().unwrap_or_else(|| name.to_string());
+        let namespaces = components;
+
+        let display = if namespaces.is_empty() {
+            function_name.clone()
+        } else {
+            format!("{}::{}", namespaces.join("::"), function_name)
+        };
+
+        DemangledSymbol { display, namespaces, name: function_name, arguments: Vec::new() }
+    }
+
+    /// Demangles a Rust v0 name (`_R...`). The v0 grammar (base62-encoded
+    /// backreferences, punycode identifiers, const generics) is considerably
+    /// more involved than legacy mangling; this only recovers the plain
+    /// length-prefixed path components it shares with Itanium/legacy names
+    /// and renders them the same way. Names using v0 features beyond that
+    /// (most real-world ones) fall back to being returned unchanged.
+    fn demangle_rust_v0(name: &str) -> DemangledSymbol {
+        let body = &name[2..];
+        let mut cursor = 0usize;
+        let mut components: Vec<String> = Vec::new();
+        while let Some(component) = Self::read_source_name(body, &mut cursor) {
+            components.push(component);
+        }
+
+        if components.is_empty() {
+            return DemangledSymbol {
+                display: name.to_string(),
+                namespaces: Vec::new(),
+                name: name.to_string(),
+                arguments: Vec::new(),
+            };
+        }
+
+        let function_name = components.pop().unwrap();
+        let namespaces = components;
+
+        let display = if namespaces.is_empty() {
+            function_name.clone()
+        } else {
+            format!("{}::{}", namespaces.join("::"), function_name)
+        };
+
+        DemangledSymbol { display, namespaces, name: function_name, arguments: Vec::new() }
+    }
+
+    /// Reads one Itanium `<source-name>` (a decimal length followed by that
+    /// many bytes) starting at `*cursor`, advancing it past the name.
+    fn read_source_name(body: &str, cursor: &mut usize) -> Option<String> {
+        let bytes = body.as_bytes();
+        let digits_start = *cursor;
+        while bytes.get(*cursor).is_some_and(u8::is_ascii_digit) {
+            *cursor += 1;
+        }
+        if *cursor == digits_start {
+            return None;
+        }
+        let length: usize = body[digits_start..*cursor].parse().ok()?;
+        let name_start = *cursor;
+        let name_end = name_start.checked_add(length)?;
+        if name_end > bytes.len() {
+            return None;
+        }
+        *cursor = name_end;
+        Some(body[name_start..name_end].to_string())
+    }
+
+    /// Reads the encoded argument list following an Itanium function name,
+    /// starting at `cursor`. A lone `v` means the function takes no arguments.
+    fn read_itanium_arguments(body: &str, mut cursor: usize) -> Vec<String> {
+        let bytes = body.as_bytes();
+        if cursor >= bytes.len() || &body[cursor..] == "v" {
+            return Vec::new();
+        }
+
+        let mut arguments = Vec::new();
+        while cursor < bytes.len() {
+            match Self::read_itanium_type(body, &mut cursor) {
+                Some(argument) => arguments.push(argument),
+                None => break,
+            }
+        }
+        arguments
+    }
+
+    /// Reads one Itanium `<type>` starting at `*cursor`, advancing it past the type.
+    fn read_itanium_type(body: &str, cursor: &mut usize) -> Option<String> {
+        let code = *body.as_bytes().get(*cursor)?;
+        match code {
+            b'P' => { *cursor += 1; Some(format!("{}*", Self::read_itanium_type(body, cursor)?)) }
+            b'R' => { *cursor += 1; Some(format!("{}&", Self::read_itanium_type(body, cursor)?)) }
+            b'K' => { *cursor += 1; Some(format!("const {}", Self::read_itanium_type(body, cursor)?)) }
+            b'v' => { *cursor += 1; Some("void".to_string()) }
+            b'b' => { *cursor += 1; Some("bool".to_string()) }
+            b'c' => { *cursor += 1; Some("char".to_string()) }
+            b'a' => { *cursor += 1; Some("signed char".to_string()) }
+            b'h' => { *cursor += 1; Some("unsigned char".to_string()) }
+            b's' => { *cursor += 1; Some("short".to_string()) }
+            b't' => { *cursor += 1; Some("unsigned short".to_string()) }
+            b'i' => { *cursor += 1; Some("int".to_string()) }
+            b'j' => { *cursor += 1; Some("unsigned int".to_string()) }
+            b'l' => { *cursor += 1; Some("long".to_string()) }
+            b'm' => { *cursor += 1; Some("unsigned long".to_string()) }
+            b'x' => { *cursor += 1; Some("long long".to_string()) }
+            b'y' => { *cursor += 1; Some("unsigned long long".to_string()) }
+            b'f' => { *cursor += 1; Some("float".to_string()) }
+            b'd' => { *cursor += 1; Some("double".to_string()) }
+            b'e' => { *cursor += 1; Some("long double".to_string()) }
+            b'0'..=b'9' => Self::read_source_name(body, cursor),
+            _ => None,
+        }
+    }
+
+    /// Retained for existing callers that only want the MSVC display string.
     #[allow(dead_code)]
     pub fn demangle_msvc_symbol(mangled_name: &str) -> String {
-        if !mangled_name.starts_with('?') {
-            return mangled_name.to_string();
-        }
-        let parts: Vec<&str> = mangled_name.trim_start_matches('?').split('@').collect();
-        let function_name = parts.get(0).unwrap_or(&mangled_name);
-        let mut namespaces: Vec<&str> = parts.iter().skip(1).take_while(|&&s| s != "").map(|&s| s).collect();
-        namespaces.reverse();
-        format!(
-            "{}::{}",
-            namespaces.join("::"),
-            function_name
-        )
-    }
-}
\ No newline at end of file
+        Self::demangle(mangled_name).display
+    }
+
+    /// Detects the mangling scheme and demangles `raw` in one call, for
+    /// callers that want to tag or branch on the scheme alongside the
+    /// rendered name (e.g. `Symbol::insert_name`, which stores both the raw
+    /// and demangled forms so a function can be matched by either).
+    pub fn demangle_with_scheme(raw: &str) -> (ManglingScheme, String) {
+        let scheme = Self::scheme(raw);
+        let demangled = Self::demangle(raw).display;
+        (scheme, demangled)
+    }
+
+    /// Folds `(address, name)` pairs from any symbol source (a linker map, a
+    /// PDB's public symbol stream, ...) into one `Symbol` per address,
+    /// merging repeated addresses into that `Symbol`'s existing `names` set
+    /// via `Symbol::insert_name` rather than overwriting it. Callers that
+    /// parse a format-specific symbol table only need to yield the address
+    /// and raw name; this is the one place the per-address merge happens.
+    pub fn merge_symbol_entries(entries: impl IntoIterator<Item = (u64, String)>) -> BTreeMap<u64, Symbol> {
+        let mut symbols: BTreeMap<u64, Symbol> = BTreeMap::new();
+        for (address, name) in entries {
+            symbols.entry(address).or_insert_with(|| Symbol::new(address)).insert_name(name);
+        }
+        symbols
+    }
+
+    /// Checks whether `token` looks like a real function symbol rather than a
+    /// linker-generated label, object/archive member path, or section marker,
+    /// the way decompiler tooling filters linker map noise. This is a
+    /// heuristic, not a grammar: GNU ld and LLD map layouts differ enough
+    /// (column widths, whether the symbol is on its own line under the
+    /// address) that `parse_linker_map` only relies on "the last plausible
+    /// token on an address line is the symbol", not a fixed column format.
+    fn is_plausible_map_symbol(token: &str) -> bool {
+        if token.is_empty() || token.starts_with("0x") {
+            return false;
+        }
+        if token.starts_with("..") || token.starts_with('@') || token.starts_with('.') {
+            return false;
+        }
+        if token.contains('/') || token.contains('\\') {
+            return false;
+        }
+        if token.ends_with(".o") || token.ends_with(".a") || token.ends_with(".lib") {
+            return false;
+        }
+        true
+    }
+
+    /// Parses a GNU ld or LLD linker `.map` file, yielding one merged
+    /// `Symbol` per virtual address found. Each line that begins with a
+    /// `0x`-prefixed address is scanned right-to-left for the first token
+    /// `is_plausible_map_symbol` accepts as a name, skipping object/archive
+    /// paths, section markers (`.text`, `.rodata`, ...), and linker-generated
+    /// labels; lines with no such token are ignored rather than guessed at.
+    ///
+    /// `image_base` is added to every parsed address, for maps emitted
+    /// relative to a section or module rather than the full image, so the
+    /// result lines up with the same virtual addresses `GraphOptions` uses.
+    /// Pass `0` for maps that already encode final load-time addresses.
+    pub fn parse_linker_map(contents: &str, image_base: u64) -> BTreeMap<u64, Symbol> {
+        let entries = contents.lines().filter_map(|line| {
+            let mut tokens = line.trim().split_whitespace();
+            let first = tokens.next()?;
+            let address = u64::from_str_radix(first.strip_prefix("0x")?, 16).ok()?;
+            let name = tokens.collect::<Vec<&str>>().into_iter().rev().find(|token| Self::is_plausible_map_symbol(token))?;
+            Some((address.wrapping_add(image_base), name.to_string()))
+        });
+        Self::merge_symbol_entries(entries)
+    }
+}
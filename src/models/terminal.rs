@@ -1,6 +1,12 @@
 use std::io::{Error, ErrorKind};
 use std::io::{self, BufRead, IsTerminal, Write};
 
+pub mod args;
+pub mod config;
+pub mod io;
+pub mod capabilities;
+pub mod error;
+
 pub struct Terminal;
 
 impl Terminal {
@@ -48,6 +48,10 @@ pub struct Args {
     #[arg(long, default_value_t = false)]
     pub disable_sha256: bool,
     #[arg(long, default_value_t = false)]
+    pub enable_xxhash: bool,
+    #[arg(long)]
+    pub xxhash_seed: Option<u64>,
+    #[arg(long, default_value_t = false)]
     pub disable_entropy: bool,
     #[arg(long, default_value_t = false)]
     pub disable_features: bool,
@@ -61,6 +65,25 @@ pub struct Args {
     pub enable_file_mapping_cache: bool,
     #[arg(long)]
     pub file_mapping_directory: Option<String>,
+    /// Stops enqueuing new work once this many instructions have been decoded
+    /// for a function's graph, marking it truncated instead of hanging forever.
+    #[arg(long)]
+    pub max_instructions: Option<usize>,
+    /// Stops enqueuing new work once this many blocks have been confirmed
+    /// valid for a function's graph, marking it truncated.
+    #[arg(long)]
+    pub max_blocks: Option<usize>,
+    /// Stops enqueuing new work once this many functions have been confirmed
+    /// valid for a graph, marking it truncated.
+    #[arg(long)]
+    pub max_functions: Option<usize>,
+    /// Stops enqueuing new work once a function's graph has been disassembling
+    /// for longer than this many milliseconds, marking it truncated.
+    #[arg(long)]
+    pub timeout_ms: Option<u64>,
+    /// Prints this build's version/schema/format/hashing capabilities as JSON and exits.
+    #[arg(long, default_value_t = false)]
+    pub capabilities: bool,
 }
 
 fn validate(args: &Args) {
@@ -78,6 +101,26 @@ fn validate(args: &Args) {
         }
     }
 
+    if args.max_instructions == Some(0) {
+        eprintln!("max instructions must be greater than 0");
+        process::exit(1);
+    }
+
+    if args.max_blocks == Some(0) {
+        eprintln!("max blocks must be greater than 0");
+        process::exit(1);
+    }
+
+    if args.max_functions == Some(0) {
+        eprintln!("max functions must be greater than 0");
+        process::exit(1);
+    }
+
+    if args.timeout_ms == Some(0) {
+        eprintln!("timeout ms must be greater than 0");
+        process::exit(1);
+    }
+
     if let Some(tags) = &args.tags {
         let mut unique_tags = HashSet::new();
         for tag in tags {
@@ -90,121 +133,173 @@ fn validate(args: &Args) {
 
 }
 
-fn parse() -> Config {
+/// Resolves the effective `Config` for this invocation by layering three sources,
+/// lowest precedence first: built-in defaults (`Config::new`), the TOML configuration
+/// file (explicit `--config`, falling back to the default configuration file), and
+/// finally CLI flags, which always win when present. Keeping all three layers behind
+/// this single entry point means every caller (the binary, tests, future front-ends)
+/// resolves configuration identically instead of re-deriving the precedence rules.
+impl Config {
+    fn layer_args(mut self, args: &Args) -> Self {
+        if args.debug != false {
+            self.general.debug = args.debug;
+        }
 
-    let args = Args::parse();
+        if args.threads.is_some() {
+            self.general.threads = args.threads.unwrap();
+        }
 
-    validate(&args);
+        if args.disable_features != false {
+            self.heuristics.features = !args.disable_features;
+        }
 
-    let mut config = Config::new();
+        if args.disable_sha256 != false {
+            self.hashing.sha256.enable = !args.disable_sha256;
+        }
 
-    let _ = config.write_default();
+        if args.enable_xxhash != false {
+            self.hashing.xxhash.enable = args.enable_xxhash;
+        }
 
-    if args.config.is_some() {
-        match Config::from_file(&args.config.unwrap().to_string()) {
-            Ok(result) => {
-                config = result;
-            },
-            Err(error) => {
-                eprintln!("{}", error);
-                process::exit(1);
-            }
+        if args.xxhash_seed.is_some() {
+            self.hashing.xxhash.seed = args.xxhash_seed.unwrap();
         }
-    } else {
-        let _ = config.from_default();
-    }
 
-    config.general.input = Some(args.input);
-    config.general.output = args.output;
+        if args.disable_entropy != false {
+            self.heuristics.entropy = !args.disable_entropy;
+        }
 
-    if args.debug != false {
-        config.general.debug = args.debug;
-    }
+        if args.disable_minhash != false {
+            self.hashing.minhash.enable = !args.disable_minhash;
+        }
 
-    if args.threads.is_some() {
-        config.general.threads = args.threads.unwrap();
-    }
+        if args.minhash_maximum_byte_size.is_some() {
+            self.hashing.minhash.maximum_byte_size = args.minhash_maximum_byte_size.unwrap();
+        }
 
-    if args.disable_features != false {
-        config.heuristics.features = !args.disable_features;
-    }
+        if args.minhash_number_of_hashes.is_some() {
+            self.hashing.minhash.number_of_hashes = args.minhash_number_of_hashes.unwrap();
+        }
 
-    if args.disable_sha256 != false {
-        config.hashing.sha256.enable = !args.disable_sha256;
-    }
+        if args.minhash_shingle_size.is_some() {
+            self.hashing.minhash.shingle_size = args.minhash_shingle_size.unwrap();
+        }
 
-    if args.disable_entropy != false {
-        config.heuristics.entropy = !args.disable_entropy;
-    }
+        if args.minhash_seed.is_some() {
+            self.hashing.minhash.seed = args.minhash_seed.unwrap();
+        }
 
-    if args.disable_minhash != false {
-        config.hashing.minhash.enable = !args.disable_minhash;
-    }
+        if let Some(file_mapping_directory) = &args.file_mapping_directory {
+            self.file_mapping.directory = file_mapping_directory.clone();
+        }
 
-    if args.minhash_maximum_byte_size.is_some() {
-        config.hashing.minhash.maximum_byte_size = args.minhash_maximum_byte_size.unwrap();
-    }
+        if args.enable_file_mapping != false {
+            self.file_mapping.enable = args.enable_file_mapping;
+        }
 
-    if args.minhash_number_of_hashes.is_some() {
-        config.hashing.minhash.number_of_hashes = args.minhash_number_of_hashes.unwrap();
-    }
+        if args.enable_file_mapping_cache != false {
+            self.file_mapping.caching = args.enable_file_mapping_cache;
+        }
 
-    if args.minhash_shingle_size.is_some() {
-        config.hashing.minhash.shingle_size = args.minhash_shingle_size.unwrap();
-    }
+        if args.disable_tlsh != false {
+            self.hashing.tlsh.enable = !args.disable_tlsh;
+        }
 
-    if args.minhash_seed.is_some() {
-        config.hashing.minhash.seed = args.minhash_seed.unwrap();
-    }
+        if args.tlsh_minimum_byte_size.is_some() {
+            self.hashing.tlsh.minimum_byte_size = args.tlsh_minimum_byte_size.unwrap();
+        }
 
-    if args.file_mapping_directory.is_some() {
-        config.file_mapping.directory = args.file_mapping_directory.unwrap();
-    }
+        if args.enable_normalized != false {
+            self.heuristics.normalization = args.enable_normalized;
+        }
 
-    if args.enable_file_mapping != false {
-        config.file_mapping.enable = args.enable_file_mapping;
-    }
+        if args.disable_linear_pass != false {
+            self.disassembler.sweep = !args.disable_linear_pass;
+        }
 
-    if args.enable_file_mapping_cache != false {
-        config.file_mapping.caching = args.enable_file_mapping_cache;
-    }
+        if args.max_instructions.is_some() {
+            self.disassembler.budget.max_instructions = args.max_instructions;
+        }
 
-    if args.disable_tlsh != false {
-        config.hashing.tlsh.enable = !args.disable_tlsh;
-    }
+        if args.max_blocks.is_some() {
+            self.disassembler.budget.max_blocks = args.max_blocks;
+        }
 
-    if args.tlsh_minimum_byte_size.is_some() {
-        config.hashing.tlsh.minimum_byte_size = args.tlsh_minimum_byte_size.unwrap();
-    }
+        if args.max_functions.is_some() {
+            self.disassembler.budget.max_functions = args.max_functions;
+        }
 
-    if args.enable_normalized != false {
-        config.heuristics.normalization = args.enable_normalized;
-    }
+        if args.timeout_ms.is_some() {
+            self.disassembler.budget.timeout_ms = args.timeout_ms;
+        }
 
-    if args.disable_linear_pass != false {
-        config.disassembler.sweep = !args.disable_linear_pass;
-    }
+        if let Some(tags) = &args.tags {
+            self.general.tags = tags.clone();
+        }
+
+        if args.disable_hashing == true {
+            self.hashing.minhash.enable = false;
+            self.hashing.sha256.enable = false;
+            self.hashing.tlsh.enable = false;
+            self.hashing.xxhash.enable = false;
+        }
 
-    if args.tags.is_some() {
-        config.general.tags = args.tags.unwrap();
+        if args.minimal == true || self.general.minimal == true {
+            self.hashing.minhash.enable = false;
+            self.hashing.sha256.enable = false;
+            self.hashing.tlsh.enable = false;
+            self.hashing.xxhash.enable = false;
+            self.heuristics.entropy = false;
+            self.heuristics.features = false;
+            self.heuristics.normalization = false;
+        }
+
+        self
     }
 
-    if args.disable_hashing == true {
-        config.hashing.minhash.enable = false;
-        config.hashing.sha256.enable = false;
-        config.hashing.tlsh.enable = false;
+    /// Builds the layered `Config` for a parsed `Args`: defaults, then the TOML
+    /// configuration file, then CLI overrides from `args` itself.
+    fn resolve(args: Args) -> Config {
+        let mut config = Config::new();
+
+        let _ = config.write_default();
+
+        if let Some(config_path) = &args.config {
+            match Config::from_file(config_path) {
+                Ok(result) => {
+                    config = result;
+                },
+                Err(error) => {
+                    eprintln!("{}", error);
+                    process::exit(1);
+                }
+            }
+        } else {
+            let _ = config.from_default();
+        }
+
+        config.general.input = Some(args.input.clone());
+        config.general.output = args.output.clone();
+
+        config.layer_args(&args)
     }
+}
 
-    if args.minimal == true || config.general.minimal == true {
-        config.hashing.minhash.enable = false;
-        config.hashing.sha256.enable = false;
-        config.hashing.tlsh.enable = false;
-        config.heuristics.entropy = false;
-        config.heuristics.features = false;
-        config.heuristics.normalization = false;
+fn parse() -> Config {
+
+    let args = Args::parse();
+
+    if args.capabilities {
+        match crate::models::terminal::capabilities::Capabilities::current().json() {
+            Ok(json) => println!("{}", json),
+            Err(error) => eprintln!("{}", error),
+        }
+        process::exit(0);
     }
 
-    config
+    validate(&args);
+
+    Config::resolve(args)
 
 }
 
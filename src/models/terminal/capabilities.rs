@@ -0,0 +1,46 @@
+use serde::Serialize;
+use crate::models::terminal::args::VERSION;
+
+/// Mirrors `crate::config::CONFIG_SCHEMA_VERSION`; kept local since the `--capabilities`
+/// flag needs to report it independent of whether a `--config` TOML file was loaded.
+const CONFIG_SCHEMA_VERSION: u32 = 3;
+
+/// Describes what this build of binlex supports, so external tooling (wrapper
+/// scripts, a long-running service shelling out to the CLI) can negotiate instead of
+/// guessing from the version string alone.
+#[derive(Serialize)]
+pub struct Capabilities {
+    /// The binlex CLI version string.
+    pub version: String,
+    /// The `Config` TOML schema version this build reads and writes.
+    pub config_schema_version: u32,
+    /// Input formats this build can analyze.
+    pub formats: Vec<String>,
+    /// Hashing backends available under `[hashing]`.
+    pub hashing: Vec<String>,
+}
+
+impl Capabilities {
+    pub fn current() -> Self {
+        Self {
+            version: VERSION.to_string(),
+            config_schema_version: CONFIG_SCHEMA_VERSION,
+            formats: vec![
+                "code".to_string(),
+                "pe".to_string(),
+                "elf".to_string(),
+            ],
+            hashing: vec![
+                "sha256".to_string(),
+                "tlsh".to_string(),
+                "minhash".to_string(),
+                "xxhash".to_string(),
+            ],
+        }
+    }
+
+    /// Serializes the capabilities to a JSON string for the `--capabilities` flag.
+    pub fn json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
@@ -21,6 +21,15 @@ pub struct Config {
 #[derive(Serialize, Deserialize)]
 pub struct ConfigDisassembler {
     pub sweep: bool,
+    pub budget: ConfigDisassemblerBudget,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConfigDisassemblerBudget {
+    pub max_instructions: Option<usize>,
+    pub max_blocks: Option<usize>,
+    pub max_functions: Option<usize>,
+    pub timeout_ms: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -36,6 +45,13 @@ pub struct ConfigHashing {
     pub sha256: ConfigSHA256,
     pub tlsh: ConfigTLSH,
     pub minhash: ConfigMinhash,
+    pub xxhash: ConfigXXHash,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct ConfigXXHash {
+    pub enable: bool,
+    pub seed: u64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -110,6 +126,10 @@ impl Config {
                     maximum_byte_size: 50,
                     seed: 0,
                 },
+                xxhash: ConfigXXHash {
+                    enable: false,
+                    seed: 0,
+                },
             },
             file_mapping: ConfigFileMapping {
                 enable: false,
@@ -118,6 +138,12 @@ impl Config {
             },
             disassembler: ConfigDisassembler {
                 sweep: true,
+                budget: ConfigDisassemblerBudget {
+                    max_instructions: None,
+                    max_blocks: None,
+                    max_functions: None,
+                    timeout_ms: None,
+                },
             }
         }
     }
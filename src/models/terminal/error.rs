@@ -0,0 +1,80 @@
+use std::fmt;
+use std::io::ErrorKind;
+use std::process;
+use crate::models::terminal::io::JSONError;
+
+/// Stable, documented process exit codes for binlex's CLI tools. Scripts driving
+/// these tools can branch on the code instead of scraping stderr text.
+pub mod exit_code {
+    /// The operation completed successfully.
+    pub const OK: i32 = 0;
+    /// The input (a file, stdin stream, or argument) was malformed or unreadable in
+    /// a way that isn't specific to one record, e.g. a missing CSV header.
+    pub const USAGE: i32 = 64;
+    /// A specific record failed to parse or deserialize (bad JSON/CSV row).
+    pub const DATA: i32 = 65;
+    /// An underlying I/O operation (open/read/write) failed.
+    pub const IO: i32 = 74;
+}
+
+/// A central error type for binlex's CLI layer, modeled on a Deno-style error-class
+/// wrapper: it carries whichever concrete error occurred (a `JSONError`, a raw I/O
+/// error, or a bare usage message) and knows how to map itself to one of the stable
+/// `exit_code` values, so every tool reports failures the same way instead of each
+/// scattering its own `eprintln!` + `process::exit` calls.
+#[derive(Debug)]
+pub enum CliError {
+    Json(JSONError),
+    Io(std::io::Error),
+    Usage(String),
+}
+
+impl CliError {
+    /// The exit code this error class maps to. Broken-pipe I/O errors are not routed
+    /// through here — see `report_and_exit`, which special-cases them to exit `0`
+    /// before consulting this mapping.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CliError::Json(JSONError::MalformedPayload(_, _)) => exit_code::USAGE,
+            CliError::Json(_) => exit_code::DATA,
+            CliError::Io(_) => exit_code::IO,
+            CliError::Usage(_) => exit_code::USAGE,
+        }
+    }
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::Json(error) => write!(f, "{}", error),
+            CliError::Io(error) => write!(f, "{}", error),
+            CliError::Usage(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl From<JSONError> for CliError {
+    fn from(error: JSONError) -> Self {
+        CliError::Json(error)
+    }
+}
+
+impl From<std::io::Error> for CliError {
+    fn from(error: std::io::Error) -> Self {
+        CliError::Io(error)
+    }
+}
+
+/// Prints `error` to stderr and terminates the process with the exit code its error
+/// class maps to. A broken pipe (the common case of piping into `head`/`less`) always
+/// exits `0`, since the consumer simply stopped reading rather than the tool failing.
+pub fn report_and_exit(error: CliError) -> ! {
+    if let CliError::Io(io_error) = &error {
+        if io_error.kind() == ErrorKind::BrokenPipe {
+            process::exit(exit_code::OK);
+        }
+    }
+
+    eprintln!("{}", error);
+    process::exit(error.exit_code());
+}
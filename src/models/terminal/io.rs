@@ -1,10 +1,10 @@
-use std::io::{stdin, ErrorKind};
+use std::io::stdin;
 use std::io::{self, BufRead, BufReader, IsTerminal, Write};
 use std::fmt::Display;
-use std::process;
 use std::fs::File;
-use serde_json::{Value, Deserializer};
+use serde_json::{Value, Deserializer, Map};
 use std::fmt;
+use crate::types::MemoryMappedFile;
 
 /// Represents a wrapper for standard input operations.
 pub struct Stdin;
@@ -39,8 +39,7 @@ impl Stdin {
                         Stdout.print(line);
                     },
                     Err(error) => {
-                        eprintln!("{}", error);
-                        process::exit(1);
+                        crate::models::terminal::error::report_and_exit(error.into());
                     },
                 }
             }
@@ -57,14 +56,9 @@ impl Stdin {
     /// an error message to standard error and exits with code `1`.
     #[allow(dead_code)]
     pub fn print<T: Display>(&self, line: T) {
-        writeln!(io::stderr(), "{}", line).unwrap_or_else(|e| {
-            if e.kind() == ErrorKind::BrokenPipe {
-                std::process::exit(0);
-            } else {
-                eprintln!("error writing to stdout: {}", e);
-                std::process::exit(1);
-            }
-        });
+        if let Err(error) = writeln!(io::stderr(), "{}", line) {
+            crate::models::terminal::error::report_and_exit(error.into());
+        }
     }
 }
 
@@ -81,14 +75,9 @@ impl Stdout {
 
     #[allow(dead_code)]
     pub fn print<T: Display>(&self, line: T) {
-        writeln!(io::stdout(), "{}", line).unwrap_or_else(|e| {
-            if e.kind() == ErrorKind::BrokenPipe {
-                std::process::exit(0);
-            } else {
-                eprintln!("error writing to stdout: {}", e);
-                std::process::exit(1);
-            }
-        });
+        if let Err(error) = writeln!(io::stdout(), "{}", line) {
+            crate::models::terminal::error::report_and_exit(error.into());
+        }
     }
 }
 
@@ -104,14 +93,31 @@ impl Stderr {
     /// an error message to standard error and exits with code `1`.
     #[allow(dead_code)]
     pub fn print<T: Display>(&self, line: T) {
-        writeln!(io::stderr(), "{}", line).unwrap_or_else(|e| {
-            if e.kind() == ErrorKind::BrokenPipe {
-                std::process::exit(0);
-            } else {
-                eprintln!("error writing to stdout: {}", e);
-                std::process::exit(1);
-            }
-        });
+        if let Err(error) = writeln!(io::stderr(), "{}", line) {
+            crate::models::terminal::error::report_and_exit(error.into());
+        }
+    }
+}
+
+/// The on-the-wire shape of a payload the `JSON` subsystem can ingest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadType {
+    /// Standard (possibly pretty-printed) JSON.
+    Json,
+    /// Newline-delimited JSON, one value per line.
+    Ndjson,
+    /// Comma-separated values with a typed header row.
+    Csv,
+}
+
+impl fmt::Display for PayloadType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PayloadType::Json => "json",
+            PayloadType::Ndjson => "ndjson",
+            PayloadType::Csv => "csv",
+        };
+        write!(f, "{}", name)
     }
 }
 
@@ -119,9 +125,33 @@ impl Stderr {
 pub enum JSONError {
     FileOpenError(String),
     StdinReadError,
-    JSONParseError(String),
+    /// A `serde_json` parse failure, with enough position data to find the record
+    /// that caused it in a multi-gigabyte NDJSON stream: the zero-based index of the
+    /// record being parsed when the error occurred, and the 1-based line/column
+    /// `serde_json::Error` reports it at.
+    JSONParseError {
+        record_index: usize,
+        line: usize,
+        column: usize,
+        message: String,
+    },
     JSONToStringError(String),
     FileWriteError(String),
+    /// A single record failed to parse under the given `PayloadType`; carries the
+    /// offending line/row so the caller can report which input failed without
+    /// aborting the rest of the parse.
+    MalformedPayload(PayloadType, String),
+}
+
+impl JSONError {
+    fn from_serde_error(record_index: usize, error: serde_json::Error) -> Self {
+        JSONError::JSONParseError {
+            record_index,
+            line: error.line(),
+            column: error.column(),
+            message: error.to_string(),
+        }
+    }
 }
 
 impl fmt::Display for JSONError {
@@ -129,9 +159,14 @@ impl fmt::Display for JSONError {
         match self {
             JSONError::FileOpenError(path) => write!(f, "failed to open file: {}", path),
             JSONError::StdinReadError => write!(f, "failed to read from standard input"),
-            JSONError::JSONParseError(err) => write!(f, "failed parsing json: {}", err),
+            JSONError::JSONParseError { record_index, line, column, message } => write!(
+                f,
+                "failed parsing json record {} (line {}, column {}): {}",
+                record_index, line, column, message,
+            ),
             JSONError::JSONToStringError(err) => write!(f, "error converting json value to string: {}", err),
             JSONError::FileWriteError(path) => write!(f, "failed to write to file: {}", path),
+            JSONError::MalformedPayload(format, detail) => write!(f, "malformed {} payload: {}", format, detail),
         }
     }
 }
@@ -175,7 +210,8 @@ impl JSON {
     fn deserialize<R: BufRead>(reader: R) -> Result<Self, JSONError> {
         let values: Vec<Value> = Deserializer::from_reader(reader)
             .into_iter::<Value>()
-            .map(|value| value.map_err(|e| JSONError::JSONParseError(e.to_string())))
+            .enumerate()
+            .map(|(index, value)| value.map_err(|e| JSONError::from_serde_error(index, e)))
             .collect::<Result<_, _>>()?;
 
         Ok(JSON { values })
@@ -190,14 +226,14 @@ impl JSON {
     {
         let mut values = Vec::new();
 
-        for item in Deserializer::from_reader(reader).into_iter::<Value>() {
+        for (index, item) in Deserializer::from_reader(reader).into_iter::<Value>().enumerate() {
             match item {
                 Ok(mut value) => {
                     if filter(&mut value) {
                         values.push(value);
                     }
                 }
-                Err(e) => return Err(JSONError::JSONParseError(e.to_string())),
+                Err(e) => return Err(JSONError::from_serde_error(index, e)),
             }
         }
 
@@ -240,6 +276,158 @@ impl JSON {
         }
     }
 
+    /// Constructs a `JSON` instance from a file path, parsed according to `format`.
+    #[allow(dead_code)]
+    pub fn from_file_with_format(path: &str, format: PayloadType) -> Result<Self, JSONError> {
+        let file = File::open(path).map_err(|_| JSONError::FileOpenError(path.to_string()))?;
+        let reader = BufReader::new(file);
+        Self::deserialize_with_format(reader, format)
+    }
+
+    /// Constructs a `JSON` instance from standard input, parsed according to `format`.
+    #[allow(dead_code)]
+    pub fn from_stdin_with_format(format: PayloadType) -> Result<Self, JSONError> {
+        if io::stdin().is_terminal() {
+            return Err(JSONError::StdinReadError);
+        }
+
+        let reader = BufReader::new(io::stdin());
+        Self::deserialize_with_format(reader, format)
+    }
+
+    /// Dispatches to the right parser for `format`. `Json` and `Ndjson` are both
+    /// handled by `deserialize`, since `serde_json::Deserializer::from_reader` already
+    /// accepts either a single document or a stream of newline-delimited values.
+    #[allow(dead_code)]
+    fn deserialize_with_format<R: BufRead>(reader: R, format: PayloadType) -> Result<Self, JSONError> {
+        match format {
+            PayloadType::Json | PayloadType::Ndjson => Self::deserialize(reader),
+            PayloadType::Csv => Self::deserialize_csv(reader),
+        }
+    }
+
+    /// Parses a CSV payload whose header row declares each column's type with a
+    /// `name:type` convention (`address:number`, `is_function:boolean`; a bare `name`
+    /// defaults to `string`), emitting one JSON object per data row.
+    #[allow(dead_code)]
+    fn deserialize_csv<R: BufRead>(reader: R) -> Result<Self, JSONError> {
+        let mut lines = reader.lines();
+
+        let header_line = lines.next()
+            .ok_or_else(|| JSONError::MalformedPayload(PayloadType::Csv, "missing header row".to_string()))?
+            .map_err(|e| JSONError::MalformedPayload(PayloadType::Csv, e.to_string()))?;
+
+        if header_line.trim().is_empty() {
+            return Err(JSONError::MalformedPayload(PayloadType::Csv, "missing header row".to_string()));
+        }
+
+        let columns: Vec<(String, String)> = header_line.split(',').map(|column| {
+            match column.split_once(':') {
+                Some((name, type_name)) => (name.trim().to_string(), type_name.trim().to_string()),
+                None => (column.trim().to_string(), "string".to_string()),
+            }
+        }).collect();
+
+        let mut values = Vec::new();
+
+        for line in lines {
+            let line = line.map_err(|e| JSONError::MalformedPayload(PayloadType::Csv, e.to_string()))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let cells: Vec<&str> = line.split(',').collect();
+            if cells.len() != columns.len() {
+                return Err(JSONError::MalformedPayload(
+                    PayloadType::Csv,
+                    format!("expected {} columns, got {}: {}", columns.len(), cells.len(), line),
+                ));
+            }
+
+            let mut object = Map::new();
+            for ((name, type_name), cell) in columns.iter().zip(cells.iter()) {
+                let cell = cell.trim();
+                let value = if cell.is_empty() {
+                    Value::Null
+                } else {
+                    match type_name.as_str() {
+                        "number" => {
+                            let number = serde_json::Number::from_f64(
+                                cell.parse::<f64>().map_err(|_| JSONError::MalformedPayload(
+                                    PayloadType::Csv,
+                                    format!("column \"{}\" expected a number, got \"{}\"", name, cell),
+                                ))?
+                            ).ok_or_else(|| JSONError::MalformedPayload(
+                                PayloadType::Csv,
+                                format!("column \"{}\" produced a non-finite number: \"{}\"", name, cell),
+                            ))?;
+                            Value::Number(number)
+                        },
+                        "boolean" => {
+                            let boolean = cell.parse::<bool>().map_err(|_| JSONError::MalformedPayload(
+                                PayloadType::Csv,
+                                format!("column \"{}\" expected a boolean, got \"{}\"", name, cell),
+                            ))?;
+                            Value::Bool(boolean)
+                        },
+                        _ => Value::String(cell.to_string()),
+                    }
+                };
+                object.insert(name.clone(), value);
+            }
+
+            values.push(Value::Object(object));
+        }
+
+        Ok(JSON { values })
+    }
+
+    /// Constructs a `JSON` instance by memory-mapping `path` and deserializing
+    /// directly out of the mapping via `serde_json::Deserializer::from_slice`, so a
+    /// multi-gigabyte binlex output file is never copied into a buffer before
+    /// parsing begins the way `from_file`'s `BufReader` would.
+    #[allow(dead_code)]
+    pub fn from_mmap(path: &str) -> Result<Self, JSONError> {
+        let mmap = MemoryMappedFile::new_readonly(path.into())
+            .map_err(|_| JSONError::FileOpenError(path.to_string()))?;
+        let mapped = mmap.mmap().map_err(|_| JSONError::FileOpenError(path.to_string()))?;
+
+        let values: Vec<Value> = Deserializer::from_slice(&mapped)
+            .into_iter::<Value>()
+            .enumerate()
+            .map(|(index, value)| value.map_err(|e| JSONError::from_serde_error(index, e)))
+            .collect::<Result<_, _>>()?;
+
+        Ok(JSON { values })
+    }
+
+    /// Streams JSON values one at a time out of `reader`, calling `filter` on each
+    /// and yielding only the ones it keeps, without ever retaining the whole
+    /// collection the way `deserialize`/`deserialize_with_filter` do. Intended for
+    /// callers (e.g. `blscaler`) that want to pipe a huge NDJSON corpus through
+    /// bounded-memory batches instead of loading it entirely up front.
+    #[allow(dead_code)]
+    pub fn stream<R, F>(reader: R, mut filter: F) -> impl Iterator<Item = Result<Value, JSONError>>
+    where
+        R: BufRead,
+        F: FnMut(&mut Value) -> bool,
+    {
+        Deserializer::from_reader(reader)
+            .into_iter::<Value>()
+            .enumerate()
+            .filter_map(move |(index, item)| match item {
+                Ok(mut value) => if filter(&mut value) { Some(Ok(value)) } else { None },
+                Err(e) => Some(Err(JSONError::from_serde_error(index, e))),
+            })
+    }
+
+    /// Returns an iterator over the already-parsed values, for callers that want to
+    /// pipe them through a `rayon` pipeline without cloning the backing `Vec`.
+    #[allow(dead_code)]
+    pub fn values_iter(&self) -> impl Iterator<Item = &Value> {
+        self.values.iter()
+    }
+
     /// Returns a reference to the parsed JSON values.
     #[allow(dead_code)]
     pub fn values(&self) -> &Vec<Value> {
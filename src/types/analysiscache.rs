@@ -0,0 +1,206 @@
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+use crate::types::memorymappedfile::MemoryMappedFile;
+
+/// Magic bytes identifying an `AnalysisCache` file on disk.
+const ANALYSIS_CACHE_MAGIC: &[u8; 4] = b"BLAC";
+
+/// Version of the on-disk `AnalysisCache` layout. Bumped whenever the record
+/// layout changes in a way that isn't backwards compatible.
+const ANALYSIS_CACHE_VERSION: u32 = 1;
+
+/// Size, in bytes, of a single fixed-size node record.
+const RECORD_SIZE: usize = 9;
+
+/// Bit set in a node record's flags byte when the address is a valid function.
+const FLAG_IS_FUNCTION: u8 = 0b0000_0001;
+
+/// Bit set in a node record's flags byte when the address is a valid block.
+const FLAG_IS_BLOCK: u8 = 0b0000_0010;
+
+/// A single lazily-parsed entry in the `AnalysisCache`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnalysisCacheRecord {
+    pub address: u64,
+    pub is_function: bool,
+    pub is_block: bool,
+}
+
+/// A versioned, on-disk cache of a fully-absorbed `Graph`'s valid function and block
+/// addresses, keyed by the originating file's identity.
+///
+/// Following the layout strategy Mercurial uses for its dirstate-v2, the file is a
+/// fixed header carrying a magic, a format version, and the "parents" that validate
+/// the cache (the file's sha256, tlsh, and size), followed by a contiguous block of
+/// fixed-size node records. Opening the cache reads only the header; records are
+/// decoded on demand, one at a time by `AnalysisCache::record`, or all of them in one
+/// pass by `function_addresses`/`block_addresses`.
+pub struct AnalysisCache {
+    file: MemoryMappedFile,
+}
+
+/// The header fields that must match the binary being analyzed for the cache to be
+/// considered valid. Analogous to Mercurial dirstate-v2's "parents".
+#[derive(Debug, Clone)]
+pub struct AnalysisCacheParents {
+    pub file_sha256: String,
+    pub file_tlsh: String,
+    pub file_size: u64,
+}
+
+impl AnalysisCache {
+    /// Creates a fresh, empty cache file at `path`, overwriting anything already there.
+    #[allow(dead_code)]
+    pub fn create(path: PathBuf, cache: bool) -> Result<Self, Error> {
+        let file = MemoryMappedFile::new(path, false, cache)?;
+        file.handle.set_len(0)?;
+        Ok(Self { file })
+    }
+
+    /// Opens an existing cache file and validates its header against `parents`.
+    ///
+    /// Returns `Ok(None)` (rather than an error) when the magic/version don't match
+    /// or the parents have diverged, since a stale cache simply means the caller
+    /// should fall back to re-disassembling and writing a fresh one.
+    pub fn open(path: PathBuf, parents: &AnalysisCacheParents) -> Result<Option<Self>, Error> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let file = MemoryMappedFile::new_readonly(path)?;
+        let size = file.size()?;
+        if size < Self::header_size() as u64 {
+            return Ok(None);
+        }
+
+        let mmap = file.mmap()?;
+        if &mmap[0..4] != ANALYSIS_CACHE_MAGIC {
+            return Ok(None);
+        }
+        let version = u32::from_le_bytes(mmap[4..8].try_into().unwrap());
+        if version != ANALYSIS_CACHE_VERSION {
+            return Ok(None);
+        }
+
+        let (stored_sha256, stored_tlsh, stored_size) = Self::read_parents(&mmap);
+        if stored_sha256 != parents.file_sha256
+            || stored_tlsh != parents.file_tlsh
+            || stored_size != parents.file_size {
+            return Ok(None);
+        }
+
+        drop(mmap);
+
+        Ok(Some(Self { file }))
+    }
+
+    /// The fixed size of the header, in bytes: magic, version, sha256, tlsh, and size.
+    fn header_size() -> usize {
+        4 + 4 + 64 + 64 + 8
+    }
+
+    fn read_parents(mmap: &[u8]) -> (String, String, u64) {
+        let sha256 = String::from_utf8_lossy(&mmap[8..72]).trim_end_matches('\0').to_string();
+        let tlsh = String::from_utf8_lossy(&mmap[72..136]).trim_end_matches('\0').to_string();
+        let size = u64::from_le_bytes(mmap[136..144].try_into().unwrap());
+        (sha256, tlsh, size)
+    }
+
+    /// Writes the header and every record for `records` to the cache file, replacing
+    /// any prior contents.
+    #[allow(dead_code)]
+    pub fn write(&mut self, parents: &AnalysisCacheParents, records: &[AnalysisCacheRecord]) -> Result<(), Error> {
+        let header_size = Self::header_size();
+        let total_size = header_size + records.len() * RECORD_SIZE;
+
+        self.file.handle.set_len(0)?;
+        self.file.write_padding(total_size)?;
+
+        let mut mmap = self.file.mmap_mut()?;
+        mmap[0..4].copy_from_slice(ANALYSIS_CACHE_MAGIC);
+        mmap[4..8].copy_from_slice(&ANALYSIS_CACHE_VERSION.to_le_bytes());
+
+        let mut sha256_bytes = [0u8; 64];
+        let sha256_src = parents.file_sha256.as_bytes();
+        sha256_bytes[..sha256_src.len().min(64)].copy_from_slice(&sha256_src[..sha256_src.len().min(64)]);
+        mmap[8..72].copy_from_slice(&sha256_bytes);
+
+        let mut tlsh_bytes = [0u8; 64];
+        let tlsh_src = parents.file_tlsh.as_bytes();
+        tlsh_bytes[..tlsh_src.len().min(64)].copy_from_slice(&tlsh_src[..tlsh_src.len().min(64)]);
+        mmap[72..136].copy_from_slice(&tlsh_bytes);
+
+        mmap[136..144].copy_from_slice(&parents.file_size.to_le_bytes());
+
+        let mut offset = header_size;
+        for record in records {
+            mmap[offset..offset + 8].copy_from_slice(&record.address.to_le_bytes());
+            let mut flags = 0u8;
+            if record.is_function {
+                flags |= FLAG_IS_FUNCTION;
+            }
+            if record.is_block {
+                flags |= FLAG_IS_BLOCK;
+            }
+            mmap[offset + 8] = flags;
+            offset += RECORD_SIZE;
+        }
+
+        mmap.flush()?;
+        Ok(())
+    }
+
+    /// The number of node records stored in the cache.
+    pub fn len(&self) -> Result<usize, Error> {
+        let size = self.file.size()? as usize;
+        Ok((size.saturating_sub(Self::header_size())) / RECORD_SIZE)
+    }
+
+    /// Lazily decodes and returns the record at `index`, without parsing any other
+    /// record in the cache.
+    pub fn record(&self, index: usize) -> Result<AnalysisCacheRecord, Error> {
+        let offset = Self::header_size() + index * RECORD_SIZE;
+        let mmap = self.file.mmap()?;
+        if offset + RECORD_SIZE > mmap.len() {
+            return Err(Error::new(ErrorKind::InvalidInput, "analysis cache record index out of range"));
+        }
+        let address = u64::from_le_bytes(mmap[offset..offset + 8].try_into().unwrap());
+        let flags = mmap[offset + 8];
+        Ok(AnalysisCacheRecord {
+            address,
+            is_function: flags & FLAG_IS_FUNCTION != 0,
+            is_block: flags & FLAG_IS_BLOCK != 0,
+        })
+    }
+
+    /// Returns the addresses of every record flagged as a valid function.
+    ///
+    /// Decodes every record in the cache and collects the matching addresses into
+    /// a `Vec`.
+    #[allow(dead_code)]
+    pub fn function_addresses(&self) -> Result<Vec<u64>, Error> {
+        let count = self.len()?;
+        let mut addresses = Vec::new();
+        for index in 0..count {
+            let record = self.record(index)?;
+            if record.is_function {
+                addresses.push(record.address);
+            }
+        }
+        Ok(addresses)
+    }
+
+    /// Returns the addresses of every record flagged as a valid block.
+    #[allow(dead_code)]
+    pub fn block_addresses(&self) -> Result<Vec<u64>, Error> {
+        let count = self.len()?;
+        let mut addresses = Vec::new();
+        for index in 0..count {
+            let record = self.record(index)?;
+            if record.is_block {
+                addresses.push(record.address);
+            }
+        }
+        Ok(addresses)
+    }
+}
@@ -1,7 +1,12 @@
 use memmap2::{Mmap, MmapMut};
+use rand::Rng;
+use std::env;
 use std::fs::OpenOptions;
 use std::io::{self, Error, Read, Seek, SeekFrom, Write};
 use std::path::PathBuf;
+use std::time::SystemTime;
+use crate::models::binary::Binary;
+use crate::types::Cipher;
 
 /// A `CachedFile` struct that provides a cached file interface,
 /// enabling file read/write operations with optional in-memory caching,
@@ -16,6 +21,22 @@ pub struct CachedFile {
     /// Flag to determine if the file should be cached. If `false`, the file will
     /// be deleted upon the object being dropped.
     pub cache: bool,
+    /// Cipher used to transparently encrypt data written to, and decrypt
+    /// data read from, this file. `None` means the file is stored in the
+    /// clear, matching the prior behavior of `CachedFile`.
+    pub encrypt: Option<Cipher>,
+    /// The file's mtime as of `new`/the last successful write through this handle,
+    /// used by `write_if_changed` to detect external modification by another writer.
+    mtime: Option<SystemTime>,
+}
+
+/// The outcome of `CachedFile::write_if_changed`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    /// The incoming data matched what was already on disk; nothing was written.
+    Unchanged,
+    /// The incoming data differed from what was on disk (or the file was empty); it was (re)written.
+    Written,
 }
 
 impl CachedFile {
@@ -47,15 +68,77 @@ impl CachedFile {
         }
 
         let handle = options.open(&path)?;
+        let mtime = handle.metadata()?.modified().ok();
 
         Ok(Self {
             path: path.to_string_lossy().into_owned(),
             handle,
             is_cached,
             cache,
+            encrypt: None,
+            mtime,
         })
     }
 
+    /// Creates a new `CachedFile` whose contents are transparently encrypted
+    /// at rest with ChaCha20.
+    ///
+    /// A random 96-bit nonce is generated and stored as a small header at the
+    /// start of the file the first time it is created; on subsequent opens of
+    /// an existing file, the nonce is read back from that header. Everything
+    /// written via `write` afterwards (not `write_padding`) is enciphered using
+    /// `key` and the recovered nonce, keeping the block counter aligned with
+    /// each write's absolute offset into the plaintext stream so appends
+    /// pick up the keystream where the previous write left off.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The `PathBuf` specifying the file's location.
+    /// * `append` - If `true`, opens the file in append mode.
+    /// * `cache` - If `true`, retains the file on disk after the `CachedFile` instance is dropped.
+    /// * `key` - The 256-bit ChaCha20 key.
+    ///
+    /// # Returns
+    ///
+    /// A `Result` containing the encrypting `CachedFile` on success, or an `io::Error` if file
+    /// creation or the nonce header read/write fails.
+    pub fn new_encrypted(path: PathBuf, append: bool, cache: bool, key: [u8; Cipher::KEY_SIZE]) -> Result<Self, Error> {
+        let mut cached_file = Self::new(path, append, cache)?;
+
+        let file_size = cached_file.handle.metadata()?.len();
+        let mut nonce = [0u8; Cipher::NONCE_SIZE];
+
+        if file_size >= Cipher::NONCE_SIZE as u64 {
+            cached_file.handle.seek(SeekFrom::Start(0))?;
+            cached_file.handle.read_exact(&mut nonce)?;
+        } else {
+            rand::thread_rng().fill(&mut nonce);
+            cached_file.handle.seek(SeekFrom::Start(0))?;
+            cached_file.handle.write_all(&nonce)?;
+            cached_file.handle.flush()?;
+        }
+        cached_file.handle.seek(SeekFrom::End(0))?;
+        cached_file.mtime = cached_file.handle.metadata()?.modified().ok();
+
+        cached_file.encrypt = Some(Cipher::new(key, nonce));
+        Ok(cached_file)
+    }
+
+    /// Like `new_encrypted`, but accepts the key base64-encoded, as supplied
+    /// by an API caller.
+    pub fn new_encrypted_with_base64_key(path: PathBuf, append: bool, cache: bool, key: &str) -> Result<Self, Error> {
+        let key = Cipher::decode_base64_key(key)?;
+        Self::new_encrypted(path, append, cache, key)
+    }
+
+    /// Like `new_encrypted`, but reads the base64-encoded key from the
+    /// environment variable named `env_var`.
+    pub fn new_encrypted_with_env_key(path: PathBuf, append: bool, cache: bool, env_var: &str) -> Result<Self, Error> {
+        let key = env::var(env_var)
+            .map_err(|error| Error::new(io::ErrorKind::NotFound, format!("{} is not set: {}", env_var, error)))?;
+        Self::new_encrypted_with_base64_key(path, append, cache, &key)
+    }
+
     /// Checks if the file is cached (exists on disk).
     ///
     /// # Returns
@@ -75,10 +158,18 @@ impl CachedFile {
         self.path.clone()
     }
 
+    /// The number of header bytes reserved at the start of the file for the
+    /// ChaCha20 nonce when `encrypt` is set, or `0` otherwise.
+    fn header_size(&self) -> u64 {
+        if self.encrypt.is_some() { Cipher::NONCE_SIZE as u64 } else { 0 }
+    }
+
     /// Writes data from a reader to the file.
     ///
     /// This method copies all data from the given reader into the file, flushing the data
-    /// to ensure it is written to disk.
+    /// to ensure it is written to disk. If this `CachedFile` was created with `new_encrypted`,
+    /// the data is enciphered with ChaCha20 before being written, using the offset into the
+    /// plaintext stream (the file size minus the nonce header) as the keystream position.
     ///
     /// # Arguments
     ///
@@ -94,16 +185,78 @@ impl CachedFile {
             return Err(Error::new(io::ErrorKind::Other, "File is read-only"));
         }
 
+        if let Some(cipher) = &self.encrypt {
+            let mut buffer = Vec::new();
+            reader.read_to_end(&mut buffer)?;
+
+            let plaintext_offset = self.handle.metadata()?.len().saturating_sub(self.header_size());
+            cipher.apply(&mut buffer, plaintext_offset);
+
+            self.handle.seek(SeekFrom::End(0))?;
+            self.handle.write_all(&buffer)?;
+            self.handle.flush()?;
+            self.mtime = self.handle.metadata()?.modified().ok();
+            return Ok(buffer.len() as u64);
+        }
+
         let bytes_written = io::copy(&mut reader, &mut self.handle)?;
         self.handle.flush()?;
+        self.mtime = self.handle.metadata()?.modified().ok();
         Ok(bytes_written)
     }
 
+    /// Writes `reader`'s contents only if they differ from what is already on disk.
+    ///
+    /// Buffers the incoming data, computes its SHA-256 (via `Binary::sha256`), and compares
+    /// it against the SHA-256 of the current on-disk contents (decrypted first, if `encrypt`
+    /// is set). If they match, nothing is written and `Unchanged` is returned, so re-running
+    /// analysis that produces byte-identical output doesn't churn the file's mtime or trigger
+    /// downstream re-processing. Otherwise the file is truncated back to just its encryption
+    /// header (if any) and the new data is written, returning `Written`.
+    ///
+    /// Before comparing, this also checks the file's mtime against the one captured when this
+    /// `CachedFile` was opened (or last written through it); if they differ, another writer has
+    /// modified the file since, and this returns an error rather than silently clobbering it.
+    pub fn write_if_changed<R: Read>(&mut self, mut reader: R) -> Result<WriteOutcome, Error> {
+        if let Some(opened_mtime) = self.mtime {
+            let current_mtime = self.handle.metadata()?.modified()?;
+            if current_mtime != opened_mtime {
+                return Err(Error::new(io::ErrorKind::Other, format!("{} was modified externally since it was opened", self.path)));
+            }
+        }
+
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer)?;
+
+        let header_size = self.header_size();
+        let payload_size = self.handle.metadata()?.len().saturating_sub(header_size);
+        let current_sha256 = if payload_size > 0 {
+            Binary::sha256(&self.read_decrypted()?)
+        } else {
+            None
+        };
+        let incoming_sha256 = Binary::sha256(&buffer);
+
+        if incoming_sha256 == current_sha256 {
+            return Ok(WriteOutcome::Unchanged);
+        }
+
+        self.handle.set_len(header_size)?;
+        self.handle.seek(SeekFrom::End(0))?;
+        self.write(&buffer[..])?;
+
+        Ok(WriteOutcome::Written)
+    }
+
     /// Adds symbolic padding (increases the file size without writing data) to the end of the file.
     ///
     /// This method sets the file length to the current size plus the specified padding length.
     /// The padding does not consume additional disk space as it is not physically written.
     ///
+    /// Padding is left as a sparse, all-zero gap even when `encrypt` is set: it is not
+    /// enciphered, so `read_decrypted` deciphers those zeros into keystream bytes rather
+    /// than returning zeros for the padded range.
+    ///
     /// # Arguments
     /// * `length` - The number of bytes to append as padding.
     ///
@@ -150,7 +303,9 @@ impl CachedFile {
     /// Maps the file into memory using `mmap`.
     ///
     /// This method uses the `memmap2` crate to map the file into memory,
-    /// allowing for direct memory access to the file contents.
+    /// allowing for direct memory access to the file contents. When `encrypt`
+    /// is set, the mapping still reflects the raw on-disk bytes (nonce header
+    /// followed by ciphertext); use `read_decrypted` to get the plaintext back.
     ///
     /// # Returns
     ///
@@ -158,6 +313,23 @@ impl CachedFile {
     pub fn mmap(&self) -> Result<Mmap, Error> {
         unsafe { Mmap::map(&self.handle) }
     }
+
+    /// Returns the plaintext contents of the file.
+    ///
+    /// If `encrypt` is set, this maps the file, skips the nonce header, and deciphers the
+    /// remaining bytes with ChaCha20. Otherwise it returns the raw mapped bytes unchanged.
+    pub fn read_decrypted(&self) -> Result<Vec<u8>, Error> {
+        let mapped = self.mmap()?;
+
+        match &self.encrypt {
+            Some(cipher) => {
+                let mut plaintext = mapped[self.header_size() as usize..].to_vec();
+                cipher.apply(&mut plaintext, 0);
+                Ok(plaintext)
+            }
+            None => Ok(mapped.to_vec()),
+        }
+    }
 }
 
 /// Automatically handles cleanup for the `CachedFile` when it goes out of scope.
@@ -0,0 +1,126 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use std::io::{Error, ErrorKind};
+
+/// The ChaCha20 stream cipher (RFC 8439), used to transparently encrypt
+/// `CachedFile` contents at rest.
+///
+/// The cipher state is sixteen 32-bit words: four fixed constants
+/// (`"expand 32-byte k"`), eight key words (a 256-bit key), a 32-bit block
+/// counter, and three nonce words (a 96-bit nonce). Each 64-byte keystream
+/// block runs 20 rounds (ten column/diagonal double-rounds of the quarter
+/// round) over a copy of the state, adds the original state back in
+/// word-wise, and serializes little-endian; the keystream is then XORed
+/// against the plaintext/ciphertext.
+pub struct Cipher {
+    key: [u32; 8],
+    nonce: [u32; 3],
+}
+
+const CONSTANTS: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+impl Cipher {
+    pub const KEY_SIZE: usize = 32;
+    pub const NONCE_SIZE: usize = 12;
+    const BLOCK_SIZE: usize = 64;
+
+    pub fn new(key: [u8; Self::KEY_SIZE], nonce: [u8; Self::NONCE_SIZE]) -> Self {
+        let mut key_words = [0u32; 8];
+        for (i, word) in key_words.iter_mut().enumerate() {
+            *word = u32::from_le_bytes(key[i * 4..i * 4 + 4].try_into().unwrap());
+        }
+
+        let mut nonce_words = [0u32; 3];
+        for (i, word) in nonce_words.iter_mut().enumerate() {
+            *word = u32::from_le_bytes(nonce[i * 4..i * 4 + 4].try_into().unwrap());
+        }
+
+        Self {
+            key: key_words,
+            nonce: nonce_words,
+        }
+    }
+
+    /// Decodes a base64-encoded 256-bit key, as accepted by
+    /// `CachedFile::new_encrypted_with_base64_key`/`..._with_env_key`.
+    pub fn decode_base64_key(encoded: &str) -> Result<[u8; Self::KEY_SIZE], Error> {
+        let decoded = STANDARD.decode(encoded.trim())
+            .map_err(|error| Error::new(ErrorKind::InvalidInput, format!("invalid base64 chacha20 key: {}", error)))?;
+
+        if decoded.len() != Self::KEY_SIZE {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("chacha20 key must decode to {} bytes, got {}", Self::KEY_SIZE, decoded.len())));
+        }
+
+        let mut key = [0u8; Self::KEY_SIZE];
+        key.copy_from_slice(&decoded);
+        Ok(key)
+    }
+
+    fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] ^= state[a];
+        state[d] = state[d].rotate_left(16);
+
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] ^= state[c];
+        state[b] = state[b].rotate_left(12);
+
+        state[a] = state[a].wrapping_add(state[b]);
+        state[d] ^= state[a];
+        state[d] = state[d].rotate_left(8);
+
+        state[c] = state[c].wrapping_add(state[d]);
+        state[b] ^= state[c];
+        state[b] = state[b].rotate_left(7);
+    }
+
+    /// Generates the 64-byte keystream block for the given block counter.
+    fn block(&self, counter: u32) -> [u8; Self::BLOCK_SIZE] {
+        let mut initial_state = [0u32; 16];
+        initial_state[0..4].copy_from_slice(&CONSTANTS);
+        initial_state[4..12].copy_from_slice(&self.key);
+        initial_state[12] = counter;
+        initial_state[13..16].copy_from_slice(&self.nonce);
+
+        let mut working_state = initial_state;
+        for _ in 0..10 {
+            Self::quarter_round(&mut working_state, 0, 4, 8, 12);
+            Self::quarter_round(&mut working_state, 1, 5, 9, 13);
+            Self::quarter_round(&mut working_state, 2, 6, 10, 14);
+            Self::quarter_round(&mut working_state, 3, 7, 11, 15);
+            Self::quarter_round(&mut working_state, 0, 5, 10, 15);
+            Self::quarter_round(&mut working_state, 1, 6, 11, 12);
+            Self::quarter_round(&mut working_state, 2, 7, 8, 13);
+            Self::quarter_round(&mut working_state, 3, 4, 9, 14);
+        }
+
+        let mut keystream = [0u8; Self::BLOCK_SIZE];
+        for i in 0..16 {
+            let word = working_state[i].wrapping_add(initial_state[i]);
+            keystream[i * 4..i * 4 + 4].copy_from_slice(&word.to_le_bytes());
+        }
+        keystream
+    }
+
+    /// XORs `data` in place against the keystream beginning at absolute byte
+    /// `offset` within the stream, so a write landing mid-stream (e.g. an
+    /// append) lines up with the correct block counter and intra-block
+    /// keystream position rather than always restarting at counter zero.
+    pub fn apply(&self, data: &mut [u8], offset: u64) {
+        let mut position = 0usize;
+        let mut block_index = (offset / Self::BLOCK_SIZE as u64) as u32;
+        let mut block_offset = (offset % Self::BLOCK_SIZE as u64) as usize;
+
+        while position < data.len() {
+            let keystream = self.block(block_index);
+            let chunk_len = (Self::BLOCK_SIZE - block_offset).min(data.len() - position);
+
+            for i in 0..chunk_len {
+                data[position + i] ^= keystream[block_offset + i];
+            }
+
+            position += chunk_len;
+            block_index = block_index.wrapping_add(1);
+            block_offset = 0;
+        }
+    }
+}
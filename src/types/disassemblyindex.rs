@@ -0,0 +1,165 @@
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+use crate::types::memorymappedfile::MemoryMappedFile;
+
+/// Magic bytes identifying a `DisassemblyIndex` file on disk.
+const DISASSEMBLY_INDEX_MAGIC: &[u8; 4] = b"BLDI";
+
+/// Version of the on-disk `DisassemblyIndex` layout.
+const DISASSEMBLY_INDEX_VERSION: u32 = 1;
+
+/// Size, in bytes, of a single fixed-size index record: an 8-byte address,
+/// a 1-byte instruction length, and a 1-byte flags field.
+const RECORD_SIZE: usize = 10;
+
+/// Bit set in a record's flags byte when the address failed to decode as a
+/// valid instruction (a trap), so re-disassembly can skip straight past it.
+const FLAG_IS_TRAP: u8 = 0b0000_0001;
+
+/// The fixed size of the header, in bytes: magic and version.
+const HEADER_SIZE: usize = 8;
+
+/// A single lazily-decoded entry in the `DisassemblyIndex`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisassemblyIndexRecord {
+    pub address: u64,
+    pub length: u8,
+    pub is_trap: bool,
+}
+
+/// A versioned, on-disk index recording the address and length of every instruction
+/// already decoded for a binary, so a disassembly pass resuming against the same
+/// image can look up whether an address has been visited (and how far it advances)
+/// without re-running the decoder.
+///
+/// Records are appended in the order they're decoded but stored pre-sorted by
+/// address by `write`, so `lookup` can binary search the mmap directly rather than
+/// scanning or materializing the whole index into memory.
+pub struct DisassemblyIndex {
+    file: MemoryMappedFile,
+}
+
+impl DisassemblyIndex {
+    /// Creates a fresh, empty index file at `path`, overwriting anything already there.
+    #[allow(dead_code)]
+    pub fn create(path: PathBuf, cache: bool) -> Result<Self, Error> {
+        let file = MemoryMappedFile::new(path, false, cache)?;
+        file.handle.set_len(0)?;
+        Ok(Self { file })
+    }
+
+    /// Opens an existing index file, returning `Ok(None)` when the magic or version
+    /// don't match so the caller can fall back to decoding from scratch.
+    #[allow(dead_code)]
+    pub fn open(path: PathBuf) -> Result<Option<Self>, Error> {
+        if !path.is_file() {
+            return Ok(None);
+        }
+
+        let file = MemoryMappedFile::new_readonly(path)?;
+        let size = file.size()?;
+        if size < HEADER_SIZE as u64 {
+            return Ok(None);
+        }
+
+        let mmap = file.mmap()?;
+        if &mmap[0..4] != DISASSEMBLY_INDEX_MAGIC {
+            return Ok(None);
+        }
+        let version = u32::from_le_bytes(mmap[4..8].try_into().unwrap());
+        if version != DISASSEMBLY_INDEX_VERSION {
+            return Ok(None);
+        }
+
+        drop(mmap);
+
+        Ok(Some(Self { file }))
+    }
+
+    /// Writes the header and every record for `records`, sorted by address, replacing
+    /// any prior contents.
+    #[allow(dead_code)]
+    pub fn write(&mut self, records: &[DisassemblyIndexRecord]) -> Result<(), Error> {
+        let mut sorted = records.to_vec();
+        sorted.sort_unstable_by_key(|record| record.address);
+
+        let total_size = HEADER_SIZE + sorted.len() * RECORD_SIZE;
+
+        self.file.handle.set_len(0)?;
+        self.file.write_padding(total_size)?;
+
+        let mut mmap = self.file.mmap_mut()?;
+        mmap[0..4].copy_from_slice(DISASSEMBLY_INDEX_MAGIC);
+        mmap[4..8].copy_from_slice(&DISASSEMBLY_INDEX_VERSION.to_le_bytes());
+
+        let mut offset = HEADER_SIZE;
+        for record in &sorted {
+            mmap[offset..offset + 8].copy_from_slice(&record.address.to_le_bytes());
+            mmap[offset + 8] = record.length;
+            let mut flags = 0u8;
+            if record.is_trap {
+                flags |= FLAG_IS_TRAP;
+            }
+            mmap[offset + 9] = flags;
+            offset += RECORD_SIZE;
+        }
+
+        mmap.flush()?;
+        Ok(())
+    }
+
+    /// The number of records stored in the index.
+    #[allow(dead_code)]
+    pub fn len(&self) -> Result<usize, Error> {
+        let size = self.file.size()? as usize;
+        Ok((size.saturating_sub(HEADER_SIZE)) / RECORD_SIZE)
+    }
+
+    /// Lazily decodes and returns the record at `index`, without parsing any other
+    /// record in the index.
+    #[allow(dead_code)]
+    pub fn record(&self, index: usize) -> Result<DisassemblyIndexRecord, Error> {
+        let offset = HEADER_SIZE + index * RECORD_SIZE;
+        let mmap = self.file.mmap()?;
+        if offset + RECORD_SIZE > mmap.len() {
+            return Err(Error::new(ErrorKind::InvalidInput, "disassembly index record index out of range"));
+        }
+        Ok(Self::decode(&mmap, offset))
+    }
+
+    fn decode(mmap: &[u8], offset: usize) -> DisassemblyIndexRecord {
+        let address = u64::from_le_bytes(mmap[offset..offset + 8].try_into().unwrap());
+        let length = mmap[offset + 8];
+        let flags = mmap[offset + 9];
+        DisassemblyIndexRecord {
+            address,
+            length,
+            is_trap: flags & FLAG_IS_TRAP != 0,
+        }
+    }
+
+    /// Binary searches the index for `address`, decoding only the records the search
+    /// actually visits instead of materializing the whole index.
+    #[allow(dead_code)]
+    pub fn lookup(&self, address: u64) -> Result<Option<DisassemblyIndexRecord>, Error> {
+        let count = self.len()?;
+        let mmap = self.file.mmap()?;
+
+        let mut low = 0usize;
+        let mut high = count;
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let offset = HEADER_SIZE + mid * RECORD_SIZE;
+            let record = Self::decode(&mmap, offset);
+            if record.address == address {
+                return Ok(Some(record));
+            } else if record.address < address {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -1,36 +1,51 @@
 use std::convert::From;
-use lz4::block::{compress, decompress};
+use std::io::{Read, Write};
+use lz4::{Decoder, EncoderBuilder};
 
+/// A string stored compressed using the standard LZ4 frame format (the same
+/// self-describing, magic-number-prefixed container the `lz4` CLI and other
+/// language bindings produce), rather than lz4's raw block format.
+///
+/// The frame format carries its own content size and integrity checksum, so unlike
+/// the previous block-format encoding, `LZ4String` no longer needs to separately
+/// track `uncompressed_size` to decompress, and the bytes it produces can be piped
+/// straight into any other LZ4 frame-compatible tool.
 pub struct LZ4String {
     compressed_data: Vec<u8>,
-    uncompressed_size: usize,
 }
 
 impl LZ4String {
+    fn encode(data: &str) -> Vec<u8> {
+        let mut encoder = EncoderBuilder::new()
+            .build(Vec::new())
+            .expect("lz4string frame encoder initialization failed");
+        encoder.write_all(data.as_bytes()).expect("lz4string compression failed");
+        let (compressed, result) = encoder.finish();
+        result.expect("lz4string compression failed");
+        compressed
+    }
 
     #[allow(dead_code)]
     pub fn new(data: &str) -> Self {
-        let compressed = compress(data.as_bytes(), None, false).expect("lz4string compression failed");
         LZ4String {
-            compressed_data: compressed,
-            uncompressed_size: data.len(),
+            compressed_data: Self::encode(data),
         }
     }
 
     #[allow(dead_code)]
     pub fn to_string(&self) -> String {
-        let decompressed = decompress(&self.compressed_data, Some(self.uncompressed_size as i32))
-            .expect("lz4string decompression failed");
+        let mut decoder = Decoder::new(self.compressed_data.as_slice())
+            .expect("lz4string frame decoder initialization failed");
+        let mut decompressed = Vec::new();
+        decoder.read_to_end(&mut decompressed).expect("lz4string decompression failed");
         String::from_utf8(decompressed).expect("lz4string invalid utf8")
     }
 }
 
 impl From<String> for LZ4String {
     fn from(data: String) -> Self {
-        let compressed = compress(data.as_bytes(), None, false).expect("lz4string compression failed");
         LZ4String {
-            compressed_data: compressed,
-            uncompressed_size: data.len(),
+            compressed_data: Self::encode(&data),
         }
     }
 }
@@ -40,4 +55,4 @@ impl std::fmt::Display for LZ4String {
         let s = self.to_string();
         write!(f, "{}", s)
     }
-}
\ No newline at end of file
+}
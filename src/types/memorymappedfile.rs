@@ -3,6 +3,32 @@ use std::fs::OpenOptions;
 use std::io::{self, Error, Read, Seek, SeekFrom, Write};
 use std::path::PathBuf;
 
+/// The reference point a `FileOps::seek` offset is relative to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekOrigin {
+    /// Seek relative to the start of the file.
+    Set,
+    /// Seek relative to the current position.
+    Cur,
+    /// Seek relative to the end of the file.
+    End,
+}
+
+/// A positioned read/write/seek interface, modeled on the classic FileOps style,
+/// for consumers that want a specific byte range rather than the whole mapped file.
+pub trait FileOps {
+    /// Reads up to `buf.len()` bytes starting at `offset`. The implementation for
+    /// `MemoryMappedFile` seeks the file handle, so the current position moves.
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<usize>;
+
+    /// Writes `buf` starting at `offset`, growing the file if necessary.
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> io::Result<usize>;
+
+    /// Moves the file's current position according to `origin` and `pos`,
+    /// returning the resulting absolute offset.
+    fn seek(&mut self, origin: SeekOrigin, pos: i64) -> io::Result<u64>;
+}
+
 #[cfg(windows)]
 use std::os::windows::fs::OpenOptionsExt;
 #[cfg(windows)]
@@ -201,6 +227,42 @@ impl MemoryMappedFile {
     }
 }
 
+impl FileOps for MemoryMappedFile {
+    /// Reads up to `buf.len()` bytes starting at `offset` by seeking the underlying file
+    /// handle and reading from it (leaving the handle's position just past the bytes
+    /// read), so callers can pull a specific byte range (e.g. a
+    /// single PE section) without mapping or copying the whole file.
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> io::Result<usize> {
+        let mut handle = &self.handle;
+        handle.seek(SeekFrom::Start(offset))?;
+        handle.read(buf)
+    }
+
+    /// Writes `buf` starting at `offset`, growing the file via `write_padding` first
+    /// if the write would extend past the current end of file.
+    fn write_at(&mut self, offset: u64, buf: &[u8]) -> io::Result<usize> {
+        let current_size = self.handle.metadata()?.len();
+        let required_size = offset + buf.len() as u64;
+        if required_size > current_size {
+            self.write_padding((required_size - current_size) as usize)?;
+        }
+
+        let mut handle = &self.handle;
+        handle.seek(SeekFrom::Start(offset))?;
+        handle.write(buf)
+    }
+
+    /// Seeks the underlying file handle and returns the resulting absolute offset.
+    fn seek(&mut self, origin: SeekOrigin, pos: i64) -> io::Result<u64> {
+        let seek_from = match origin {
+            SeekOrigin::Set => SeekFrom::Start(pos as u64),
+            SeekOrigin::Cur => SeekFrom::Current(pos),
+            SeekOrigin::End => SeekFrom::End(pos),
+        };
+        self.handle.seek(seek_from)
+    }
+}
+
 /// Automatically handles cleanup for the `MemoryMappedFile` when it goes out of scope.
 ///
 /// If caching is disabled, this `Drop` implementation deletes the file from disk
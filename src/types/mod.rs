@@ -1,6 +1,18 @@
 pub mod lz4string;
 pub mod memorymappedfile;
 pub mod genomics;
+pub mod outputstore;
+pub mod analysiscache;
+pub mod disassemblyindex;
+pub mod nodegraph;
+pub mod cachedfile;
+pub mod cipher;
 
 pub use lz4string::LZ4String;
-pub use memorymappedfile::MemoryMappedFile;
+pub use memorymappedfile::{MemoryMappedFile, FileOps, SeekOrigin};
+pub use outputstore::{OutputStore, OutputStoreReader};
+pub use analysiscache::{AnalysisCache, AnalysisCacheParents, AnalysisCacheRecord};
+pub use disassemblyindex::{DisassemblyIndex, DisassemblyIndexRecord};
+pub use nodegraph::Nodegraph;
+pub use cachedfile::{CachedFile, WriteOutcome};
+pub use cipher::Cipher;
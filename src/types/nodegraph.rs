@@ -0,0 +1,134 @@
+use std::io::{Error, ErrorKind};
+use std::fs;
+use twox_hash::XxHash64;
+use std::hash::{Hash, Hasher};
+
+/// Magic bytes identifying a saved `Nodegraph` filter on disk.
+const NODEGRAPH_MAGIC: &[u8; 4] = b"BLNG";
+
+/// Version of the on-disk `Nodegraph` layout.
+const NODEGRAPH_VERSION: u32 = 1;
+
+/// A fixed-size Bloom filter over a file's byte-shingles, used as a cheap "do these
+/// two samples share any code at all" pre-filter ahead of the more expensive
+/// per-function MinHash/TLSH comparisons.
+///
+/// Unlike `hashing::sbt`'s per-node filter (sized for one signature's worth of
+/// MinHash values), a `Nodegraph` is meant to summarize an entire file's shingles, so
+/// both the bit width and the number of hash functions are configurable per instance.
+pub struct Nodegraph {
+    bits: Vec<u64>,
+    size_bits: usize,
+    num_hashes: usize,
+}
+
+impl Nodegraph {
+    /// Creates an empty filter with `size_bits` bits (rounded up to a multiple of 64)
+    /// and `num_hashes` independent hash functions.
+    pub fn new(size_bits: usize, num_hashes: usize) -> Self {
+        let words = (size_bits + 63) / 64;
+        Self {
+            bits: vec![0u64; words.max(1)],
+            size_bits: words.max(1) * 64,
+            num_hashes: num_hashes.max(1),
+        }
+    }
+
+    /// Populates the filter with every overlapping `shingle_size`-byte shingle of
+    /// `data`, reusing the same sliding-window shingling the MinHash path uses.
+    #[allow(dead_code)]
+    pub fn from_bytes(data: &[u8], shingle_size: usize, size_bits: usize, num_hashes: usize) -> Self {
+        let mut nodegraph = Self::new(size_bits, num_hashes);
+        if data.len() >= shingle_size {
+            for shingle in data.windows(shingle_size) {
+                nodegraph.insert(shingle);
+            }
+        }
+        nodegraph
+    }
+
+    fn bit_indexes<T: Hash>(&self, item: T) -> Vec<usize> {
+        let mut hasher = XxHash64::default();
+        item.hash(&mut hasher);
+        let base = hasher.finish();
+
+        (0..self.num_hashes).map(|i| {
+            let seeded = base.wrapping_add((i as u64).wrapping_mul(0x9E3779B97F4A7C15));
+            (seeded % self.size_bits as u64) as usize
+        }).collect()
+    }
+
+    /// Inserts a shingle (or any hashable item) into the filter.
+    pub fn insert<T: Hash>(&mut self, item: T) {
+        for bit in self.bit_indexes(item) {
+            self.bits[bit / 64] |= 1u64 << (bit % 64);
+        }
+    }
+
+    /// Returns whether every bit associated with `item` is set, i.e. whether `item`
+    /// was (probably) inserted. False positives are possible; false negatives are not.
+    pub fn contains<T: Hash>(&self, item: T) -> bool {
+        self.bit_indexes(item).into_iter().all(|bit| self.bits[bit / 64] & (1u64 << (bit % 64)) != 0)
+    }
+
+    fn popcount(&self) -> u32 {
+        self.bits.iter().map(|word| word.count_ones()).sum()
+    }
+
+    /// Estimates the fraction of shingles shared between `self` and `other` as the
+    /// popcount of their bitwise AND over the smaller of the two filters' popcounts,
+    /// so a near-empty filter intersected with a dense one doesn't read as "no
+    /// overlap" simply because the dense filter has many more bits set overall.
+    #[allow(dead_code)]
+    pub fn similarity(&self, other: &Nodegraph) -> f64 {
+        if self.size_bits != other.size_bits { return 0.0; }
+
+        let intersection: u32 = self.bits.iter().zip(other.bits.iter())
+            .map(|(a, b)| (a & b).count_ones())
+            .sum();
+
+        let smaller = self.popcount().min(other.popcount());
+        if smaller == 0 { return 0.0; }
+
+        intersection as f64 / smaller as f64
+    }
+
+    /// Serializes the filter to a compact binary format: magic, version, bit width,
+    /// hash count, then the raw bit words.
+    #[allow(dead_code)]
+    pub fn save(&self, path: &str) -> Result<(), Error> {
+        let mut buffer = Vec::with_capacity(16 + self.bits.len() * 8);
+        buffer.extend_from_slice(NODEGRAPH_MAGIC);
+        buffer.extend_from_slice(&NODEGRAPH_VERSION.to_le_bytes());
+        buffer.extend_from_slice(&(self.size_bits as u64).to_le_bytes());
+        buffer.extend_from_slice(&(self.num_hashes as u64).to_le_bytes());
+        for word in &self.bits {
+            buffer.extend_from_slice(&word.to_le_bytes());
+        }
+        fs::write(path, buffer)
+    }
+
+    /// Loads a filter previously written by `save`.
+    #[allow(dead_code)]
+    pub fn load(path: &str) -> Result<Self, Error> {
+        let buffer = fs::read(path)?;
+        if buffer.len() < 24 || &buffer[0..4] != NODEGRAPH_MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "not a nodegraph file"));
+        }
+        let version = u32::from_le_bytes(buffer[4..8].try_into().unwrap());
+        if version != NODEGRAPH_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, "unsupported nodegraph version"));
+        }
+        let size_bits = u64::from_le_bytes(buffer[8..16].try_into().unwrap()) as usize;
+        let num_hashes = u64::from_le_bytes(buffer[16..24].try_into().unwrap()) as usize;
+
+        let mut bits = Vec::with_capacity((size_bits + 63) / 64);
+        let mut offset = 24;
+        while offset + 8 <= buffer.len() {
+            bits.push(u64::from_le_bytes(buffer[offset..offset + 8].try_into().unwrap()));
+            offset += 8;
+        }
+
+        Ok(Self { bits, size_bits, num_hashes })
+    }
+}
@@ -0,0 +1,202 @@
+use std::io::{Error, ErrorKind};
+use std::path::PathBuf;
+use crate::types::memorymappedfile::MemoryMappedFile;
+
+/// Magic bytes identifying an `OutputStore` file on disk.
+const OUTPUT_STORE_MAGIC: &[u8; 4] = b"BLOS";
+
+/// Version of the on-disk `OutputStore` footer layout.
+const OUTPUT_STORE_VERSION: u32 = 1;
+
+/// Number of bytes a backing file is pre-grown by whenever it runs out of space.
+///
+/// Growing in page-aligned chunks means `write_padding`/`set_len` only has to be
+/// called once every `GROWTH_CHUNK_SIZE` bytes instead of on every record; each
+/// append still maps the file afresh.
+const GROWTH_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// A single record's location within the backing file.
+#[derive(Debug, Clone, Copy)]
+struct OutputStoreIndexEntry {
+    /// Byte offset of the record's length-prefix within the file.
+    offset: u64,
+    /// Length of the record's payload in bytes (excluding the length prefix).
+    length: u64,
+}
+
+/// An append-only, memory-mapped output store.
+///
+/// `OutputStore` writes each record to disk as soon as it is produced instead of
+/// buffering the full result set in a `Vec`, which keeps peak memory bounded by the
+/// in-memory index rather than the size of the corpus. Records are length-prefixed so
+/// they can be located without a linear scan once the store is finalized.
+pub struct OutputStore {
+    file: MemoryMappedFile,
+    cursor: u64,
+    capacity: u64,
+    index: Vec<OutputStoreIndexEntry>,
+}
+
+impl OutputStore {
+    /// Creates a new `OutputStore` backed by a file at `path`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The `PathBuf` specifying the backing file's location.
+    /// * `cache` - If `true`, the backing file is retained on disk after the store is dropped.
+    pub fn new(path: PathBuf, cache: bool) -> Result<Self, Error> {
+        let file = MemoryMappedFile::new(path, false, cache)?;
+        Ok(Self {
+            file,
+            cursor: 0,
+            capacity: 0,
+            index: Vec::new(),
+        })
+    }
+
+    /// Ensures the backing file has at least `additional` bytes of sparse room after
+    /// `self.cursor`, growing it in `GROWTH_CHUNK_SIZE` increments so repeated small
+    /// appends don't each trigger their own `set_len` call.
+    fn reserve(&mut self, additional: u64) -> Result<(), Error> {
+        let required = self.cursor + additional;
+        if required <= self.capacity {
+            return Ok(());
+        }
+        let mut grow_by = GROWTH_CHUNK_SIZE as u64;
+        while self.capacity + grow_by < required {
+            grow_by += GROWTH_CHUNK_SIZE as u64;
+        }
+        self.file.write_padding(grow_by as usize)?;
+        self.capacity += grow_by;
+        Ok(())
+    }
+
+    /// Appends a single length-prefixed record to the store.
+    ///
+    /// Returns the record's index within the store (not its byte offset).
+    pub fn append(&mut self, record: &[u8]) -> Result<usize, Error> {
+        let length = record.len() as u64;
+        self.reserve(8 + length)?;
+
+        let mut mmap = self.file.mmap_mut()?;
+        let offset = self.cursor as usize;
+        mmap[offset..offset + 8].copy_from_slice(&length.to_le_bytes());
+        mmap[offset + 8..offset + 8 + record.len()].copy_from_slice(record);
+        mmap.flush_range(offset, 8 + record.len())?;
+
+        self.index.push(OutputStoreIndexEntry {
+            offset: self.cursor,
+            length,
+        });
+        self.cursor += 8 + length;
+
+        Ok(self.index.len() - 1)
+    }
+
+    /// The number of records appended so far.
+    #[allow(dead_code)]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Finalizes the store by writing the index and footer, then truncating the file
+    /// to its logical size so the sparse tail reserved by `reserve` is dropped.
+    ///
+    /// After `finalize`, the file can be reopened and `OutputStore::open` will locate
+    /// every record via the footer without re-scanning the record stream.
+    pub fn finalize(mut self) -> Result<(), Error> {
+        let index_offset = self.cursor;
+
+        self.reserve((self.index.len() as u64) * 16 + 24)?;
+
+        {
+            let mut mmap = self.file.mmap_mut()?;
+            let mut offset = index_offset as usize;
+            for entry in &self.index {
+                mmap[offset..offset + 8].copy_from_slice(&entry.offset.to_le_bytes());
+                mmap[offset + 8..offset + 16].copy_from_slice(&entry.length.to_le_bytes());
+                offset += 16;
+            }
+            mmap[offset..offset + 4].copy_from_slice(OUTPUT_STORE_MAGIC);
+            mmap[offset + 4..offset + 8].copy_from_slice(&OUTPUT_STORE_VERSION.to_le_bytes());
+            mmap[offset + 8..offset + 16].copy_from_slice(&index_offset.to_le_bytes());
+            mmap[offset + 16..offset + 24].copy_from_slice(&(self.index.len() as u64).to_le_bytes());
+            mmap.flush()?;
+            offset += 24;
+            self.cursor = offset as u64;
+        }
+
+        self.file.handle.set_len(self.cursor)?;
+
+        Ok(())
+    }
+
+    /// Opens a previously finalized `OutputStore` for random-access reads.
+    ///
+    /// Only the footer and index are parsed eagerly; individual records are
+    /// materialized lazily by `read`.
+    pub fn open(path: PathBuf) -> Result<OutputStoreReader, Error> {
+        let file = MemoryMappedFile::new_readonly(path)?;
+        let mmap = file.mmap()?;
+        let size = mmap.len();
+
+        if size < 24 {
+            return Err(Error::new(ErrorKind::InvalidData, "output store file is too small"));
+        }
+
+        let footer = &mmap[size - 24..];
+        if &footer[0..4] != OUTPUT_STORE_MAGIC {
+            return Err(Error::new(ErrorKind::InvalidData, "invalid output store magic"));
+        }
+        let version = u32::from_le_bytes(footer[4..8].try_into().unwrap());
+        if version != OUTPUT_STORE_VERSION {
+            return Err(Error::new(ErrorKind::InvalidData, format!("unsupported output store version {}", version)));
+        }
+        let index_offset = u64::from_le_bytes(footer[8..16].try_into().unwrap()) as usize;
+        let count = u64::from_le_bytes(footer[16..24].try_into().unwrap()) as usize;
+
+        let mut index = Vec::with_capacity(count);
+        let mut offset = index_offset;
+        for _ in 0..count {
+            let entry_offset = u64::from_le_bytes(mmap[offset..offset + 8].try_into().unwrap());
+            let entry_length = u64::from_le_bytes(mmap[offset + 8..offset + 16].try_into().unwrap());
+            index.push(OutputStoreIndexEntry { offset: entry_offset, length: entry_length });
+            offset += 16;
+        }
+
+        Ok(OutputStoreReader { file, index })
+    }
+}
+
+/// A read-only handle over a finalized `OutputStore` file.
+pub struct OutputStoreReader {
+    file: MemoryMappedFile,
+    index: Vec<OutputStoreIndexEntry>,
+}
+
+impl OutputStoreReader {
+    /// The number of records in the store.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[allow(dead_code)]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Reads the record at `index` without materializing any other record.
+    pub fn read(&self, index: usize) -> Result<Vec<u8>, Error> {
+        let entry = self.index.get(index)
+            .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "output store index out of range"))?;
+        let mmap = self.file.mmap()?;
+        let start = entry.offset as usize + 8;
+        let end = start + entry.length as usize;
+        Ok(mmap[start..end].to_vec())
+    }
+}